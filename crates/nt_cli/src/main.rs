@@ -1,5 +1,5 @@
 use clap::Parser;
-use nt_core::{Result, ArticleStorage, Article, Scraper};
+use nt_core::{Result, ArticleStorage, Article, Capabilities, Scraper};
 use nt_storage::{StorageBackend, UrlConfig};
 use chrono::Utc;
 use nt_scrappers::cli::{ScraperArgs, ScraperCommands as NtScraperCommands, handle_command};
@@ -7,7 +7,8 @@ use std::sync::Arc;
 use tracing::info;
 use std::str::FromStr;
 use std::time::Duration;
-use nt_scrappers::ScraperManager;
+use nt_scrappers::{ScraperManager, ScrapeReport, ScrapeLock};
+use nt_scrappers::lock::DEFAULT_LOCK_STALE_AFTER;
 use nt_scrappers::scrapers::argentina::ClarinScraper;
 
 const DEFAULT_VECTOR_SIZE: u64 = 768;
@@ -59,7 +60,16 @@ impl FromStr for HumanDuration {
     }
 }
 
-async fn check_storage(storage: &Arc<dyn ArticleStorage>, storage_type: &str) -> Result<()> {
+/// Checks that `storage` answers, by storing, reading back and deleting a probe article.
+///
+/// With `read_only` set, as in dry runs, it only lists articles so nothing is written.
+async fn check_storage(storage: &Arc<dyn ArticleStorage>, storage_type: &str, read_only: bool) -> Result<()> {
+    if read_only {
+        storage.list_articles(0, 1).await?;
+        info!("🏦 Storage backend reachable (using {}), skipping the write check", storage_type);
+        return Ok(());
+    }
+
     let test_article = Article {
         url: "http://test.com".to_string(),
         title: "Test Article".to_string(),
@@ -70,6 +80,7 @@ async fn check_storage(storage: &Arc<dyn ArticleStorage>, storage_type: &str) ->
         summary: None,
         authors: vec!["Test Author".to_string()],
         related_articles: Vec::new(),
+        tags: Vec::new(),
     };
 
     storage.store_article(&test_article, &vec![0.0; DEFAULT_VECTOR_SIZE as usize]).await?;
@@ -95,10 +106,10 @@ async fn check_storage_with_retry(storage: &Arc<dyn ArticleStorage>, storage_typ
     let mut last_error = None;
 
     while retries < max_retries {
-        match tokio::time::timeout(timeout, check_storage(storage, storage_type)).await {
+        match nt_core::timeout(timeout, check_storage(storage, storage_type, false)).await {
             Ok(result) => return result,
             Err(timeout_error) => {
-                last_error = Some(nt_core::Error::Storage(format!("Storage health check timed out: {}", timeout_error)));
+                last_error = Some(timeout_error);
                 retries += 1;
                 if retries < max_retries {
                     info!("Storage health check failed, retrying {}/{}...", retries, max_retries);
@@ -120,8 +131,11 @@ pub struct Cli {
     model_url: Option<String>,
     #[arg(long)]
     backend_url: Option<String>,
-    #[arg(long, default_value = "ollama", help = "Model to use for inference. Available models: ollama (default), deepseek")]
+    #[arg(long, default_value = "ollama", help = "Model to use for inference. Run `doctor` to list the available models")]
     model: String,
+    /// File that summaries and embeddings are cached in, so unchanged content is not inferred again
+    #[arg(long)]
+    inference_cache: Option<std::path::PathBuf>,
     #[command(subcommand)]
     command: Commands,
 }
@@ -132,6 +146,33 @@ enum Commands {
         #[command(subcommand)]
         command: Option<ScraperCommands>,
     },
+    /// Print a JSON report of how an article's sections diverge from stored coverage
+    Divergence {
+        /// The article to analyze; scraped if it is not stored yet
+        url: String,
+        /// How many similar stored articles to compare against
+        #[arg(long, default_value_t = 5)]
+        limit: usize,
+    },
+    /// Delete stored articles published longer ago than the given age
+    Prune {
+        /// Maximum age of the articles kept (e.g. 30d, 12h, 1d12h)
+        #[arg(long)]
+        older_than: HumanDuration,
+    },
+    /// Print how many articles are stored, per source, and the dates they span
+    Stats,
+    /// Print the storage backends, inference models and scrapers available in this build
+    Doctor,
+    /// Serve the HTTP API over the configured storage and model
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:3000")]
+        addr: std::net::SocketAddr,
+        /// How article ids in the API map to URLs (base64_url or url_hash)
+        #[arg(long, default_value = "base64_url")]
+        id_scheme: nt_core::ArticleIdScheme,
+    },
 }
 
 #[derive(clap::Subcommand, Debug)]
@@ -143,13 +184,55 @@ enum ScraperCommands {
         /// Run in periodic mode with the specified interval (e.g. 1h, 30m, 1d, 1h15m30s)
         #[arg(long, default_value = "1h")]
         interval: Option<HumanDuration>,
+        /// Update already stored articles if they changed instead of skipping them
+        #[arg(long)]
+        update_existing: bool,
+        /// Fetch and parse articles without writing to storage, logging what would be stored
+        #[arg(long)]
+        dry_run: bool,
     },
     List,
     Url {
         url: String,
+        /// Update the stored article if it changed instead of skipping it
+        #[arg(long)]
+        update_existing: bool,
+        /// Fetch and parse the article without writing to storage, logging what would be stored
+        #[arg(long)]
+        dry_run: bool,
+        /// Print which selectors matched and the fields extracted, as JSON, instead of scraping
+        #[arg(long)]
+        debug_parse: bool,
     },
 }
 
+/// Prints the final tally of a scrape cycle
+fn print_report(report: &ScrapeReport) {
+    println!("📊 Scrape summary: {}", report);
+    for (url, error) in &report.failed {
+        println!("   ❌ {}: {}", url, error);
+    }
+}
+
+fn print_capabilities(capabilities: &Capabilities, storage: &str, model: &str) {
+    let mark = |names: &[String], selected: &str| {
+        names
+            .iter()
+            .map(|name| if name.eq_ignore_ascii_case(selected) { format!("{} (selected)", name) } else { name.clone() })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    println!("💾 Storage backends: {}", mark(&capabilities.storage_backends, storage));
+    println!("🧠 Inference models: {}", mark(&capabilities.inference_models, model));
+    println!("🦗 Scrapers: {}", capabilities.scrapers.join(", "));
+    if !capabilities.storage_backends.iter().any(|name| name.eq_ignore_ascii_case(storage)) {
+        println!("   ❌ Storage backend {} is not available in this build", storage);
+    }
+    if !capabilities.inference_models.iter().any(|name| name.eq_ignore_ascii_case(model)) {
+        println!("   ❌ Inference model {} is not available in this build", model);
+    }
+}
+
 async fn create_storage<T: StorageBackend + ArticleStorage + 'static>(backend_url: Option<&str>) -> Result<Arc<dyn ArticleStorage>> {
     let mut retries = 3;
     let mut last_error = None;
@@ -195,14 +278,37 @@ async fn main() -> Result<()> {
     tracing_subscriber::fmt::init();
     let cli = Cli::parse();
 
+    // Reported before connecting, so it works when the configured backends are down
+    if let Commands::Doctor = cli.command {
+        print_capabilities(&nt_scrappers::runtime_capabilities().await, &cli.storage, &cli.model);
+        return Ok(());
+    }
+
     let storage: Arc<dyn ArticleStorage> = nt_storage::create_storage(
         cli.storage.as_str(),
         cli.backend_url.as_deref()
     ).await?;
+    run(cli, storage).await
+}
+
+impl Commands {
+    /// Whether the command was asked not to write to storage
+    fn is_dry_run(&self) -> bool {
+        matches!(
+            self,
+            Commands::Scrape { command: Some(ScraperCommands::Source { dry_run: true, .. } | ScraperCommands::Url { dry_run: true, .. }) }
+        )
+    }
+}
+
+/// Runs `cli`'s command against `storage`
+async fn run(cli: Cli, storage: Arc<dyn ArticleStorage>) -> Result<()> {
+    // Instances scraping into the same storage share this lock in periodic mode
+    let lock_key = format!("{}|{}", cli.storage, cli.backend_url.as_deref().unwrap_or_default());
 
     // Check storage connection
     info!("💾 Checking storage connection...");
-    check_storage(&storage, cli.storage.as_str()).await?;
+    check_storage(&storage, cli.storage.as_str(), cli.command.is_dry_run()).await?;
     info!("✨ Storage initialized successfully (using {})", cli.storage);
 
     // Initialize inference model based on configuration
@@ -219,6 +325,10 @@ async fn main() -> Result<()> {
     let inference = nt_inference::models::create_model(Some(config)).await?;
     info!("🧠 Inference model initialized successfully (using {})", inference.name());
     let mut manager = ScraperManager::new(storage.clone(), inference.clone()).await?;
+    if let Some(path) = &cli.inference_cache {
+        manager.set_inference_cache(path).await?;
+        info!("🗃️ Caching inference results in {}", path.display());
+    }
     
     // Add all available scrapers
     let mut scraper_names = Vec::new();
@@ -230,25 +340,39 @@ async fn main() -> Result<()> {
     info!("🦗 Scrapers initialized successfully: {}", scraper_names.join(", "));
 
     match cli.command {
-        Commands::Scrape { command } => match command.unwrap_or(ScraperCommands::Source { source: None, interval: None }) {
-            ScraperCommands::Source { source, interval } => {
+        Commands::Scrape { command } => match command.unwrap_or(ScraperCommands::Source { source: None, interval: None, update_existing: false, dry_run: false }) {
+            ScraperCommands::Source { source, interval, update_existing, dry_run } => {
                 info!("🦗 Scraping articles from {}", if source.is_none() || source.as_ref().unwrap().is_empty() { "all sources" } else { source.as_ref().unwrap() });
                 let args = ScraperArgs {
-                    command: NtScraperCommands::Source { source: source.map(|s| s.to_string()) },
+                    command: NtScraperCommands::Source { source: source.map(|s| s.to_string()), update_existing, dry_run },
                 };
                 
                 if let Some(interval) = interval {
+                    // Cycles refresh the lock, so it only goes stale if this instance died
+                    let lock = ScrapeLock::acquire(&lock_key, interval.0 + DEFAULT_LOCK_STALE_AFTER)?;
                     info!("Running in periodic mode with {} interval", interval.0.as_secs());
-                    loop {
-                        info!("Starting scrape cycle");
-                        if let Err(e) = handle_command(args.clone(), &mut manager).await {
-                            eprintln!("Error during scrape: {}", e);
+                    let periodic = async {
+                        loop {
+                            info!("Starting scrape cycle");
+                            if let Err(e) = lock.refresh() {
+                                info!("⚠️ Failed to refresh lock {}: {}", lock.path().display(), e);
+                            }
+                            match handle_command(args.clone(), &mut manager).await {
+                                Ok(report) => print_report(&report),
+                                Err(e) => eprintln!("Error during scrape: {}", e),
+                            }
+                            info!("Waiting {}s before next scrape", interval.0.as_secs());
+                            tokio::time::sleep(interval.0).await;
                         }
-                        info!("Waiting {}s before next scrape", interval.0.as_secs());
-                        tokio::time::sleep(interval.0).await;
+                    };
+                    // Stop on Ctrl-C so the lock is released on the way out
+                    tokio::select! {
+                        _ = periodic => {}
+                        _ = tokio::signal::ctrl_c() => info!("Interrupted, releasing {}", lock.path().display()),
                     }
+                    return Ok(());
                 } else {
-                    handle_command(args, &mut manager).await?;
+                    print_report(&handle_command(args, &mut manager).await?);
                 }
             }
             ScraperCommands::List => {
@@ -257,35 +381,156 @@ async fn main() -> Result<()> {
                 };
                 handle_command(args, &mut manager).await?;
             }
-            ScraperCommands::Url { url } => {
+            ScraperCommands::Url { url, update_existing, dry_run, debug_parse } => {
                 info!("Scraping single URL: {}", url);
                 let args = ScraperArgs {
-                    command: NtScraperCommands::Url { url: url.clone() },
+                    command: NtScraperCommands::Url { url: url.clone(), update_existing, dry_run, debug_parse },
                 };
-                handle_command(args, &mut manager).await?;
+                let report = handle_command(args, &mut manager).await?;
+                if !debug_parse {
+                    print_report(&report);
+                }
             }
         },
+        Commands::Divergence { url, limit } => {
+            let article = match storage.get_article(&url).await? {
+                Some(article) => article,
+                None => {
+                    info!("Article not stored, scraping {}", url);
+                    manager.scrape_url(&url).await?
+                }
+            };
+            let analyzer = nt_inference::divergence::DivergenceAnalyzer::new(inference.clone());
+            let analysis = analyzer.analyze_against(&article, storage.as_ref(), limit).await?;
+            println!("{}", serde_json::to_string_pretty(&analysis)?);
+            return Ok(());
+        }
+        Commands::Prune { older_than } => {
+            let age = chrono::Duration::from_std(older_than.0)
+                .map_err(|e| nt_core::Error::External(e.into()))?;
+            let cutoff = Utc::now() - age;
+            let deleted = storage.delete_older_than(cutoff).await?;
+            println!("🗑️ Pruned {} articles published before {}", deleted, cutoff.to_rfc3339());
+            return Ok(());
+        }
+        Commands::Doctor => unreachable!("handled before initialization"),
+        Commands::Serve { addr, id_scheme } => {
+            let state = nt_web::AppState::new(inference.clone(), storage.clone()).await?;
+            nt_web::serve(state, nt_web::WebConfig { addr, id_scheme }).await?;
+            return Ok(());
+        }
+        Commands::Stats => {
+            let stats = storage.stats().await?;
+            println!("📚 {} articles, {} with embeddings", stats.articles, stats.embeddings);
+            if let (Some(earliest), Some(latest)) = (stats.earliest, stats.latest) {
+                println!("📅 Published from {} to {}", earliest.to_rfc3339(), latest.to_rfc3339());
+            }
+            for (source, count) in &stats.per_source {
+                println!("  {}: {}", source, count);
+            }
+            return Ok(());
+        }
     }
 
-    // Create a test article
-    let test_article = Article {
-        url: "http://test.com".to_string(),
-        title: "Test Article".to_string(),
-        content: "This is a test article.".to_string(),
-        published_at: chrono::Utc::now(),
-        source: "test".to_string(),
-        sections: vec![],
-        summary: None,
-        authors: vec!["Test Author".to_string()],
-        related_articles: Vec::new(),
-    };
+    Ok(())
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use chrono::DateTime;
+    use nt_storage::InMemoryStorage;
 
-    // Try to store the article with a test embedding
-    storage.store_article(&test_article, &vec![0.0; DEFAULT_VECTOR_SIZE as usize]).await?;
-    
-    // Try to retrieve it
-    let similar = storage.find_similar(&vec![0.0; DEFAULT_VECTOR_SIZE as usize], 1).await?;
-    println!("Found {} similar articles", similar.len());
+    /// Counts the calls that write to the wrapped storage
+    struct CountingStorage {
+        inner: InMemoryStorage,
+        writes: AtomicUsize,
+    }
 
-    Ok(())
-} 
\ No newline at end of file
+    impl CountingStorage {
+        fn write(&self) {
+            self.writes.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ArticleStorage for CountingStorage {
+        async fn store_article(&self, article: &Article, embedding: &[f32]) -> Result<()> {
+            self.write();
+            self.inner.store_article(article, embedding).await
+        }
+
+        async fn find_similar_scored(&self, embedding: &[f32], limit: usize) -> Result<Vec<(Article, f32)>> {
+            self.inner.find_similar_scored(embedding, limit).await
+        }
+
+        async fn get_by_source(&self, source: &str) -> Result<Vec<Article>> {
+            self.inner.get_by_source(source).await
+        }
+
+        async fn get_article(&self, url: &str) -> Result<Option<Article>> {
+            self.inner.get_article(url).await
+        }
+
+        async fn get_by_tag(&self, tag: &str) -> Result<Vec<Article>> {
+            self.inner.get_by_tag(tag).await
+        }
+
+        async fn list_articles(&self, offset: usize, limit: usize) -> Result<Vec<Article>> {
+            self.inner.list_articles(offset, limit).await
+        }
+
+        async fn list_tags(&self) -> Result<Vec<(String, usize)>> {
+            self.inner.list_tags().await
+        }
+
+        async fn search_text(&self, query: &str, limit: usize) -> Result<Vec<Article>> {
+            self.inner.search_text(query, limit).await
+        }
+
+        async fn delete_article(&self, url: &str) -> Result<()> {
+            self.write();
+            self.inner.delete_article(url).await
+        }
+
+        async fn delete_older_than(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+            self.write();
+            self.inner.delete_older_than(cutoff).await
+        }
+
+        async fn get_article_embedding(&self, url: &str) -> Result<Vec<f32>> {
+            self.inner.get_article_embedding(url).await
+        }
+    }
+
+    async fn counting_storage() -> Arc<CountingStorage> {
+        Arc::new(CountingStorage { inner: InMemoryStorage::new().await.unwrap(), writes: AtomicUsize::new(0) })
+    }
+
+    fn cli(args: &[&str]) -> Cli {
+        // A backend URL of its own keeps the periodic scrape lock away from real runs
+        let lock = format!("dry-run-test-{}", std::process::id());
+        let base = ["nt", "--model", "dummy", "--backend-url", lock.as_str()];
+        Cli::parse_from(base.iter().chain(args))
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_does_not_write_to_storage() {
+        let storage = counting_storage().await;
+
+        // Source scrapes run in periodic mode, so stop after the first cycle
+        let scrape = run(cli(&["scrape", "source", "nowhere", "--dry-run"]), storage.clone());
+        assert!(tokio::time::timeout(Duration::from_secs(2), scrape).await.is_err());
+        assert_eq!(storage.writes.load(Ordering::SeqCst), 0);
+
+        // Fails to find a scraper, after the storage check
+        let result = run(cli(&["scrape", "url", "http://unknown.test/a", "--dry-run"]), storage.clone()).await;
+        assert!(result.is_err());
+        assert_eq!(storage.writes.load(Ordering::SeqCst), 0);
+        assert_eq!(storage.inner.list_articles(0, 10).await.unwrap().len(), 0);
+
+        // Other commands still check storage by writing to it
+        run(cli(&["stats"]), storage.clone()).await.unwrap();
+        assert_eq!(storage.writes.load(Ordering::SeqCst), 2);
+    }
+}
@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use async_trait::async_trait;
 use thiserror::Error;
 use std::any::Any;
+use std::future::Future;
+use std::time::Duration;
 
 pub trait UrlConfig {
     fn get_url(&self) -> String;
@@ -39,10 +41,49 @@ pub enum Error {
 
     #[error("External error: {0}")]
     External(#[from] anyhow::Error),
+
+    #[error("Operation timed out after {0:?}")]
+    Timeout(Duration),
+
+    #[error("Article already exists: {0}")]
+    AlreadyExists(String),
+
+    #[error("Not modified since last fetch: {0}")]
+    NotModified(String),
+
+    #[error("Operation cancelled: {0}")]
+    Cancelled(String),
+}
+
+impl Error {
+    /// Returns true if this error is a timeout
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Error::Timeout(_))
+    }
+
+    /// Returns true if the server reported the resource unchanged since the last fetch
+    pub fn is_not_modified(&self) -> bool {
+        matches!(self, Error::NotModified(_))
+    }
+
+    /// Returns true if the operation was aborted through its cancellation token
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, Error::Cancelled(_))
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Runs a future with a deadline, mapping an elapsed deadline to `Error::Timeout`
+pub async fn timeout<F, T>(duration: Duration, future: F) -> Result<T>
+where
+    F: Future<Output = T>,
+{
+    tokio::time::timeout(duration, future)
+        .await
+        .map_err(|_| Error::Timeout(duration))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Article {
     pub url: String,
@@ -54,6 +95,84 @@ pub struct Article {
     pub sections: Vec<ArticleSection>,
     pub authors: Vec<String>,
     pub related_articles: Vec<RelatedArticle>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl Article {
+    /// The id addressing this article under the default [`ArticleIdScheme`]
+    pub fn id(&self) -> String {
+        ArticleIdScheme::default().id(&self.url)
+    }
+
+    /// SHA-256 of the article content, hex encoded
+    pub fn content_hash(&self) -> String {
+        content_hash(&self.content)
+    }
+
+    /// Drops sections whose content repeats an earlier section, returning how many were dropped
+    pub fn dedup_sections(&mut self) -> usize {
+        let before = self.sections.len();
+        let mut seen = std::collections::HashSet::new();
+        self.sections.retain(|section| seen.insert(content_hash(&section.content)));
+        before - self.sections.len()
+    }
+}
+
+/// SHA-256 of `text`, hex encoded
+pub fn content_hash(text: &str) -> String {
+    use sha2::{Digest, Sha256};
+    format!("{:x}", Sha256::digest(text.as_bytes()))
+}
+
+/// How an article URL maps to the id that addresses the article, e.g. in web API paths.
+///
+/// URLs contain slashes and other characters that do not fit in a path
+/// segment, so they are never used as ids directly.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArticleIdScheme {
+    /// The URL, unpadded base64url encoded; decodes back to the URL
+    #[default]
+    Base64Url,
+    /// SHA-256 of the URL, hex encoded; fixed length, but only resolved by
+    /// looking through the stored articles
+    UrlHash,
+}
+
+impl ArticleIdScheme {
+    /// The id of the article stored under `url`
+    pub fn id(&self, url: &str) -> String {
+        use base64::Engine;
+        match self {
+            Self::Base64Url => base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(url),
+            Self::UrlHash => content_hash(url),
+        }
+    }
+
+    /// The URL `id` was made from, or `None` if `id` is malformed or the
+    /// scheme does not decode
+    pub fn url(&self, id: &str) -> Option<String> {
+        use base64::Engine;
+        match self {
+            Self::Base64Url => base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(id).ok()
+                .and_then(|bytes| String::from_utf8(bytes).ok()),
+            Self::UrlHash => None,
+        }
+    }
+}
+
+impl std::str::FromStr for ArticleIdScheme {
+    type Err = String;
+
+    /// Parses the scheme's serialized name, `base64_url` or `url_hash`
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "base64_url" => Ok(Self::Base64Url),
+            "url_hash" => Ok(Self::UrlHash),
+            _ => Err(format!("Unknown article id scheme: {} (expected base64_url or url_hash)", s)),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,6 +189,124 @@ pub struct ArticleSection {
     pub embedding: Option<Vec<f32>>,
 }
 
+/// Metadata conditions an article has to meet, all of them at once.
+///
+/// The default filter matches every article.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArticleFilter {
+    /// Only articles from these sources, or from any source if empty
+    #[serde(default)]
+    pub sources: Vec<String>,
+    /// Never articles from these sources
+    #[serde(default)]
+    pub exclude_sources: Vec<String>,
+    /// Only articles published at or after this time
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+    /// Only articles published before this time
+    #[serde(default)]
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl ArticleFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also allow articles from `source`
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.sources.push(source.into());
+        self
+    }
+
+    /// Leave out articles from `source`
+    pub fn exclude_source(mut self, source: impl Into<String>) -> Self {
+        self.exclude_sources.push(source.into());
+        self
+    }
+
+    /// Leave out articles published before `time`
+    pub fn since(mut self, time: DateTime<Utc>) -> Self {
+        self.since = Some(time);
+        self
+    }
+
+    /// Leave out articles published at or after `time`
+    pub fn until(mut self, time: DateTime<Utc>) -> Self {
+        self.until = Some(time);
+        self
+    }
+
+    /// Returns true if the filter lets every article through
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns true if `article` meets every condition
+    pub fn matches(&self, article: &Article) -> bool {
+        (self.sources.is_empty() || self.sources.contains(&article.source))
+            && !self.exclude_sources.contains(&article.source)
+            && self.since.is_none_or(|since| article.published_at >= since)
+            && self.until.is_none_or(|until| article.published_at < until)
+    }
+}
+
+/// What a storage holds, see [`ArticleStorage::stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageStats {
+    /// Number of stored articles
+    pub articles: usize,
+    /// Number of stored articles from each source
+    pub per_source: std::collections::BTreeMap<String, usize>,
+    /// Publication date of the oldest stored article
+    pub earliest: Option<DateTime<Utc>>,
+    /// Publication date of the newest stored article
+    pub latest: Option<DateTime<Utc>>,
+    /// Number of articles stored with an embedding
+    pub embeddings: usize,
+}
+
+impl StorageStats {
+    /// Count one more stored article
+    pub fn record(&mut self, article: &Article, has_embedding: bool) {
+        self.articles += 1;
+        self.record_source(&article.source, article.published_at);
+        if has_embedding {
+            self.embeddings += 1;
+        }
+    }
+
+    /// Count an article in the per-source counts and the date range only, for
+    /// backends that get the totals from the store
+    pub fn record_source(&mut self, source: &str, published_at: DateTime<Utc>) {
+        *self.per_source.entry(source.to_string()).or_default() += 1;
+        self.earliest = Some(self.earliest.map_or(published_at, |d| d.min(published_at)));
+        self.latest = Some(self.latest.map_or(published_at, |d| d.max(published_at)));
+    }
+}
+
+/// The model that generated a storage's embeddings, see
+/// [`ArticleStorage::embedding_model`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbeddingModelInfo {
+    /// Name of the inference model
+    pub name: String,
+    /// Number of values in each embedding
+    pub dimension: usize,
+}
+
+/// What this build can do: the storage backends and inference models compiled
+/// in, and the scrapers registered.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// Names accepted by `nt_storage::create_storage`
+    pub storage_backends: Vec<String>,
+    /// Names accepted by `nt_inference::models::create_model`
+    pub inference_models: Vec<String>,
+    /// Names of the registered scrapers
+    pub scrapers: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ArticleStatus {
     New,
@@ -88,6 +325,39 @@ pub struct SourceMetadata {
     pub name: &'static str,
     pub emoji: &'static str,
     pub region: RegionMetadata,
+    /// ISO 639-1 code of the language the source publishes in
+    pub language: &'static str,
+}
+
+/// What one CSS selector matched while parsing a page, see [`ParseTrace`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelectorMatch {
+    /// Article field the selector extracts, e.g. `title`
+    pub field: String,
+    pub selector: String,
+    /// Number of elements matched
+    pub matches: usize,
+    /// Text of the first element matched, if any
+    pub first: Option<String>,
+}
+
+/// How a scraper parsed a page, see [`Scraper::debug_parse`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParseTrace {
+    pub url: String,
+    /// Selectors tried, in the order they were applied
+    pub selectors: Vec<SelectorMatch>,
+    /// Values extracted for the article, by field name
+    pub fields: std::collections::BTreeMap<String, String>,
+    /// Why no article could be built from the page, if none could
+    pub error: Option<String>,
+}
+
+impl ParseTrace {
+    /// The first selector tried for `field`
+    pub fn selector(&self, field: &str) -> Option<&SelectorMatch> {
+        self.selectors.iter().find(|m| m.field == field)
+    }
 }
 
 #[async_trait]
@@ -106,6 +376,34 @@ pub trait Scraper: Send + Sync {
 
     /// Returns a list of CLI shorthand names for this scraper
     fn cli_names(&self) -> Vec<&str>;
+
+    /// Explains how `html`, fetched from `url`, is parsed: which selectors
+    /// matched and what was extracted. Returns `None` if the scraper cannot.
+    fn debug_parse(&self, _url: &str, _html: &str) -> Option<ParseTrace> {
+        None
+    }
+}
+
+/// Operations and limits supported by an inference model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelCapabilities {
+    pub can_embed: bool,
+    pub can_summarize: bool,
+    /// Largest input the model accepts, in tokens, if it is limited
+    pub max_context_tokens: Option<usize>,
+    /// Length of the vectors returned by `generate_embeddings`, if known
+    pub embedding_dim: Option<usize>,
+}
+
+impl Default for ModelCapabilities {
+    fn default() -> Self {
+        Self {
+            can_embed: false,
+            can_summarize: true,
+            max_context_tokens: None,
+            embedding_dim: None,
+        }
+    }
 }
 
 #[async_trait]
@@ -113,6 +411,14 @@ pub trait InferenceModel: Send + Sync {
     /// Returns the name of the model
     fn name(&self) -> &str;
 
+    /// Describes what the model can do, so callers can check before using it.
+    ///
+    /// The default claims summarization with unknown limits, and no embeddings,
+    /// so models that embed have to say so along with the embedding size.
+    fn capabilities(&self) -> ModelCapabilities {
+        ModelCapabilities::default()
+    }
+
     /// Summarize an entire article
     async fn summarize_article(&self, article: &Article) -> Result<String>;
 
@@ -121,24 +427,242 @@ pub trait InferenceModel: Send + Sync {
 
     /// Generate embeddings for a piece of text
     async fn generate_embeddings(&self, text: &str) -> Result<Vec<f32>>;
+
+    /// Generate embeddings for several pieces of text, in the same order.
+    ///
+    /// The default implementation embeds them one by one; models with a native
+    /// batch endpoint should override it.
+    async fn generate_embeddings_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for text in texts {
+            embeddings.push(self.generate_embeddings(text).await?);
+        }
+        Ok(embeddings)
+    }
 }
 
 #[async_trait]
 pub trait ArticleStorage: Send + Sync + Any {
-    /// Store an article with its embedding
+    /// Store an article with its embedding, replacing any article stored under the same URL
     async fn store_article(&self, article: &Article, embedding: &[f32]) -> Result<()>;
 
+    /// Store an article only if no article exists under its URL yet.
+    ///
+    /// Returns `Error::AlreadyExists` on conflict. The default implementation checks
+    /// for an existing embedding first, so it is not atomic; backends should override
+    /// it where the store can enforce uniqueness itself.
+    async fn insert_only(&self, article: &Article, embedding: &[f32]) -> Result<()> {
+        if self.get_article_embedding(&article.url).await.is_ok() {
+            return Err(Error::AlreadyExists(article.url.clone()));
+        }
+        self.store_article(article, embedding).await
+    }
+
+    /// Store several articles with their embeddings.
+    ///
+    /// The default implementation stores them one by one; backends with a native
+    /// bulk write should override it.
+    async fn store_articles(&self, batch: &[(Article, Vec<f32>)]) -> Result<()> {
+        for (article, embedding) in batch {
+            self.store_article(article, embedding).await?;
+        }
+        Ok(())
+    }
+
     /// Find similar articles based on embedding
-    async fn find_similar(&self, embedding: &[f32], limit: usize) -> Result<Vec<Article>>;
+    async fn find_similar(&self, embedding: &[f32], limit: usize) -> Result<Vec<Article>> {
+        let scored = self.find_similar_scored(embedding, limit).await?;
+        Ok(scored.into_iter().map(|(article, _)| article).collect())
+    }
+
+    /// Find similar articles along with their similarity scores, best match first
+    async fn find_similar_scored(&self, embedding: &[f32], limit: usize) -> Result<Vec<(Article, f32)>>;
+
+    /// Find similar articles meeting `filter`, with their similarity scores, best match first.
+    ///
+    /// The default implementation over-fetches from [`find_similar_scored`](Self::find_similar_scored)
+    /// and filters the results, widening the search until enough articles match;
+    /// backends that can filter during the search should override it.
+    async fn find_similar_filtered(&self, embedding: &[f32], limit: usize, filter: &ArticleFilter) -> Result<Vec<(Article, f32)>> {
+        if filter.is_empty() {
+            return self.find_similar_scored(embedding, limit).await;
+        }
+
+        let mut fetch = limit.max(1).saturating_mul(4);
+        loop {
+            let scored = self.find_similar_scored(embedding, fetch).await?;
+            let exhausted = scored.len() < fetch || fetch == usize::MAX;
+            let matching: Vec<(Article, f32)> = scored.into_iter()
+                .filter(|(article, _)| filter.matches(article))
+                .collect();
+            if matching.len() >= limit || exhausted {
+                return Ok(matching.into_iter().take(limit).collect());
+            }
+            fetch = fetch.saturating_mul(4);
+        }
+    }
+
+    /// Find articles similar to `embedding` that also differ from each other,
+    /// with their similarity scores, in the order they were picked.
+    ///
+    /// Candidates are reranked with [`mmr_rerank`]: `lambda` 1.0 keeps the plain
+    /// top-k, lower values trade relevance for covering distinct stories. The
+    /// default implementation reranks the best [`MMR_CANDIDATE_FACTOR`] times
+    /// `limit` results of [`find_similar_scored`](Self::find_similar_scored),
+    /// fetching their embeddings with [`get_article_embeddings`](Self::get_article_embeddings).
+    async fn find_similar_diverse(&self, embedding: &[f32], limit: usize, lambda: f32) -> Result<Vec<(Article, f32)>> {
+        let scored = self.find_similar_scored(embedding, limit.saturating_mul(MMR_CANDIDATE_FACTOR)).await?;
+        let urls: Vec<String> = scored.iter().map(|(article, _)| article.url.clone()).collect();
+        let embeddings = self.get_article_embeddings(&urls).await?;
+        let candidates = scored.into_iter()
+            .zip(embeddings)
+            .map(|((article, score), article_embedding)| (article, score, article_embedding))
+            .collect();
+        Ok(mmr_rerank(candidates, limit, lambda))
+    }
 
     /// Get all articles from a specific source
     async fn get_by_source(&self, source: &str) -> Result<Vec<Article>>;
 
+    /// Get a single article by its URL, or `None` if it is not stored
+    async fn get_article(&self, url: &str) -> Result<Option<Article>>;
+
+    /// Get a single article by its id under `scheme`, or `None` if it is not stored.
+    ///
+    /// Ids that decode to a URL are looked up with [`get_article`](Self::get_article).
+    /// Otherwise the default implementation pages through [`list_articles`](Self::list_articles);
+    /// backends keeping an id index should override it.
+    async fn get_by_id(&self, id: &str, scheme: ArticleIdScheme) -> Result<Option<Article>> {
+        const PAGE_SIZE: usize = 500;
+        match scheme {
+            ArticleIdScheme::Base64Url => match scheme.url(id) {
+                Some(url) => self.get_article(&url).await,
+                None => Ok(None),
+            },
+            ArticleIdScheme::UrlHash => {
+                let mut offset = 0;
+                loop {
+                    let page = self.list_articles(offset, PAGE_SIZE).await?;
+                    if let Some(article) = page.iter().find(|article| scheme.id(&article.url) == id) {
+                        return Ok(Some(article.clone()));
+                    }
+                    if page.len() < PAGE_SIZE {
+                        return Ok(None);
+                    }
+                    offset += page.len();
+                }
+            }
+        }
+    }
+
+    /// Get all articles carrying the given tag
+    async fn get_by_tag(&self, tag: &str) -> Result<Vec<Article>>;
+
+    /// List stored articles ordered by URL, skipping `offset` and returning at most `limit`
+    async fn list_articles(&self, offset: usize, limit: usize) -> Result<Vec<Article>>;
+
+    /// List up to `limit` stored articles starting at `cursor`, with the cursor
+    /// of the next page, or `None` once every article has been listed.
+    ///
+    /// Pass `None` to start from the beginning. Cursors are opaque: the default
+    /// implementation encodes an offset into [`list_articles`](Self::list_articles),
+    /// backends override it to resume after the last article returned instead.
+    async fn list_articles_page(&self, cursor: Option<&str>, limit: usize) -> Result<(Vec<Article>, Option<String>)> {
+        let offset = match cursor {
+            Some(cursor) => cursor.parse()
+                .map_err(|_| Error::Storage(format!("Invalid page cursor: {}", cursor)))?,
+            None => 0,
+        };
+        let articles = self.list_articles(offset, limit).await?;
+        let next = (articles.len() == limit).then(|| (offset + limit).to_string());
+        Ok((articles, next))
+    }
+
+    /// List every distinct tag with the number of articles carrying it, most used first
+    async fn list_tags(&self) -> Result<Vec<(String, usize)>>;
+
+    /// Find articles whose title or content contains every term of `query`,
+    /// ignoring case, best match first
+    async fn search_text(&self, query: &str, limit: usize) -> Result<Vec<Article>>;
+
     /// Delete an article by its URL
     async fn delete_article(&self, url: &str) -> Result<()>;
 
+    /// Delete every article published before `cutoff`, returning how many were removed
+    async fn delete_older_than(&self, cutoff: DateTime<Utc>) -> Result<usize>;
+
     /// Get the embedding for an article by its URL
     async fn get_article_embedding(&self, url: &str) -> Result<Vec<f32>>;
+
+    /// Get the embeddings for several articles by their URLs, in the same order.
+    ///
+    /// Articles stored without an embedding get an empty one. The default
+    /// implementation asks [`get_article_embedding`](Self::get_article_embedding)
+    /// for each URL; backends should override it with a single query.
+    async fn get_article_embeddings(&self, urls: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut embeddings = Vec::with_capacity(urls.len());
+        for url in urls {
+            embeddings.push(self.get_article_embedding(url).await?);
+        }
+        Ok(embeddings)
+    }
+
+    /// The model the stored embeddings were generated with, when recorded by
+    /// [`set_embedding_model`](Self::set_embedding_model).
+    ///
+    /// The default implementation records nothing.
+    async fn embedding_model(&self) -> Result<Option<EmbeddingModelInfo>> {
+        Ok(None)
+    }
+
+    /// Record the model the stored embeddings were generated with
+    async fn set_embedding_model(&self, _model: &EmbeddingModelInfo) -> Result<()> {
+        Ok(())
+    }
+
+    /// Number of values every embedding must have, for backends whose vector
+    /// index is built for a single dimension, or `None` when any size is accepted
+    async fn index_dimension(&self) -> Result<Option<usize>> {
+        Ok(None)
+    }
+
+    /// Delete every stored article and rebuild the vector index for embeddings
+    /// of `dimension` values. Only backends with an
+    /// [`index_dimension`](Self::index_dimension) support it.
+    async fn recreate_index(&self, dimension: usize) -> Result<()> {
+        Err(Error::Storage(format!("This storage cannot rebuild its index for {} dimensions", dimension)))
+    }
+
+    /// Count the stored articles per source, with their publication date range
+    /// and how many of them have an embedding.
+    ///
+    /// The default implementation pages through [`list_articles`](Self::list_articles),
+    /// fetching each page's embeddings with [`get_article_embeddings`](Self::get_article_embeddings);
+    /// backends should override it with aggregate queries.
+    async fn stats(&self) -> Result<StorageStats> {
+        const PAGE_SIZE: usize = 500;
+        let mut stats = StorageStats::default();
+        loop {
+            let page = self.list_articles(stats.articles, PAGE_SIZE).await?;
+            let urls: Vec<String> = page.iter().map(|article| article.url.clone()).collect();
+            let embeddings = self.get_article_embeddings(&urls).await?;
+            for (article, embedding) in page.iter().zip(embeddings) {
+                stats.record(article, !embedding.is_empty());
+            }
+            if page.len() < PAGE_SIZE {
+                return Ok(stats);
+            }
+        }
+    }
+}
+
+/// Cursor of the page after `articles` for backends paging by URL, the last URL
+/// of a full page, see [`ArticleStorage::list_articles_page`]
+pub fn next_url_cursor(articles: &[Article], limit: usize) -> Option<String> {
+    if articles.len() < limit {
+        return None;
+    }
+    articles.last().map(|article| article.url.clone())
 }
 
 pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
@@ -152,4 +676,160 @@ pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     }
     
     dot_product / (norm_a * norm_b)
-} 
\ No newline at end of file
+}
+
+/// How many times `limit` candidates [`ArticleStorage::find_similar_diverse`] reranks
+pub const MMR_CANDIDATE_FACTOR: usize = 4;
+
+/// Picks up to `limit` of `candidates`, given as item, relevance score and
+/// embedding, by Maximal Marginal Relevance.
+///
+/// Each pick maximizes `lambda * relevance - (1 - lambda) * redundancy`, the
+/// redundancy being the highest cosine similarity to an item already picked,
+/// or 0 for none.
+/// `lambda` is clamped to [0, 1]; 1.0 orders by relevance alone. Items are
+/// returned with their relevance score, in the order they were picked.
+pub fn mmr_rerank<T>(candidates: Vec<(T, f32, Vec<f32>)>, limit: usize, lambda: f32) -> Vec<(T, f32)> {
+    let lambda = lambda.clamp(0.0, 1.0);
+    let mut remaining: Vec<Option<(T, f32, Vec<f32>)>> = candidates.into_iter().map(Some).collect();
+    let mut picked: Vec<(T, f32, Vec<f32>)> = Vec::new();
+
+    while picked.len() < limit {
+        let best = remaining.iter().enumerate()
+            .filter_map(|(i, candidate)| candidate.as_ref().map(|c| (i, c)))
+            .map(|(i, (_, relevance, embedding))| {
+                let redundancy = picked.iter()
+                    .map(|(_, _, other)| cosine_similarity(embedding, other))
+                    .fold(0.0, f32::max);
+                (i, lambda * relevance - (1.0 - lambda) * redundancy)
+            })
+            // Earlier candidates win ties, keeping the input order for equal scores
+            .fold(None, |best: Option<(usize, f32)>, (i, score)| match best {
+                Some((_, best_score)) if best_score >= score => best,
+                _ => Some((i, score)),
+            });
+        let Some((i, _)) = best else { break };
+        picked.extend(remaining[i].take());
+    }
+
+    picked.into_iter().map(|(item, relevance, _)| (item, relevance)).collect()
+}
+
+/// Count how many of the given articles carry each tag, most used first and
+/// alphabetically among ties.
+pub fn count_tags<'a>(articles: impl IntoIterator<Item = &'a Article>) -> Vec<(String, usize)> {
+    let mut counts: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+    for article in articles {
+        for tag in &article.tags {
+            *counts.entry(tag.as_str()).or_default() += 1;
+        }
+    }
+
+    let mut tags: Vec<(String, usize)> = counts.into_iter()
+        .map(|(tag, count)| (tag.to_string(), count))
+        .collect();
+    tags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    tags
+}
+
+/// Keep the articles whose title or content contains every whitespace-separated
+/// term of `query`, ignoring case.
+///
+/// Matches are ranked by how often the terms occur, with title occurrences
+/// weighing more than content ones, and at most `limit` are returned.
+pub fn search_articles(articles: impl IntoIterator<Item = Article>, query: &str, limit: usize) -> Vec<Article> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches: Vec<(Article, usize)> = articles.into_iter()
+        .filter_map(|article| {
+            let title = article.title.to_lowercase();
+            let content = article.content.to_lowercase();
+            let mut score = 0;
+            for term in &terms {
+                let hits = 3 * title.matches(term.as_str()).count() + content.matches(term.as_str()).count();
+                if hits == 0 {
+                    return None;
+                }
+                score += hits;
+            }
+            Some((article, score))
+        })
+        .collect();
+
+    matches.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.url.cmp(&b.0.url)));
+    matches.into_iter().take(limit).map(|(article, _)| article).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_timeout_elapsed() {
+        let duration = Duration::from_millis(10);
+        let result = timeout(duration, tokio::time::sleep(Duration::from_secs(5))).await;
+        match result {
+            Err(Error::Timeout(d)) => assert_eq!(d, duration),
+            other => panic!("Expected timeout error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeout_completes() {
+        let result = timeout(Duration::from_secs(1), async { 42 }).await;
+        assert_eq!(result.unwrap(), 42);
+    }
+
+    #[test]
+    fn test_is_timeout() {
+        assert!(Error::Timeout(Duration::from_secs(1)).is_timeout());
+        assert!(!Error::Storage("test".to_string()).is_timeout());
+        assert!(!Error::Scraping("test".to_string()).is_timeout());
+        assert!(!Error::Database("test".to_string()).is_timeout());
+    }
+
+    #[test]
+    fn test_article_id_schemes() {
+        let url = "https://example.com/news/2024/05/story?id=7";
+        let id = ArticleIdScheme::Base64Url.id(url);
+        assert!(!id.contains('/'));
+        assert_eq!(ArticleIdScheme::Base64Url.url(&id).as_deref(), Some(url));
+        assert_eq!(ArticleIdScheme::Base64Url.url("not base64!"), None);
+
+        let hash = ArticleIdScheme::UrlHash.id(url);
+        assert_eq!(hash.len(), 64);
+        assert_eq!(ArticleIdScheme::UrlHash.url(&hash), None);
+
+        assert_eq!("url_hash".parse(), Ok(ArticleIdScheme::UrlHash));
+        assert_eq!("base64_url".parse(), Ok(ArticleIdScheme::Base64Url));
+        assert!("sha256".parse::<ArticleIdScheme>().is_err());
+    }
+
+    #[test]
+    fn test_article_filter_matches() {
+        let now = Utc::now();
+        let article = Article {
+            url: "http://test.com".to_string(),
+            title: "Test Article".to_string(),
+            content: "Content.".to_string(),
+            summary: None,
+            published_at: now,
+            source: "clarin".to_string(),
+            sections: vec![],
+            authors: vec![],
+            related_articles: vec![],
+            tags: vec![],
+        };
+
+        assert!(ArticleFilter::new().matches(&article));
+        assert!(ArticleFilter::new().source("lanacion").source("clarin").matches(&article));
+        assert!(!ArticleFilter::new().source("lanacion").matches(&article));
+        assert!(!ArticleFilter::new().exclude_source("clarin").matches(&article));
+        assert!(ArticleFilter::new().since(now).until(now + chrono::Duration::days(1)).matches(&article));
+        assert!(!ArticleFilter::new().until(now).matches(&article));
+        assert!(!ArticleFilter::new().since(now + chrono::Duration::seconds(1)).matches(&article));
+    }
+}
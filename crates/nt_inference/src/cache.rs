@@ -0,0 +1,428 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use serde::{Deserialize, Serialize};
+use nt_core::{content_hash, Article, ArticleSection, ModelCapabilities, Result};
+use super::InferenceModel;
+
+/// New results a persistent cache collects before writing its file
+pub const DEFAULT_SAVE_EVERY: usize = 32;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheEntries {
+    summaries: HashMap<String, String>,
+    embeddings: HashMap<String, Vec<f32>>,
+    /// Results added since the file was last written
+    #[serde(skip)]
+    unsaved: usize,
+}
+
+/// Wraps a model and reuses its summaries and embeddings for content it has already seen.
+///
+/// Results are keyed by the hash of the wrapped model's identity, the kind of request
+/// and the summarized or embedded text, so a cache file shared by several models never
+/// hands one model's results to another, and a section is never given the summary of
+/// an article with the same text. A persistent cache is loaded from its file when created and
+/// written back every [`DEFAULT_SAVE_EVERY`] new results, on [`flush`](Self::flush),
+/// and when dropped.
+pub struct CachingModel {
+    inner: Arc<dyn InferenceModel>,
+    identity: String,
+    entries: Mutex<CacheEntries>,
+    path: Option<PathBuf>,
+    save_every: usize,
+    /// Keeps two saves from writing the temporary file at once
+    saving: tokio::sync::Mutex<()>,
+}
+
+impl fmt::Debug for CachingModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries = self.entries.lock().unwrap();
+        f.debug_struct("CachingModel")
+            .field("inner", &self.inner.name())
+            .field("summaries", &entries.summaries.len())
+            .field("embeddings", &entries.embeddings.len())
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+/// Names a model by its name and embedding size, which is all models expose about
+/// which weights they run
+fn model_identity(model: &dyn InferenceModel) -> String {
+    match model.capabilities().embedding_dim {
+        Some(dimension) => format!("{}/{}", model.name(), dimension),
+        None => model.name().to_string(),
+    }
+}
+
+impl CachingModel {
+    pub fn new(inner: Arc<dyn InferenceModel>) -> Self {
+        Self {
+            identity: model_identity(inner.as_ref()),
+            inner,
+            entries: Mutex::new(CacheEntries::default()),
+            path: None,
+            save_every: DEFAULT_SAVE_EVERY,
+            saving: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Creates a cache stored in `path`, starting from its contents if the file exists.
+    pub async fn persistent(inner: Arc<dyn InferenceModel>, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let entries = match tokio::fs::read(&path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => CacheEntries::default(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            identity: model_identity(inner.as_ref()),
+            inner,
+            entries: Mutex::new(entries),
+            path: Some(path),
+            save_every: DEFAULT_SAVE_EVERY,
+            saving: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Writes the file once `results` new results were added instead of every
+    /// [`DEFAULT_SAVE_EVERY`]
+    pub fn with_save_every(mut self, results: usize) -> Self {
+        self.save_every = results.max(1);
+        self
+    }
+
+    /// Number of cached summaries, including section summaries
+    pub fn summary_count(&self) -> usize {
+        self.entries.lock().unwrap().summaries.len()
+    }
+
+    /// Number of cached embeddings
+    pub fn embedding_count(&self) -> usize {
+        self.entries.lock().unwrap().embeddings.len()
+    }
+
+    /// Keys a request of the given kind, i.e. `article`, `section` or `embedding`
+    fn key(&self, kind: &str, text: &str) -> String {
+        content_hash(&format!("{}\n{}\n{}", self.identity, kind, text))
+    }
+
+    fn cached_summary(&self, key: &str) -> Option<String> {
+        self.entries.lock().unwrap().summaries.get(key).cloned()
+    }
+
+    /// Writes the file if enough results were added since it was last written
+    async fn save_if_due(&self) -> Result<()> {
+        if self.entries.lock().unwrap().unsaved >= self.save_every {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Writes the results added since the last save to the cache file, if any.
+    ///
+    /// The file is replaced in one step, so a crash mid-write leaves the previous version.
+    pub async fn flush(&self) -> Result<()> {
+        let Some(path) = &self.path else { return Ok(()) };
+        let _saving = self.saving.lock().await;
+        let (bytes, saved) = {
+            let entries = self.entries.lock().unwrap();
+            if entries.unsaved == 0 {
+                return Ok(());
+            }
+            (serde_json::to_vec(&*entries)?, entries.unsaved)
+        };
+        let tmp = path.with_extension("tmp");
+        tokio::fs::write(&tmp, bytes).await?;
+        tokio::fs::rename(&tmp, path).await?;
+        // Results added while writing wait for the next save
+        let mut entries = self.entries.lock().unwrap();
+        entries.unsaved = entries.unsaved.saturating_sub(saved);
+        Ok(())
+    }
+}
+
+impl Drop for CachingModel {
+    fn drop(&mut self) {
+        let Some(path) = &self.path else { return };
+        let entries = self.entries.get_mut().unwrap_or_else(|e| e.into_inner());
+        if entries.unsaved == 0 {
+            return;
+        }
+        let tmp = path.with_extension("tmp");
+        let saved = serde_json::to_vec(&*entries)
+            .map_err(std::io::Error::from)
+            .and_then(|bytes| std::fs::write(&tmp, bytes))
+            .and_then(|()| std::fs::rename(&tmp, path));
+        if let Err(e) = saved {
+            tracing::warn!("Could not save inference cache {}: {}", path.display(), e);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl InferenceModel for CachingModel {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn capabilities(&self) -> ModelCapabilities {
+        self.inner.capabilities()
+    }
+
+    async fn summarize_article(&self, article: &Article) -> Result<String> {
+        let key = self.key("article", &article.content);
+        if let Some(summary) = self.cached_summary(&key) {
+            tracing::debug!("Using cached summary for {}", article.url);
+            return Ok(summary);
+        }
+
+        let summary = self.inner.summarize_article(article).await?;
+        {
+            let mut entries = self.entries.lock().unwrap();
+            entries.summaries.insert(key, summary.clone());
+            entries.unsaved += 1;
+        }
+        self.save_if_due().await?;
+        Ok(summary)
+    }
+
+    async fn summarize_sections(&self, sections: &[ArticleSection]) -> Result<Vec<String>> {
+        let keys: Vec<String> = sections.iter().map(|section| self.key("section", &section.content)).collect();
+        let mut summaries: Vec<Option<String>> = keys.iter().map(|key| self.cached_summary(key)).collect();
+
+        let missing: Vec<usize> = (0..sections.len()).filter(|&i| summaries[i].is_none()).collect();
+        if missing.is_empty() {
+            return Ok(summaries.into_iter().flatten().collect());
+        }
+
+        let uncached: Vec<ArticleSection> = missing.iter().map(|&i| sections[i].clone()).collect();
+        let generated = self.inner.summarize_sections(&uncached).await?;
+        {
+            let mut entries = self.entries.lock().unwrap();
+            for (&i, summary) in missing.iter().zip(generated) {
+                entries.summaries.insert(keys[i].clone(), summary.clone());
+                entries.unsaved += 1;
+                summaries[i] = Some(summary);
+            }
+        }
+        self.save_if_due().await?;
+        Ok(summaries.into_iter().flatten().collect())
+    }
+
+    async fn generate_embeddings(&self, text: &str) -> Result<Vec<f32>> {
+        let key = self.key("embedding", text);
+        if let Some(embedding) = self.entries.lock().unwrap().embeddings.get(&key) {
+            return Ok(embedding.clone());
+        }
+
+        let embedding = self.inner.generate_embeddings(text).await?;
+        {
+            let mut entries = self.entries.lock().unwrap();
+            entries.embeddings.insert(key, embedding.clone());
+            entries.unsaved += 1;
+        }
+        self.save_if_due().await?;
+        Ok(embedding)
+    }
+
+    async fn generate_embeddings_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let keys: Vec<String> = texts.iter().map(|text| self.key("embedding", text)).collect();
+        let mut embeddings: Vec<Option<Vec<f32>>> = {
+            let entries = self.entries.lock().unwrap();
+            keys.iter().map(|key| entries.embeddings.get(key).cloned()).collect()
+        };
+
+        let missing: Vec<usize> = (0..texts.len()).filter(|&i| embeddings[i].is_none()).collect();
+        if missing.is_empty() {
+            return Ok(embeddings.into_iter().flatten().collect());
+        }
+
+        let uncached: Vec<String> = missing.iter().map(|&i| texts[i].clone()).collect();
+        let generated = self.inner.generate_embeddings_batch(&uncached).await?;
+        {
+            let mut entries = self.entries.lock().unwrap();
+            for (&i, embedding) in missing.iter().zip(generated) {
+                entries.embeddings.insert(keys[i].clone(), embedding.clone());
+                entries.unsaved += 1;
+                embeddings[i] = Some(embedding);
+            }
+        }
+        self.save_if_due().await?;
+        Ok(embeddings.into_iter().flatten().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use crate::models::dummy::DummyModel;
+
+    /// Counts the calls that reach the wrapped model
+    #[derive(Default)]
+    struct CountingModel {
+        summaries: AtomicUsize,
+        embeddings: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl InferenceModel for CountingModel {
+        fn name(&self) -> &str {
+            "Counting"
+        }
+
+        async fn summarize_article(&self, article: &Article) -> Result<String> {
+            self.summaries.fetch_add(1, Ordering::SeqCst);
+            DummyModel.summarize_article(article).await
+        }
+
+        async fn summarize_sections(&self, sections: &[ArticleSection]) -> Result<Vec<String>> {
+            self.summaries.fetch_add(sections.len(), Ordering::SeqCst);
+            DummyModel.summarize_sections(sections).await
+        }
+
+        async fn generate_embeddings(&self, text: &str) -> Result<Vec<f32>> {
+            self.embeddings.fetch_add(1, Ordering::SeqCst);
+            DummyModel.generate_embeddings(text).await
+        }
+    }
+
+    fn article(content: &str) -> Article {
+        Article {
+            url: "http://test.com".to_string(),
+            title: "Test Article".to_string(),
+            content: content.to_string(),
+            published_at: chrono::Utc::now(),
+            source: "test".to_string(),
+            sections: vec![],
+            summary: None,
+            authors: vec![],
+            related_articles: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    fn section(content: &str) -> ArticleSection {
+        ArticleSection { content: content.to_string(), summary: None, embedding: None }
+    }
+
+    #[tokio::test]
+    async fn test_summary_is_cached_by_content() {
+        let inner = Arc::new(CountingModel::default());
+        let model = CachingModel::new(inner.clone());
+
+        let first = model.summarize_article(&article("The budget passed on Monday.")).await.unwrap();
+        let second = model.summarize_article(&article("The budget passed on Monday.")).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(inner.summaries.load(Ordering::SeqCst), 1);
+
+        model.summarize_article(&article("The budget failed on Tuesday.")).await.unwrap();
+        assert_eq!(inner.summaries.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sections_only_summarize_uncached() {
+        let inner = Arc::new(CountingModel::default());
+        let model = CachingModel::new(inner.clone());
+
+        model.summarize_sections(&[section("First section.")]).await.unwrap();
+        let summaries = model.summarize_sections(&[section("Second section."), section("First section.")]).await.unwrap();
+        assert_eq!(summaries, ["Second section.", "First section."]);
+        assert_eq!(inner.summaries.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_batch_only_embeds_uncached() {
+        let inner = Arc::new(CountingModel::default());
+        let model = CachingModel::new(inner.clone());
+
+        let first = model.generate_embeddings("First text.").await.unwrap();
+        let texts = ["Second text.".to_string(), "First text.".to_string()];
+        let embeddings = model.generate_embeddings_batch(&texts).await.unwrap();
+        assert_eq!(embeddings[0][0], "Second text.".len() as f32 / 1000.0);
+        assert_eq!(embeddings[1], first);
+        assert_eq!(inner.embeddings.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_persistent_cache_is_reused() {
+        let path = std::env::temp_dir().join(format!("nt_inference_cache_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let inner = Arc::new(CountingModel::default());
+        let model = CachingModel::persistent(inner.clone(), &path).await.unwrap();
+        model.summarize_article(&article("Persisted content.")).await.unwrap();
+        model.generate_embeddings("Persisted content.").await.unwrap();
+        // Fewer results than a batch are written when the cache is dropped
+        drop(model);
+
+        let model = CachingModel::persistent(inner.clone(), &path).await.unwrap();
+        assert_eq!((model.summary_count(), model.embedding_count()), (1, 1));
+        model.summarize_article(&article("Persisted content.")).await.unwrap();
+        model.generate_embeddings("Persisted content.").await.unwrap();
+        assert_eq!(inner.summaries.load(Ordering::SeqCst), 1);
+        assert_eq!(inner.embeddings.load(Ordering::SeqCst), 1);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_persistent_cache_saves_in_batches() {
+        let path = std::env::temp_dir().join(format!("nt_inference_cache_batch_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let model = CachingModel::persistent(Arc::new(CountingModel::default()), &path).await.unwrap().with_save_every(2);
+        model.generate_embeddings("First text.").await.unwrap();
+        assert!(!path.exists());
+        model.generate_embeddings("Second text.").await.unwrap();
+        assert!(path.exists());
+        assert!(!path.with_extension("tmp").exists());
+
+        model.generate_embeddings("Third text.").await.unwrap();
+        model.flush().await.unwrap();
+        let saved: CacheEntries = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(saved.embeddings.len(), 3);
+
+        drop(model);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cache_is_keyed_by_model() {
+        let path = std::env::temp_dir().join(format!("nt_inference_cache_models_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let model = CachingModel::persistent(Arc::new(DummyModel), &path).await.unwrap();
+        model.generate_embeddings("Shared text.").await.unwrap();
+        drop(model);
+
+        // Another model sharing the file does not get the first model's embedding
+        let inner = Arc::new(CountingModel::default());
+        let model = CachingModel::persistent(inner.clone(), &path).await.unwrap();
+        assert_eq!(model.embedding_count(), 1);
+        model.generate_embeddings("Shared text.").await.unwrap();
+        assert_eq!(inner.embeddings.load(Ordering::SeqCst), 1);
+        assert_eq!(model.embedding_count(), 2);
+
+        drop(model);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sections_do_not_reuse_article_summaries() {
+        let inner = Arc::new(CountingModel::default());
+        let model = CachingModel::new(inner.clone());
+        model.summarize_article(&article("Same text.")).await.unwrap();
+
+        // A section with the text of an article body gets its own summary
+        model.summarize_sections(&[section("Same text.")]).await.unwrap();
+        assert_eq!(inner.summaries.load(Ordering::SeqCst), 2);
+        assert_eq!(model.summary_count(), 2);
+
+        model.summarize_sections(&[section("Same text.")]).await.unwrap();
+        assert_eq!(inner.summaries.load(Ordering::SeqCst), 2);
+    }
+}
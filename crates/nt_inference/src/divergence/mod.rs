@@ -1,4 +1,5 @@
-use nt_core::{Article, ArticleSection, Result};
+use nt_core::{Article, ArticleSection, ArticleStorage, Result, cosine_similarity};
+use serde::{Deserialize, Serialize};
 use super::InferenceModel;
 use std::sync::Arc;
 use std::fmt;
@@ -35,6 +36,54 @@ impl DivergenceAnalyzer {
         Ok(analysis)
     }
 
+    /// Compares every section of `article` with the sections of the `limit`
+    /// stored articles closest to it.
+    ///
+    /// A section's divergence score is one minus its best similarity to any
+    /// compared section, so sections no other source covers score highest.
+    pub async fn analyze_against(
+        &self,
+        article: &Article,
+        storage: &dyn ArticleStorage,
+        limit: usize,
+    ) -> Result<DivergenceAnalysis> {
+        let article_embedding = self.model.generate_embeddings(&article.content).await?;
+        let candidates: Vec<Article> = storage.find_similar(&article_embedding, limit.saturating_add(1)).await?
+            .into_iter()
+            .filter(|candidate| candidate.url != article.url)
+            .take(limit)
+            .collect();
+
+        let mut compared = Vec::new();
+        for candidate in &candidates {
+            for section in &candidate.sections {
+                let embedding = match &section.embedding {
+                    Some(embedding) => embedding.clone(),
+                    None => self.model.generate_embeddings(&section.content).await?,
+                };
+                compared.push((candidate.url.as_str(), section.content.as_str(), embedding));
+            }
+        }
+
+        let mut analysis = self.analyze_article(article).await?;
+        for section in &mut analysis.sections {
+            let Some(embedding) = &section.embedding else { continue };
+            section.similar_sections = compared.iter()
+                .map(|(url, content, other)| SimilarSection {
+                    content: content.to_string(),
+                    similarity_score: cosine_similarity(embedding, other),
+                    source_url: url.to_string(),
+                })
+                .collect();
+            section.similar_sections.sort_by(|a, b| b.similarity_score.total_cmp(&a.similarity_score));
+            section.similar_sections.truncate(limit);
+            section.divergence_score = section.similar_sections.first()
+                .map(|best| 1.0 - best.similarity_score);
+        }
+
+        Ok(analysis)
+    }
+
     async fn analyze_section(&self, section: &ArticleSection) -> Result<SectionAnalysis> {
         let embedding = self.model.generate_embeddings(&section.content).await?;
         
@@ -47,24 +96,35 @@ impl DivergenceAnalyzer {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DivergenceAnalysis {
     pub article_url: String,
     pub article_title: String,
     pub sections: Vec<SectionAnalysis>,
 }
 
-#[derive(Debug)]
-#[allow(dead_code)]
+impl DivergenceAnalysis {
+    /// Sections scoring at least `threshold`, most divergent first
+    pub fn divergent_sections(&self, threshold: f32) -> Vec<&SectionAnalysis> {
+        let mut sections: Vec<&SectionAnalysis> = self.sections.iter()
+            .filter(|section| section.divergence_score.is_some_and(|score| score >= threshold))
+            .collect();
+        sections.sort_by(|a, b| b.divergence_score.unwrap_or(0.0).total_cmp(&a.divergence_score.unwrap_or(0.0)));
+        sections
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SectionAnalysis {
-    #[allow(dead_code)]
-    content: String,
-    embedding: Option<Vec<f32>>,
-    divergence_score: Option<f32>,
-    similar_sections: Vec<SimilarSection>,
+    pub content: String,
+    /// Left out of reports, where it would dwarf everything else
+    #[serde(skip)]
+    pub embedding: Option<Vec<f32>>,
+    pub divergence_score: Option<f32>,
+    pub similar_sections: Vec<SimilarSection>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SimilarSection {
     pub content: String,
     pub similarity_score: f32,
@@ -99,9 +159,90 @@ mod tests {
             summary: None,
             authors: vec![],
             related_articles: vec![],
+            tags: Vec::new(),
         };
 
         let analysis = analyzer.analyze_article(&article).await.unwrap();
         assert_eq!(analysis.article_url, article.url);
     }
+
+    /// Embeds text by counting a few topic keywords, so similarity is predictable
+    struct KeywordModel;
+
+    #[async_trait::async_trait]
+    impl InferenceModel for KeywordModel {
+        fn name(&self) -> &str {
+            "Keyword"
+        }
+
+        async fn summarize_article(&self, article: &Article) -> Result<String> {
+            Ok(article.title.clone())
+        }
+
+        async fn summarize_sections(&self, sections: &[ArticleSection]) -> Result<Vec<String>> {
+            Ok(sections.iter().map(|s| s.content.clone()).collect())
+        }
+
+        async fn generate_embeddings(&self, text: &str) -> Result<Vec<f32>> {
+            let text = text.to_lowercase();
+            Ok(["budget", "protest", "weather"].iter()
+                .map(|keyword| text.matches(keyword).count() as f32)
+                .collect())
+        }
+    }
+
+    fn article(url: &str, sections: &[&str]) -> Article {
+        Article {
+            url: url.to_string(),
+            title: url.to_string(),
+            content: sections.join("\n"),
+            published_at: Utc::now(),
+            source: "test".to_string(),
+            sections: sections.iter()
+                .map(|content| ArticleSection {
+                    content: content.to_string(),
+                    summary: None,
+                    embedding: None,
+                })
+                .collect(),
+            summary: None,
+            authors: vec![],
+            related_articles: vec![],
+            tags: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_divergence_report() {
+        let model = Arc::new(KeywordModel);
+        let storage = nt_storage::backends::memory::MemoryStorage::new().await.unwrap();
+        for stored in [
+            article("http://a.test/budget", &["The budget passed.", "The budget cuts schools."]),
+            article("http://b.test/budget", &["Lawmakers approved the budget."]),
+        ] {
+            let embedding = model.generate_embeddings(&stored.content).await.unwrap();
+            storage.store_article(&stored, &embedding).await.unwrap();
+        }
+
+        let analyzer = DivergenceAnalyzer::new(model);
+        let target = article("http://c.test/budget", &["The budget passed easily.", "A protest followed."]);
+        let analysis = analyzer.analyze_against(&target, &storage, 5).await.unwrap();
+
+        assert_eq!(analysis.sections.len(), 2);
+        assert_eq!(analysis.sections[0].divergence_score, Some(0.0));
+        assert_eq!(analysis.sections[1].divergence_score, Some(1.0));
+        assert_eq!(analysis.sections[0].similar_sections.len(), 3);
+
+        let divergent = analysis.divergent_sections(0.5);
+        assert_eq!(divergent.len(), 1);
+        assert_eq!(divergent[0].content, "A protest followed.");
+
+        let report = serde_json::to_value(&analysis).unwrap();
+        assert_eq!(report["article_url"], "http://c.test/budget");
+        assert_eq!(report["sections"][1]["divergence_score"], 1.0);
+        assert!(report["sections"][0].get("embedding").is_none());
+
+        let parsed: DivergenceAnalysis = serde_json::from_value(report).unwrap();
+        assert_eq!(parsed.sections[1].content, "A protest followed.");
+    }
 } 
\ No newline at end of file
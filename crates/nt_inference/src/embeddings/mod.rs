@@ -1,22 +1,60 @@
 use std::sync::Arc;
 use nt_core::{Article, Result};
+use nt_storage::BackendConfig;
 use super::InferenceModel;
 
 pub struct EmbeddingGenerator {
     model: Arc<dyn InferenceModel>,
+    /// Scale embeddings to unit length
+    normalize: bool,
+    /// Pad or truncate embeddings to this length
+    dimension: Option<usize>,
 }
 
 impl EmbeddingGenerator {
     pub fn new(model: Arc<dyn InferenceModel>) -> Self {
-        Self { model }
+        Self { model, normalize: false, dimension: None }
+    }
+
+    /// L2-normalizes every embedding before returning it.
+    pub fn with_normalization(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Zero-pads or truncates every embedding to `dimension` values.
+    pub fn with_dimension(mut self, dimension: usize) -> Self {
+        self.dimension = Some(dimension);
+        self
+    }
+
+    /// Sizes embeddings to fit the vectors stored by `config`.
+    pub fn for_backend(self, config: &BackendConfig) -> Self {
+        self.with_dimension(config.vector_size as usize)
     }
 
     pub async fn generate_article_embedding(&self, article: &Article) -> Result<Vec<f32>> {
-        self.model.generate_embeddings(&article.content).await
+        let embedding = self.model.generate_embeddings(&article.content).await?;
+        Ok(self.adjust(embedding))
     }
 
     pub async fn generate_text_embedding(&self, text: &str) -> Result<Vec<f32>> {
-        self.model.generate_embeddings(text).await
+        let embedding = self.model.generate_embeddings(text).await?;
+        Ok(self.adjust(embedding))
+    }
+
+    /// Applies the configured dimension, then normalization.
+    fn adjust(&self, mut embedding: Vec<f32>) -> Vec<f32> {
+        if let Some(dimension) = self.dimension {
+            embedding.resize(dimension, 0.0);
+        }
+        if self.normalize {
+            let norm = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+            if norm > 0.0 {
+                embedding.iter_mut().for_each(|x| *x /= norm);
+            }
+        }
+        embedding
     }
 }
 
@@ -24,6 +62,7 @@ impl EmbeddingGenerator {
 mod tests {
     use super::*;
     use crate::models::deepseek::DeepSeekModel;
+    use crate::models::dummy::DummyModel;
 
     #[tokio::test]
     async fn test_embedding_generation() {
@@ -40,6 +79,7 @@ mod tests {
             published_at: chrono::Utc::now(),
             sections: vec![],
             related_articles: vec![],
+            tags: Vec::new(),
         };
 
         let embedding = generator.generate_article_embedding(&article).await.unwrap();
@@ -48,4 +88,30 @@ mod tests {
         let text_embedding = generator.generate_text_embedding("Test text").await.unwrap();
         assert!(!text_embedding.is_empty());
     }
-} 
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_normalized_embedding() {
+        let model = Arc::new(DummyModel::new(None).await.unwrap());
+        let generator = EmbeddingGenerator::new(model).with_normalization(true);
+
+        let embedding = generator.generate_text_embedding("Some text to embed").await.unwrap();
+        let norm = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-5, "norm was {}", norm);
+    }
+
+    #[tokio::test]
+    async fn test_embedding_dimension_adjustment() {
+        let model: Arc<dyn InferenceModel> = Arc::new(DummyModel::new(None).await.unwrap());
+
+        let truncated = EmbeddingGenerator::new(model.clone()).with_dimension(16);
+        assert_eq!(truncated.generate_text_embedding("Test text").await.unwrap().len(), 16);
+
+        let config = BackendConfig::new(String::new(), "test".to_string(), nt_storage::EmbeddingModel::default(), 1024);
+        let padded = EmbeddingGenerator::new(model).for_backend(&config).with_normalization(true);
+        let embedding = padded.generate_text_embedding("Test text").await.unwrap();
+        assert_eq!(embedding.len(), 1024);
+        assert!(embedding[768..].iter().all(|&x| x == 0.0));
+        let norm = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-5);
+    }
+}
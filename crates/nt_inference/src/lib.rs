@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 pub mod models;
 pub mod embeddings;
 pub mod divergence;
+pub mod tokens;
+pub mod cache;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InferenceConfig {
@@ -82,10 +84,11 @@ impl Default for Config {
 pub mod prelude {
     pub use super::{Config, ModelConfig, InferenceConfig};
     pub use super::models::create_model;
-    pub use nt_core::{Article, ArticleSection, Result, Error};
+    pub use nt_core::{Article, ArticleSection, ModelCapabilities, Result, Error};
 }
 
 pub use models::create_model;
+pub use cache::CachingModel;
 
 #[cfg(test)]
 mod tests {
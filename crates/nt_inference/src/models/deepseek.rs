@@ -1,5 +1,5 @@
 use std::fmt;
-use nt_core::{Result, Article, ArticleSection};
+use nt_core::{Result, Article, ArticleSection, ModelCapabilities};
 use super::InferenceModel;
 
 pub struct DeepSeekModel {
@@ -29,6 +29,15 @@ impl InferenceModel for DeepSeekModel {
         "DeepSeek"
     }
 
+    fn capabilities(&self) -> ModelCapabilities {
+        ModelCapabilities {
+            can_embed: true,
+            can_summarize: true,
+            max_context_tokens: Some(64_000),
+            embedding_dim: Some(768),
+        }
+    }
+
     async fn summarize_article(&self, article: &Article) -> Result<String> {
         if self.api_key.is_none() {
             return Err(nt_core::Error::Inference("DeepSeek API key is required".to_string()));
@@ -107,6 +116,15 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_capabilities() {
+        let model = DeepSeekModel::new(Some("test-key".to_string())).unwrap();
+        let capabilities = model.capabilities();
+        assert!(capabilities.can_embed && capabilities.can_summarize);
+        assert_eq!(capabilities.max_context_tokens, Some(64_000));
+        assert_eq!(capabilities.embedding_dim, Some(768));
+    }
+
     #[tokio::test]
     async fn test_model_methods_require_api_key() {
         let model = DeepSeekModel::new(Some("test-key".to_string()));
@@ -124,6 +142,7 @@ mod tests {
             summary: None,
             authors: vec!["Test Author".to_string()],
             related_articles: Vec::new(),
+            tags: Vec::new(),
         };
 
         let result = model.summarize_article(&article).await;
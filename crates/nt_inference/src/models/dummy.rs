@@ -1,6 +1,6 @@
 use std::fmt;
-use nt_core::{Result, Article, ArticleSection};
-use super::{InferenceModel, Config};
+use nt_core::{Result, Article, ArticleSection, ModelCapabilities};
+use super::{InferenceModel, Config, summarize_sections_concurrently, SECTION_CONCURRENCY};
 
 pub struct DummyModel;
 
@@ -22,6 +22,15 @@ impl InferenceModel for DummyModel {
         "Dummy"
     }
 
+    fn capabilities(&self) -> ModelCapabilities {
+        ModelCapabilities {
+            can_embed: true,
+            can_summarize: true,
+            max_context_tokens: None,
+            embedding_dim: Some(768),
+        }
+    }
+
     async fn summarize_article(&self, article: &Article) -> Result<String> {
         // Take first 20 words and join them
         let words: Vec<&str> = article.content.split_whitespace().take(20).collect();
@@ -30,10 +39,10 @@ impl InferenceModel for DummyModel {
 
     async fn summarize_sections(&self, sections: &[ArticleSection]) -> Result<Vec<String>> {
         // For each section, take first 20 words
-        Ok(sections.iter().map(|section| {
+        summarize_sections_concurrently(sections, SECTION_CONCURRENCY, |section| async move {
             let words: Vec<&str> = section.content.split_whitespace().take(20).collect();
-            words.join(" ")
-        }).collect())
+            Ok(words.join(" "))
+        }).await
     }
 
     async fn generate_embeddings(&self, text: &str) -> Result<Vec<f32>> {
@@ -80,6 +89,7 @@ mod tests {
             summary: None,
             authors: vec!["Test Author".to_string()],
             related_articles: Vec::new(),
+            tags: Vec::new(),
         };
 
         let result = model.summarize_article(&article).await;
@@ -107,4 +117,57 @@ mod tests {
         assert_eq!(embedding.len(), 768);
         assert!(embedding[0] > 0.0); // Text length feature should be non-zero
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_available_models() {
+        let models = crate::models::get_available_models();
+        assert!(models.contains_key("dummy"));
+        assert!(models.contains_key("deepseek"));
+        assert_eq!(models.contains_key("ollama"), cfg!(feature = "ollama"));
+    }
+
+    #[tokio::test]
+    async fn test_capabilities() {
+        let config = Config { model_name: Some("dummy".to_string()), ..Config::default() };
+        let model = crate::create_model(Some(config)).await.unwrap();
+        let capabilities = model.capabilities();
+        assert_eq!(capabilities, ModelCapabilities {
+            can_embed: true,
+            can_summarize: true,
+            max_context_tokens: None,
+            embedding_dim: Some(768),
+        });
+
+        let embedding = model.generate_embeddings("Test text").await.unwrap();
+        assert_eq!(Some(embedding.len()), capabilities.embedding_dim);
+    }
+
+    #[tokio::test]
+    async fn test_section_summaries_keep_input_order() {
+        let model = DummyModel::new(None).await.unwrap();
+        let sections: Vec<ArticleSection> = (0..10)
+            .map(|i| ArticleSection { content: format!("Section {} content", i), summary: None, embedding: None })
+            .collect();
+
+        let summaries = model.summarize_sections(&sections).await.unwrap();
+        assert_eq!(summaries.len(), sections.len());
+        for (i, summary) in summaries.iter().enumerate() {
+            assert_eq!(summary, &format!("Section {} content", i));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_summaries_keep_input_order() {
+        let sections: Vec<ArticleSection> = (0..8)
+            .map(|i| ArticleSection { content: i.to_string(), summary: None, embedding: None })
+            .collect();
+
+        // Earlier sections take longer, so they finish last
+        let summaries = summarize_sections_concurrently(&sections, 8, |section| async move {
+            let i: u64 = section.content.parse().unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(5 * (8 - i))).await;
+            Ok(section.content.clone())
+        }).await.unwrap();
+        assert_eq!(summaries, ["0", "1", "2", "3", "4", "5", "6", "7"]);
+    }
+}
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 use std::fmt;
-use nt_core::{Result, Article, ArticleSection};
+use nt_core::{Result, Article, ArticleSection, ModelCapabilities};
 use super::{InferenceModel, Config};
 use crate::ModelConfig;
 use crate::InferenceConfig;
@@ -16,8 +16,12 @@ use {
     langchain_rust::llm::ollama::client::{Ollama, OllamaClient},
     langchain_rust::llm::client::GenerationOptions,
     langchain_rust::language_models::llm::LLM,
+    super::{summarize_sections_concurrently, SECTION_CONCURRENCY},
 };
 
+/// Length of the embeddings made without Ollama
+const FALLBACK_EMBEDDING_DIM: usize = 768;
+
 #[derive(Debug)]
 pub struct LangChainModelConfig {
     ollama_host: String,
@@ -67,15 +71,66 @@ impl LangChainModelConfig {
     }
 }
 
+/// Client for Ollama's `/api/embed` endpoint, which embeds several texts in one request.
+#[derive(Debug, Clone)]
+pub struct OllamaEmbeddings {
+    client: reqwest::Client,
+    url: String,
+    model: String,
+}
+
+impl OllamaEmbeddings {
+    pub fn new(config: &LangChainModelConfig) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: format!("{}:{}/api/embed", config.get_ollama_host(), config.get_ollama_port()),
+            model: config.get_model_name().to_string(),
+        }
+    }
+
+    /// Embeds `texts` with one request, returning the embeddings in the same order
+    pub async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        #[derive(serde::Deserialize)]
+        struct EmbedResponse {
+            embeddings: Vec<Vec<f32>>,
+        }
+
+        let response: EmbedResponse = self.client.post(&self.url)
+            .json(&serde_json::json!({ "model": self.model, "input": texts }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if response.embeddings.len() != texts.len() {
+            return Err(nt_core::Error::Inference(format!(
+                "Expected {} embeddings from Ollama, got {}", texts.len(), response.embeddings.len()
+            )));
+        }
+        Ok(response.embeddings)
+    }
+
+    /// Length of the embeddings the configured model returns, found by embedding a probe text
+    pub async fn dimension(&self) -> Result<usize> {
+        let embedded = self.embed(&["dimension".to_string()]).await?;
+        Ok(embedded.first().map_or(0, Vec::len))
+    }
+}
+
 pub struct LangChainModel {
     #[cfg(feature = "ollama")]
     ollama_client: Option<Ollama>,
+    #[cfg(feature = "ollama")]
+    embeddings: Option<OllamaEmbeddings>,
+    /// Length of the embeddings this model returns
+    embedding_dim: usize,
 }
 
 impl fmt::Debug for LangChainModel {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("LangChainModel")
             .field("ollama_client", &"<Ollama>")
+            .field("embedding_dim", &self.embedding_dim)
             .finish()
     }
 }
@@ -83,6 +138,9 @@ impl fmt::Debug for LangChainModel {
 impl LangChainModel {
     pub async fn new(config: Option<Config>) -> Result<Self> {
         #[cfg(feature = "ollama")]
+        let embeddings = config.as_ref()
+            .map(|config| OllamaEmbeddings::new(&LangChainModelConfig::from_inference_config(&config.inference_config)));
+        #[cfg(feature = "ollama")]
         let ollama_client = if let Some(config) = config {
             let model_config = LangChainModelConfig::from_inference_config(&config.inference_config);
             let client = Arc::new(OllamaClient::new(
@@ -143,9 +201,21 @@ impl LangChainModel {
             None
         };
 
+        // Embedding models differ in size, so ask the configured one
+        #[cfg(feature = "ollama")]
+        let embedding_dim = match &embeddings {
+            Some(embeddings) => embeddings.dimension().await?,
+            None => FALLBACK_EMBEDDING_DIM,
+        };
+        #[cfg(not(feature = "ollama"))]
+        let embedding_dim = FALLBACK_EMBEDDING_DIM;
+
         Ok(Self {
             #[cfg(feature = "ollama")]
             ollama_client,
+            #[cfg(feature = "ollama")]
+            embeddings,
+            embedding_dim,
         })
     }
 }
@@ -156,6 +226,16 @@ impl InferenceModel for LangChainModel {
         "LangChain"
     }
 
+    fn capabilities(&self) -> ModelCapabilities {
+        ModelCapabilities {
+            can_embed: true,
+            can_summarize: true,
+            // Ollama's default context window
+            max_context_tokens: Some(2048),
+            embedding_dim: Some(self.embedding_dim),
+        }
+    }
+
     async fn summarize_article(&self, article: &Article) -> Result<String> {
         #[cfg(feature = "ollama")]
         {
@@ -175,15 +255,12 @@ impl InferenceModel for LangChainModel {
         #[cfg(feature = "ollama")]
         {
             if let Some(ollama) = &self.ollama_client {
-                let mut summaries = Vec::new();
-                for section in sections {
+                return summarize_sections_concurrently(sections, SECTION_CONCURRENCY, |section| async move {
                     let prompt = format!("Please summarize the following section, make sure to include all the details and produce output in the same language as the original article:\n\n{}", section.content);
-                    let response = ollama.invoke(&prompt)
+                    ollama.invoke(&prompt)
                         .await
-                        .map_err(|e| nt_core::Error::External(anyhow!("Failed to generate section summary: {}", e)))?;
-                    summaries.push(response);
-                }
-                return Ok(summaries);
+                        .map_err(|e| nt_core::Error::External(anyhow!("Failed to generate section summary: {}", e)))
+                }).await;
             }
         }
         // Fallback to basic summaries if Ollama is not available
@@ -191,8 +268,15 @@ impl InferenceModel for LangChainModel {
     }
 
     async fn generate_embeddings(&self, text: &str) -> Result<Vec<f32>> {
-        // For now, generate a simple embedding based on text length and character frequencies
-        let mut embedding = vec![0.0; 768];
+        #[cfg(feature = "ollama")]
+        {
+            if let Some(embeddings) = &self.embeddings {
+                let mut embedded = embeddings.embed(&[text.to_string()]).await?;
+                return Ok(embedded.remove(0));
+            }
+        }
+        // Fallback when Ollama is not available: generate a simple embedding based on text length and character frequencies
+        let mut embedding = vec![0.0; FALLBACK_EMBEDDING_DIM];
         
         // Use text length as a feature
         let text_len = text.len() as f32;
@@ -205,10 +289,91 @@ impl InferenceModel for LangChainModel {
         }
         
         // Fill the embedding with character frequencies
-        for (i, (_, &count)) in char_freq.iter().enumerate().take(767) {
+        for (i, (_, &count)) in char_freq.iter().enumerate().take(FALLBACK_EMBEDDING_DIM - 1) {
             embedding[i + 1] = count as f32 / text_len;
         }
         
         Ok(embedding)
     }
-} 
\ No newline at end of file
+
+    async fn generate_embeddings_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        #[cfg(feature = "ollama")]
+        {
+            if let Some(embeddings) = &self.embeddings {
+                return embeddings.embed(texts).await;
+            }
+        }
+        let mut embedded = Vec::with_capacity(texts.len());
+        for text in texts {
+            embedded.push(self.generate_embeddings(text).await?);
+        }
+        Ok(embedded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Answers `/api/embed` requests with one embedding per input, holding its length
+    async fn spawn_embed_server() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                let body = loop {
+                    match socket.read(&mut buf).await {
+                        Ok(0) | Err(_) => break None,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    let Some((headers, body)) = text.split_once("\r\n\r\n") else { continue };
+                    let length = headers.lines()
+                        .find_map(|line| line.to_lowercase().strip_prefix("content-length: ").map(str::to_string))
+                        .and_then(|length| length.parse::<usize>().ok())
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        break Some(body.to_string());
+                    }
+                };
+                let Some(body) = body else { continue };
+                let request: serde_json::Value = serde_json::from_str(&body).unwrap();
+                let embeddings: Vec<Vec<f32>> = request["input"].as_array().unwrap().iter()
+                    .map(|text| vec![text.as_str().unwrap().len() as f32])
+                    .collect();
+                let body = serde_json::json!({ "model": request["model"], "embeddings": embeddings }).to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                    body.len(), body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{}/nomic-embed-text", addr)
+    }
+
+    #[tokio::test]
+    async fn test_ollama_embeds_batch_in_one_request() {
+        let url = spawn_embed_server().await;
+        let config = InferenceConfig { model_url: Some(url), ..Default::default() };
+        let embeddings = OllamaEmbeddings::new(&LangChainModelConfig::from_inference_config(&config));
+        assert_eq!(embeddings.model, "nomic-embed-text");
+
+        let texts = vec!["one".to_string(), "three".to_string()];
+        assert_eq!(embeddings.embed(&texts).await.unwrap(), vec![vec![3.0], vec![5.0]]);
+        assert!(embeddings.embed(&[]).await.unwrap().is_empty());
+        assert_eq!(embeddings.dimension().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_without_ollama() {
+        let model = LangChainModel::new(None).await.unwrap();
+        let capabilities = model.capabilities();
+        assert!(capabilities.can_embed);
+        assert_eq!(capabilities.embedding_dim, Some(FALLBACK_EMBEDDING_DIM));
+        assert_eq!(model.generate_embeddings("Test text").await.unwrap().len(), FALLBACK_EMBEDDING_DIM);
+    }
+}
@@ -1,5 +1,8 @@
+use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
-use nt_core::{Result, InferenceModel};
+use futures_util::{stream, StreamExt, TryStreamExt};
+use nt_core::{Result, InferenceModel, ArticleSection};
 use crate::Config;
 
 pub mod deepseek;
@@ -30,6 +33,50 @@ pub async fn create_model(config: Option<Config>) -> Result<Arc<dyn InferenceMod
             let model = dummy::DummyModel::new(Some(config)).await?;
             Ok(Arc::new(model))
         }
-        _ => Err(nt_core::Error::Inference(format!("Unknown model: {}. Available models: ollama, deepseek, dummy", model_name))),
+        _ => {
+            let mut available = get_available_models().into_keys().collect::<Vec<_>>();
+            available.sort();
+            Err(nt_core::Error::Inference(format!("Unknown model: {}. Available models: {}", model_name, available.join(", "))))
+        }
     }
-} 
\ No newline at end of file
+}
+
+/// Models [`create_model`] can build in this build, with a description of each.
+pub fn get_available_models() -> HashMap<String, String> {
+    let mut models = HashMap::new();
+    models.insert("deepseek".to_string(), "DeepSeek API".to_string());
+    models.insert("dummy".to_string(), "Offline model for tests".to_string());
+
+    #[cfg(feature = "ollama")]
+    models.insert("ollama".to_string(), "Ollama through LangChain".to_string());
+
+    models
+}
+
+/// Sections summarized at the same time by [`summarize_sections_concurrently`]
+pub const SECTION_CONCURRENCY: usize = 4;
+
+/// Runs `summarize` on up to `limit` sections at once, returning the summaries in section order.
+pub async fn summarize_sections_concurrently<'a, F, Fut>(
+    sections: &'a [ArticleSection],
+    limit: usize,
+    summarize: F,
+) -> Result<Vec<String>>
+where
+    F: Fn(&'a ArticleSection) -> Fut,
+    Fut: Future<Output = Result<String>>,
+{
+    // Collected up front so the stream holds plain futures rather than a borrowing closure
+    let pending: Vec<_> = sections.iter().enumerate()
+        .map(|(i, section)| {
+            let summary = summarize(section);
+            async move { summary.await.map(|summary| (i, summary)) }
+        })
+        .collect();
+    let mut summaries: Vec<(usize, String)> = stream::iter(pending)
+        .buffer_unordered(limit.max(1))
+        .try_collect()
+        .await?;
+    summaries.sort_by_key(|(i, _)| *i);
+    Ok(summaries.into_iter().map(|(_, summary)| summary).collect())
+}
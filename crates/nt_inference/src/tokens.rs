@@ -0,0 +1,186 @@
+use std::borrow::Cow;
+use nt_core::{Article, ArticleSection, InferenceModel, Result};
+
+/// Rough number of characters per token, used to estimate lengths without a tokenizer
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// Tokens of the context window left for the prompt's instructions and the summary
+pub const RESERVED_TOKENS: usize = 512;
+
+/// Approximate number of tokens in `text`
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Shortens `text` to roughly `max_tokens` tokens.
+///
+/// The cut is made after the last complete sentence that fits, unless that would
+/// drop more than half of the allowed length, in which case it falls back to the
+/// last word boundary.
+pub fn truncate_to_tokens(text: &str, max_tokens: usize) -> &str {
+    let max_chars = max_tokens.saturating_mul(CHARS_PER_TOKEN);
+    let Some((cut, _)) = text.char_indices().nth(max_chars) else {
+        return text;
+    };
+    let head = &text[..cut];
+
+    let sentence_end = head.char_indices()
+        .rev()
+        .filter(|&(_, c)| matches!(c, '.' | '!' | '?'))
+        .map(|(i, c)| i + c.len_utf8())
+        .find(|&end| text[end..].starts_with(char::is_whitespace));
+    if let Some(end) = sentence_end.filter(|&end| end >= cut / 2) {
+        return &text[..end];
+    }
+
+    match head.rfind(char::is_whitespace) {
+        Some(end) => head[..end].trim_end(),
+        None => head,
+    }
+}
+
+/// Tokens of text a model with a context window of `context_tokens` can be given
+/// to summarize, after [`RESERVED_TOKENS`].
+///
+/// Windows too small to spare the reserve still get half of them for the text.
+pub fn input_budget(context_tokens: usize) -> usize {
+    context_tokens.saturating_sub(RESERVED_TOKENS).max(context_tokens / 2)
+}
+
+/// `text` cut to `budget` tokens, if a budget is set and the text is over it
+fn fit(text: &str, budget: Option<usize>) -> Option<&str> {
+    budget.filter(|&max_tokens| estimate_tokens(text) > max_tokens)
+        .map(|max_tokens| truncate_to_tokens(text, max_tokens))
+}
+
+/// Summarizes `article`, truncating its content so it fits the model's context
+/// window along with the prompt and the summary.
+pub async fn summarize_truncated(model: &dyn InferenceModel, article: &Article) -> Result<String> {
+    let budget = model.capabilities().max_context_tokens.map(input_budget);
+    let article = match fit(&article.content, budget) {
+        Some(content) => {
+            let mut truncated = article.clone();
+            truncated.content = content.to_string();
+            tracing::debug!("Truncated {} to {:?} tokens for summarization", article.url, budget);
+            Cow::Owned(truncated)
+        }
+        None => Cow::Borrowed(article),
+    };
+    model.summarize_article(&article).await
+}
+
+/// Summarizes `sections`, truncating each one like [`summarize_truncated`] does
+/// with an article.
+pub async fn summarize_sections_truncated(model: &dyn InferenceModel, sections: &[ArticleSection]) -> Result<Vec<String>> {
+    let budget = model.capabilities().max_context_tokens.map(input_budget);
+    if sections.iter().all(|section| fit(&section.content, budget).is_none()) {
+        return model.summarize_sections(sections).await;
+    }
+
+    let truncated: Vec<ArticleSection> = sections.iter()
+        .map(|section| match fit(&section.content, budget) {
+            Some(content) => ArticleSection { content: content.to_string(), ..section.clone() },
+            None => section.clone(),
+        })
+        .collect();
+    model.summarize_sections(&truncated).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_text_is_kept() {
+        assert_eq!(truncate_to_tokens("A short text.", 100), "A short text.");
+    }
+
+    #[test]
+    fn test_truncates_on_sentence_boundary() {
+        let text = "The council met on Monday to discuss the budget. ".repeat(20);
+        let truncated = truncate_to_tokens(&text, 50);
+
+        assert!(truncated.ends_with("budget."));
+        assert!(truncated.len() <= 50 * CHARS_PER_TOKEN);
+        assert!(truncated.len() > 50 * CHARS_PER_TOKEN - 50);
+        assert!(text.starts_with(truncated));
+    }
+
+    #[test]
+    fn test_falls_back_to_word_boundary() {
+        let text = "word ".repeat(100);
+        let truncated = truncate_to_tokens(&text, 10);
+
+        assert_eq!(truncated, "word ".repeat(8).trim_end());
+    }
+
+    /// Records the length of the text it is asked to summarize
+    struct LimitedModel {
+        max_context_tokens: usize,
+        seen: std::sync::Mutex<Vec<usize>>,
+    }
+
+    #[async_trait::async_trait]
+    impl InferenceModel for LimitedModel {
+        fn name(&self) -> &str {
+            "Limited"
+        }
+
+        fn capabilities(&self) -> nt_core::ModelCapabilities {
+            nt_core::ModelCapabilities {
+                max_context_tokens: Some(self.max_context_tokens),
+                ..Default::default()
+            }
+        }
+
+        async fn summarize_article(&self, article: &Article) -> Result<String> {
+            self.seen.lock().unwrap().push(estimate_tokens(&article.content));
+            Ok(String::new())
+        }
+
+        async fn summarize_sections(&self, sections: &[ArticleSection]) -> Result<Vec<String>> {
+            let mut seen = self.seen.lock().unwrap();
+            seen.extend(sections.iter().map(|section| estimate_tokens(&section.content)));
+            Ok(vec![String::new(); sections.len()])
+        }
+
+        async fn generate_embeddings(&self, _text: &str) -> Result<Vec<f32>> {
+            Ok(vec![])
+        }
+    }
+
+    #[test]
+    fn test_input_budget_reserves_headroom() {
+        assert_eq!(input_budget(4096), 4096 - RESERVED_TOKENS);
+        assert_eq!(input_budget(600), 300);
+    }
+
+    #[tokio::test]
+    async fn test_article_and_sections_fit_with_headroom() {
+        let model = LimitedModel { max_context_tokens: 2048, seen: Default::default() };
+        let long = "The council met on Monday to discuss the budget. ".repeat(400);
+        let article = Article {
+            url: "http://test.com".to_string(),
+            title: "Test Article".to_string(),
+            content: long.clone(),
+            published_at: chrono::Utc::now(),
+            source: "test".to_string(),
+            sections: vec![],
+            summary: None,
+            authors: vec![],
+            related_articles: Vec::new(),
+            tags: Vec::new(),
+        };
+        let sections = [
+            ArticleSection { content: long, summary: None, embedding: None },
+            ArticleSection { content: "Short section.".to_string(), summary: None, embedding: None },
+        ];
+
+        summarize_truncated(&model, &article).await.unwrap();
+        summarize_sections_truncated(&model, &sections).await.unwrap();
+        let seen = model.seen.lock().unwrap();
+        assert!(seen[0] <= input_budget(2048));
+        assert!(seen[1] <= input_budget(2048));
+        assert_eq!(seen[2], estimate_tokens("Short section."));
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::VecDeque;
+use std::process::Stdio;
+use std::sync::Mutex;
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::sync::mpsc;
+
+/// A line of output produced by a command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandOutput {
+    Stdout(String),
+    Stderr(String),
+}
+
+/// Runs commands on behalf of [`ProgressManager::spawn_command`](crate::progress_manager::ProgressManager::spawn_command).
+///
+/// Implementations forward every output line to `output` as it is produced and
+/// return the exit code once the command has finished.
+#[async_trait]
+pub trait CommandRunner: Send + Sync + std::fmt::Debug {
+    /// Run `program` with `args`.
+    ///
+    /// # Returns
+    /// The exit code of the command, or None if it was terminated by a signal
+    async fn run(&self, program: &str, args: &[String], output: mpsc::Sender<CommandOutput>) -> Result<Option<i32>>;
+}
+
+/// Runs commands as real subprocesses
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TokioCommandRunner;
+
+#[async_trait]
+impl CommandRunner for TokioCommandRunner {
+    async fn run(&self, program: &str, args: &[String], output: mpsc::Sender<CommandOutput>) -> Result<Option<i32>> {
+        let mut child = tokio::process::Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let stderr = child.stderr.take().expect("stderr is piped");
+        let (stdout, stderr) = tokio::join!(
+            forward_lines(stdout, output.clone(), CommandOutput::Stdout),
+            forward_lines(stderr, output, CommandOutput::Stderr),
+        );
+        stdout?;
+        stderr?;
+
+        Ok(child.wait().await?.code())
+    }
+}
+
+async fn forward_lines<R: AsyncRead + Unpin>(
+    reader: R,
+    output: mpsc::Sender<CommandOutput>,
+    wrap: fn(String) -> CommandOutput,
+) -> Result<()> {
+    let mut lines = BufReader::new(reader).lines();
+    while let Some(line) = lines.next_line().await? {
+        // Keep draining the pipe even if nobody listens anymore, so the child never blocks
+        let _ = output.send(wrap(line)).await;
+    }
+    Ok(())
+}
+
+/// Replays scripted output and exit code instead of running a process.
+///
+/// Useful for testing code built on [`ProgressManager::spawn_command`](crate::progress_manager::ProgressManager::spawn_command)
+/// deterministically.
+#[derive(Debug, Default)]
+pub struct MockCommandRunner {
+    output: Vec<CommandOutput>,
+    exit_code: Option<i32>,
+    /// Program and arguments of every run, oldest first
+    calls: Mutex<VecDeque<(String, Vec<String>)>>,
+}
+
+impl MockCommandRunner {
+    /// Create a runner that produces no output and exits with code 0
+    pub fn new() -> Self {
+        Self {
+            exit_code: Some(0),
+            ..Self::default()
+        }
+    }
+
+    /// Add a line written to stdout
+    pub fn stdout(mut self, line: impl Into<String>) -> Self {
+        self.output.push(CommandOutput::Stdout(line.into()));
+        self
+    }
+
+    /// Add a line written to stderr
+    pub fn stderr(mut self, line: impl Into<String>) -> Self {
+        self.output.push(CommandOutput::Stderr(line.into()));
+        self
+    }
+
+    /// Set the exit code, or None to simulate termination by a signal
+    pub fn exit_code(mut self, code: Option<i32>) -> Self {
+        self.exit_code = code;
+        self
+    }
+
+    /// Take the recorded program and arguments of the oldest run not taken yet
+    pub fn take_call(&self) -> Option<(String, Vec<String>)> {
+        self.calls.lock().unwrap().pop_front()
+    }
+}
+
+#[async_trait]
+impl CommandRunner for MockCommandRunner {
+    async fn run(&self, program: &str, args: &[String], output: mpsc::Sender<CommandOutput>) -> Result<Option<i32>> {
+        self.calls.lock().unwrap().push_back((program.to_string(), args.to_vec()));
+        for line in &self.output {
+            let _ = output.send(line.clone()).await;
+        }
+        Ok(self.exit_code)
+    }
+}
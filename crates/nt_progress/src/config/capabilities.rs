@@ -1,3 +1,4 @@
+use crate::core::base_config::progress_percentage;
 use crate::core::job_traits::JobTracker;
 use crate::errors::ModeCreationError;
 
@@ -178,13 +179,7 @@ pub trait WithProgress: JobTracker + Send + Sync {
     /// # Returns
     /// A float between 0.0 and 100.0 representing the progress percentage
     fn get_progress_percentage(&self) -> f64 {
-        let total = self.get_total_jobs();
-        if total == 0 {
-            return 0.0;
-        }
-        
-        let completed = self.get_completed_jobs();
-        ((completed as f64) / (total as f64) * 100.0).min(100.0)
+        progress_percentage(self.get_completed_jobs(), self.get_total_jobs())
     }
     
     /// Get the number of completed jobs.
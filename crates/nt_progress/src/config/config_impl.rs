@@ -3,13 +3,17 @@ use crate::core::thread_config::{ThreadConfig, ThreadConfigExt};
 use crate::config::mode_parameters::ThreadMode;
 use crate::modes::window::Window;
 use crate::modes::window_with_title::WindowWithTitle;
+use crate::config::capabilities::WithCustomSize;
+use crate::terminal::TextWrapper;
 use crate::modes::limited::Limited;
 use crate::modes::capturing::Capturing;
+use crate::modes::spinner::Spinner;
 use crate::core::job_traits::{
     JobTracker, PausableJob, HierarchicalJobTracker, PrioritizedJob, DependentJob,
-    HasBaseConfig
+    PersistentJob, HasBaseConfig
 };
-use crate::core::base_config::BaseConfig;
+use crate::core::base_config::{BaseConfig, JobState, JobStatus};
+use crate::core::clock::Clock;
 
 // Add an internal incremented counter for tests
 #[cfg(test)]
@@ -24,12 +28,21 @@ thread_local! {
 #[derive(Debug)]
 pub struct Config {
     config: Box<dyn ThreadConfig>,
+    /// Whether captured lines are prefixed with their index within the task
+    line_numbers: bool,
+    /// Whether messages are split on embedded newlines before reaching the mode
+    split_newlines: bool,
+    /// Appended to lines cut to fit the terminal width
+    truncation_indicator: String,
 }
 
 impl Clone for Config {
     fn clone(&self) -> Self {
         Self {
             config: self.config.clone_box(),
+            line_numbers: self.line_numbers,
+            split_newlines: self.split_newlines,
+            truncation_indicator: self.truncation_indicator.clone(),
         }
     }
 }
@@ -68,6 +81,17 @@ impl Config {
                     "Progress".to_string(),
                 )?) as Box<dyn ThreadConfig>;
                 Ok(Self::from(window_with_title))
+            },
+            ThreadMode::Spinner => {
+                let spinner = Box::new(Spinner::new(total_jobs)) as Box<dyn ThreadConfig>;
+                Ok(Self::from(spinner))
+            },
+            ThreadMode::Custom(name) => {
+                // Custom modes only exist in a factory's registry
+                Err(ModeCreationError::ModeNotRegistered {
+                    mode_name: name,
+                    available_modes: Vec::new(),
+                })
             }
         }
     }
@@ -82,13 +106,22 @@ impl Config {
     
     /// Process a message and update the display.
     ///
+    /// Unless [`split_newlines`](Self::split_newlines) is disabled, a message
+    /// containing newlines is handed to the mode one line at a time.
+    ///
     /// # Parameters
     /// * `message` - The message to process
     ///
     /// # Returns
     /// A vector of strings representing the lines to display
     pub fn handle_message(&mut self, message: String) -> Vec<String> {
-        self.config.handle_message(message)
+        if !self.split_newlines || !message.contains('\n') {
+            return self.config.handle_message(message);
+        }
+        for line in message.lines() {
+            self.config.handle_message(line.to_string());
+        }
+        self.config.get_lines()
     }
     
     /// Get the current lines to display.
@@ -99,6 +132,68 @@ impl Config {
         self.config.get_lines()
     }
     
+    /// Prefix each captured line with its index within the task.
+    ///
+    /// Numbering is off by default. It helps correlate the ordering of
+    /// interleaved output from several tasks.
+    pub fn line_numbers(mut self, enabled: bool) -> Self {
+        self.line_numbers = enabled;
+        self
+    }
+    
+    /// Enable or disable line numbering, see [`line_numbers`](Self::line_numbers).
+    pub fn set_line_numbers(&mut self, enabled: bool) {
+        self.line_numbers = enabled;
+    }
+    
+    /// Check whether captured lines are numbered.
+    pub fn has_line_numbers(&self) -> bool {
+        self.line_numbers
+    }
+    
+    /// Split messages on embedded newlines so each becomes its own display line.
+    ///
+    /// Enabled by default. When disabled, the whole message is passed to the
+    /// mode, which may show it as a single line.
+    pub fn split_newlines(mut self, enabled: bool) -> Self {
+        self.split_newlines = enabled;
+        self
+    }
+    
+    /// Enable or disable newline splitting, see [`split_newlines`](Self::split_newlines).
+    pub fn set_split_newlines(&mut self, enabled: bool) {
+        self.split_newlines = enabled;
+    }
+    
+    /// Check whether messages are split on embedded newlines.
+    pub fn splits_newlines(&self) -> bool {
+        self.split_newlines
+    }
+    
+    /// Set the indicator appended to lines cut to fit the terminal width.
+    ///
+    /// Defaults to "…". The indicator counts towards the width of the line;
+    /// an empty indicator cuts lines without any visual cue.
+    pub fn truncation_indicator(mut self, indicator: String) -> Self {
+        self.truncation_indicator = indicator;
+        self
+    }
+    
+    /// Set the truncation indicator, see [`truncation_indicator`](Self::truncation_indicator).
+    pub fn set_truncation_indicator(&mut self, indicator: String) {
+        self.truncation_indicator = indicator;
+    }
+    
+    /// Get the indicator appended to truncated lines.
+    pub fn get_truncation_indicator(&self) -> &str {
+        &self.truncation_indicator
+    }
+    
+    /// Create a [`TextWrapper`] for `max_width` columns that uses this config's truncation indicator.
+    pub fn text_wrapper(&self, max_width: usize) -> TextWrapper {
+        TextWrapper::new(max_width).truncation_marker(self.truncation_indicator.clone())
+    }
+    
     /// Try to downcast the config to a specific type.
     ///
     /// # Type Parameters
@@ -418,24 +513,7 @@ impl Config {
     /// # Returns
     /// A mutable reference to the JobTracker, or None if the job tracker is not available
     fn as_job_tracker_mut(&mut self) -> Option<&mut dyn JobTracker> {
-        let type_id = self.config.as_any().type_id();
-        let any_mut = self.config.as_any_mut();
-        
-        match () {
-            _ if type_id == std::any::TypeId::of::<WindowWithTitle>() => {
-                any_mut.downcast_mut::<WindowWithTitle>().map(|t| t as &mut dyn JobTracker)
-            }
-            _ if type_id == std::any::TypeId::of::<Window>() => {
-                any_mut.downcast_mut::<Window>().map(|t| t as &mut dyn JobTracker)
-            }
-            _ if type_id == std::any::TypeId::of::<Limited>() => {
-                any_mut.downcast_mut::<Limited>().map(|t| t as &mut dyn JobTracker)
-            }
-            _ if type_id == std::any::TypeId::of::<Capturing>() => {
-                any_mut.downcast_mut::<Capturing>().map(|t| t as &mut dyn JobTracker)
-            }
-            _ => None,
-        }
+        self.config.base_mut().map(|base| base as &mut dyn JobTracker)
     }
     
     /// Helper method to get this config as a HierarchicalJobTracker.
@@ -443,17 +521,7 @@ impl Config {
     /// # Returns
     /// A reference to the HierarchicalJobTracker, or None if not available
     fn as_hierarchical_job_tracker(&self) -> Option<&dyn HierarchicalJobTracker> {
-        if let Some(tracker) = self.config.as_any().downcast_ref::<WindowWithTitle>() {
-            Some(tracker as &dyn HierarchicalJobTracker)
-        } else if let Some(tracker) = self.config.as_any().downcast_ref::<Window>() {
-            Some(tracker as &dyn HierarchicalJobTracker)
-        } else if let Some(tracker) = self.config.as_any().downcast_ref::<Limited>() {
-            Some(tracker as &dyn HierarchicalJobTracker)
-        } else if let Some(tracker) = self.config.as_any().downcast_ref::<Capturing>() {
-            Some(tracker as &dyn HierarchicalJobTracker)
-        } else {
-            None
-        }
+        self.config.base().map(|base| base as &dyn HierarchicalJobTracker)
     }
     
     /// Helper method to get this config as a mutable HierarchicalJobTracker.
@@ -461,24 +529,7 @@ impl Config {
     /// # Returns
     /// A mutable reference to the HierarchicalJobTracker, or None if not available
     fn as_hierarchical_job_tracker_mut(&mut self) -> Option<&mut dyn HierarchicalJobTracker> {
-        let type_id = self.config.as_any().type_id();
-        let any_mut = self.config.as_any_mut();
-        
-        match () {
-            _ if type_id == std::any::TypeId::of::<WindowWithTitle>() => {
-                any_mut.downcast_mut::<WindowWithTitle>().map(|t| t as &mut dyn HierarchicalJobTracker)
-            }
-            _ if type_id == std::any::TypeId::of::<Window>() => {
-                any_mut.downcast_mut::<Window>().map(|t| t as &mut dyn HierarchicalJobTracker)
-            }
-            _ if type_id == std::any::TypeId::of::<Limited>() => {
-                any_mut.downcast_mut::<Limited>().map(|t| t as &mut dyn HierarchicalJobTracker)
-            }
-            _ if type_id == std::any::TypeId::of::<Capturing>() => {
-                any_mut.downcast_mut::<Capturing>().map(|t| t as &mut dyn HierarchicalJobTracker)
-            }
-            _ => None,
-        }
+        self.config.base_mut().map(|base| base as &mut dyn HierarchicalJobTracker)
     }
     
     /// Set the parent job ID for this config.
@@ -552,17 +603,7 @@ impl Config {
     /// # Returns
     /// A reference to the PausableJob, or None if not available
     fn as_pausable_job(&self) -> Option<&dyn PausableJob> {
-        if let Some(tracker) = self.config.as_any().downcast_ref::<WindowWithTitle>() {
-            Some(tracker as &dyn PausableJob)
-        } else if let Some(tracker) = self.config.as_any().downcast_ref::<Window>() {
-            Some(tracker as &dyn PausableJob)
-        } else if let Some(tracker) = self.config.as_any().downcast_ref::<Limited>() {
-            Some(tracker as &dyn PausableJob)
-        } else if let Some(tracker) = self.config.as_any().downcast_ref::<Capturing>() {
-            Some(tracker as &dyn PausableJob)
-        } else {
-            None
-        }
+        self.config.base().map(|base| base as &dyn PausableJob)
     }
     
     /// Helper method to get this config as a mutable PausableJob.
@@ -570,24 +611,7 @@ impl Config {
     /// # Returns
     /// A mutable reference to the PausableJob, or None if not available
     fn as_pausable_job_mut(&mut self) -> Option<&mut dyn PausableJob> {
-        let type_id = self.config.as_any().type_id();
-        let any_mut = self.config.as_any_mut();
-        
-        match () {
-            _ if type_id == std::any::TypeId::of::<WindowWithTitle>() => {
-                any_mut.downcast_mut::<WindowWithTitle>().map(|t| t as &mut dyn PausableJob)
-            }
-            _ if type_id == std::any::TypeId::of::<Window>() => {
-                any_mut.downcast_mut::<Window>().map(|t| t as &mut dyn PausableJob)
-            }
-            _ if type_id == std::any::TypeId::of::<Limited>() => {
-                any_mut.downcast_mut::<Limited>().map(|t| t as &mut dyn PausableJob)
-            }
-            _ if type_id == std::any::TypeId::of::<Capturing>() => {
-                any_mut.downcast_mut::<Capturing>().map(|t| t as &mut dyn PausableJob)
-            }
-            _ => None,
-        }
+        self.config.base_mut().map(|base| base as &mut dyn PausableJob)
     }
     
     /// Pause this job.
@@ -623,17 +647,7 @@ impl Config {
     /// # Returns
     /// A reference to the PrioritizedJob, or None if not available
     fn as_prioritized_job(&self) -> Option<&dyn PrioritizedJob> {
-        if let Some(tracker) = self.config.as_any().downcast_ref::<WindowWithTitle>() {
-            Some(tracker as &dyn PrioritizedJob)
-        } else if let Some(tracker) = self.config.as_any().downcast_ref::<Window>() {
-            Some(tracker as &dyn PrioritizedJob)
-        } else if let Some(tracker) = self.config.as_any().downcast_ref::<Limited>() {
-            Some(tracker as &dyn PrioritizedJob)
-        } else if let Some(tracker) = self.config.as_any().downcast_ref::<Capturing>() {
-            Some(tracker as &dyn PrioritizedJob)
-        } else {
-            None
-        }
+        self.config.base().map(|base| base as &dyn PrioritizedJob)
     }
     
     /// Helper method to get this config as a mutable PrioritizedJob.
@@ -641,26 +655,164 @@ impl Config {
     /// # Returns
     /// A mutable reference to the PrioritizedJob, or None if not available
     fn as_prioritized_job_mut(&mut self) -> Option<&mut dyn PrioritizedJob> {
-        let type_id = self.config.as_any().type_id();
-        let any_mut = self.config.as_any_mut();
-        
-        match () {
-            _ if type_id == std::any::TypeId::of::<WindowWithTitle>() => {
-                any_mut.downcast_mut::<WindowWithTitle>().map(|t| t as &mut dyn PrioritizedJob)
-            }
-            _ if type_id == std::any::TypeId::of::<Window>() => {
-                any_mut.downcast_mut::<Window>().map(|t| t as &mut dyn PrioritizedJob)
-            }
-            _ if type_id == std::any::TypeId::of::<Limited>() => {
-                any_mut.downcast_mut::<Limited>().map(|t| t as &mut dyn PrioritizedJob)
-            }
-            _ if type_id == std::any::TypeId::of::<Capturing>() => {
-                any_mut.downcast_mut::<Capturing>().map(|t| t as &mut dyn PrioritizedJob)
-            }
-            _ => None,
+        self.config.base_mut().map(|base| base as &mut dyn PrioritizedJob)
+    }
+    
+    /// Whether the underlying mode carries a BaseConfig, and so a job status.
+    ///
+    /// User-defined modes may not, in which case status accessors are no-ops.
+    pub(crate) fn tracks_status(&self) -> bool {
+        self.config.base().is_some()
+    }
+
+    /// Get the built-in mode this config was created for.
+    ///
+    /// # Returns
+    /// The thread mode, or None for user-defined modes
+    pub fn thread_mode(&self) -> Option<ThreadMode> {
+        let any = self.config.as_any();
+        if let Some(window) = any.downcast_ref::<WindowWithTitle>() {
+            // Collapsing only hides lines, the window keeps its size
+            Some(ThreadMode::WindowWithTitle(window.get_max_lines()))
+        } else if any.is::<Window>() {
+            Some(ThreadMode::Window(self.lines_to_display()))
+        } else if any.is::<Limited>() {
+            Some(ThreadMode::Limited)
+        } else if any.is::<Capturing>() {
+            Some(ThreadMode::Capturing)
+        } else if any.is::<Spinner>() {
+            Some(ThreadMode::Spinner)
+        } else {
+            None
+        }
+    }
+
+    /// Capture the job state of this config for persistence.
+    ///
+    /// A persistence ID is assigned from `default_id` if the job has none yet.
+    ///
+    /// # Returns
+    /// The job state, or None if the mode does not carry one
+    pub fn job_state(&mut self, default_id: impl FnOnce() -> String) -> Option<JobState> {
+        let job = self.as_persistent_job_mut()?;
+        if !job.has_persistence_id() {
+            job.set_persistence_id(default_id());
+        }
+        job.job_state().ok()
+    }
+
+    /// Apply a previously captured job state to this config, if the mode carries one.
+    pub fn apply_job_state(&mut self, state: &JobState) {
+        if let Some(job) = self.as_persistent_job_mut() {
+            job.apply_job_state(state);
+        }
+    }
+
+    /// Helper method to get this config as a mutable PersistentJob.
+    ///
+    /// # Returns
+    /// The BaseConfig the job state is saved from, or None if the mode does not carry one
+    fn as_persistent_job_mut(&mut self) -> Option<&mut impl PersistentJob> {
+        self.config.base_mut()
+    }
+
+    /// Get the status of this job.
+    ///
+    /// # Returns
+    /// The job status, or None if the mode does not track one
+    pub fn job_status(&self) -> Option<JobStatus> {
+        self.tracks_status().then(|| self.base_config().get_status())
+    }
+
+    /// Time this job with `clock`, if the mode tracks a status.
+    ///
+    /// The elapsed time and the time since the last update start over.
+    pub fn set_clock(&mut self, clock: std::sync::Arc<dyn Clock>) {
+        if let Some(base) = self.config.base_mut() {
+            base.set_clock(clock);
+        }
+    }
+
+    /// Record that the job reported progress or output, if the mode tracks a status.
+    pub fn touch(&self) {
+        if let Some(base) = self.config.base() {
+            base.touch();
+        }
+    }
+
+    /// Replace the parent, child and dependency IDs of this job, if the mode
+    /// tracks them.
+    ///
+    /// See [`BaseConfig::remap_job_ids`].
+    pub fn remap_job_ids(&mut self, ids: &std::collections::HashMap<usize, usize>) {
+        if let Some(base) = self.config.base_mut() {
+            base.remap_job_ids(ids);
+        }
+    }
+
+    /// Get how long the job has gone without reporting progress or output.
+    ///
+    /// # Returns
+    /// The duration, or None if the mode does not track a status
+    pub fn time_since_update(&self) -> Option<std::time::Duration> {
+        self.config.base().map(BaseConfig::time_since_update)
+    }
+
+    /// Set the status of this job, if the mode tracks one.
+    ///
+    /// Completing the job resets its retry count.
+    ///
+    /// # Parameters
+    /// * `status` - The new job status
+    pub fn set_job_status(&mut self, status: JobStatus) {
+        if !self.tracks_status() {
+            return;
+        }
+        if status == JobStatus::Completed {
+            self.base_config_mut().mark_completed();
+        } else {
+            self.base_config_mut().set_status(status);
+        }
+    }
+
+    /// Mark the job for retry, if the mode tracks a status.
+    ///
+    /// # Returns
+    /// The new retry count, or 0 if the mode does not track a status
+    pub fn mark_retry(&mut self) -> usize {
+        if self.tracks_status() {
+            self.base_config_mut().retry()
+        } else {
+            0
+        }
+    }
+    
+    /// Start counting retries over, if the mode tracks a status.
+    pub fn reset_retries(&mut self) {
+        if self.tracks_status() {
+            self.base_config_mut().reset_retry_count();
+        }
+    }
+    
+    /// Set the maximum number of retries shown for this job.
+    pub fn set_max_retries(&mut self, max_retries: usize) {
+        if self.tracks_status() {
+            self.base_config_mut().set_max_retries(max_retries);
         }
     }
     
+    /// Annotation such as "(retry 2/3)" to show next to a job that is retrying.
+    ///
+    /// # Returns
+    /// None unless the job status is Retry
+    pub fn retry_annotation(&self) -> Option<String> {
+        if self.job_status() != Some(JobStatus::Retry) {
+            return None;
+        }
+        let base = self.base_config();
+        Some(format!("(retry {}/{})", base.get_retry_count(), base.get_max_retries()))
+    }
+    
     /// Set the priority of this job.
     ///
     /// # Parameters
@@ -693,17 +845,7 @@ impl Config {
     /// # Returns
     /// A reference to the DependentJob, or None if not available
     fn as_dependent_job(&self) -> Option<&dyn DependentJob> {
-        if let Some(tracker) = self.config.as_any().downcast_ref::<WindowWithTitle>() {
-            Some(tracker as &dyn DependentJob)
-        } else if let Some(tracker) = self.config.as_any().downcast_ref::<Window>() {
-            Some(tracker as &dyn DependentJob)
-        } else if let Some(tracker) = self.config.as_any().downcast_ref::<Limited>() {
-            Some(tracker as &dyn DependentJob)
-        } else if let Some(tracker) = self.config.as_any().downcast_ref::<Capturing>() {
-            Some(tracker as &dyn DependentJob)
-        } else {
-            None
-        }
+        self.config.base().map(|base| base as &dyn DependentJob)
     }
     
     /// Helper method to get this config as a mutable DependentJob.
@@ -711,24 +853,7 @@ impl Config {
     /// # Returns
     /// A mutable reference to the DependentJob, or None if not available
     fn as_dependent_job_mut(&mut self) -> Option<&mut dyn DependentJob> {
-        let type_id = self.config.as_any().type_id();
-        let any_mut = self.config.as_any_mut();
-        
-        match () {
-            _ if type_id == std::any::TypeId::of::<WindowWithTitle>() => {
-                any_mut.downcast_mut::<WindowWithTitle>().map(|t| t as &mut dyn DependentJob)
-            }
-            _ if type_id == std::any::TypeId::of::<Window>() => {
-                any_mut.downcast_mut::<Window>().map(|t| t as &mut dyn DependentJob)
-            }
-            _ if type_id == std::any::TypeId::of::<Limited>() => {
-                any_mut.downcast_mut::<Limited>().map(|t| t as &mut dyn DependentJob)
-            }
-            _ if type_id == std::any::TypeId::of::<Capturing>() => {
-                any_mut.downcast_mut::<Capturing>().map(|t| t as &mut dyn DependentJob)
-            }
-            _ => None,
-        }
+        self.config.base_mut().map(|base| base as &mut dyn DependentJob)
     }
     
     /// Remove a dependency from this job.
@@ -841,54 +966,17 @@ impl Default for Config {
 
 impl From<Box<dyn ThreadConfig>> for Config {
     fn from(config: Box<dyn ThreadConfig>) -> Self {
-        Self { config }
+        Self { config, line_numbers: false, split_newlines: true, truncation_indicator: "…".to_string() }
     }
 }
 
 impl HasBaseConfig for Config {
     fn base_config(&self) -> &BaseConfig {
-        // Try each known type that implements HasBaseConfig
-        if let Some(window) = self.config.as_any().downcast_ref::<WindowWithTitle>() {
-            window.base_config()
-        } else if let Some(window) = self.config.as_any().downcast_ref::<Window>() {
-            window.base_config()
-        } else if let Some(limited) = self.config.as_any().downcast_ref::<Limited>() {
-            limited.base_config()
-        } else if let Some(capturing) = self.config.as_any().downcast_ref::<Capturing>() {
-            capturing.base_config()
-        } else {
-            // This should never happen with the current implementation
-            panic!("Unsupported ThreadConfig type for HasBaseConfig");
-        }
+        self.config.base().expect("Unsupported ThreadConfig type for HasBaseConfig")
     }
     
     fn base_config_mut(&mut self) -> &mut BaseConfig {
-        let type_id = self.config.as_any().type_id();
-        let any_mut = self.config.as_any_mut();
-        
-        match () {
-            _ if type_id == std::any::TypeId::of::<WindowWithTitle>() => {
-                any_mut.downcast_mut::<WindowWithTitle>()
-                    .map(|w| w.base_config_mut())
-                    .unwrap()
-            }
-            _ if type_id == std::any::TypeId::of::<Window>() => {
-                any_mut.downcast_mut::<Window>()
-                    .map(|w| w.base_config_mut())
-                    .unwrap()
-            }
-            _ if type_id == std::any::TypeId::of::<Limited>() => {
-                any_mut.downcast_mut::<Limited>()
-                    .map(|l| l.base_config_mut())
-                    .unwrap()
-            }
-            _ if type_id == std::any::TypeId::of::<Capturing>() => {
-                any_mut.downcast_mut::<Capturing>()
-                    .map(|c| c.base_config_mut())
-                    .unwrap()
-            }
-            _ => panic!("Unsupported ThreadConfig type for HasBaseConfig"),
-        }
+        self.config.base_mut().expect("Unsupported ThreadConfig type for HasBaseConfig")
     }
 }
 
@@ -995,4 +1083,18 @@ mod tests {
         assert!(config.is_cancelled());
         assert_eq!(config.get_cancellation_reason(), Some("WindowWithTitle cancellation".to_string()));
     }
+    
+    #[test]
+    fn test_config_truncation_indicator() {
+        let config = Config::new(ThreadMode::Limited, 1).unwrap();
+        assert_eq!(config.get_truncation_indicator(), "…");
+        
+        let config = config.truncation_indicator(">>".to_string());
+        let line = config.text_wrapper(10).truncate("a line that is far too long");
+        assert_eq!(line, "a line t>>");
+        assert!(crate::terminal::display_width(&line) <= 10);
+        
+        let config = config.clone().truncation_indicator(String::new());
+        assert_eq!(config.text_wrapper(10).truncate("a line that is far too long"), "a line tha");
+    }
 } 
\ No newline at end of file
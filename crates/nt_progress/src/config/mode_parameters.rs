@@ -1,10 +1,11 @@
+use serde::{Deserialize, Serialize};
 use crate::errors::ModeCreationError;
 
 /// Enum defining the available thread display modes.
 ///
 /// This enum is used to select the mode for displaying
 /// thread output in the terminal.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ThreadMode {
     /// Limited mode shows only the most recent message.
     Limited,
@@ -19,6 +20,13 @@ pub enum ThreadMode {
     /// WindowWithTitle mode shows a window with a title bar.
     /// The parameter specifies the maximum number of lines including the title.
     WindowWithTitle(usize),
+    
+    /// Spinner mode shows an animated spinner beside a status message.
+    /// The message is the task's title.
+    Spinner,
+    
+    /// A user-defined mode, resolved by name through the factory's registry.
+    Custom(String),
 }
 
 /// Parameters for creating a display mode.
@@ -40,6 +48,8 @@ pub struct ModeParameters {
     pub title_support: Option<bool>,
     /// Whether to enable passthrough (if applicable)
     pub passthrough: Option<bool>,
+    /// The spinner frames to cycle through (if applicable)
+    pub frames: Option<Vec<String>>,
 }
 
 impl ModeParameters {
@@ -58,6 +68,7 @@ impl ModeParameters {
             emoji_support: None,
             title_support: None,
             passthrough: None,
+            frames: None,
         }
     }
     
@@ -115,6 +126,19 @@ impl ModeParameters {
             .with_title_support(true)
     }
     
+    /// Create parameters for Spinner mode.
+    ///
+    /// The default frames are used unless set with [`with_frames`](Self::with_frames).
+    ///
+    /// # Parameters
+    /// * `total_jobs` - The total number of jobs to track
+    ///
+    /// # Returns
+    /// A ModeParameters instance configured for Spinner mode
+    pub fn spinner(total_jobs: usize) -> Self {
+        Self::new(total_jobs)
+    }
+    
     /// Validate that the parameters are valid for the given mode.
     ///
     /// # Parameters
@@ -183,6 +207,14 @@ impl ModeParameters {
         self.passthrough
     }
     
+    /// Get the spinner frames.
+    ///
+    /// # Returns
+    /// The frames, or None if not set
+    pub fn frames(&self) -> Option<&[String]> {
+        self.frames.as_deref()
+    }
+    
     /// Set the maximum number of lines to display.
     ///
     /// # Parameters
@@ -243,6 +275,18 @@ impl ModeParameters {
         self
     }
     
+    /// Set the spinner frames.
+    ///
+    /// # Parameters
+    /// * `frames` - The glyphs to cycle through, in order
+    ///
+    /// # Returns
+    /// Self for method chaining
+    pub fn with_frames(mut self, frames: Vec<String>) -> Self {
+        self.frames = Some(frames);
+        self
+    }
+    
     /// Validate that the required parameters are present for the given mode.
     ///
     /// # Parameters
@@ -281,7 +325,7 @@ impl ModeParameters {
                     });
                 }
             }
-            "limited" | "capturing" => {
+            "limited" | "capturing" | "spinner" => {
                 // These modes don't have required parameters
             }
             _ => {
@@ -332,6 +376,15 @@ impl ModeParameters {
             }
         }
         
+        // Check that the spinner has something to draw
+        if self.frames.as_ref().is_some_and(|frames| frames.is_empty()) {
+            return Err(ModeCreationError::MissingParameter {
+                param_name: "frames".to_string(),
+                mode_name: mode_name.to_string(),
+                reason: Some("Spinner requires at least one frame".to_string()),
+            });
+        }
+        
         Ok(())
     }
 }
@@ -369,6 +422,17 @@ mod tests {
         
         let params = ModeParameters::capturing(10);
         assert_eq!(params.total_jobs(), 10);
+        
+        let params = ModeParameters::spinner(10).with_frames(vec!["+".to_string(), "x".to_string()]);
+        assert_eq!(params.total_jobs(), 10);
+        assert_eq!(params.frames(), Some(&["+".to_string(), "x".to_string()][..]));
+        assert!(params.validate("spinner").is_ok());
+        
+        let params = ModeParameters::spinner(10).with_frames(Vec::new());
+        assert!(matches!(
+            params.validate("spinner"),
+            Err(ModeCreationError::MissingParameter { .. })
+        ));
     }
     
     #[test]
@@ -10,10 +10,11 @@ use serde::{Serialize, Deserialize};
 
 use super::job_traits::HasBaseConfig;
 use super::job_statistics::JobStatistics;
+use super::clock::{Clock, SystemClock};
 use crate::config::capabilities::WithProgress;
 
 /// Represents the current status of a job.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum JobStatus {
     /// Job is waiting to be started
     Pending,
@@ -39,6 +40,16 @@ impl std::fmt::Display for JobStatus {
     }
 }
 
+/// Percentage of `total` that `completed` represents, clamped to [0, 100].
+///
+/// An unknown total of zero jobs reports no progress.
+pub fn progress_percentage(completed: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (completed.min(total) as f64) / (total as f64) * 100.0
+}
+
 /// Base configuration for progress tracking shared across different display modes.
 ///
 /// This struct provides core functionality for tracking job progress
@@ -71,8 +82,10 @@ pub struct BaseConfig {
     max_retries: Arc<AtomicUsize>,
     /// Current status of the job
     status: Arc<Mutex<JobStatus>>,
-    /// Time of the last progress update
+    /// Time of the last progress update or output, consulted by stall detection
     last_update_time: Arc<Mutex<Instant>>,
+    /// Time of the last progress update, used for the speed
+    last_progress_time: Arc<Mutex<Instant>>,
     /// Current progress speed (units per second)
     progress_speed: Arc<Mutex<Option<f64>>>,
     /// Estimated time to completion
@@ -87,6 +100,8 @@ pub struct BaseConfig {
     persistence_id: Arc<Mutex<Option<String>>>,
     /// Whether this job should be persisted
     has_persistence: Arc<AtomicBool>,
+    /// Source of the times used for speed and time estimates
+    clock: Arc<dyn Clock>,
 }
 
 impl BaseConfig {
@@ -98,6 +113,19 @@ impl BaseConfig {
     /// # Returns
     /// A new BaseConfig instance
     pub fn new(total_jobs: usize) -> Self {
+        Self::with_clock(total_jobs, Arc::new(SystemClock))
+    }
+
+    /// Creates a new BaseConfig reading the time from `clock`.
+    ///
+    /// # Parameters
+    /// * `total_jobs` - The total number of jobs to track
+    /// * `clock` - The clock used for elapsed time, speed and ETA
+    ///
+    /// # Returns
+    /// A new BaseConfig instance
+    pub fn with_clock(total_jobs: usize, clock: Arc<dyn Clock>) -> Self {
+        let now = clock.now();
         Self {
             total_jobs,
             completed_jobs: Arc::new(AtomicUsize::new(0)),
@@ -112,16 +140,47 @@ impl BaseConfig {
             retry_count: Arc::new(AtomicUsize::new(0)),
             max_retries: Arc::new(AtomicUsize::new(3)), // Default to 3 retries
             status: Arc::new(Mutex::new(JobStatus::Pending)),
-            last_update_time: Arc::new(Mutex::new(Instant::now())),
+            last_update_time: Arc::new(Mutex::new(now)),
+            last_progress_time: Arc::new(Mutex::new(now)),
             progress_speed: Arc::new(Mutex::new(None)),
             estimated_time_remaining: Arc::new(Mutex::new(None)),
-            start_time: Arc::new(Mutex::new(Instant::now())),
+            start_time: Arc::new(Mutex::new(now)),
             cancelled: Arc::new(AtomicBool::new(false)),
             cancellation_reason: Arc::new(Mutex::new(None)),
             persistence_id: Arc::new(Mutex::new(None)),
             has_persistence: Arc::new(AtomicBool::new(false)),
+            clock,
         }
     }
+
+    /// Replace the clock, restarting the elapsed time and speed tracking from its current time.
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        let now = clock.now();
+        *self.start_time.lock().unwrap() = now;
+        *self.last_update_time.lock().unwrap() = now;
+        *self.last_progress_time.lock().unwrap() = now;
+        self.clock = clock;
+    }
+
+    /// Get the current time of the clock this job is timed with.
+    pub fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    /// Record that the job reported progress or output, without changing the speed.
+    pub fn touch(&self) {
+        *self.last_update_time.lock().unwrap() = self.clock.now();
+    }
+
+    /// Get when the job last reported progress or output.
+    pub fn get_last_update_time(&self) -> Instant {
+        *self.last_update_time.lock().unwrap()
+    }
+
+    /// Get how long the job has gone without reporting progress or output.
+    pub fn time_since_update(&self) -> Duration {
+        self.clock.now().saturating_duration_since(self.get_last_update_time())
+    }
     
     /// Get the total number of jobs.
     ///
@@ -334,6 +393,27 @@ impl BaseConfig {
         self.dependencies.lock().unwrap().clone()
     }
     
+    /// Replace the job IDs this job refers to, e.g. after its tasks moved to
+    /// another manager under new IDs.
+    ///
+    /// The parent, child and dependency IDs found in `ids` are replaced by the
+    /// ID they map to; the others are kept.
+    ///
+    /// # Parameters
+    /// * `ids` - The new ID of each job, keyed by its old ID
+    pub fn remap_job_ids(&mut self, ids: &std::collections::HashMap<usize, usize>) {
+        let remap = |id: &mut usize| {
+            if let Some(new_id) = ids.get(id) {
+                *id = *new_id;
+            }
+        };
+        if let Some(parent_id) = self.parent_job_id.as_mut() {
+            remap(parent_id);
+        }
+        self.child_job_ids.lock().unwrap().iter_mut().for_each(remap);
+        self.dependencies.lock().unwrap().iter_mut().for_each(remap);
+    }
+    
     /// Check if this job has dependencies.
     ///
     /// # Returns
@@ -406,7 +486,7 @@ impl BaseConfig {
         let total = self.total_jobs;
         if total > 0 {
             // Set completion time
-            let now = std::time::Instant::now();
+            let now = self.clock.now();
             *self.last_update_time.lock().unwrap() = now;
             
             // Clear ETA since job is complete
@@ -446,6 +526,11 @@ impl BaseConfig {
         self.retry_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1
     }
     
+    /// Reset the retry counter, e.g. when a new round of retries starts.
+    pub fn reset_retry_count(&mut self) {
+        self.retry_count.store(0, std::sync::atomic::Ordering::SeqCst);
+    }
+    
     /// Check if the job is in the specified status.
     ///
     /// # Parameters
@@ -564,7 +649,7 @@ impl BaseConfig {
     /// the timer needs to be reset for any reason.
     pub fn reset_start_time(&mut self) {
         let mut start = self.start_time.lock().unwrap();
-        *start = std::time::Instant::now();
+        *start = self.clock.now();
     }
     
     /// Get the elapsed time since the job started.
@@ -573,7 +658,7 @@ impl BaseConfig {
     /// The duration since the job started
     pub fn get_elapsed_time(&self) -> Duration {
         let start = *self.start_time.lock().unwrap();
-        start.elapsed()
+        self.clock.now().saturating_duration_since(start)
     }
     
     /// Get the estimated time remaining until the job completes.
@@ -599,7 +684,7 @@ impl BaseConfig {
     /// # Returns
     /// The updated progress percentage
     pub fn update_time_estimates(&self) -> f64 {
-        let now = std::time::Instant::now();
+        let now = self.clock.now();
         let total = self.get_total_jobs();
         let completed = self.get_completed_jobs();
         
@@ -607,11 +692,9 @@ impl BaseConfig {
             return 0.0;
         }
         
-        let progress = (completed as f64) / (total as f64);
-        
         // Calculate speed and ETA
         {
-            let mut last_update = self.last_update_time.lock().unwrap();
+            let mut last_update = self.last_progress_time.lock().unwrap();
             let delta_time = now.duration_since(*last_update);
             let mut speed = self.progress_speed.lock().unwrap();
             let mut eta = self.estimated_time_remaining.lock().unwrap();
@@ -622,27 +705,31 @@ impl BaseConfig {
                 let progress_per_second = 1.0 / delta_time.as_secs_f64();
                 
                 // Update the speed using exponential moving average
-                *speed = Some(match *speed {
+                let next_speed = match *speed {
                     Some(current_speed) => current_speed * 0.7 + progress_per_second * 0.3,
                     None => progress_per_second,
-                });
+                };
+                if next_speed.is_finite() {
+                    *speed = Some(next_speed);
+                }
                 
                 // Calculate estimated time remaining
                 if let Some(current_speed) = *speed {
-                    let remaining_jobs = total - completed;
-                    if remaining_jobs > 0 && current_speed > 0.0 {
+                    let remaining_jobs = total.saturating_sub(completed);
+                    *eta = if remaining_jobs > 0 && current_speed > 0.0 {
                         let remaining_seconds = (remaining_jobs as f64) / (current_speed * delta_time.as_secs_f64());
-                        *eta = Some(Duration::from_secs_f64(remaining_seconds.max(0.0)));
+                        Duration::try_from_secs_f64(remaining_seconds.max(0.0)).ok()
                     } else {
-                        *eta = None;
-                    }
+                        None
+                    };
                 }
             }
             
             *last_update = now;
+            *self.last_update_time.lock().unwrap() = now;
         }
         
-        progress * 100.0
+        progress_percentage(completed, total)
     }
     
     /// Check if this job has been cancelled.
@@ -733,11 +820,7 @@ impl JobStatistics for BaseConfig {
             is_cancelled: self.is_cancelled(),
             parent_job_id: self.get_parent_job_id(),
             child_job_count: self.get_child_job_ids().len(),
-            progress_percentage: if self.total_jobs > 0 {
-                (self.get_completed_jobs() as f64 / self.total_jobs as f64) * 100.0
-            } else {
-                0.0
-            },
+            progress_percentage: progress_percentage(self.get_completed_jobs(), self.total_jobs),
         }
     }
 
@@ -1194,6 +1277,55 @@ mod tests {
         assert_eq!(JobStatus::Retry.to_string(), "Retry");
     }
     
+    #[test]
+    fn test_time_estimates_with_mock_clock() {
+        use crate::core::clock::MockClock;
+        
+        let clock = MockClock::new();
+        let base = BaseConfig::with_clock(10, Arc::new(clock.clone()));
+        
+        // One job in one second
+        clock.advance(Duration::from_secs(1));
+        base.increment_completed_jobs();
+        assert_eq!(base.get_progress_speed(), Some(1.0));
+        assert_eq!(base.get_estimated_time_remaining(), Some(Duration::from_secs(9)));
+        
+        // The next job takes two seconds, which the moving average folds in
+        clock.advance(Duration::from_secs(2));
+        base.increment_completed_jobs();
+        let speed = 0.7 + 0.3 * 0.5;
+        assert_eq!(base.get_progress_speed(), Some(speed));
+        assert_eq!(base.get_estimated_time_remaining(), Some(Duration::from_secs_f64(8.0 / (speed * 2.0))));
+        
+        assert_eq!(base.get_elapsed_time(), Duration::from_secs(3));
+    }
+    
+    #[test]
+    fn test_pathological_progress_stays_sane() {
+        use crate::core::clock::MockClock;
+        
+        let clock = MockClock::new();
+        let mut base = BaseConfig::with_clock(4, Arc::new(clock.clone()));
+        
+        // More jobs completed than there are
+        clock.advance(Duration::from_secs(1));
+        base.set_completed_jobs(10);
+        assert_eq!(base.update_time_estimates(), 100.0);
+        assert_eq!(base.get_estimated_time_remaining(), None);
+        assert!(base.get_progress_speed().is_some_and(f64::is_finite));
+        assert_eq!(base.generate_statistics_report().progress_percentage, 100.0);
+        
+        // The total drops to zero after progress was made
+        base.set_total_jobs(0);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(base.update_time_estimates(), 0.0);
+        assert_eq!(base.generate_statistics_report().progress_percentage, 0.0);
+        assert!(base.get_progress_speed().is_some_and(f64::is_finite));
+        
+        assert_eq!(progress_percentage(3, 0), 0.0);
+        assert_eq!(progress_percentage(usize::MAX, 2), 100.0);
+    }
+    
     #[test]
     fn test_base_config_elapsed_time() {
         let mut base = BaseConfig::new(10);
@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use super::base_config::JobStatus;
+use super::base_config::{progress_percentage, JobState, JobStatus};
 
 /// Trait for types that contain a BaseConfig, either directly or through composition.
 ///
@@ -432,6 +432,18 @@ pub trait PersistentJob:
     /// # Returns
     /// `true` if the job has a persistence ID, `false` otherwise
     fn has_persistence_id(&self) -> bool;
+
+    /// Capture the current job state, as saved by [`save_state`](Self::save_state).
+    ///
+    /// # Returns
+    /// The job state, or an error if the job has no persistence ID
+    fn job_state(&self) -> std::io::Result<JobState>;
+
+    /// Apply a previously captured job state, as loaded by [`load_state`](Self::load_state).
+    ///
+    /// # Parameters
+    /// * `state` - The job state to apply
+    fn apply_job_state(&mut self, state: &JobState);
 }
 
 // Generic implementations for base traits
@@ -501,13 +513,7 @@ impl<T: HasBaseConfig + Send + Sync + Debug> HierarchicalJobTracker for T {
     
     fn get_cumulative_progress(&self) -> f64 {
         // Basic implementation - can be overridden by specific modes
-        let total = self.get_total_jobs();
-        if total == 0 {
-            return 0.0;
-        }
-        
-        let completed = self.base_config().get_completed_jobs();
-        (completed as f64) / (total as f64)
+        progress_percentage(self.base_config().get_completed_jobs(), self.get_total_jobs()) / 100.0
     }
 }
 
@@ -666,6 +672,14 @@ impl<T: HasBaseConfig + Send + Sync + Debug> PersistentJob for T {
     fn has_persistence_id(&self) -> bool {
         self.base_config().has_persistence_id()
     }
+
+    fn job_state(&self) -> std::io::Result<JobState> {
+        JobState::from_base_config(self.base_config())
+    }
+
+    fn apply_job_state(&mut self, state: &JobState) {
+        state.apply_to_base_config(self.base_config_mut());
+    }
 }
 
 #[cfg(test)]
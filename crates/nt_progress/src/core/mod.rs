@@ -5,9 +5,11 @@ pub mod base_config;
 pub mod job_traits;
 pub mod thread_config;
 pub mod job_statistics;
+pub mod clock;
 
 // Re-export key components
 pub use base_config::BaseConfig;
+pub use clock::{Clock, MockClock, SystemClock};
 pub use job_traits::{
     HasBaseConfig, JobTracker, PausableJob, HierarchicalJobTracker,
     PrioritizedJob, DependentJob
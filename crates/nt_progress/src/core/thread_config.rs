@@ -4,12 +4,11 @@ use std::fmt::Debug;
 
 use crate::modes::window::Window;
 use crate::modes::window_with_title::WindowWithTitle;
-use crate::modes::limited::Limited;
-use crate::modes::capturing::Capturing;
 use crate::config::capabilities::{
     Capability, WithTitle, WithCustomSize, WithEmoji, WithTitleAndEmoji, 
     StandardWindow, WithWrappedText, WithProgress
 };
+use super::base_config::BaseConfig;
 use super::job_traits::{PausableJob, PrioritizedJob, DependentJob};
 
 /// Core trait for thread configuration in progress display modes.
@@ -75,6 +74,41 @@ pub trait ThreadConfig: Send + Sync + Debug {
     /// # Returns
     /// A reference to self as an Any
     fn as_any(&self) -> &dyn Any;
+
+    /// Returns the BaseConfig this mode keeps its job state in.
+    ///
+    /// Modes that carry a BaseConfig track a job status, progress, priority and
+    /// dependencies. The default is for modes that do not.
+    ///
+    /// # Returns
+    /// Some(&BaseConfig) if the mode carries one, None otherwise
+    fn base(&self) -> Option<&BaseConfig> {
+        None
+    }
+
+    /// Returns the BaseConfig this mode keeps its job state in, mutably.
+    ///
+    /// # Returns
+    /// Some(&mut BaseConfig) if the mode carries one, None otherwise
+    fn base_mut(&mut self) -> Option<&mut BaseConfig> {
+        None
+    }
+
+    /// Returns this mode's title, for modes that have one.
+    ///
+    /// # Returns
+    /// Some(&dyn WithTitle) if the mode has a title, None otherwise
+    fn title_capability(&self) -> Option<&dyn WithTitle> {
+        None
+    }
+
+    /// Returns this mode's title mutably, for modes that have one.
+    ///
+    /// # Returns
+    /// Some(&mut dyn WithTitle) if the mode has a title, None otherwise
+    fn title_capability_mut(&mut self) -> Option<&mut dyn WithTitle> {
+        None
+    }
 }
 
 /// Extension trait for ThreadConfig that provides capability checking and conversion.
@@ -88,7 +122,7 @@ pub trait ThreadConfigExt: ThreadConfig {
     /// # Returns
     /// `true` if the config supports setting and getting a title.
     fn supports_title(&self) -> bool {
-        self.as_any().is::<WindowWithTitle>()
+        self.title_capability().is_some()
     }
     
     /// Try to get this config as a WithTitle.
@@ -96,7 +130,7 @@ pub trait ThreadConfigExt: ThreadConfig {
     /// # Returns
     /// Some(&dyn WithTitle) if the config supports titles, None otherwise.
     fn as_title(&self) -> Option<&dyn WithTitle> {
-        self.as_any().downcast_ref::<WindowWithTitle>().map(|w| w as &dyn WithTitle)
+        self.title_capability()
     }
     
     /// Try to get this config as a mutable WithTitle.
@@ -104,7 +138,7 @@ pub trait ThreadConfigExt: ThreadConfig {
     /// # Returns
     /// Some(&mut dyn WithTitle) if the config supports titles, None otherwise.
     fn as_title_mut(&mut self) -> Option<&mut dyn WithTitle> {
-        self.as_any_mut().downcast_mut::<WindowWithTitle>().map(|w| w as &mut dyn WithTitle)
+        self.title_capability_mut()
     }
     
     /// Check if this config supports the WithCustomSize capability.
@@ -370,16 +404,7 @@ pub trait ThreadConfigExt: ThreadConfig {
     /// # Returns
     /// Some(&dyn PrioritizedJob) if the config supports job prioritization, None otherwise.
     fn as_prioritized_job(&self) -> Option<&dyn PrioritizedJob> {
-        let any = self.as_any();
-        if let Some(w) = any.downcast_ref::<Window>() {
-            Some(w as &dyn PrioritizedJob)
-        } else if let Some(w) = any.downcast_ref::<WindowWithTitle>() {
-            Some(w as &dyn PrioritizedJob)
-        } else if let Some(l) = any.downcast_ref::<Limited>() {
-            Some(l as &dyn PrioritizedJob)
-        } else {
-            any.downcast_ref::<Capturing>().map(|c| c as &dyn PrioritizedJob)
-        }
+        self.base().map(|base| base as &dyn PrioritizedJob)
     }
 
     /// Try to get this config as a mutable PrioritizedJob.
@@ -387,17 +412,7 @@ pub trait ThreadConfigExt: ThreadConfig {
     /// # Returns
     /// Some(&mut dyn PrioritizedJob) if the config supports job prioritization, None otherwise.
     fn as_prioritized_job_mut(&mut self) -> Option<&mut dyn PrioritizedJob> {
-        let type_id = self.as_any().type_id();
-        let any = self.as_any_mut();
-        if type_id == TypeId::of::<Window>() {
-            any.downcast_mut::<Window>().map(|w| w as &mut dyn PrioritizedJob)
-        } else if type_id == TypeId::of::<WindowWithTitle>() {
-            any.downcast_mut::<WindowWithTitle>().map(|w| w as &mut dyn PrioritizedJob)
-        } else if type_id == TypeId::of::<Limited>() {
-            any.downcast_mut::<Limited>().map(|l| l as &mut dyn PrioritizedJob)
-        } else {
-            any.downcast_mut::<Capturing>().map(|c| c as &mut dyn PrioritizedJob)
-        }
+        self.base_mut().map(|base| base as &mut dyn PrioritizedJob)
     }
 
     /// Add support for checking if a config supports pausing and resuming jobs.
@@ -414,16 +429,7 @@ pub trait ThreadConfigExt: ThreadConfig {
     /// # Returns
     /// Some(&dyn PausableJob) if the config supports pausing and resuming jobs, None otherwise.
     fn as_pausable_job(&self) -> Option<&dyn PausableJob> {
-        let any = self.as_any();
-        if let Some(w) = any.downcast_ref::<Window>() {
-            Some(w as &dyn PausableJob)
-        } else if let Some(w) = any.downcast_ref::<WindowWithTitle>() {
-            Some(w as &dyn PausableJob)
-        } else if let Some(l) = any.downcast_ref::<Limited>() {
-            Some(l as &dyn PausableJob)
-        } else {
-            any.downcast_ref::<Capturing>().map(|c| c as &dyn PausableJob)
-        }
+        self.base().map(|base| base as &dyn PausableJob)
     }
 
     /// Try to get this config as a mutable PausableJob.
@@ -431,17 +437,7 @@ pub trait ThreadConfigExt: ThreadConfig {
     /// # Returns
     /// Some(&mut dyn PausableJob) if the config supports pausing and resuming jobs, None otherwise.
     fn as_pausable_job_mut(&mut self) -> Option<&mut dyn PausableJob> {
-        let type_id = self.as_any().type_id();
-        let any = self.as_any_mut();
-        if type_id == TypeId::of::<Window>() {
-            any.downcast_mut::<Window>().map(|w| w as &mut dyn PausableJob)
-        } else if type_id == TypeId::of::<WindowWithTitle>() {
-            any.downcast_mut::<WindowWithTitle>().map(|w| w as &mut dyn PausableJob)
-        } else if type_id == TypeId::of::<Limited>() {
-            any.downcast_mut::<Limited>().map(|l| l as &mut dyn PausableJob)
-        } else {
-            any.downcast_mut::<Capturing>().map(|c| c as &mut dyn PausableJob)
-        }
+        self.base_mut().map(|base| base as &mut dyn PausableJob)
     }
 
     /// Add support for checking if a config supports job dependencies.
@@ -458,16 +454,7 @@ pub trait ThreadConfigExt: ThreadConfig {
     /// # Returns
     /// Some(&dyn DependentJob) if the config supports job dependencies, None otherwise.
     fn as_dependent_job(&self) -> Option<&dyn DependentJob> {
-        let any = self.as_any();
-        if let Some(w) = any.downcast_ref::<Window>() {
-            Some(w as &dyn DependentJob)
-        } else if let Some(w) = any.downcast_ref::<WindowWithTitle>() {
-            Some(w as &dyn DependentJob)
-        } else if let Some(l) = any.downcast_ref::<Limited>() {
-            Some(l as &dyn DependentJob)
-        } else {
-            any.downcast_ref::<Capturing>().map(|c| c as &dyn DependentJob)
-        }
+        self.base().map(|base| base as &dyn DependentJob)
     }
 
     /// Try to get this config as a mutable DependentJob.
@@ -475,17 +462,7 @@ pub trait ThreadConfigExt: ThreadConfig {
     /// # Returns
     /// Some(&mut dyn DependentJob) if the config supports job dependencies, None otherwise.
     fn as_dependent_job_mut(&mut self) -> Option<&mut dyn DependentJob> {
-        let type_id = self.as_any().type_id();
-        let any = self.as_any_mut();
-        if type_id == TypeId::of::<Window>() {
-            any.downcast_mut::<Window>().map(|w| w as &mut dyn DependentJob)
-        } else if type_id == TypeId::of::<WindowWithTitle>() {
-            any.downcast_mut::<WindowWithTitle>().map(|w| w as &mut dyn DependentJob)
-        } else if type_id == TypeId::of::<Limited>() {
-            any.downcast_mut::<Limited>().map(|l| l as &mut dyn DependentJob)
-        } else {
-            any.downcast_mut::<Capturing>().map(|c| c as &mut dyn DependentJob)
-        }
+        self.base_mut().map(|base| base as &mut dyn DependentJob)
     }
 }
 
@@ -0,0 +1,36 @@
+//! Internal diagnostics such as render errors and cleanup warnings.
+//!
+//! With the `tracing` feature they are emitted as `tracing` events, so they
+//! show up alongside the diagnostics of the other crates; otherwise warnings
+//! and errors are printed to stderr and debug messages go to `log`.
+
+/// Report a problem the display recovered from
+macro_rules! diag_warn {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "tracing")]
+        tracing::warn!($($arg)*);
+        #[cfg(not(feature = "tracing"))]
+        eprintln!("Warning: {}", format_args!($($arg)*));
+    }};
+}
+
+/// Report an operation that failed
+macro_rules! diag_error {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "tracing")]
+        tracing::error!($($arg)*);
+        #[cfg(not(feature = "tracing"))]
+        eprintln!($($arg)*);
+    }};
+}
+
+/// Report something expected but worth knowing when investigating, such as a
+/// progress update dropped because the message channel was full
+macro_rules! diag_debug {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+        #[cfg(not(feature = "tracing"))]
+        log::debug!($($arg)*);
+    }};
+}
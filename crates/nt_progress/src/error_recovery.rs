@@ -1,6 +1,8 @@
+use std::collections::HashMap;
 use std::time::Duration;
 use tokio::time::sleep;
 use anyhow::Result;
+use crate::config::ThreadMode;
 use crate::errors::{ProgressError, ErrorSeverity};
 
 /// Retry configuration for operations
@@ -27,6 +29,20 @@ impl Default for RetryConfig {
     }
 }
 
+impl RetryConfig {
+    /// Delay before the given retry, counting from 1
+    ///
+    /// With exponential backoff the delay starts at twice `base_delay` and
+    /// doubles on every retry, otherwise it is always `retry_delay`.
+    pub fn delay(&self, retry: u32) -> Duration {
+        if self.use_exponential_backoff {
+            self.base_delay.saturating_mul(2u32.saturating_pow(retry))
+        } else {
+            self.retry_delay
+        }
+    }
+}
+
 /// Execute an operation with retry capability
 pub async fn with_retry<F, T, E>(config: &RetryConfig, mut operation: F) -> Result<T>
 where
@@ -34,7 +50,6 @@ where
     E: Into<ProgressError>,
 {
     let mut attempts = 0;
-    let mut current_delay = config.base_delay;
 
     loop {
         match operation() {
@@ -47,16 +62,9 @@ where
                     return Err(anyhow::anyhow!(error));
                 }
 
-                // Calculate next delay
-                if config.use_exponential_backoff {
-                    current_delay *= 2;
-                } else {
-                    current_delay = config.retry_delay;
-                }
-
                 // Wait before retrying
-                sleep(current_delay).await;
                 attempts += 1;
+                sleep(config.delay(attempts)).await;
             }
         }
     }
@@ -94,7 +102,7 @@ where
             }
 
             if config.log_fallback {
-                eprintln!("Primary operation failed, using fallback: {}", error);
+                diag_warn!("Primary operation failed, using fallback: {}", error);
             }
 
             fallback(error)
@@ -146,13 +154,13 @@ impl ErrorRecovery {
         match error.severity() {
             ErrorSeverity::Low => {
                 // For low severity errors, just log and continue
-                eprintln!("Low severity error: {}", error);
+                diag_warn!("Low severity error: {}", error);
                 Ok(())
             }
             ErrorSeverity::Medium => {
                 // For medium severity errors, try recovery if possible
                 if let Some(hint) = error.recovery_hint() {
-                    eprintln!("Medium severity error: {}\nRecovery hint: {}", error, hint);
+                    diag_error!("Medium severity error: {}\nRecovery hint: {}", error, hint);
                 }
                 Ok(())
             }
@@ -174,4 +182,68 @@ impl ErrorRecovery {
             }
         }
     }
-} 
\ No newline at end of file
+}
+
+/// What to do after an operation failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecoveryAction {
+    /// Run the operation again once
+    Retry,
+    /// Create the task in this mode instead
+    FallbackMode(ThreadMode),
+    /// Ignore the failure and carry on
+    Skip,
+    /// Report the error to the caller
+    Fail,
+}
+
+/// Decides how to recover from a failed operation
+pub trait RecoveryStrategy: Send + Sync {
+    /// Choose the recovery action for `error`
+    fn recover(&self, error: &ProgressError) -> RecoveryAction;
+}
+
+impl<F> RecoveryStrategy for F
+where
+    F: Fn(&ProgressError) -> RecoveryAction + Send + Sync,
+{
+    fn recover(&self, error: &ProgressError) -> RecoveryAction {
+        self(error)
+    }
+}
+
+/// Pairs error kinds, identified by [`ProgressError::error_code`], with the
+/// strategy used to recover from them.
+///
+/// Errors without a registered strategy are not recovered from.
+#[derive(Default)]
+pub struct RecoveryRegistry {
+    strategies: HashMap<&'static str, Box<dyn RecoveryStrategy>>,
+}
+
+impl std::fmt::Debug for RecoveryRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecoveryRegistry")
+            .field("error_codes", &self.strategies.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl RecoveryRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the strategy for errors with the given code, replacing any previous one
+    pub fn register(&mut self, error_code: &'static str, strategy: impl RecoveryStrategy + 'static) {
+        self.strategies.insert(error_code, Box::new(strategy));
+    }
+
+    /// Choose the recovery action for `error`
+    pub fn action_for(&self, error: &ProgressError) -> RecoveryAction {
+        self.strategies.get(error.error_code())
+            .map(|strategy| strategy.recover(error))
+            .unwrap_or(RecoveryAction::Fail)
+    }
+}
@@ -3,6 +3,7 @@ use std::error::Error;
 use std::io;
 use std::backtrace::Backtrace;
 use std::time::{Duration, Instant};
+use crate::thread::ThreadState;
 
 /// Errors that can occur when working with the nt_progress library
 #[derive(Debug)]
@@ -11,12 +12,30 @@ pub enum ProgressError {
     ModeCreation(ModeCreationError),
     /// Error when interacting with a task handle
     TaskOperation(String),
+    /// No task is registered under the given thread ID
+    ThreadNotFound(usize),
     /// Error when operating on the progress display
     DisplayOperation(String),
     /// Error from an external source (e.g., IO)
     External(Box<dyn Error + Send + Sync>),
     /// IO error
     Io(io::Error),
+    /// A thread was asked to move to a state it cannot reach from its current one
+    InvalidStateTransition {
+        /// The thread's current state
+        from: ThreadState,
+        /// The requested state
+        to: ThreadState,
+    },
+    /// A task is waiting on dependencies that were cancelled or failed and can never complete
+    DeadlockedTask(usize, Vec<usize>),
+    /// More tasks failed than the display's failure threshold allows
+    FailureThresholdExceeded {
+        /// IDs of the failed tasks, in the order they failed
+        failed: Vec<usize>,
+        /// The number of failures that was allowed
+        threshold: usize,
+    },
     /// Error with context information
     WithContext(Box<ProgressError>, ErrorContext),
     /// Error that can be retried
@@ -32,6 +51,10 @@ pub enum ProgressError {
     },
 }
 
+/// Environment variable that enables backtrace capture in [`ErrorContext`]
+/// when set to anything other than `0`
+pub const BACKTRACE_ENV: &str = "NT_PROGRESS_BACKTRACE";
+
 /// Context information for errors
 #[derive(Debug)]
 pub struct ErrorContext {
@@ -45,8 +68,10 @@ pub struct ErrorContext {
     pub thread_id: Option<usize>,
     /// Timestamp when the error occurred
     pub timestamp: Instant,
-    /// Stack trace for debugging
+    /// Stack trace for debugging, captured only when [`BACKTRACE_ENV`] is set and shown by `Debug`
     pub backtrace: Option<Backtrace>,
+    /// The lower-level error that caused this one
+    pub source: Option<Box<dyn Error + Send + Sync>>,
     /// Suggested recovery action
     pub recovery_hint: Option<String>,
     /// Error severity level
@@ -75,7 +100,8 @@ impl ErrorContext {
             details: None,
             thread_id: None,
             timestamp: Instant::now(),
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Self::capture_backtrace(),
+            source: None,
             recovery_hint: None,
             severity: ErrorSeverity::Medium,
         }
@@ -104,6 +130,19 @@ impl ErrorContext {
         self.severity = severity;
         self
     }
+
+    /// Chain the lower-level error that caused this one
+    pub fn with_source(mut self, source: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    fn capture_backtrace() -> Option<Backtrace> {
+        match std::env::var(BACKTRACE_ENV) {
+            Ok(value) if value != "0" => Some(Backtrace::force_capture()),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for ErrorContext {
@@ -124,12 +163,27 @@ impl fmt::Display for ErrorContext {
 
 impl fmt::Display for ProgressError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Wrappers show the code of the error they wrap, so it is only written once
+        if !matches!(self, ProgressError::WithContext(..) | ProgressError::Retryable { .. }) {
+            write!(f, "[{}] ", self.error_code())?;
+        }
+
         match self {
             ProgressError::ModeCreation(err) => write!(f, "Mode creation error: {}", err),
             ProgressError::TaskOperation(msg) => write!(f, "Task operation error: {}", msg),
+            ProgressError::ThreadNotFound(thread_id) => write!(f, "Thread {} not found", thread_id),
             ProgressError::DisplayOperation(msg) => write!(f, "Display operation error: {}", msg),
             ProgressError::External(err) => write!(f, "External error: {}", err),
             ProgressError::Io(err) => write!(f, "IO error: {}", err),
+            ProgressError::InvalidStateTransition { from, to } => {
+                write!(f, "Invalid thread state transition from {} to {}", from, to)
+            }
+            ProgressError::DeadlockedTask(thread_id, deps) => {
+                write!(f, "Task {} is deadlocked on unsatisfiable dependencies {:?}", thread_id, deps)
+            }
+            ProgressError::FailureThresholdExceeded { failed, threshold } => {
+                write!(f, "{} tasks failed, more than the allowed {}: {:?}", failed.len(), threshold, failed)
+            }
             ProgressError::WithContext(err, ctx) => write!(f, "{} ({})", err, ctx),
             ProgressError::Retryable { error, .. } => write!(f, "Retryable error: {}", error),
         }
@@ -142,7 +196,11 @@ impl Error for ProgressError {
             ProgressError::ModeCreation(err) => Some(err),
             ProgressError::External(err) => Some(err.as_ref()),
             ProgressError::Io(err) => Some(err),
-            ProgressError::WithContext(err, _) => Some(err.as_ref()),
+            // The wrapped error is part of the message, the chained one is its cause
+            ProgressError::WithContext(err, ctx) => match &ctx.source {
+                Some(source) => Some(source.as_ref()),
+                None => err.source(),
+            },
             ProgressError::Retryable { error, .. } => Some(error.as_ref()),
             _ => None,
         }
@@ -492,14 +550,38 @@ impl ProgressError {
         match self {
             ProgressError::ModeCreation(_) => ErrorSeverity::High,
             ProgressError::TaskOperation(_) => ErrorSeverity::Medium,
+            ProgressError::ThreadNotFound(_) => ErrorSeverity::Medium,
             ProgressError::DisplayOperation(_) => ErrorSeverity::Low,
             ProgressError::External(_) => ErrorSeverity::Medium,
             ProgressError::Io(_) => ErrorSeverity::Medium,
+            ProgressError::InvalidStateTransition { .. } => ErrorSeverity::Medium,
+            ProgressError::DeadlockedTask(..) => ErrorSeverity::High,
+            ProgressError::FailureThresholdExceeded { .. } => ErrorSeverity::High,
             ProgressError::WithContext(err, ctx) => ctx.severity,
             ProgressError::Retryable { error, .. } => error.severity(),
         }
     }
 
+    /// Get a stable code identifying the kind of this error.
+    ///
+    /// Context and retry wrappers report the code of the error they wrap, so
+    /// callers can match on the underlying failure without parsing messages.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            ProgressError::ModeCreation(_) => "MODE_CREATION",
+            ProgressError::TaskOperation(_) => "TASK_OP",
+            ProgressError::ThreadNotFound(_) => "THREAD_NOT_FOUND",
+            ProgressError::DisplayOperation(_) => "DISPLAY_OP",
+            ProgressError::External(_) => "EXTERNAL",
+            ProgressError::Io(_) => "IO",
+            ProgressError::InvalidStateTransition { .. } => "INVALID_STATE_TRANSITION",
+            ProgressError::DeadlockedTask(..) => "DEADLOCKED_TASK",
+            ProgressError::FailureThresholdExceeded { .. } => "FAILURE_THRESHOLD_EXCEEDED",
+            ProgressError::WithContext(err, _) => err.error_code(),
+            ProgressError::Retryable { error, .. } => error.error_code(),
+        }
+    }
+
     /// Check if this error can be retried
     pub fn is_retryable(&self) -> bool {
         matches!(self, ProgressError::Retryable { .. })
@@ -33,12 +33,42 @@ pub trait ProgressWriter: Write + Send + Sync + Debug {
     fn is_ready(&self) -> bool;
 }
 
+/// How raw bytes written to an [`OutputBuffer`] are turned into text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputEncoding {
+    /// UTF-8, replacing invalid sequences with U+FFFD
+    #[default]
+    Lossy,
+    /// UTF-8, rejecting writes that are not valid UTF-8
+    Utf8Strict,
+    /// ISO-8859-1, mapping each byte to the code point of the same value
+    Latin1,
+}
+
+impl OutputEncoding {
+    /// Decode `buf` according to this encoding
+    ///
+    /// # Returns
+    /// The decoded text, or an `InvalidData` error if the encoding is
+    /// [`Utf8Strict`](Self::Utf8Strict) and `buf` is not valid UTF-8
+    pub fn decode(&self, buf: &[u8]) -> io::Result<String> {
+        match self {
+            OutputEncoding::Lossy => Ok(String::from_utf8_lossy(buf).into_owned()),
+            OutputEncoding::Utf8Strict => String::from_utf8(buf.to_vec())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            OutputEncoding::Latin1 => Ok(buf.iter().map(|&b| char::from(b)).collect()),
+        }
+    }
+}
+
 /// A buffer that can be used to store and manage output
 pub struct OutputBuffer {
     /// The maximum number of lines to store
     max_lines: usize,
     /// The stored lines
     lines: Vec<String>,
+    /// How bytes passed to [`Write::write`] are decoded
+    encoding: OutputEncoding,
 }
 
 impl OutputBuffer {
@@ -47,9 +77,20 @@ impl OutputBuffer {
         Self {
             max_lines,
             lines: Vec::with_capacity(max_lines),
+            encoding: OutputEncoding::default(),
         }
     }
 
+    /// Set how bytes written to the buffer are decoded
+    pub fn set_encoding(&mut self, encoding: OutputEncoding) {
+        self.encoding = encoding;
+    }
+
+    /// Get how bytes written to the buffer are decoded
+    pub fn encoding(&self) -> OutputEncoding {
+        self.encoding
+    }
+
     /// Add a line to the buffer
     pub fn add_line(&mut self, line: String) {
         if self.lines.len() >= self.max_lines {
@@ -71,7 +112,7 @@ impl OutputBuffer {
 
 impl Write for OutputBuffer {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        let line = String::from_utf8_lossy(buf).to_string();
+        let line = self.encoding.decode(buf)?;
         self.add_line(line);
         Ok(buf.len())
     }
@@ -101,6 +142,7 @@ impl Debug for OutputBuffer {
         f.debug_struct("OutputBuffer")
             .field("max_lines", &self.max_lines)
             .field("lines", &self.lines)
+            .field("encoding", &self.encoding)
             .finish()
     }
 }
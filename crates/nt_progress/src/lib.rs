@@ -9,7 +9,7 @@ use std::{
     future::Future,
 };
 use tokio::{
-    sync::{mpsc, Mutex},
+    sync::{broadcast::error::RecvError, mpsc, oneshot, Mutex, Notify},
     task::JoinHandle,
 };
 use anyhow::{Result, anyhow};
@@ -18,6 +18,8 @@ use std::cell::RefCell;
 use crate::modes::factory::ModeFactory;
 use crate::ui::renderer::Renderer;
 use crate::progress_manager::ProgressManager;
+#[macro_use]
+mod diagnostics;
 pub mod io;
 
 pub mod modes;
@@ -29,6 +31,8 @@ pub mod terminal;
 pub mod thread;
 pub mod progress_manager;
 pub mod error_recovery;
+pub mod command;
+pub mod notify;
 #[cfg(test)]
 pub mod tests;
 
@@ -36,19 +40,35 @@ pub use modes::{ModeRegistry, ModeCreator};
 pub use core::ThreadConfig;
 pub use config::{Config, ModeParameters, ThreadMode};
 pub use errors::{ModeCreationError, ProgressError, ErrorContext};
+pub use error_recovery::{RecoveryAction, RecoveryRegistry, RecoveryStrategy, RetryConfig};
 pub use ui::formatter::{ProgressTemplate, TemplateContext, TemplateVar, TemplatePreset, ColorName, ProgressIndicator};
-pub use io::{ProgressWriter, OutputBuffer, TeeWriter};
+pub use io::{ProgressWriter, OutputBuffer, OutputEncoding, TeeWriter};
 pub use io::custom::{CustomWriter, WriterCapabilities, WriterRegistry};
-pub use thread::TaskHandle;
+pub use thread::{TaskHandle, PersistedTask};
+pub use command::{CommandOutput, CommandRunner, MockCommandRunner, TokioCommandRunner};
+pub use notify::NotifyConfig;
+pub use tokio_util::sync::CancellationToken;
 pub use core::job_traits::{JobTracker, HierarchicalJobTracker, JobStatusTracker};
+pub use core::base_config::JobStatus;
+pub use core::clock::{Clock, MockClock, SystemClock};
 pub use core::job_traits::{FailureHandlingJob, PausableJob, PrioritizedJob, DependentJob, PersistentJob};
-pub use ui::progress_bar::{ProgressBar, ProgressBarConfig, ProgressBarStyle, MultiProgressBar};
+pub use ui::progress_bar::{LabelPosition, ProgressBar, ProgressBarConfig, ProgressBarStyle, MultiProgressBar, SharedProgressBar};
+pub use ui::renderer::{RenderTarget, TaskRenderInfo};
 
 thread_local! {
     static CURRENT_THREAD_ID: AtomicUsize = const { AtomicUsize::new(0) };
     static CURRENT_WRITER: RefCell<Option<ThreadLogger>> = const { RefCell::new(None) };
 }
 
+/// How often the display thread looks for deadlocked tasks
+const DEADLOCK_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How long [`ProgressDisplay::flush`] waits for the display thread before checking it still runs
+const FLUSH_RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// Callback invoked with a [`ProgressError::DeadlockedTask`] when a deadlock is detected
+type DeadlockHandler = Box<dyn Fn(&ProgressError) + Send + Sync>;
+
 /// Message sent from a thread to the progress display
 #[derive(Debug, Clone)]
 pub struct ThreadMessage {
@@ -56,8 +76,9 @@ pub struct ThreadMessage {
     pub thread_id: usize,
     /// The lines of output from the thread
     pub lines: Vec<String>,
-    /// The configuration for the thread
-    pub config: Config,
+    /// The configuration for the thread, None for messages that only signal
+    /// a progress change
+    pub config: Option<Config>,
 }
 
 /// A display for tracking progress of multiple threads or tasks.
@@ -118,6 +139,22 @@ pub struct ProgressDisplay {
     running: Arc<AtomicBool>,
     /// Background processing task
     processing_task: Arc<Mutex<Option<JoinHandle<()>>>>,
+    /// Restart the display instead of failing when it is used after `stop`
+    auto_restart: Arc<AtomicBool>,
+    /// Callbacks notified of deadlocked tasks
+    deadlock_handlers: Arc<Mutex<Vec<DeadlockHandler>>>,
+    /// Task signalling finished tasks, see [`notify_on_complete`](Self::notify_on_complete)
+    notifier: Arc<Mutex<Option<JoinHandle<()>>>>,
+    /// Task counting failed tasks, see [`set_failure_threshold`](Self::set_failure_threshold)
+    failure_watcher: Arc<Mutex<Option<JoinHandle<()>>>>,
+    /// IDs of the failed tasks and the threshold, once the threshold was exceeded
+    failure_threshold_exceeded: Arc<std::sync::Mutex<Option<(Vec<usize>, usize)>>>,
+    /// Tasks forwarding the messages of displays adopted with [`adopt`](Self::adopt)
+    forwarders: Arc<Mutex<Vec<JoinHandle<()>>>>,
+    /// Wakes the display thread to serve a [`flush`](Self::flush)
+    flush_notify: Arc<Notify>,
+    /// Callers of [`flush`](Self::flush) waiting for the display thread to render
+    flush_requests: Arc<std::sync::Mutex<Vec<oneshot::Sender<()>>>>,
 }
 
 impl std::fmt::Debug for ProgressDisplay {
@@ -128,6 +165,13 @@ impl std::fmt::Debug for ProgressDisplay {
             .field("message_rx", &"Arc<Mutex<mpsc::Receiver<ThreadMessage>>>")
             .field("running", &self.running)
             .field("processing_task", &self.processing_task)
+            .field("auto_restart", &self.auto_restart)
+            .field("deadlock_handlers", &"Arc<Mutex<Vec<DeadlockHandler>>>")
+            .field("notifier", &self.notifier)
+            .field("failure_watcher", &self.failure_watcher)
+            .field("failure_threshold_exceeded", &self.failure_threshold_exceeded)
+            .field("forwarders", &self.forwarders)
+            .field("flush_requests", &self.flush_requests)
             .finish()
     }
 }
@@ -140,15 +184,23 @@ impl ProgressDisplay {
 
     /// Create a new ProgressDisplay with a specific mode
     pub async fn new_with_mode(mode: ThreadMode) -> Result<Self> {
-        let mut factory = ModeFactory::new();
-        factory.set_default_mode(mode);
-        Self::new_with_factory(Arc::new(factory)).await
+        Self::builder().mode(mode).build().await
     }
 
     /// Create a new ProgressDisplay with a specific factory
     pub async fn new_with_factory(factory: Arc<ModeFactory>) -> Result<Self> {
-        let (message_tx, message_rx) = mpsc::channel(1000);
-        let renderer = Arc::new(Renderer::new());
+        Self::builder().factory(factory).build().await
+    }
+
+    /// Start configuring a new ProgressDisplay
+    pub fn builder() -> ProgressDisplayBuilder {
+        ProgressDisplayBuilder::new()
+    }
+
+    /// Create a ProgressDisplay from its parts and start the display thread
+    async fn from_parts(factory: Arc<ModeFactory>, renderer: Renderer, capacity: usize) -> Result<Self> {
+        let (message_tx, message_rx) = mpsc::channel(capacity);
+        let renderer = Arc::new(renderer);
         let progress_manager = Arc::new(ProgressManager::new(factory.clone(), message_tx));
         
         let display = Self {
@@ -157,99 +209,398 @@ impl ProgressDisplay {
             message_rx: Arc::new(Mutex::new(message_rx)),
             running: Arc::new(AtomicBool::new(true)),
             processing_task: Arc::new(Mutex::new(None)),
+            auto_restart: Arc::new(AtomicBool::new(false)),
+            deadlock_handlers: Arc::new(Mutex::new(Vec::new())),
+            notifier: Arc::new(Mutex::new(None)),
+            failure_watcher: Arc::new(Mutex::new(None)),
+            failure_threshold_exceeded: Arc::new(std::sync::Mutex::new(None)),
+            forwarders: Arc::new(Mutex::new(Vec::new())),
+            flush_notify: Arc::new(Notify::new()),
+            flush_requests: Arc::new(std::sync::Mutex::new(Vec::new())),
         };
 
-        // Create a weak reference for the processing task
-        let display_arc = Arc::new(display);
-        let display_weak = Arc::downgrade(&display_arc);
-
-        let processing_task = tokio::spawn({
-            let display_weak = display_weak.clone();
-            async move {
-                if let Some(display) = display_weak.upgrade() {
-                    display.start_display_thread().await;
-                }
-            }
+        // The processing task runs on a clone sharing the display's state
+        let processing_display = display.clone();
+        let processing_task = tokio::spawn(async move {
+            processing_display.start_display_thread().await;
         });
+        *display.processing_task.lock().await = Some(processing_task);
 
-        let mut guard = display_arc.processing_task.lock().await;
-        *guard = Some(processing_task);
-        drop(guard);
+        Ok(display)
+    }
 
-        Ok(Arc::try_unwrap(display_arc).expect("Failed to unwrap Arc - this should never happen"))
+    /// Check whether the display is running, i.e. has not been stopped.
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// Start the display again after [`stop`](Self::stop).
+    ///
+    /// Does nothing if the display is running. Callbacks registered with
+    /// [`notify_on_complete`](Self::notify_on_complete) and the failure
+    /// threshold are cleared by `stop` and have to be set again.
+    pub async fn restart(&self) -> Result<()> {
+        let mut guard = self.processing_task.lock().await;
+        if self.running.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        let display = self.clone();
+        *guard = Some(tokio::spawn(async move {
+            display.start_display_thread().await;
+        }));
+        Ok(())
+    }
+
+    /// Restart the display when a task is created or spawned after [`stop`](Self::stop),
+    /// instead of returning an error.
+    ///
+    /// Useful when the display outlives a single batch of work, e.g. in a REPL.
+    pub fn set_auto_restart(&self, enabled: bool) {
+        self.auto_restart.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Make sure the display is running before `operation`, restarting it if
+    /// auto restart is enabled.
+    async fn ensure_running(&self, operation: &str) -> Result<()> {
+        if self.is_running() {
+            return Ok(());
+        }
+        if self.auto_restart.load(Ordering::SeqCst) {
+            log::debug!("Restarting stopped display for {}", operation);
+            return self.restart().await;
+        }
+
+        let ctx = ErrorContext::new(operation, "ProgressDisplay")
+            .with_details("Display is not running")
+            .with_recovery_hint("Call restart() or enable auto restart");
+        Err(anyhow::Error::from(ProgressError::DisplayOperation("Display is not running".to_string()).into_context(ctx)))
     }
 
     /// Create a new task with the specified mode
     pub async fn create_task(&self, mode: ThreadMode, total_jobs: usize) -> Result<TaskHandle> {
         // Check if the display is running
-        if !self.running.load(Ordering::SeqCst) {
-            let ctx = ErrorContext::new("creating task", "ProgressDisplay")
-                .with_details("Display is not running");
-            return Err(anyhow::Error::from(ProgressError::DisplayOperation("Display is not running".to_string()).into_context(ctx)));
-        }
+        self.ensure_running("creating task").await?;
         
         self.progress_manager.create_task(mode, total_jobs).await
     }
 
+    /// Create a new task from a single task saved with [`TaskHandle::persist`].
+    ///
+    /// The new task gets the saved mode, title, progress and status, under a new thread ID.
+    pub async fn restore_task(&self, task: PersistedTask) -> Result<TaskHandle> {
+        self.ensure_running("restoring task").await?;
+        
+        self.progress_manager.restore_task(task).await
+    }
+
+    /// Spawn a task running the future returned by `f`.
+    ///
+    /// The future receives the task's handle, whose
+    /// [`cancellation_token`](TaskHandle::cancellation_token) is triggered by
+    /// `cancel_task`, `cancel_all` and `stop`.
     pub async fn spawn<F, R>(&self, f: F) -> Result<TaskHandle>
     where
         F: FnOnce(TaskHandle) -> R + Send + 'static,
         R: Future<Output = Result<()>> + Send + 'static,
     {
-        if !self.running.load(Ordering::SeqCst) {
-            let ctx = ErrorContext::new("spawning task", "ProgressDisplay")
-                .with_details("Display is not running");
-            return Err(anyhow::Error::from(ProgressError::DisplayOperation("Display is not running".to_string()).into_context(ctx)));
-        }
+        self.ensure_running("spawning task").await?;
 
         self.progress_manager.spawn(f).await
     }
 
+    /// Run a command in a new task, capturing its output.
+    ///
+    /// See [`ProgressManager::spawn_command`](progress_manager::ProgressManager::spawn_command).
+    pub async fn spawn_command(
+        &self,
+        mode: ThreadMode,
+        runner: Arc<dyn CommandRunner>,
+        program: impl Into<String>,
+        args: Vec<String>,
+    ) -> Result<TaskHandle> {
+        self.ensure_running("spawning command").await?;
+
+        self.progress_manager.spawn_command(mode, runner, program, args).await
+    }
+
     /// Create a new task with the specified mode and title
     pub async fn spawn_with_mode<F, R>(&self, mode: ThreadMode, f: F) -> Result<TaskHandle>
     where
         F: FnOnce() -> R + Send + 'static,
         R: Into<String> + Send + 'static,
     {
-        if !self.running.load(Ordering::SeqCst) {
-            let ctx = ErrorContext::new("spawning task with mode", "ProgressDisplay")
-                .with_details("Display is not running");
-            return Err(anyhow::Error::from(ProgressError::DisplayOperation("Display is not running".to_string()).into_context(ctx)));
-        }
+        self.ensure_running("spawning task with mode").await?;
         
         let title = f().into();
         self.progress_manager.create_task_with_title(mode, title).await
     }
 
     pub async fn display(&self) -> std::io::Result<()> {
+        self.progress_manager.refresh_spinner_outputs().await;
+        if self.renderer.is_quiet() {
+            let statuses = self.progress_manager.task_statuses().await;
+            let outputs = self.progress_manager.outputs().lock().await;
+            return self.renderer.render_failures(&outputs, &statuses).await;
+        }
+        let tasks = self.progress_manager.render_info().await;
         let outputs = self.progress_manager.outputs().lock().await;
-        self.renderer.render(&outputs).await
+        self.renderer.render(&outputs, &tasks).await
+    }
+
+    /// Signal when every task has finished or when a task fails, as set in `config`.
+    ///
+    /// Tasks are seen finishing when their thread state becomes terminal. Calling
+    /// this again replaces the previous configuration.
+    pub async fn notify_on_complete(&self, config: NotifyConfig) {
+        let thread_manager = self.progress_manager.thread_manager().clone();
+        let notifier = notify::spawn_notifier(config, thread_manager, self.renderer.clone());
+        if let Some(previous) = self.notifier.lock().await.replace(notifier) {
+            previous.abort();
+        }
+    }
+
+    /// Cancel all tasks once more than `threshold` of them have failed.
+    ///
+    /// Tasks are seen failing when their thread state becomes `Failed`, from
+    /// this call on. Once the threshold is exceeded, [`join_all`](Self::join_all)
+    /// returns a [`ProgressError::FailureThresholdExceeded`]. Calling this again
+    /// replaces the previous threshold and starts counting from zero.
+    pub async fn set_failure_threshold(&self, threshold: usize) {
+        let thread_manager = self.progress_manager.thread_manager().clone();
+        let exceeded = self.failure_threshold_exceeded.clone();
+        let mut finished = thread_manager.subscribe_finished();
+        let watcher = tokio::spawn(async move {
+            let mut failed = Vec::new();
+            loop {
+                let thread_id = match finished.recv().await {
+                    Ok((thread_id, thread::ThreadState::Failed(_))) => thread_id,
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return,
+                };
+                failed.push(thread_id);
+                if failed.len() > threshold {
+                    log::warn!("{} tasks failed, cancelling all tasks", failed.len());
+                    *exceeded.lock().unwrap() = Some((failed, threshold));
+                    if let Err(e) = thread_manager.cancel_all().await {
+                        diag_error!("Error cancelling tasks: {}", e);
+                    }
+                    return;
+                }
+            }
+        });
+        if let Some(previous) = self.failure_watcher.lock().await.replace(watcher) {
+            previous.abort();
+        }
+    }
+
+    /// Get the error describing the exceeded failure threshold, if it was exceeded.
+    pub fn failure_threshold_error(&self) -> Option<ProgressError> {
+        self.failure_threshold_exceeded.lock().unwrap().clone()
+            .map(|(failed, threshold)| ProgressError::FailureThresholdExceeded { failed, threshold })
+    }
+
+    /// Send all further rendering to `target` instead of the detected terminal stream
+    pub async fn set_render_target(&self, target: RenderTarget) {
+        self.renderer.set_target(target).await;
+    }
+
+    /// Draw higher priority tasks first (the default), or keep them in task ID order
+    pub fn set_priority_ordering(&self, enabled: bool) {
+        self.renderer.set_priority_ordering(enabled);
+    }
+
+    /// Only show the tasks that fail or are retried, for quiet CI logs.
+    ///
+    /// In quiet mode the screen is not redrawn: the output of a task is written
+    /// once when its job status becomes Failed or Retry, and [`stop`](Self::stop)
+    /// writes a summary counting the tasks by outcome.
+    pub fn set_quiet(&self, quiet: bool) {
+        self.renderer.set_quiet(quiet);
+    }
+
+    /// Take over the tasks of another display and stop it.
+    ///
+    /// The tasks of `other` are registered here under new thread IDs, with their
+    /// output so far, and are rendered by this display from then on. Handles
+    /// obtained from `other` keep working: their messages are forwarded here.
+    /// Unlike [`stop`](Self::stop), the adopted tasks are not cancelled.
+    pub async fn adopt(&self, other: ProgressDisplay) -> Result<()> {
+        self.ensure_running("adopting display").await?;
+        if Arc::ptr_eq(&self.progress_manager, &other.progress_manager) {
+            let ctx = ErrorContext::new("adopting display", "ProgressDisplay")
+                .with_details("A display cannot adopt itself");
+            return Err(anyhow::Error::from(ProgressError::DisplayOperation("Cannot adopt the same display".to_string()).into_context(ctx)));
+        }
+
+        // Stop the other display without touching its tasks
+        other.running.store(false, Ordering::SeqCst);
+        if let Some(task) = other.processing_task.lock().await.take() {
+            task.abort();
+        }
+        if let Some(notifier) = other.notifier.lock().await.take() {
+            notifier.abort();
+        }
+        if let Some(watcher) = other.failure_watcher.lock().await.take() {
+            watcher.abort();
+        }
+        other.renderer.stop().await?;
+
+        // Take the other display's receiver, leaving it a closed one
+        let mut rx = {
+            let mut rx = other.message_rx.lock().await;
+            Self::drain(&other.progress_manager, &mut rx).await;
+            std::mem::replace(&mut *rx, mpsc::channel(1).1)
+        };
+        let ids = self.progress_manager.adopt_tasks(&other.progress_manager).await;
+
+        // Messages sent through the other display's handles carry the old IDs.
+        // The receiver closes once those handles and the other display are dropped.
+        let forwarder = tokio::spawn({
+            let tx = self.progress_manager.message_tx().clone();
+            async move {
+                while let Some(mut msg) = rx.recv().await {
+                    let Some(&thread_id) = ids.get(&msg.thread_id) else { continue };
+                    msg.thread_id = thread_id;
+                    if tx.send(msg).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        self.forwarders.lock().await.push(forwarder);
+        let mut forwarders = other.forwarders.lock().await;
+        self.forwarders.lock().await.extend(forwarders.drain(..));
+        drop(forwarders);
+
+        Ok(())
+    }
+
+    /// Render every message sent so far, without waiting for the next batch.
+    ///
+    /// Returns once the output is written, e.g. before prompting the user. Messages
+    /// sent after the call starts may or may not be included.
+    pub async fn flush(&self) -> Result<()> {
+        loop {
+            // Without a display thread holding the receiver, drain it here
+            if let Ok(mut rx) = self.message_rx.try_lock() {
+                Self::drain(&self.progress_manager, &mut rx).await;
+                drop(rx);
+                return self.display().await.map_err(|e| {
+                    let message = e.to_string();
+                    let ctx = ErrorContext::new("flushing output", "ProgressDisplay")
+                        .with_details("Failed to render queued messages")
+                        .with_source(e);
+                    anyhow::Error::from(ProgressError::DisplayOperation(message).into_context(ctx))
+                });
+            }
+
+            let (tx, rx) = oneshot::channel();
+            self.flush_requests.lock().unwrap().push(tx);
+            self.flush_notify.notify_one();
+            // The display thread may stop before serving the request, then the
+            // receiver is free on the next attempt
+            if let Ok(Ok(())) = tokio::time::timeout(FLUSH_RETRY_INTERVAL, rx).await {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Handle every message already in the channel
+    async fn drain(progress_manager: &ProgressManager, rx: &mut mpsc::Receiver<ThreadMessage>) {
+        while let Ok(msg) = rx.try_recv() {
+            progress_manager.handle_message(msg).await;
+        }
     }
 
-    /// Stop the display and clean up all resources
+    /// Handle the messages still in the channel and render the result, with
+    /// the summary in quiet mode
+    async fn flush_on_stop(&self) -> std::io::Result<()> {
+        {
+            // Waits for the display thread to notice shutdown and release the receiver
+            let mut rx = self.message_rx.lock().await;
+            Self::drain(&self.progress_manager, &mut rx).await;
+        }
+        self.display().await?;
+        if self.renderer.is_quiet() {
+            let statuses = self.progress_manager.task_statuses().await;
+            self.renderer.render_summary(&statuses).await?;
+        }
+        Ok(())
+    }
+
+    /// Cancel and join all tasks, handling their messages meanwhile.
+    ///
+    /// The display thread exits once `running` is cleared, so without this a
+    /// task logging while it shuts down would block on a full channel until it
+    /// is aborted, losing its last lines.
+    async fn shut_down_tasks(&self) -> Result<()> {
+        // Waits for the display thread to notice shutdown and release the receiver
+        let mut rx = self.message_rx.lock().await;
+        let shutdown = async {
+            self.progress_manager.cancel_all().await?;
+            self.progress_manager.join_all().await
+        };
+        tokio::pin!(shutdown);
+        loop {
+            tokio::select! {
+                result = &mut shutdown => return result,
+                Some(msg) = rx.recv() => self.progress_manager.handle_message(msg).await,
+            }
+        }
+    }
+
+    /// Stop the display and clean up all resources.
+    ///
+    /// Messages sent before the call, and by tasks while they shut down, are
+    /// rendered one last time before the processing task is stopped.
     pub async fn stop(&self) -> Result<()> {
+        // Held throughout, so a concurrent restart waits for the stop to finish
+        // instead of spawning a processing task that is then aborted
+        let mut guard = self.processing_task.lock().await;
+        
         // First, signal that we're shutting down
         self.running.store(false, Ordering::SeqCst);
         
-        // Cancel all tasks first
-        self.progress_manager.cancel_all().await?;
+        // Cancel and join all tasks, still draining the channel they write to
+        let mut result = self.shut_down_tasks().await;
         
-        // Join all tasks to ensure they're properly cleaned up
-        self.progress_manager.join_all().await?;
+        // Show the output still queued before tearing the display down
+        if let Err(e) = self.flush_on_stop().await {
+            let message = e.to_string();
+            let ctx = ErrorContext::new("rendering final output", "ProgressDisplay")
+                .with_details("Failed to render queued messages")
+                .with_source(e);
+            let error = anyhow::Error::from(ProgressError::DisplayOperation(message).into_context(ctx));
+            result = result.and(Err(error));
+        }
         
         // Stop the terminal event detection
         if let Err(e) = self.renderer.stop().await {
+            let message = e.to_string();
             let ctx = ErrorContext::new("stopping terminal event detection", "ProgressDisplay")
-                .with_details(format!("Failed to stop terminal event detection: {}", e));
-            return Err(anyhow::Error::from(ProgressError::DisplayOperation(e.to_string()).into_context(ctx)));
+                .with_details("Failed to stop terminal event detection")
+                .with_source(e);
+            let error = anyhow::Error::from(ProgressError::DisplayOperation(message).into_context(ctx));
+            result = result.and(Err(error));
+        }
+        
+        if let Some(notifier) = self.notifier.lock().await.take() {
+            notifier.abort();
+        }
+        if let Some(watcher) = self.failure_watcher.lock().await.take() {
+            watcher.abort();
+        }
+        for forwarder in self.forwarders.lock().await.drain(..) {
+            forwarder.abort();
         }
         
         // Stop the processing task last
-        let mut guard = self.processing_task.lock().await;
         if let Some(task) = guard.take() {
             task.abort();
         }
-        Ok(())
+        // The first failure is reported once everything has been torn down
+        result
     }
 
     /// Set the title for a specific thread (if it supports titles)
@@ -272,9 +623,22 @@ impl ProgressDisplay {
         self.progress_manager.update_progress(thread_id).await
     }
 
+    /// Get the lines a specific thread currently displays
+    pub async fn get_task_lines(&self, thread_id: usize) -> Result<Vec<String>> {
+        self.progress_manager.get_task_lines(thread_id).await
+    }
+
     /// Join all tasks to ensure they're properly cleaned up
+    ///
+    /// Fails with [`ProgressError::FailureThresholdExceeded`] if the failure
+    /// threshold set with [`set_failure_threshold`](Self::set_failure_threshold)
+    /// was exceeded.
     pub async fn join_all(&self) -> Result<()> {
-        self.progress_manager.join_all().await
+        self.progress_manager.join_all().await?;
+        match self.failure_threshold_error() {
+            Some(error) => Err(error.into()),
+            None => Ok(()),
+        }
     }
 
     /// Cancel all tasks (abort execution)
@@ -297,6 +661,42 @@ impl ProgressDisplay {
         &self.progress_manager
     }
     
+    /// Register a callback to be notified of deadlocked tasks.
+    ///
+    /// The callback receives a [`ProgressError::DeadlockedTask`] for every task
+    /// found waiting on a cancelled or failed dependency. The display thread checks
+    /// every 30 seconds, so a task is reported on each check until it is unblocked.
+    pub async fn on_deadlock<F>(&self, f: F)
+    where
+        F: Fn(&ProgressError) + Send + Sync + 'static,
+    {
+        self.deadlock_handlers.lock().await.push(Box::new(f));
+    }
+
+    /// Look for deadlocked tasks and notify the registered deadlock callbacks.
+    ///
+    /// Deadlocks are printed as warnings when no callback is registered.
+    ///
+    /// # Returns
+    /// A [`ProgressError::DeadlockedTask`] for each deadlocked task
+    pub async fn check_deadlocks(&self) -> Vec<ProgressError> {
+        let errors: Vec<ProgressError> = self.progress_manager.deadlocked_dependencies().await
+            .into_iter()
+            .map(|(thread_id, deps)| ProgressError::DeadlockedTask(thread_id, deps))
+            .collect();
+
+        let handlers = self.deadlock_handlers.lock().await;
+        for error in &errors {
+            if handlers.is_empty() {
+                diag_warn!("{}", error);
+            }
+            for handler in handlers.iter() {
+                handler(error);
+            }
+        }
+        errors
+    }
+
     /// Background thread that receives messages and processes them
     async fn start_display_thread(&self) {
         let mut rx = self.message_rx.lock().await;
@@ -304,9 +704,25 @@ impl ProgressDisplay {
         // Process messages in batches for better performance
         let mut batch_size = 0;
         const MAX_BATCH_SIZE: usize = 50;
+        let mut last_deadlock_check = std::time::Instant::now();
         
         while self.running.load(Ordering::SeqCst) {
+            if last_deadlock_check.elapsed() >= DEADLOCK_CHECK_INTERVAL {
+                self.check_deadlocks().await;
+                last_deadlock_check = std::time::Instant::now();
+            }
+
             tokio::select! {
+                _ = self.flush_notify.notified() => {
+                    Self::drain(&self.progress_manager, &mut rx).await;
+                    batch_size = 0;
+                    if let Err(e) = self.display().await {
+                        diag_error!("Error displaying progress: {}", e);
+                    }
+                    for request in self.flush_requests.lock().unwrap().drain(..) {
+                        let _ = request.send(());
+                    }
+                }
                 // Try to receive a message with a small timeout
                 msg_option = tokio::time::timeout(
                     tokio::time::Duration::from_millis(10), 
@@ -321,7 +737,7 @@ impl ProgressDisplay {
                             // If we've processed enough messages, update the display
                             if batch_size >= MAX_BATCH_SIZE {
                                 if let Err(e) = self.display().await {
-                                    eprintln!("Error displaying progress: {}", e);
+                                    diag_error!("Error displaying progress: {}", e);
                                 }
                                 batch_size = 0;
                             }
@@ -342,10 +758,12 @@ impl ProgressDisplay {
                             return;
                         },
                         Err(_) => {
-                            // Timeout, update display with current state
-                            if batch_size > 0 {
+                            // Timeout, update display with current state or
+                            // to animate spinners
+                            let spinners_ticked = self.progress_manager.tick_spinners().await;
+                            if batch_size > 0 || spinners_ticked {
                                 if let Err(e) = self.display().await {
-                                    eprintln!("Error displaying progress: {}", e);
+                                    diag_error!("Error displaying progress: {}", e);
                                 }
                                 batch_size = 0;
                             }
@@ -429,11 +847,7 @@ impl ProgressDisplay {
         F: FnOnce() -> R + Send + 'static,
         R: Into<String> + Send + 'static,
     {
-        if !self.running.load(Ordering::SeqCst) {
-            let ctx = ErrorContext::new("spawning child task", "ProgressDisplay")
-                .with_details("Display is not running");
-            return Err(anyhow::Error::from(ProgressError::DisplayOperation("Display is not running".to_string()).into_context(ctx)));
-        }
+        self.ensure_running("spawning child task").await?;
         
         let title = f().into();
         self.progress_manager.create_child_task_with_title(parent_id, mode, title, total_jobs).await
@@ -567,13 +981,143 @@ impl ProgressDisplay {
     }
 }
 
+/// Builder for [`ProgressDisplay`].
+///
+/// ```rust
+/// # use nt_progress::{ProgressDisplay, ThreadMode};
+/// # async fn example() -> anyhow::Result<()> {
+/// let display = ProgressDisplay::builder()
+///     .mode(ThreadMode::Window(5))
+///     .capacity(100)
+///     .template("{progress:bar} {percent}%")
+///     .quiet(true)
+///     .headless(true)
+///     .build()
+///     .await?;
+/// display.stop().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ProgressDisplayBuilder {
+    factory: Option<Arc<ModeFactory>>,
+    mode: Option<ThreadMode>,
+    capacity: usize,
+    writer: Option<Box<dyn std::io::Write + Send + 'static>>,
+    headless: bool,
+    priority_ordering: bool,
+    quiet: bool,
+    template: Option<String>,
+}
+
+impl ProgressDisplayBuilder {
+    /// Create a builder with the same defaults as [`ProgressDisplay::new`]
+    pub fn new() -> Self {
+        Self {
+            factory: None,
+            mode: None,
+            capacity: 1000,
+            writer: None,
+            headless: false,
+            priority_ordering: true,
+            quiet: false,
+            template: None,
+        }
+    }
+
+    /// Use the given factory to create task modes
+    pub fn factory(mut self, factory: Arc<ModeFactory>) -> Self {
+        self.factory = Some(factory);
+        self
+    }
+
+    /// Set the default mode of the factory
+    pub fn mode(mut self, mode: ThreadMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Set how many thread messages can be queued before senders wait
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Render to the given writer instead of stdout
+    pub fn writer(mut self, writer: Box<dyn std::io::Write + Send + 'static>) -> Self {
+        self.writer = Some(writer);
+        self
+    }
+
+    /// Discard all rendered output, e.g. when not attached to a terminal
+    pub fn headless(mut self, headless: bool) -> Self {
+        self.headless = headless;
+        self
+    }
+
+    /// Draw higher priority tasks first (the default), or keep them in task ID order
+    pub fn priority_ordering(mut self, enabled: bool) -> Self {
+        self.priority_ordering = enabled;
+        self
+    }
+
+    /// Only draw failing tasks and a final summary, see [`Renderer::set_quiet`]
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    /// Set the progress format of every task whose mode tracks progress
+    pub fn template(mut self, template: impl Into<String>) -> Self {
+        self.template = Some(template.into());
+        self
+    }
+
+    /// Create the display and start its display thread
+    pub async fn build(self) -> Result<ProgressDisplay> {
+        if self.capacity == 0 {
+            let ctx = ErrorContext::new("building display", "ProgressDisplayBuilder")
+                .with_details("Message capacity must be greater than zero");
+            return Err(anyhow::Error::from(ProgressError::DisplayOperation("Invalid message capacity".to_string()).into_context(ctx)));
+        }
+
+        let mut factory = match self.factory {
+            Some(factory) => factory,
+            None => Arc::new(ModeFactory::new()),
+        };
+        if let Some(mode) = self.mode {
+            Arc::make_mut(&mut factory).set_default_mode(mode);
+        }
+
+        let renderer = if self.headless {
+            Renderer::with_writer(Box::new(std::io::sink()))
+        } else {
+            match self.writer {
+                Some(writer) => Renderer::with_writer(writer),
+                None => Renderer::new(),
+            }
+        };
+        renderer.set_priority_ordering(self.priority_ordering);
+        renderer.set_quiet(self.quiet);
+
+        let display = ProgressDisplay::from_parts(factory, renderer, self.capacity).await?;
+        display.progress_manager.set_default_template(self.template);
+        Ok(display)
+    }
+}
+
+impl Default for ProgressDisplayBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Drop for ProgressDisplay {
     fn drop(&mut self) {
         // Signal that we're shutting down
         self.running.store(false, Ordering::SeqCst);
         
         // Log a warning about cleanup
-        eprintln!("Warning: ProgressDisplay dropped - cleanup should be handled by calling stop() explicitly");
+        diag_warn!("ProgressDisplay dropped - cleanup should be handled by calling stop() explicitly");
     }
 }
 
@@ -599,7 +1143,7 @@ impl ThreadLogger {
         let message = ThreadMessage {
             thread_id: self.thread_id,
             lines,
-            config: self.config.clone(),
+            config: Some(self.config.clone()),
         };
         
         self.message_tx.send(message).await.map_err(|e| anyhow!("Failed to send message: {}", e))
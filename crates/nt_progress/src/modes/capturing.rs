@@ -1,16 +1,98 @@
 use crate::core::{ThreadConfig, HasBaseConfig, BaseConfig};
+use crate::errors::ProgressError;
 use super::window_base::SingleLineBase;
 use std::any::Any;
 use std::fmt::Debug;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Default maximum size of the capture buffer (10MB).
+pub const DEFAULT_MAX_BUFFER_BYTES: usize = 10 * 1024 * 1024;
+
+/// Default maximum number of lines in the capture buffer.
+pub const DEFAULT_MAX_BUFFER_LINES: usize = 100_000;
+
+/// Line appended to the capture buffer when [`OverflowStrategy::Stop`] stops capturing.
+pub const CAPTURE_STOPPED_MARKER: &str = "[capture stopped: buffer limit reached]";
+
+/// What to do when a captured line would exceed the buffer limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowStrategy {
+    /// Drop the oldest lines until the new line fits
+    #[default]
+    DropOldest,
+    /// Reject the new line and keep the existing buffer
+    DropNewest,
+    /// Reject the new line and return an error to the caller
+    Error,
+    /// Append [`CAPTURE_STOPPED_MARKER`] and ignore all further lines
+    Stop,
+}
+
+/// Buffer limits for Capturing mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapturingConfig {
+    /// Maximum number of bytes kept in the capture buffer
+    pub max_buffer_bytes: usize,
+    /// Maximum number of lines kept in the capture buffer
+    pub max_buffer_lines: usize,
+    /// Strategy applied when the buffer is full
+    pub overflow_strategy: OverflowStrategy,
+}
+
+impl Default for CapturingConfig {
+    fn default() -> Self {
+        Self {
+            max_buffer_bytes: DEFAULT_MAX_BUFFER_BYTES,
+            max_buffer_lines: DEFAULT_MAX_BUFFER_LINES,
+            overflow_strategy: OverflowStrategy::default(),
+        }
+    }
+}
+
+/// Captured lines and their bookkeeping.
+#[derive(Debug, Default)]
+struct CaptureBuffer {
+    lines: Vec<String>,
+    size_bytes: usize,
+    overflow_count: usize,
+    /// Set once [`OverflowStrategy::Stop`] has stopped capturing
+    stopped: bool,
+}
+
+impl CaptureBuffer {
+    /// Whether `line_len` more bytes and one more line stay within `config`'s limits
+    fn fits(&self, line_len: usize, config: &CapturingConfig) -> bool {
+        self.size_bytes + line_len <= config.max_buffer_bytes && self.lines.len() < config.max_buffer_lines
+    }
+}
+
+/// Lines captured so far, returned by [`Capturing::captured`].
+///
+/// Dereferences to `&[String]` without copying the buffer, which stays locked
+/// until this is dropped.
+pub struct CapturedLines<'a>(MutexGuard<'a, CaptureBuffer>);
+
+impl Deref for CapturedLines<'_> {
+    type Target = [String];
+
+    fn deref(&self) -> &[String] {
+        &self.0.lines
+    }
+}
 
 /// Configuration for Capturing mode
 ///
 /// Capturing mode captures output without displaying it in the terminal.
 /// This is useful for saving output for later use or for automated testing.
+/// The capture buffer is bounded by [`CapturingConfig::max_buffer_bytes`] and
+/// [`CapturingConfig::max_buffer_lines`], and is shared between clones of the
+/// same configuration.
 #[derive(Debug, Clone)]
 pub struct Capturing {
     base: SingleLineBase,
-    capture: Vec<String>,
+    config: CapturingConfig,
+    capture: Arc<Mutex<CaptureBuffer>>,
 }
 
 impl Capturing {
@@ -22,15 +104,123 @@ impl Capturing {
     /// # Returns
     /// A new Capturing configuration
     pub fn new(total_jobs: usize) -> Self {
+        Self::with_config(total_jobs, CapturingConfig::default())
+    }
+
+    /// Creates a new Capturing mode configuration with custom buffer limits
+    ///
+    /// # Parameters
+    /// * `total_jobs` - The total number of jobs to track
+    /// * `config` - The buffer limits to apply
+    ///
+    /// # Returns
+    /// A new Capturing configuration
+    pub fn with_config(total_jobs: usize, config: CapturingConfig) -> Self {
         Self {
             base: SingleLineBase::new(total_jobs, false),
-            capture: Vec::new(),
+            config,
+            capture: Arc::new(Mutex::new(CaptureBuffer::default())),
         }
     }
+
+    /// Get the buffer limits for this mode
+    pub fn capturing_config(&self) -> CapturingConfig {
+        self.config
+    }
+
+    /// Replace the buffer limits for this mode
+    ///
+    /// Lowering the limit does not trim lines that are already captured;
+    /// the new limit applies to subsequent captures.
+    pub fn set_capturing_config(&mut self, config: CapturingConfig) {
+        self.config = config;
+    }
     
     /// Get captured output
-    pub fn captured(&self) -> &[String] {
-        &self.capture
+    pub fn captured(&self) -> CapturedLines<'_> {
+        CapturedLines(self.capture.lock().unwrap())
+    }
+
+    /// Get the current size of the capture buffer in bytes
+    pub fn buffer_size_bytes(&self) -> usize {
+        self.capture.lock().unwrap().size_bytes
+    }
+
+    /// Get the number of times the buffer limit has been hit
+    pub fn overflow_count(&self) -> usize {
+        self.capture.lock().unwrap().overflow_count
+    }
+
+    /// Check whether [`OverflowStrategy::Stop`] has stopped capturing
+    pub fn is_stopped(&self) -> bool {
+        self.capture.lock().unwrap().stopped
+    }
+
+    /// Append a line to the capture buffer, applying the overflow strategy
+    /// if the line would exceed the buffer limits.
+    ///
+    /// # Parameters
+    /// * `line` - The line to capture
+    ///
+    /// # Returns
+    /// `Ok(())` if the line was captured or dropped according to the strategy,
+    /// or an error if the strategy is [`OverflowStrategy::Error`] and the buffer is full
+    pub fn capture(&mut self, line: String) -> Result<(), ProgressError> {
+        let mut buffer = self.capture.lock().unwrap();
+        let max = self.config.max_buffer_bytes;
+
+        if buffer.stopped {
+            return Ok(());
+        }
+
+        if !buffer.fits(line.len(), &self.config) {
+            buffer.overflow_count += 1;
+            match self.config.overflow_strategy {
+                OverflowStrategy::DropOldest => {
+                    let mut freed = 0;
+                    let mut dropped = 0;
+                    while dropped < buffer.lines.len()
+                        && (buffer.size_bytes - freed + line.len() > max
+                            || buffer.lines.len() - dropped >= self.config.max_buffer_lines)
+                    {
+                        freed += buffer.lines[dropped].len();
+                        dropped += 1;
+                    }
+                    buffer.lines.drain(..dropped);
+                    buffer.size_bytes -= freed;
+                    diag_warn!(
+                        "Capture buffer exceeded {} bytes or {} lines, dropped {} oldest line(s)",
+                        max, self.config.max_buffer_lines, dropped
+                    );
+                    // A single line larger than the whole buffer is never kept
+                    if !buffer.fits(line.len(), &self.config) {
+                        return Ok(());
+                    }
+                }
+                OverflowStrategy::DropNewest => {
+                    diag_warn!("Capture buffer exceeded {} bytes, dropped new line", max);
+                    return Ok(());
+                }
+                OverflowStrategy::Error => {
+                    return Err(ProgressError::TaskOperation(format!(
+                        "Capture buffer full ({} of {} bytes, {} of {} lines used)",
+                        buffer.size_bytes, max, buffer.lines.len(), self.config.max_buffer_lines
+                    )));
+                }
+                OverflowStrategy::Stop => {
+                    diag_warn!("Capture buffer limit reached, no longer capturing");
+                    // The marker is the only thing allowed past the limits
+                    buffer.stopped = true;
+                    buffer.size_bytes += CAPTURE_STOPPED_MARKER.len();
+                    buffer.lines.push(CAPTURE_STOPPED_MARKER.to_string());
+                    return Ok(());
+                }
+            }
+        }
+
+        buffer.size_bytes += line.len();
+        buffer.lines.push(line);
+        Ok(())
     }
 }
 
@@ -72,6 +262,14 @@ impl ThreadConfig for Capturing {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn base(&self) -> Option<&BaseConfig> {
+        Some(self.base_config())
+    }
+
+    fn base_mut(&mut self) -> Option<&mut BaseConfig> {
+        Some(self.base_config_mut())
+    }
 }
 
 #[cfg(test)]
@@ -104,4 +302,97 @@ mod tests {
         // Test completed jobs
         assert_eq!(capturing.increment_completed_jobs(), 1);
     }
-} 
\ No newline at end of file
+
+    fn small_buffer(strategy: OverflowStrategy) -> Capturing {
+        Capturing::with_config(1, CapturingConfig {
+            max_buffer_bytes: 10,
+            overflow_strategy: strategy,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_capture_drop_oldest() {
+        let mut capturing = small_buffer(OverflowStrategy::DropOldest);
+        capturing.capture("aaaa".to_string()).unwrap();
+        capturing.capture("bbbb".to_string()).unwrap();
+        assert_eq!(capturing.buffer_size_bytes(), 8);
+
+        capturing.capture("cccc".to_string()).unwrap();
+        assert_eq!(*capturing.captured(), ["bbbb", "cccc"]);
+        assert_eq!(capturing.buffer_size_bytes(), 8);
+        assert_eq!(capturing.overflow_count(), 1);
+    }
+
+    #[test]
+    fn test_capture_drop_newest() {
+        let mut capturing = small_buffer(OverflowStrategy::DropNewest);
+        capturing.capture("aaaa".to_string()).unwrap();
+        capturing.capture("bbbb".to_string()).unwrap();
+
+        capturing.capture("cccc".to_string()).unwrap();
+        assert_eq!(*capturing.captured(), ["aaaa", "bbbb"]);
+        assert_eq!(capturing.buffer_size_bytes(), 8);
+        assert_eq!(capturing.overflow_count(), 1);
+    }
+
+    #[test]
+    fn test_capture_error_on_overflow() {
+        let mut capturing = small_buffer(OverflowStrategy::Error);
+        capturing.capture("aaaaaaaa".to_string()).unwrap();
+
+        assert!(capturing.capture("bbbb".to_string()).is_err());
+        assert_eq!(*capturing.captured(), ["aaaaaaaa"]);
+        assert_eq!(capturing.overflow_count(), 1);
+    }
+
+    #[test]
+    fn test_capture_line_larger_than_buffer() {
+        let mut capturing = small_buffer(OverflowStrategy::DropOldest);
+        capturing.capture("aaaa".to_string()).unwrap();
+        capturing.capture("x".repeat(20)).unwrap();
+        assert!(capturing.captured().is_empty());
+        assert_eq!(capturing.buffer_size_bytes(), 0);
+    }
+
+    #[test]
+    fn test_capture_line_limit() {
+        let mut capturing = Capturing::with_config(1, CapturingConfig {
+            max_buffer_lines: 3,
+            ..Default::default()
+        });
+        for i in 0..1000 {
+            capturing.capture(format!("line {}", i)).unwrap();
+        }
+        assert_eq!(*capturing.captured(), ["line 997", "line 998", "line 999"]);
+        assert_eq!(capturing.buffer_size_bytes(), 24);
+        assert_eq!(capturing.overflow_count(), 997);
+    }
+
+    #[test]
+    fn test_capture_stop_with_marker() {
+        let mut capturing = small_buffer(OverflowStrategy::Stop);
+        capturing.capture("aaaa".to_string()).unwrap();
+        capturing.capture("bbbb".to_string()).unwrap();
+        assert!(!capturing.is_stopped());
+
+        for _ in 0..1000 {
+            capturing.capture("cccc".to_string()).unwrap();
+        }
+        assert!(capturing.is_stopped());
+        assert_eq!(*capturing.captured(), ["aaaa", "bbbb", CAPTURE_STOPPED_MARKER]);
+        assert_eq!(capturing.buffer_size_bytes(), 8 + CAPTURE_STOPPED_MARKER.len());
+
+        // Lines that would fit again are still ignored
+        capturing.capture("d".to_string()).unwrap();
+        assert_eq!(capturing.captured().len(), 3);
+    }
+
+    #[test]
+    fn test_default_capturing_config() {
+        let config = CapturingConfig::default();
+        assert_eq!(config.max_buffer_bytes, 10 * 1024 * 1024);
+        assert_eq!(config.max_buffer_lines, DEFAULT_MAX_BUFFER_LINES);
+        assert_eq!(config.overflow_strategy, OverflowStrategy::DropOldest);
+    }
+}
@@ -2,7 +2,7 @@ use std::sync::Arc;
 use std::collections::HashMap;
 use crate::core::ThreadConfig;
 use crate::config::ThreadMode;
-use super::{Limited, Capturing, Window, WindowWithTitle};
+use super::{Limited, Capturing, Window, WindowWithTitle, Spinner};
 use crate::config::ModeParameters;
 use crate::errors::ModeCreationError;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -41,9 +41,9 @@ pub trait ModeCreator: Send + Sync + Debug {
 }
 
 /// Registry for mode creators
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ModeRegistry {
-    creators: HashMap<String, Box<dyn ModeCreator>>,
+    creators: HashMap<String, Arc<dyn ModeCreator>>,
 }
 
 impl ModeRegistry {
@@ -57,7 +57,17 @@ impl ModeRegistry {
     /// Register a new mode creator
     pub fn register<T: ModeCreator + 'static>(&mut self, creator: T) {
         let name = creator.mode_name().to_string();
-        self.creators.insert(name, Box::new(creator));
+        self.creators.insert(name, Arc::new(creator));
+    }
+    
+    /// Register a mode creator under the given name
+    pub fn register_as(&mut self, name: impl Into<String>, creator: Box<dyn ModeCreator>) {
+        self.creators.insert(name.into(), Arc::from(creator));
+    }
+    
+    /// Check whether the given mode name is one of the built-in modes
+    fn is_builtin(mode_name: &str) -> bool {
+        matches!(mode_name, "limited" | "capturing" | "window" | "window_with_title" | "spinner")
     }
     
     /// Validate parameters for a specific mode.
@@ -79,6 +89,11 @@ impl ModeRegistry {
             ));
         }
         
+        // Custom creators validate their own parameters
+        if !Self::is_builtin(mode_name) {
+            return Ok(());
+        }
+        
         // Validate the parameters
         params.validate(mode_name)
     }
@@ -126,6 +141,8 @@ impl ModeRegistry {
             ThreadMode::Capturing => self.create("capturing", &ModeParameters::capturing(total_jobs)),
             ThreadMode::Window(max_lines) => self.create("window", &ModeParameters::window(total_jobs, max_lines)),
             ThreadMode::WindowWithTitle(max_lines) => self.create("window_with_title", &ModeParameters::window_with_title(total_jobs, max_lines, "Progress".to_string())),
+            ThreadMode::Spinner => self.create("spinner", &ModeParameters::spinner(total_jobs)),
+            ThreadMode::Custom(name) => self.create(&name, &ModeParameters::new(total_jobs)),
         }
     }
 }
@@ -185,7 +202,7 @@ impl ModeCreator for WindowCreator {
         // If creation fails, try with a reasonable fallback size
         if result.is_err() {
             let max_lines = params.max_lines().unwrap_or(0);
-            eprintln!("Warning: Requested window size {} was invalid, using size 3 instead", max_lines);
+            diag_warn!("Requested window size {} was invalid, using size 3 instead", max_lines);
             
             // Try with a reasonable fallback size
             let mut fallback_params = params.clone();
@@ -195,7 +212,7 @@ impl ModeCreator for WindowCreator {
             }
             
             // Last resort: fall back to Limited mode
-            eprintln!("Warning: Could not create Window mode, falling back to Limited mode");
+            diag_warn!("Could not create Window mode, falling back to Limited mode");
             return Ok(Box::new(Limited::new(params.total_jobs())));
         }
         
@@ -235,7 +252,7 @@ impl ModeCreator for WindowWithTitleCreator {
         // If creation fails, try with a reasonable fallback size
         if result.is_err() {
             let max_lines = params.max_lines().unwrap_or(0);
-            eprintln!("Warning: Requested window size {} was invalid, using size 3 instead", max_lines);
+            diag_warn!("Requested window size {} was invalid, using size 3 instead", max_lines);
             
             // Try with a reasonable fallback size
             let mut fallback_params = params.clone();
@@ -252,7 +269,7 @@ impl ModeCreator for WindowWithTitleCreator {
             }
             
             // Last resort: fall back to Limited mode
-            eprintln!("Warning: Could not create WindowWithTitle mode, falling back to Limited mode");
+            diag_warn!("Could not create WindowWithTitle mode, falling back to Limited mode");
             return Ok(Box::new(Limited::new(params.total_jobs())));
         }
         
@@ -264,6 +281,42 @@ impl ModeCreator for WindowWithTitleCreator {
     }
 }
 
+/// Creator for Spinner mode
+#[derive(Debug)]
+pub struct SpinnerCreator;
+
+impl ModeCreator for SpinnerCreator {
+    fn create(&self, params: &ModeParameters) -> Result<Box<dyn ThreadConfig>, ModeCreationError> {
+        params.validate(self.mode_name())?;
+        let mut mode = Spinner::new(params.total_jobs());
+        if let Some(frames) = params.frames() {
+            mode.set_frames(frames.to_vec())?;
+        }
+        if let Some(title) = params.title() {
+            mode.handle_message(title.to_string());
+        }
+        Ok(Box::new(mode))
+    }
+    
+    fn create_with_fallback(&self, params: &ModeParameters) -> Result<Box<dyn ThreadConfig>, ModeCreationError> {
+        let result = self.create(params);
+        
+        // If the frames are unusable, spin with the default ones
+        if result.is_err() {
+            diag_warn!("Requested spinner frames were invalid, using the default frames instead");
+            let mut fallback_params = params.clone();
+            fallback_params.frames = None;
+            return self.create(&fallback_params);
+        }
+        
+        result
+    }
+    
+    fn mode_name(&self) -> &'static str {
+        "spinner"
+    }
+}
+
 /// A factory for creating mode instances
 ///
 /// ModeFactory provides a way to create mode instances without using static
@@ -293,6 +346,7 @@ impl ModeFactory {
         registry.register(CapturingCreator);
         registry.register(WindowCreator);
         registry.register(WindowWithTitleCreator);
+        registry.register(SpinnerCreator);
         
         Self::with_registry(Arc::new(registry))
     }
@@ -314,7 +368,15 @@ impl ModeFactory {
     
     /// Get the default mode for this factory
     pub fn default_mode(&self) -> ThreadMode {
-        self.default_mode
+        self.default_mode.clone()
+    }
+    
+    /// Register a mode creator under the given name
+    ///
+    /// The mode can then be used with `ThreadMode::Custom(name)`. If the
+    /// registry is shared with other factories, this factory gets its own copy.
+    pub fn register(&mut self, name: impl Into<String>, creator: Box<dyn ModeCreator>) {
+        Arc::make_mut(&mut self.registry).register_as(name, creator);
     }
     
     /// Create a new mode instance
@@ -325,7 +387,13 @@ impl ModeFactory {
     ///
     /// # Returns
     /// A Result containing either the created ThreadConfig or an error
+    ///
+    /// # Errors
+    /// Returns ModeCreationError for a zero window size, whether or not
+    /// fallbacks are enabled
     pub fn create_mode(&self, mode: ThreadMode, total_jobs: usize) -> Result<Box<dyn ThreadConfig>, ModeCreationError> {
+        Self::validate_mode(&mode)?;
+        
         if should_propagate_errors() {
             // If error propagation is enabled, use direct creation without fallbacks
             match mode {
@@ -333,6 +401,8 @@ impl ModeFactory {
                 ThreadMode::Capturing => self.registry.create("capturing", &ModeParameters::capturing(total_jobs)),
                 ThreadMode::Window(size) => self.registry.create("window", &ModeParameters::window(total_jobs, size)),
                 ThreadMode::WindowWithTitle(size) => self.registry.create("window_with_title", &ModeParameters::window_with_title(total_jobs, size, "Progress".to_string())),
+                ThreadMode::Spinner => self.registry.create("spinner", &ModeParameters::spinner(total_jobs)),
+                ThreadMode::Custom(name) => self.registry.create(&name, &ModeParameters::new(total_jobs)),
             }
         } else {
             // With error propagation disabled, provide fallbacks
@@ -343,7 +413,7 @@ impl ModeFactory {
                     let result = self.registry.create("window", &ModeParameters::window(total_jobs, size));
                     if result.is_err() {
                         // Try with a reasonable fallback size
-                        eprintln!("Warning: Requested window size {} was invalid, using size 3 instead", size);
+                        diag_warn!("Requested window size {} was invalid, using size 3 instead", size);
                         self.registry.create("window", &ModeParameters::window(total_jobs, 3))
                     } else {
                         result
@@ -353,15 +423,15 @@ impl ModeFactory {
                     let result = self.registry.create("window_with_title", &ModeParameters::window_with_title(total_jobs, size, "Progress".to_string()));
                     if result.is_err() {
                         // Try with a reasonable fallback size
-                        eprintln!("Warning: Requested window size {} was invalid, using size 3 instead", size);
+                        diag_warn!("Requested window size {} was invalid, using size 3 instead", size);
                         let fallback = self.registry.create("window_with_title", &ModeParameters::window_with_title(total_jobs, 3, "Progress".to_string()));
                         if fallback.is_err() {
                             // Try with Window mode as a fallback
-                            eprintln!("Warning: Could not create WindowWithTitle mode, falling back to Window mode");
+                            diag_warn!("Could not create WindowWithTitle mode, falling back to Window mode");
                             let window = self.registry.create("window", &ModeParameters::window(total_jobs, 3));
                             if window.is_err() {
                                 // Last resort: fall back to Limited mode
-                                eprintln!("Warning: Could not create any window mode, falling back to Limited mode");
+                                diag_warn!("Could not create any window mode, falling back to Limited mode");
                                 self.registry.create("limited", &ModeParameters::limited(total_jobs))
                             } else {
                                 window
@@ -372,11 +442,28 @@ impl ModeFactory {
                     } else {
                         result
                     }
-                }
+                },
+                ThreadMode::Spinner => self.registry.create("spinner", &ModeParameters::spinner(total_jobs)),
+                ThreadMode::Custom(name) => self.registry.create(&name, &ModeParameters::new(total_jobs)),
             }
         }
     }
     
+    /// Reject modes whose parameters can never produce a usable display
+    fn validate_mode(mode: &ThreadMode) -> Result<(), ModeCreationError> {
+        let (mode_name, min_size) = match mode {
+            ThreadMode::Window(0) => ("Window", 1),
+            ThreadMode::WindowWithTitle(0) => ("WindowWithTitle", 2),
+            _ => return Ok(()),
+        };
+        Err(ModeCreationError::InvalidWindowSize {
+            size: 0,
+            min_size,
+            mode_name: mode_name.to_string(),
+            reason: Some("Window size must be greater than zero".to_string()),
+        })
+    }
+    
     /// Get a reference to the underlying registry
     pub fn registry(&self) -> &ModeRegistry {
         &self.registry
@@ -462,6 +549,7 @@ mod tests {
         assert!(registry.creators.contains_key("capturing"));
         assert!(registry.creators.contains_key("window"));
         assert!(registry.creators.contains_key("window_with_title"));
+        assert!(registry.creators.contains_key("spinner"));
     }
     
     #[test]
@@ -479,7 +567,7 @@ mod tests {
         set_error_propagation(false);
         
         // Should recover with fallback
-        let result = factory.create_mode(ThreadMode::Window(0), 1);
+        let result = factory.create_mode(ThreadMode::WindowWithTitle(1), 1);
         assert!(result.is_ok());
         
         // Reset for other tests
@@ -502,6 +590,30 @@ mod tests {
         assert_eq!(config.lines_to_display(), 3);
     }
     
+    #[test]
+    fn test_default_mode_getter() {
+        let mut factory = ModeFactory::new();
+        assert_eq!(factory.default_mode(), ThreadMode::Limited);
+        
+        factory.set_default_mode(ThreadMode::WindowWithTitle(4));
+        assert_eq!(factory.default_mode(), ThreadMode::WindowWithTitle(4));
+        
+        // Clones share the configured default
+        assert_eq!(factory.clone().default_mode(), ThreadMode::WindowWithTitle(4));
+    }
+    
+    #[test]
+    fn test_zero_window_size_rejected() {
+        // Zero-sized windows are rejected even when fallbacks are enabled
+        let factory = ModeFactory::new();
+        for mode in [ThreadMode::Window(0), ThreadMode::WindowWithTitle(0)] {
+            match factory.create_mode(mode, 10) {
+                Err(ModeCreationError::InvalidWindowSize { size, .. }) => assert_eq!(size, 0),
+                other => panic!("Expected InvalidWindowSize, got {:?}", other.map(|_| ())),
+            }
+        }
+    }
+    
     #[test]
     fn test_factory_error_handling() {
         // First test with error propagation enabled - should error on invalid sizes
@@ -521,10 +633,7 @@ mod tests {
         let factory = ModeFactory::new();
         
         // Test with invalid sizes - should succeed with fallbacks
-        let result = factory.create_mode(ThreadMode::Window(0), 10);
-        assert!(result.is_ok(), "With error propagation disabled, invalid window size should use fallback");
-        
-        let result = factory.create_mode(ThreadMode::WindowWithTitle(0), 10);
+        let result = factory.create_mode(ThreadMode::WindowWithTitle(1), 10);
         assert!(result.is_ok(), "With error propagation disabled, invalid window with title size should use fallback");
         
         // Test with valid sizes - always succeeds
@@ -585,6 +694,24 @@ mod tests {
         set_error_propagation(false);
     }
 
+    #[test]
+    fn test_spinner_creation() {
+        let factory = ModeFactory::new();
+        let config = factory.create_mode(ThreadMode::Spinner, 10).unwrap();
+        let spinner = config.as_any().downcast_ref::<Spinner>().unwrap();
+        assert_eq!(config.lines_to_display(), 1);
+        assert_eq!(spinner.current_frame(), "⠋");
+
+        // Callers can supply their own glyphs
+        let params = ModeParameters::spinner(10)
+            .with_frames(vec!["◐".to_string(), "◓".to_string()])
+            .with_title("Loading".to_string());
+        let config = factory.registry().create("spinner", &params).unwrap();
+        let spinner = config.as_any().downcast_ref::<Spinner>().unwrap();
+        assert_eq!(spinner.frames(), &["◐".to_string(), "◓".to_string()]);
+        assert_eq!(config.get_lines(), vec!["◐ Loading"]);
+    }
+
     #[test]
     fn test_factory_with_custom_registry() {
         let mut registry = ModeRegistry::new();
@@ -81,6 +81,14 @@ impl ThreadConfig for Limited {
         self
     }
 
+    fn base(&self) -> Option<&BaseConfig> {
+        Some(self.base_config())
+    }
+
+    fn base_mut(&mut self) -> Option<&mut BaseConfig> {
+        Some(self.base_config_mut())
+    }
+
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
@@ -6,11 +6,13 @@ pub mod capturing;
 pub mod window;
 pub mod window_with_title;
 pub mod window_base;
+pub mod spinner;
 pub mod factory;
 
 // Re-export key components
 pub use limited::Limited;
-pub use capturing::Capturing;
+pub use capturing::{Capturing, CapturedLines, CapturingConfig, OverflowStrategy};
 pub use window::Window;
 pub use window_with_title::WindowWithTitle;
+pub use spinner::Spinner;
 pub use factory::{ModeFactory, ModeRegistry, ModeCreator};
\ No newline at end of file
@@ -0,0 +1,281 @@
+use crate::core::{ThreadConfig, HasBaseConfig, BaseConfig};
+use super::window_base::SingleLineBase;
+use crate::config::capabilities::WithTitle;
+use std::any::Any;
+use std::time::{Duration, Instant};
+use crate::errors::ModeCreationError;
+use anyhow::Result;
+
+/// Frames used when none are supplied
+pub const DEFAULT_SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Time each frame is shown before the spinner advances, by default
+pub const DEFAULT_SPINNER_INTERVAL: Duration = Duration::from_millis(80);
+
+/// Configuration for Spinner mode
+///
+/// In Spinner mode, a single line shows an animated spinner followed by a
+/// status message. The message is the task's title, and is also replaced by
+/// each message received.
+///
+/// The spinner does not move on its own: the display thread calls
+/// [`tick`](Self::tick) while idle, which advances the frame once the frame
+/// interval has elapsed.
+///
+/// # Example
+///
+/// ```
+/// use nt_progress::core::ThreadConfig;
+/// use nt_progress::modes::Spinner;
+///
+/// let mut spinner = Spinner::with_frames(1, vec!["a".to_string(), "b".to_string()]).unwrap();
+/// assert_eq!(spinner.handle_message("loading".to_string()), vec!["a loading"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Spinner {
+    single_line_base: SingleLineBase,
+    frames: Vec<String>,
+    frame: usize,
+    interval: Duration,
+    /// When the current frame was first ticked, read from the job's clock
+    last_frame_at: Option<Instant>,
+}
+
+impl Spinner {
+    /// Creates a new Spinner mode configuration with the default frames.
+    ///
+    /// # Parameters
+    /// * `total_jobs` - The total number of jobs to track
+    ///
+    /// # Returns
+    /// A new Spinner instance
+    pub fn new(total_jobs: usize) -> Self {
+        Self {
+            single_line_base: SingleLineBase::new(total_jobs, false),
+            frames: DEFAULT_SPINNER_FRAMES.iter().map(|frame| frame.to_string()).collect(),
+            frame: 0,
+            interval: DEFAULT_SPINNER_INTERVAL,
+            last_frame_at: None,
+        }
+    }
+
+    /// Creates a new Spinner mode configuration cycling through `frames`.
+    ///
+    /// # Parameters
+    /// * `total_jobs` - The total number of jobs to track
+    /// * `frames` - The glyphs to cycle through, in order
+    ///
+    /// # Returns
+    /// A Result containing either the new Spinner or a ModeCreationError
+    ///
+    /// # Errors
+    /// Returns a MissingParameter error if `frames` is empty
+    pub fn with_frames(total_jobs: usize, frames: Vec<String>) -> Result<Self, ModeCreationError> {
+        let mut spinner = Self::new(total_jobs);
+        spinner.set_frames(frames)?;
+        Ok(spinner)
+    }
+
+    /// Replace the frames the spinner cycles through, restarting from the first.
+    ///
+    /// # Errors
+    /// Returns a MissingParameter error if `frames` is empty
+    pub fn set_frames(&mut self, frames: Vec<String>) -> Result<(), ModeCreationError> {
+        if frames.is_empty() {
+            return Err(ModeCreationError::MissingParameter {
+                param_name: "frames".to_string(),
+                mode_name: "Spinner".to_string(),
+                reason: Some("Spinner requires at least one frame".to_string()),
+            });
+        }
+        self.frames = frames;
+        self.frame = 0;
+        Ok(())
+    }
+
+    /// Get the frames the spinner cycles through.
+    pub fn frames(&self) -> &[String] {
+        &self.frames
+    }
+
+    /// Get the frame currently shown.
+    pub fn current_frame(&self) -> &str {
+        &self.frames[self.frame]
+    }
+
+    /// Set how long each frame is shown before the spinner advances.
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Get how long each frame is shown before the spinner advances.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Advance to the next frame if the current one has been shown for the
+    /// frame interval.
+    ///
+    /// Time is read from the clock of the job's [`BaseConfig`], so a
+    /// [`MockClock`](crate::core::clock::MockClock) drives the animation in tests.
+    ///
+    /// # Returns
+    /// `true` if the frame changed and the spinner needs redrawing
+    pub fn tick(&mut self) -> bool {
+        let now = self.base_config().now();
+        let shown_since = *self.last_frame_at.get_or_insert(now);
+        if now.saturating_duration_since(shown_since) < self.interval {
+            return false;
+        }
+        self.last_frame_at = Some(now);
+        let previous = self.frame;
+        self.frame = (self.frame + 1) % self.frames.len();
+        self.frame != previous
+    }
+
+    /// Render the current frame followed by the status message.
+    fn render_line(&self) -> String {
+        let message = self.single_line_base.get_line();
+        if message.is_empty() {
+            self.current_frame().to_string()
+        } else {
+            format!("{} {}", self.current_frame(), message)
+        }
+    }
+}
+
+impl ThreadConfig for Spinner {
+    fn lines_to_display(&self) -> usize {
+        1
+    }
+
+    fn handle_message(&mut self, message: String) -> Vec<String> {
+        self.single_line_base.update_line(message);
+        self.get_lines()
+    }
+
+    fn get_lines(&self) -> Vec<String> {
+        vec![self.render_line()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn base(&self) -> Option<&BaseConfig> {
+        Some(self.base_config())
+    }
+
+    fn base_mut(&mut self) -> Option<&mut BaseConfig> {
+        Some(self.base_config_mut())
+    }
+
+    fn title_capability(&self) -> Option<&dyn WithTitle> {
+        Some(self)
+    }
+
+    fn title_capability_mut(&mut self) -> Option<&mut dyn WithTitle> {
+        Some(self)
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn ThreadConfig> {
+        Box::new(self.clone())
+    }
+}
+
+impl WithTitle for Spinner {
+    fn set_title(&mut self, title: String) -> Result<(), ModeCreationError> {
+        self.single_line_base.update_line(title);
+        Ok(())
+    }
+
+    fn get_title(&self) -> &str {
+        self.single_line_base.line()
+    }
+}
+
+impl HasBaseConfig for Spinner {
+    fn base_config(&self) -> &BaseConfig {
+        self.single_line_base.base_config()
+    }
+
+    fn base_config_mut(&mut self) -> &mut BaseConfig {
+        self.single_line_base.base_config_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::MockClock;
+    use std::sync::Arc;
+
+    fn frames(glyphs: &str) -> Vec<String> {
+        glyphs.chars().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn test_spinner_shows_frame_and_message() {
+        let mut spinner = Spinner::new(1);
+        assert_eq!(spinner.lines_to_display(), 1);
+        assert_eq!(spinner.get_lines(), vec!["⠋"]);
+
+        assert_eq!(spinner.handle_message("fetching".to_string()), vec!["⠋ fetching"]);
+        spinner.set_title("indexing".to_string()).unwrap();
+        assert_eq!(spinner.get_title(), "indexing");
+        assert_eq!(spinner.get_lines(), vec!["⠋ indexing"]);
+    }
+
+    #[test]
+    fn test_spinner_tick_cycles_frames() {
+        let mut spinner = Spinner::with_frames(1, frames("ab")).unwrap();
+        spinner.set_interval(Duration::ZERO);
+
+        assert_eq!(spinner.current_frame(), "a");
+        assert!(spinner.tick());
+        assert_eq!(spinner.current_frame(), "b");
+        assert!(spinner.tick());
+        assert_eq!(spinner.current_frame(), "a");
+
+        // A single frame never changes
+        spinner.set_frames(frames("*")).unwrap();
+        assert!(!spinner.tick());
+        assert_eq!(spinner.current_frame(), "*");
+    }
+
+    #[test]
+    fn test_spinner_waits_for_interval() {
+        let mut spinner = Spinner::new(1);
+        spinner.set_interval(Duration::from_secs(3600));
+        assert!(!spinner.tick());
+        assert_eq!(spinner.current_frame(), "⠋");
+    }
+
+    #[test]
+    fn test_spinner_ticks_with_job_clock() {
+        let clock = MockClock::new();
+        let mut spinner = Spinner::with_frames(1, frames("ab")).unwrap();
+        spinner.base_config_mut().set_clock(Arc::new(clock.clone()));
+        spinner.set_interval(Duration::from_millis(80));
+
+        assert!(!spinner.tick());
+        clock.advance(Duration::from_millis(79));
+        assert!(!spinner.tick());
+        clock.advance(Duration::from_millis(1));
+        assert!(spinner.tick());
+        assert_eq!(spinner.current_frame(), "b");
+        assert!(!spinner.tick());
+    }
+
+    #[test]
+    fn test_spinner_rejects_empty_frames() {
+        assert!(matches!(
+            Spinner::with_frames(1, Vec::new()),
+            Err(ModeCreationError::MissingParameter { .. })
+        ));
+    }
+}
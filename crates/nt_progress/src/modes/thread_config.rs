@@ -28,6 +28,9 @@ pub use crate::core::thread_config::ThreadConfig;
 /// 3. Add your mode to the ThreadMode enum
 /// 4. Update Config::new to handle your mode
 ///
+/// Modes defined outside this crate can instead be registered with
+/// `ModeFactory::register` and selected with `ThreadMode::Custom`.
+///
 /// See the README.md file in this directory for a complete example.
 pub trait ThreadConfig: Send + Sync + Debug {
     /// Returns the number of lines this mode needs to display.
@@ -1,4 +1,5 @@
 use crate::core::{ThreadConfig, HasBaseConfig, BaseConfig};
+use crate::core::base_config::progress_percentage;
 use super::window_base::WindowBase;
 use crate::config::capabilities::{WithCustomSize, StandardWindow, WithWrappedText, WithProgress};
 use crate::core::job_traits::JobTracker;
@@ -70,6 +71,14 @@ impl ThreadConfig for Window {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn base(&self) -> Option<&BaseConfig> {
+        Some(self.base_config())
+    }
+
+    fn base_mut(&mut self) -> Option<&mut BaseConfig> {
+        Some(self.base_config_mut())
+    }
 }
 
 impl WithCustomSize for Window {
@@ -151,7 +160,7 @@ impl WithProgress for Window {
     
     fn update_progress(&mut self) -> f64 {
         let completed = self.base_config().increment_completed_jobs();
-        ((completed as f64) / (self.get_total_jobs() as f64) * 100.0).min(100.0)
+        progress_percentage(completed, self.get_total_jobs())
     }
     
     fn set_progress(&mut self, completed: usize) -> f64 {
@@ -162,7 +171,7 @@ impl WithProgress for Window {
         }
         
         self.base_config_mut().set_completed_jobs(completed);
-        ((completed as f64) / (self.get_total_jobs() as f64) * 100.0).min(100.0)
+        progress_percentage(completed, self.get_total_jobs())
     }
     
     fn get_elapsed_time(&self) -> std::time::Duration {
@@ -6,6 +6,7 @@ use crate::io::ProgressWriter;
 
 use crate::core::base_config::BaseConfig;
 use crate::core::job_traits::HasBaseConfig;
+use crate::terminal::{byte_index_at_width, display_width};
 
 /// Base implementation for window-based display modes.
 /// 
@@ -107,16 +108,13 @@ impl WindowBase {
             let mut remaining = line;
             
             while !remaining.is_empty() {
-                if remaining.len() <= WRAP_WIDTH {
+                if display_width(&remaining) <= WRAP_WIDTH {
                     self.lines.push_back(remaining);
                     break;
                 }
                 
-                // Try to find a space to break at
-                let mut break_pos = WRAP_WIDTH;
-                while break_pos > 0 && !remaining.is_char_boundary(break_pos) {
-                    break_pos -= 1;
-                }
+                // Break at the last column that fits, counting wide characters as two
+                let break_pos = byte_index_at_width(&remaining, WRAP_WIDTH);
                 
                 // Find a good breaking position (space)
                 let optimal_pos = remaining[..break_pos].rfind(' ').unwrap_or(break_pos);
@@ -302,6 +300,11 @@ impl SingleLineBase {
         self.current_line.clone()
     }
     
+    /// Get the current line without cloning it.
+    pub fn line(&self) -> &str {
+        &self.current_line
+    }
+    
     /// Check if passthrough is enabled.
     ///
     /// # Returns
@@ -426,6 +429,19 @@ mod tests {
         assert_eq!(lines[2], "Line 3");
     }
     
+    #[test]
+    fn test_window_base_wraps_wide_characters() {
+        let mut window = WindowBase::new(10, 5).unwrap();
+        window.set_line_wrapping(true);
+        
+        // 25 rockets are 50 columns wide but 100 bytes long
+        window.add_message("🚀".repeat(25));
+        
+        let lines = window.get_lines();
+        assert_eq!(lines, vec!["🚀".repeat(20), "🚀".repeat(5)]);
+        assert!(lines.iter().all(|line| display_width(line) <= 40));
+    }
+    
     #[test]
     fn test_window_base_threaded_messages() {
         let mut window = WindowBase::new(10, 5).unwrap();
@@ -1,4 +1,5 @@
 use crate::core::{ThreadConfig, HasBaseConfig, BaseConfig};
+use crate::core::base_config::progress_percentage;
 use super::window_base::WindowBase;
 use crate::config::capabilities::{WithTitle, WithCustomSize, WithEmoji, WithTitleAndEmoji, StandardWindow, WithWrappedText, WithProgress};
 use crate::core::job_traits::JobTracker;
@@ -18,6 +19,8 @@ pub struct WindowWithTitle {
     emojis: Vec<String>,
     supports_emoji: bool,
     supports_title: bool,
+    /// Show only the title line, keeping the content lines buffered
+    collapsed: bool,
 }
 
 impl WindowWithTitle {
@@ -56,6 +59,7 @@ impl WindowWithTitle {
             emojis: Vec::new(),
             supports_emoji: true,  // Enable emoji support by default
             supports_title: true,  // Enable title support by default
+            collapsed: false,
         })
     }
     
@@ -131,11 +135,32 @@ impl WindowWithTitle {
         self.supports_emoji
     }
 
+    /// Collapse the window to its title line, or expand it again.
+    ///
+    /// Messages are still buffered while collapsed and reappear on expanding.
+    ///
+    /// # Parameters
+    /// * `collapsed` - Whether to show only the title line
+    pub fn set_collapsed(&mut self, collapsed: bool) {
+        self.collapsed = collapsed;
+    }
+
+    /// Check if the window is collapsed to its title line.
+    ///
+    /// # Returns
+    /// true if only the title line is displayed, false otherwise
+    pub fn is_collapsed(&self) -> bool {
+        self.collapsed
+    }
+
     pub fn render(&self, width: usize) -> String {
         let mut output = String::new();
         
         // Add title line
         output.push_str(&self.render_title(width));
+        if self.collapsed {
+            return output;
+        }
         output.push('\n');
         
         // Add content lines
@@ -157,6 +182,9 @@ impl HasBaseConfig for WindowWithTitle {
 
 impl ThreadConfig for WindowWithTitle {
     fn lines_to_display(&self) -> usize {
+        if self.collapsed {
+            return 1;
+        }
         self.window_base.max_lines() + 1 // +1 for title
     }
 
@@ -169,6 +197,10 @@ impl ThreadConfig for WindowWithTitle {
     }
 
     fn get_lines(&self) -> Vec<String> {
+        if self.collapsed {
+            return vec![self.render_title(80)];
+        }
+
         // Get lines from window base
         let mut lines = self.window_base.get_lines();
         
@@ -189,6 +221,22 @@ impl ThreadConfig for WindowWithTitle {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn base(&self) -> Option<&BaseConfig> {
+        Some(self.base_config())
+    }
+
+    fn base_mut(&mut self) -> Option<&mut BaseConfig> {
+        Some(self.base_config_mut())
+    }
+
+    fn title_capability(&self) -> Option<&dyn WithTitle> {
+        Some(self)
+    }
+
+    fn title_capability_mut(&mut self) -> Option<&mut dyn WithTitle> {
+        Some(self)
+    }
 }
 
 impl WithTitle for WindowWithTitle {
@@ -357,12 +405,12 @@ impl WithProgress for WindowWithTitle {
     
     fn update_progress(&mut self) -> f64 {
         let completed = self.base_config().increment_completed_jobs();
-        ((completed as f64) / (self.get_total_jobs() as f64) * 100.0).min(100.0)
+        progress_percentage(completed, self.get_total_jobs())
     }
     
     fn set_progress(&mut self, completed: usize) -> f64 {
         self.base_config_mut().set_completed_jobs(completed);
-        ((completed as f64) / (self.get_total_jobs() as f64) * 100.0).min(100.0)
+        progress_percentage(completed, self.get_total_jobs())
     }
 }
 
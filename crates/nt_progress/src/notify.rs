@@ -0,0 +1,110 @@
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+use crate::thread::{ThreadManager, ThreadState};
+use crate::ui::renderer::Renderer;
+
+/// How [`ProgressDisplay::notify_on_complete`](crate::ProgressDisplay::notify_on_complete)
+/// signals that work is done.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotifyConfig {
+    /// Ring the terminal bell
+    pub bell: bool,
+    /// Show a desktop notification, requires the `desktop-notifications` feature
+    pub desktop: bool,
+    /// Notify once every task has finished
+    pub on_complete: bool,
+    /// Notify whenever a task fails
+    pub on_failure: bool,
+}
+
+impl Default for NotifyConfig {
+    fn default() -> Self {
+        Self {
+            bell: true,
+            desktop: false,
+            on_complete: true,
+            on_failure: true,
+        }
+    }
+}
+
+/// Watch the threads of `thread_manager` and notify as configured when they finish.
+pub(crate) fn spawn_notifier(config: NotifyConfig, thread_manager: Arc<ThreadManager>, renderer: Arc<Renderer>) -> JoinHandle<()> {
+    let mut finished = thread_manager.subscribe_finished();
+    tokio::spawn(async move {
+        loop {
+            let (thread_id, state) = match finished.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return,
+            };
+            let message = match state {
+                ThreadState::Failed(reason) if config.on_failure => format!("Task {} failed: {}", thread_id, reason),
+                ThreadState::Completed if config.on_complete && thread_manager.all_finished().await => {
+                    "All tasks completed".to_string()
+                }
+                _ => continue,
+            };
+
+            log::info!("{}", message);
+            if config.bell {
+                if let Err(e) = renderer.bell().await {
+                    diag_error!("Error ringing the terminal bell: {}", e);
+                }
+            }
+            #[cfg(feature = "desktop-notifications")]
+            if config.desktop {
+                desktop_notification(&message);
+            }
+        }
+    })
+}
+
+/// Show `message` with the platform's notification tool, if it has one.
+#[cfg(feature = "desktop-notifications")]
+fn desktop_notification(message: &str) {
+    if let Err(e) = notification_command(message, cfg!(target_os = "macos")).spawn() {
+        log::warn!("Failed to show desktop notification: {}", e);
+    }
+}
+
+/// Build the command showing `message`, with osascript on macOS and notify-send elsewhere.
+///
+/// The message is passed to osascript as an argument of its run handler rather
+/// than spliced into the script, so quotes and backslashes need no escaping.
+#[cfg(feature = "desktop-notifications")]
+fn notification_command(message: &str, macos: bool) -> tokio::process::Command {
+    if macos {
+        let mut command = tokio::process::Command::new("osascript");
+        command
+            .args(["-e", "on run argv"])
+            .args(["-e", "display notification (item 1 of argv) with title \"nt_progress\""])
+            .args(["-e", "end run"])
+            .arg(message);
+        command
+    } else {
+        let mut command = tokio::process::Command::new("notify-send");
+        command.arg("nt_progress").arg(message);
+        command
+    }
+}
+
+#[cfg(all(test, feature = "desktop-notifications"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_message_is_an_argument() {
+        let message = r#"Task 1 failed: "quoted" \ path"#;
+        let command = notification_command(message, true);
+        let args: Vec<_> = command.as_std().get_args().collect();
+        let (last, script) = args.split_last().unwrap();
+        assert_eq!(*last, message);
+        assert!(!script.iter().any(|arg| arg.to_string_lossy().contains("quoted")));
+
+        let command = notification_command(message, false);
+        let args: Vec<_> = command.as_std().get_args().collect();
+        assert_eq!(args, ["nt_progress", message]);
+    }
+}
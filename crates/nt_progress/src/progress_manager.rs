@@ -1,10 +1,16 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
 use anyhow::Result;
 
 use crate::errors::{ErrorContext, ProgressError};
-use crate::thread::{ThreadManager, TaskHandle, ThreadState};
+use crate::error_recovery::{RecoveryAction, RecoveryRegistry, RecoveryStrategy};
+use crate::core::ThreadConfig;
+use crate::core::clock::{Clock, SystemClock};
+use crate::core::base_config::JobStatus;
+use crate::thread::{ThreadManager, TaskHandle, ThreadState, PersistedTask};
+use crate::command::{CommandOutput, CommandRunner};
 use crate::config::Config;
 use crate::config::ThreadMode;
 use crate::modes::factory::ModeFactory;
@@ -12,11 +18,14 @@ use crate::ThreadMessage;
 use tokio::task::JoinHandle;
 use tokio::sync::mpsc;
 use crate::ui::progress_bar::{ProgressBar, ProgressBarConfig, ProgressBarStyle, MultiProgressBar};
+use crate::ui::renderer::TaskRenderInfo;
 
 /// Manages progress tracking and state across multiple threads/tasks
 pub struct ProgressManager {
     /// Map of thread IDs to their output lines
     outputs: Arc<Mutex<HashMap<usize, Vec<String>>>>,
+    /// Number of lines numbered so far for each thread
+    line_counts: Mutex<HashMap<usize, usize>>,
     /// Thread manager for handling thread lifecycle
     thread_manager: Arc<ThreadManager>,
     /// Factory for creating thread config modes
@@ -25,6 +34,39 @@ pub struct ProgressManager {
     message_tx: mpsc::Sender<ThreadMessage>,
     /// Collection of multi-progress bars for grouped display
     multi_bars: Arc<Mutex<HashMap<String, MultiProgressBar>>>,
+    /// Strategies consulted when a task operation fails
+    recovery: Arc<Mutex<RecoveryRegistry>>,
+    /// Settings for flagging tasks that stopped reporting progress
+    stall_detection: std::sync::Mutex<StallDetection>,
+    /// Last job status of the tasks removed by joining or cancelling all tasks
+    removed_statuses: Mutex<HashMap<usize, JobStatus>>,
+    /// Progress format given to new tasks whose mode tracks progress
+    default_template: std::sync::Mutex<Option<String>>,
+}
+
+/// Settings for flagging tasks that stopped reporting progress, see
+/// [`ProgressManager::stalled_tasks`].
+#[derive(Debug)]
+struct StallDetection {
+    /// How long any task may go without an update, or None to not check
+    timeout: Option<Duration>,
+    /// Timeouts of tasks that do not use the default one
+    task_timeouts: HashMap<usize, Duration>,
+    /// Whether "(stalled)" is appended to the first line of stalled tasks
+    annotate: bool,
+    /// Given to every task to time its updates
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for StallDetection {
+    fn default() -> Self {
+        Self {
+            timeout: None,
+            task_timeouts: HashMap::new(),
+            annotate: false,
+            clock: Arc::new(SystemClock),
+        }
+    }
 }
 
 impl ProgressManager {
@@ -32,18 +74,79 @@ impl ProgressManager {
     pub fn new(factory: Arc<ModeFactory>, message_tx: mpsc::Sender<ThreadMessage>) -> Self {
         Self {
             outputs: Arc::new(Mutex::new(HashMap::new())),
+            line_counts: Mutex::new(HashMap::new()),
             thread_manager: Arc::new(ThreadManager::new()),
             factory,
             message_tx,
             multi_bars: Arc::new(Mutex::new(HashMap::new())),
+            recovery: Arc::new(Mutex::new(RecoveryRegistry::new())),
+            stall_detection: std::sync::Mutex::new(StallDetection::default()),
+            removed_statuses: Mutex::new(HashMap::new()),
+            default_template: std::sync::Mutex::new(None),
         }
     }
     
+    /// Get the factory used to create task modes
+    pub fn factory(&self) -> &Arc<ModeFactory> {
+        &self.factory
+    }
+    
+    /// Set the progress format of tasks created from now on, for modes that
+    /// track progress, or None to keep each mode's own format.
+    pub fn set_default_template(&self, template: Option<String>) {
+        *self.default_template.lock().unwrap() = template;
+    }
+    
+    /// Get the progress format given to new tasks, if any
+    pub fn default_template(&self) -> Option<String> {
+        self.default_template.lock().unwrap().clone()
+    }
+    
+    /// Register how to recover when a task operation fails with the given error code.
+    ///
+    /// # Parameters
+    /// * `error_code` - The [`ProgressError::error_code`] the strategy handles
+    /// * `strategy` - Chooses the recovery action for each such error
+    pub async fn register_recovery_strategy(&self, error_code: &'static str, strategy: impl RecoveryStrategy + 'static) {
+        self.recovery.lock().await.register(error_code, strategy);
+    }
+    
+    /// Choose how to recover from a failed task operation
+    async fn recovery_action(&self, error: &anyhow::Error) -> RecoveryAction {
+        match error.downcast_ref::<ProgressError>() {
+            Some(error) => self.recovery.lock().await.action_for(error),
+            None => RecoveryAction::Fail,
+        }
+    }
+    
+    /// Create the config for a new task, recovering from creation failures
+    /// with the registered strategies
+    async fn create_mode(&self, mode: ThreadMode, total_jobs: usize) -> Result<Box<dyn ThreadConfig>> {
+        let error = match self.factory.create_mode(mode.clone(), total_jobs) {
+            Ok(config) => return Ok(config),
+            Err(error) => anyhow::Error::from(ProgressError::from(error)),
+        };
+        let retry_mode = match self.recovery_action(&error).await {
+            RecoveryAction::Retry => mode,
+            RecoveryAction::FallbackMode(fallback) => fallback,
+            RecoveryAction::Skip | RecoveryAction::Fail => return Err(error),
+        };
+        Ok(self.factory.create_mode(retry_mode, total_jobs).map_err(ProgressError::from)?)
+    }
+    
     /// Create a new task with the specified mode
     pub async fn create_task(&self, mode: ThreadMode, total_jobs: usize) -> Result<TaskHandle> {       
         let thread_id = self.thread_manager.next_thread_id();
-        let config = Config::from(self.factory.create_mode(mode, total_jobs)?);
+        let config = Config::from(self.create_mode(mode, total_jobs).await?);
         let task_handle = TaskHandle::new(thread_id, config, self.message_tx.clone());
+        if let Some(template) = self.default_template() {
+            let mut config = task_handle.config().lock().await;
+            if config.supports_progress() {
+                config.set_progress_format(&template)?;
+            }
+        }
+        let clock = self.stall_detection.lock().unwrap().clock.clone();
+        task_handle.set_clock(clock).await;
         let join_handle = tokio::spawn(async move {
             Ok(())
         });
@@ -51,6 +154,19 @@ impl ProgressManager {
         Ok(task_handle)
     }
     
+    /// Create a new task from a task saved with [`TaskHandle::persist`]
+    pub async fn restore_task(&self, task: PersistedTask) -> Result<TaskHandle> {
+        let handle = self.create_task(task.mode, task.state.total_jobs).await?;
+        {
+            let mut config = handle.config().lock().await;
+            if let Some(title) = task.title {
+                config.set_title(title)?;
+            }
+            config.apply_job_state(&task.state);
+        }
+        Ok(handle)
+    }
+    
     /// Create a new task with the specified mode and title
     pub async fn create_task_with_title(&self, mode: ThreadMode, title: String) -> Result<TaskHandle> {
         let mut handle = self.create_task(mode, 1).await?;
@@ -70,26 +186,51 @@ impl ProgressManager {
     
     /// Join all threads (wait for completion)
     pub async fn join_all(&self) -> Result<()> {
-        self.thread_manager.join_all().await
+        self.keeping_statuses(self.thread_manager.join_all()).await
     }
     
     /// Cancel all threads (abort execution)
     pub async fn cancel_all(&self) -> Result<()> {
-        self.thread_manager.cancel_all().await
+        self.keeping_statuses(self.thread_manager.cancel_all()).await
+    }
+    
+    /// Run `unregister`, which removes every task, keeping the last job status
+    /// of the tasks for [`task_statuses`](Self::task_statuses)
+    async fn keeping_statuses(&self, unregister: impl std::future::Future<Output = Result<()>>) -> Result<()> {
+        let handles = self.thread_manager.get_tasks(&self.thread_manager.get_active_threads().await).await;
+        let result = unregister.await;
+        let mut removed_statuses = self.removed_statuses.lock().await;
+        self.stall_detection.lock().unwrap().task_timeouts.clear();
+        for handle in handles.into_iter().flatten() {
+            if let Some(status) = handle.config().lock().await.job_status() {
+                removed_statuses.insert(handle.thread_id(), status);
+            }
+        }
+        result
+    }
+    
+    /// Cancel a single thread (abort execution)
+    pub async fn cancel_task(&self, thread_id: usize, reason: Option<String>) -> Result<()> {
+        self.thread_manager.cancel_thread(thread_id, reason).await
     }
     
     /// Set the title for a specific thread
     pub async fn set_title(&self, thread_id: usize, title: String) -> Result<()> {
         if let Some(handle) = self.thread_manager.get_task(thread_id).await {
-            handle.set_title(title).await?;
+            if let Err(error) = handle.set_title(title.clone()).await {
+                match self.recovery_action(&error).await {
+                    RecoveryAction::Retry => handle.set_title(title).await?,
+                    RecoveryAction::Skip => {}
+                    RecoveryAction::FallbackMode(_) | RecoveryAction::Fail => return Err(error),
+                }
+            }
             Ok(())
         } else {
             let ctx = ErrorContext::new("setting title", "ProgressManager")
                 .with_thread_id(thread_id)
                 .with_details("Thread not found");
             
-            let error_msg = format!("Thread {} not found", thread_id);
-            let error = ProgressError::TaskOperation(error_msg).into_context(ctx);
+            let error = ProgressError::ThreadNotFound(thread_id).into_context(ctx);
             Err(anyhow::anyhow!(error))
         }
     }
@@ -97,16 +238,20 @@ impl ProgressManager {
     /// Add an emoji to a specific thread
     pub async fn add_emoji(&self, thread_id: usize, emoji: &str) -> Result<()> {
         if let Some(handle) = self.thread_manager.get_task(thread_id).await {
-            let mut config = handle.config().lock().await;
-            config.add_emoji(emoji)?;
+            if let Err(error) = handle.add_emoji(emoji).await {
+                match self.recovery_action(&error).await {
+                    RecoveryAction::Retry => handle.add_emoji(emoji).await?,
+                    RecoveryAction::Skip => {}
+                    RecoveryAction::FallbackMode(_) | RecoveryAction::Fail => return Err(error),
+                }
+            }
             Ok(())
         } else {
             let ctx = ErrorContext::new("adding emoji", "ProgressManager")
                 .with_thread_id(thread_id)
                 .with_details("Thread not found");
             
-            let error_msg = format!("Thread {} not found", thread_id);
-            let error = ProgressError::TaskOperation(error_msg).into_context(ctx);
+            let error = ProgressError::ThreadNotFound(thread_id).into_context(ctx);
             Err(anyhow::anyhow!(error))
         }
     }
@@ -131,8 +276,7 @@ impl ProgressManager {
                         .with_thread_id(thread_id)
                         .with_details("Thread not found");
                     
-                    let error_msg = format!("Thread {} not found", thread_id);
-                    let error = ProgressError::TaskOperation(error_msg).into_context(ctx);
+                    let error = ProgressError::ThreadNotFound(thread_id).into_context(ctx);
                     Err(anyhow::anyhow!(error))
                 }
             },
@@ -167,8 +311,7 @@ impl ProgressManager {
                 .with_thread_id(thread_id)
                 .with_details("Thread not found");
             
-            let error_msg = format!("Thread {} not found", thread_id);
-            let error = ProgressError::TaskOperation(error_msg).into_context(ctx);
+            let error = ProgressError::ThreadNotFound(thread_id).into_context(ctx);
             Err(anyhow::anyhow!(error))
         }
     }
@@ -189,12 +332,41 @@ impl ProgressManager {
                 .with_thread_id(thread_id)
                 .with_details("Thread not found");
             
-            let error_msg = format!("Thread {} not found", thread_id);
-            let error = ProgressError::TaskOperation(error_msg).into_context(ctx);
+            let error = ProgressError::ThreadNotFound(thread_id).into_context(ctx);
             Err(anyhow::anyhow!(error))
         }
     }
     
+    /// Set the progress of several threads at once.
+    ///
+    /// The tasks are looked up under a single lock of the thread registry, and
+    /// each task's config is only locked while its own progress is set.
+    ///
+    /// # Parameters
+    /// * `updates` - Pairs of thread ID and number of completed jobs
+    ///
+    /// # Returns
+    /// The new progress percentage of each thread, or the error for it, in the order of `updates`
+    pub async fn set_progress_bulk(&self, updates: &[(usize, usize)]) -> Vec<Result<f64>> {
+        let thread_ids: Vec<usize> = updates.iter().map(|(thread_id, _)| *thread_id).collect();
+        let handles = self.thread_manager.get_tasks(&thread_ids).await;
+
+        let mut results = Vec::with_capacity(updates.len());
+        for (&(thread_id, completed), handle) in updates.iter().zip(handles) {
+            let result = match handle {
+                Some(handle) => handle.set_progress(completed).await,
+                None => {
+                    let ctx = ErrorContext::new("setting progress", "ProgressManager")
+                        .with_thread_id(thread_id)
+                        .with_details("Thread not found");
+                    Err(anyhow::anyhow!(ProgressError::ThreadNotFound(thread_id).into_context(ctx)))
+                }
+            };
+            results.push(result);
+        }
+        results
+    }
+
     /// Get the current progress percentage for a specific thread
     ///
     /// # Parameters
@@ -210,8 +382,27 @@ impl ProgressManager {
                 .with_thread_id(thread_id)
                 .with_details("Thread not found");
             
-            let error_msg = format!("Thread {} not found", thread_id);
-            let error = ProgressError::TaskOperation(error_msg).into_context(ctx);
+            let error = ProgressError::ThreadNotFound(thread_id).into_context(ctx);
+            Err(anyhow::anyhow!(error))
+        }
+    }
+    
+    /// Get the lines a specific thread currently displays
+    ///
+    /// # Parameters
+    /// * `thread_id` - The ID of the thread to get lines for
+    ///
+    /// # Returns
+    /// The lines as laid out by the thread's mode
+    pub async fn get_task_lines(&self, thread_id: usize) -> Result<Vec<String>> {
+        if let Some(handle) = self.thread_manager.get_task(thread_id).await {
+            handle.get_display_lines().await
+        } else {
+            let ctx = ErrorContext::new("getting display lines", "ProgressManager")
+                .with_thread_id(thread_id)
+                .with_details("Thread not found");
+            
+            let error = ProgressError::ThreadNotFound(thread_id).into_context(ctx);
             Err(anyhow::anyhow!(error))
         }
     }
@@ -229,8 +420,7 @@ impl ProgressManager {
                 .with_thread_id(thread_id)
                 .with_details("Thread not found");
             
-            let error_msg = format!("Thread {} not found", thread_id);
-            let error = ProgressError::TaskOperation(error_msg).into_context(ctx);
+            let error = ProgressError::ThreadNotFound(thread_id).into_context(ctx);
             Err(anyhow::anyhow!(error))
         }
     }
@@ -244,7 +434,8 @@ impl ProgressManager {
     /// * `thread_id` - The ID of the thread to update the progress bar for
     /// * `current` - The current number of completed items
     /// * `total` - The total number of items
-    /// * `config` - The progress bar configuration to use
+    /// * `config` - The progress bar configuration to use; its template, when
+    ///   not empty, takes precedence over the one derived from its style
     ///
     /// # Returns
     /// Result with () if successful
@@ -262,14 +453,14 @@ impl ProgressManager {
             let mut progress_bar = ProgressBar::new(config.clone());
             progress_bar.update_with_values(current, total);
             
-            // Get the template to use for formatting
-            let template = progress_bar.template();
+            // An explicit template overrides the one derived from the style
+            let template = config.build_template();
             
             // Set the progress display format
             handle.set_progress_format(&template).await?;
             
             // Generate a progress display message
-            let mut ctx = crate::ui::formatter::TemplateContext::new();
+            let mut ctx = handle.time_context().await;
             ctx.set("progress", progress_bar.progress())
                .set("completed", current)
                .set("total", total)
@@ -291,8 +482,7 @@ impl ProgressManager {
                 .with_thread_id(thread_id)
                 .with_details("Thread not found");
             
-            let error_msg = format!("Thread {} not found", thread_id);
-            let error = ProgressError::TaskOperation(error_msg).into_context(ctx);
+            let error = ProgressError::ThreadNotFound(thread_id).into_context(ctx);
             Err(anyhow::anyhow!(error))
         }
     }
@@ -343,36 +533,189 @@ impl ProgressManager {
         let thread_outputs = outputs.entry(msg.thread_id).or_insert_with(Vec::new);
         
         // Add new messages
-        thread_outputs.extend(msg.lines);
+        if msg.config.as_ref().is_some_and(Config::has_line_numbers) {
+            let mut line_counts = self.line_counts.lock().await;
+            let count = line_counts.entry(msg.thread_id).or_insert(0);
+            thread_outputs.extend(msg.lines.into_iter().map(|line| {
+                *count += 1;
+                format!("[{}] {}", count, line)
+            }));
+        } else {
+            thread_outputs.extend(msg.lines);
+        }
     }
     
+    /// Move every task of `other` into this manager, along with its output.
+    ///
+    /// Tasks get new thread IDs from this manager and keep their state, join
+    /// handle, progress and stall timeout. Parent, child and dependency IDs are
+    /// updated to the new IDs, and the kept status of tasks already removed
+    /// from `other` moves here too. Handles still held for the old IDs share
+    /// the task's config, but their messages keep going to `other`'s channel.
+    ///
+    /// # Returns
+    /// The new thread ID of each adopted or removed task, keyed by its ID in `other`
+    pub async fn adopt_tasks(&self, other: &ProgressManager) -> HashMap<usize, usize> {
+        let threads = other.thread_manager.take_threads().await;
+        let removed_statuses: Vec<_> = other.removed_statuses.lock().await.drain().collect();
+        let mut ids = HashMap::new();
+        for old_id in threads.iter().map(|(old_id, _)| *old_id).chain(removed_statuses.iter().map(|(old_id, _)| *old_id)) {
+            ids.insert(old_id, self.thread_manager.next_thread_id());
+        }
+
+        for (old_id, mut ctx) in threads {
+            let new_id = ids[&old_id];
+            let handle = ctx.handle().rebind(new_id, self.message_tx.clone());
+            handle.config().lock().await.remap_job_ids(&ids);
+            ctx.replace_handle(handle);
+            self.thread_manager.insert_thread(new_id, ctx).await;
+            other.thread_manager.mark_moved(old_id, &self.thread_manager, new_id);
+        }
+        self.removed_statuses.lock().await
+            .extend(removed_statuses.into_iter().map(|(old_id, status)| (ids[&old_id], status)));
+
+        // Take the output first, so no two managers' locks are held at once
+        let (adopted_outputs, adopted_line_counts) = {
+            let mut other_outputs = other.outputs.lock().await;
+            let mut other_line_counts = other.line_counts.lock().await;
+            let outputs: Vec<_> = ids.iter()
+                .filter_map(|(old_id, new_id)| Some((*new_id, other_outputs.remove(old_id)?)))
+                .collect();
+            let line_counts: Vec<_> = ids.iter()
+                .filter_map(|(old_id, new_id)| Some((*new_id, other_line_counts.remove(old_id)?)))
+                .collect();
+            (outputs, line_counts)
+        };
+        self.outputs.lock().await.extend(adopted_outputs);
+        self.line_counts.lock().await.extend(adopted_line_counts);
+
+        let task_timeouts: Vec<_> = {
+            let mut detection = other.stall_detection.lock().unwrap();
+            ids.iter()
+                .filter_map(|(old_id, new_id)| Some((*new_id, detection.task_timeouts.remove(old_id)?)))
+                .collect()
+        };
+        self.stall_detection.lock().unwrap().task_timeouts.extend(task_timeouts);
+        ids
+    }
+
     /// Get a reference to the outputs
     pub fn outputs(&self) -> &Arc<Mutex<HashMap<usize, Vec<String>>>> {
         &self.outputs
     }
     
+    /// Get the sender tasks of this manager send their messages on
+    pub(crate) fn message_tx(&self) -> &mpsc::Sender<ThreadMessage> {
+        &self.message_tx
+    }
+
     /// Get the thread manager
     pub fn thread_manager(&self) -> &Arc<ThreadManager> {
         &self.thread_manager
     }
     
     /// Create a new spawn task that runs the given function
+    ///
+    /// The task completes when the future returned by `f` resolves to `Ok` and
+    /// fails with the error's message when it resolves to `Err`.
     pub async fn spawn<F, R>(&self, f: F) -> Result<TaskHandle>
     where
         F: FnOnce(TaskHandle) -> R + Send + 'static,
         R: std::future::Future<Output = Result<()>> + Send + 'static,
     {
         let handle = self.create_task(ThreadMode::Limited, 1).await?;
+        let thread_id = handle.thread_id();
+        let thread_manager = self.thread_manager.clone();
+        let future = f(handle.clone());
+        let (registered_tx, registered_rx) = tokio::sync::oneshot::channel::<()>();
         
         // Spawn the task
-        let task_handle: JoinHandle<Result<()>> = tokio::spawn(f(handle.clone()));
+        let task_handle: JoinHandle<Result<()>> = tokio::spawn(async move {
+            // Wait for registration so the final state is not overwritten
+            let _ = registered_rx.await;
+            let result = future.await;
+            let state = match &result {
+                Ok(()) => ThreadState::Completed,
+                Err(e) => ThreadState::Failed(e.to_string()),
+            };
+            // The task may already have been cancelled
+            let _ = thread_manager.update_thread_state(thread_id, state).await;
+            result
+        });
         
         // Store the handle
-        self.thread_manager.register_thread(handle.thread_id(), handle.clone(), task_handle).await;
+        self.thread_manager.register_thread(thread_id, handle.clone(), task_handle).await;
+        let _ = registered_tx.send(());
         
         Ok(handle)
     }
     
+    /// Run a command in a new task, capturing its output.
+    ///
+    /// Stdout and stderr lines are captured as the command produces them. The
+    /// task completes when the command exits with code 0 and fails otherwise.
+    ///
+    /// # Parameters
+    /// * `mode` - The display mode for the task
+    /// * `runner` - Runs the command, see [`TokioCommandRunner`](crate::command::TokioCommandRunner)
+    /// * `program` - The program to run
+    /// * `args` - The arguments passed to the program
+    pub async fn spawn_command(
+        &self,
+        mode: ThreadMode,
+        runner: Arc<dyn CommandRunner>,
+        program: impl Into<String>,
+        args: Vec<String>,
+    ) -> Result<TaskHandle> {
+        let program = program.into();
+        let handle = self.create_task(mode, 1).await?;
+        let thread_id = handle.thread_id();
+        let thread_manager = self.thread_manager.clone();
+        let mut task = handle.clone();
+        let (registered_tx, registered_rx) = tokio::sync::oneshot::channel::<()>();
+
+        let join_handle: JoinHandle<Result<()>> = tokio::spawn(async move {
+            // Wait for registration so the final state is not overwritten
+            let _ = registered_rx.await;
+            task.set_status(JobStatus::Running).await?;
+
+            let (output_tx, mut output_rx) = mpsc::channel(64);
+            let forward = async {
+                while let Some(output) = output_rx.recv().await {
+                    match output {
+                        CommandOutput::Stdout(line) => task.capture_stdout(line).await?,
+                        CommandOutput::Stderr(line) => task.capture_stderr(line).await?,
+                    }
+                }
+                Ok::<(), anyhow::Error>(())
+            };
+            let (exit_code, forwarded) = tokio::join!(runner.run(&program, &args, output_tx), forward);
+            forwarded?;
+
+            let details = match exit_code {
+                Ok(Some(0)) => {
+                    task.set_progress(1).await?;
+                    task.set_status(JobStatus::Completed).await?;
+                    thread_manager.update_thread_state(thread_id, ThreadState::Completed).await?;
+                    return Ok(());
+                }
+                Ok(Some(code)) => format!("{} exited with code {}", program, code),
+                Ok(None) => format!("{} was terminated by a signal", program),
+                Err(e) => format!("Failed to run {}: {}", program, e),
+            };
+            task.set_status(JobStatus::Failed).await?;
+            thread_manager.update_thread_state(thread_id, ThreadState::Failed(details.clone())).await?;
+            let ctx = ErrorContext::new("running command", "ProgressManager")
+                .with_thread_id(thread_id)
+                .with_details(details.clone());
+            Err(ProgressError::TaskOperation(details).into_context(ctx).into())
+        });
+
+        self.thread_manager.register_thread(thread_id, handle.clone(), join_handle).await;
+        let _ = registered_tx.send(());
+        Ok(handle)
+    }
+
     /// Create a child task that is linked to a parent task.
     ///
     /// This method creates a new task that is a child of the specified parent task.
@@ -401,7 +744,7 @@ impl ProgressManager {
             let ctx = ErrorContext::new("creating child task", "ProgressManager")
                 .with_thread_id(parent_id)
                 .with_details("Parent thread not found");
-            let error = ProgressError::TaskOperation(format!("Parent thread {} not found", parent_id))
+            let error = ProgressError::ThreadNotFound(parent_id)
                 .into_context(ctx);
             Err(anyhow::anyhow!(error))
         }
@@ -441,7 +784,7 @@ impl ProgressManager {
             let ctx = ErrorContext::new("creating child task with title", "ProgressManager")
                 .with_thread_id(parent_id)
                 .with_details("Parent thread not found");
-            let error = ProgressError::TaskOperation(format!("Parent thread {} not found", parent_id))
+            let error = ProgressError::ThreadNotFound(parent_id)
                 .into_context(ctx);
             Err(anyhow::anyhow!(error))
         }
@@ -470,7 +813,7 @@ impl ProgressManager {
             let ctx = ErrorContext::new("getting child tasks", "ProgressManager")
                 .with_thread_id(parent_id)
                 .with_details("Parent thread not found");
-            let error = ProgressError::TaskOperation(format!("Parent thread {} not found", parent_id))
+            let error = ProgressError::ThreadNotFound(parent_id)
                 .into_context(ctx);
             Err(anyhow::anyhow!(error))
         }
@@ -526,7 +869,7 @@ impl ProgressManager {
             let ctx = ErrorContext::new("getting cumulative progress", "ProgressManager")
                 .with_thread_id(thread_id)
                 .with_details("Thread not found");
-            let error = ProgressError::TaskOperation(format!("Thread {} not found", thread_id))
+            let error = ProgressError::ThreadNotFound(thread_id)
                 .into_context(ctx);
             Err(anyhow::anyhow!(error))
         }
@@ -550,8 +893,7 @@ impl ProgressManager {
                 .with_thread_id(thread_id)
                 .with_details("Thread not found");
             
-            let error_msg = format!("Thread {} not found", thread_id);
-            let error = ProgressError::TaskOperation(error_msg).into_context(ctx);
+            let error = ProgressError::ThreadNotFound(thread_id).into_context(ctx);
             Err(anyhow::anyhow!(error))
         }
     }
@@ -574,8 +916,7 @@ impl ProgressManager {
                 .with_thread_id(thread_id)
                 .with_details("Thread not found");
             
-            let error_msg = format!("Thread {} not found", thread_id);
-            let error = ProgressError::TaskOperation(error_msg).into_context(ctx);
+            let error = ProgressError::ThreadNotFound(thread_id).into_context(ctx);
             Err(anyhow::anyhow!(error))
         }
     }
@@ -595,8 +936,7 @@ impl ProgressManager {
                 .with_thread_id(thread_id)
                 .with_details("Thread not found");
             
-            let error_msg = format!("Thread {} not found", thread_id);
-            let error = ProgressError::TaskOperation(error_msg).into_context(ctx);
+            let error = ProgressError::ThreadNotFound(thread_id).into_context(ctx);
             Err(anyhow::anyhow!(error))
         }
     }
@@ -606,13 +946,13 @@ impl ProgressManager {
     /// # Returns
     /// A Result indicating success or an error
     pub async fn pause_all(&self) -> Result<()> {
-        // Get all active threads
-        let active_threads = self.thread_manager.get_active_threads().await;
+        // Get all running threads
+        let running_threads = self.thread_manager.get_threads_by_state(ThreadState::Running).await;
         
         // Pause each thread
-        for thread_id in active_threads {
+        for thread_id in running_threads {
             if let Err(e) = self.pause_thread(thread_id).await {
-                eprintln!("Warning: Failed to pause thread {}: {}", thread_id, e);
+                diag_warn!("Failed to pause thread {}: {}", thread_id, e);
             }
         }
         
@@ -630,13 +970,242 @@ impl ProgressManager {
         // Resume each thread
         for thread_id in paused_threads {
             if let Err(e) = self.resume_thread(thread_id).await {
-                eprintln!("Warning: Failed to resume thread {}: {}", thread_id, e);
+                diag_warn!("Failed to resume thread {}: {}", thread_id, e);
             }
         }
         
         Ok(())
     }
     
+    /// Find tasks that can never finish because they wait on a cancelled or failed task.
+    ///
+    /// # Returns
+    /// The IDs of the deadlocked tasks
+    pub async fn detect_deadlocked_tasks(&self) -> Vec<usize> {
+        self.deadlocked_dependencies().await
+            .into_iter()
+            .map(|(thread_id, _)| thread_id)
+            .collect()
+    }
+
+    /// Get the job status of every task whose mode tracks one, keyed by thread ID.
+    ///
+    /// A task whose thread completed or failed reports that status, see
+    /// [`ThreadState::job_status`].
+    ///
+    /// Tasks removed by [`join_all`](Self::join_all) or [`cancel_all`](Self::cancel_all)
+    /// keep the status they had then.
+    pub async fn task_statuses(&self) -> HashMap<usize, JobStatus> {
+        let mut statuses = self.removed_statuses.lock().await.clone();
+        for thread_id in self.thread_manager.get_active_threads().await {
+            if let Some(handle) = self.thread_manager.get_task(thread_id).await {
+                if let Some(status) = handle.config().lock().await.job_status() {
+                    statuses.insert(thread_id, status);
+                }
+            }
+        }
+        statuses
+    }
+
+    /// Advance the frame of every spinner whose frame interval has elapsed.
+    ///
+    /// Only the configs of tasks in Spinner mode are locked.
+    ///
+    /// # Returns
+    /// `true` if any spinner changed frame and needs redrawing
+    pub async fn tick_spinners(&self) -> bool {
+        let mut ticked = false;
+        for thread_id in self.thread_manager.get_active_threads().await {
+            if let Some(handle) = self.thread_manager.get_task(thread_id).await.filter(TaskHandle::is_spinner) {
+                if let Some(spinner) = handle.config().lock().await.as_type_mut::<crate::modes::Spinner>() {
+                    ticked |= spinner.tick();
+                }
+            }
+        }
+        ticked
+    }
+
+    /// Replace the output of every spinner task with its current line.
+    ///
+    /// Spinners are drawn from their live state rather than from the messages
+    /// they received, so frame and title changes show on the next redraw.
+    pub async fn refresh_spinner_outputs(&self) {
+        let mut lines = HashMap::new();
+        for thread_id in self.thread_manager.get_active_threads().await {
+            if let Some(handle) = self.thread_manager.get_task(thread_id).await.filter(TaskHandle::is_spinner) {
+                if let Some(spinner) = handle.config().lock().await.as_type::<crate::modes::Spinner>() {
+                    lines.insert(thread_id, spinner.get_lines());
+                }
+            }
+        }
+        if !lines.is_empty() {
+            self.outputs.lock().await.extend(lines);
+        }
+    }
+
+    /// Collect how every registered task is drawn, keyed by thread ID.
+    ///
+    /// Each task's config is locked once for its priority, collapsed title, retry
+    /// annotation and truncation indicator. Stalled tasks are annotated with "(stalled)" when enabled
+    /// with [`set_stall_annotation`](Self::set_stall_annotation).
+    pub async fn render_info(&self) -> HashMap<usize, TaskRenderInfo> {
+        let stall_timeouts = {
+            let detection = self.stall_detection.lock().unwrap();
+            detection.annotate.then(|| (detection.timeout, detection.task_timeouts.clone()))
+        };
+
+        let mut tasks = HashMap::new();
+        for thread_id in self.thread_manager.get_active_threads().await {
+            let state = self.thread_manager.get_thread_state(thread_id).await;
+            let Some(handle) = self.thread_manager.get_task(thread_id).await else {
+                continue;
+            };
+            let config = handle.config().lock().await;
+            let collapsed_title = config.as_type::<crate::modes::WindowWithTitle>()
+                .filter(|window| window.is_collapsed())
+                .and_then(|window| window.get_lines().into_iter().next());
+            let mut annotation = config.retry_annotation();
+            let stalled = stall_timeouts.as_ref().is_some_and(|(timeout, task_timeouts)| {
+                Self::is_stalled(&config, state.as_ref(), task_timeouts.get(&thread_id).copied().or(*timeout))
+            });
+            if stalled {
+                annotation = Some(match annotation {
+                    Some(annotation) => format!("{} (stalled)", annotation),
+                    None => "(stalled)".to_string(),
+                });
+            }
+            tasks.insert(thread_id, TaskRenderInfo {
+                priority: config.get_priority(),
+                collapsed_title,
+                annotation,
+                truncation_indicator: Some(config.get_truncation_indicator().to_string()),
+            });
+        }
+        tasks
+    }
+
+    /// Set how long a task may go without reporting progress or output before
+    /// [`stalled_tasks`](Self::stalled_tasks) reports it, or None to not check.
+    pub fn set_stall_timeout(&self, timeout: Option<Duration>) {
+        self.stall_detection.lock().unwrap().timeout = timeout;
+    }
+
+    /// Give one task its own stall timeout, used even when there is no default one.
+    pub fn set_task_stall_timeout(&self, thread_id: usize, timeout: Duration) {
+        self.stall_detection.lock().unwrap().task_timeouts.insert(thread_id, timeout);
+    }
+
+    /// Set whether "(stalled)" is appended to the first line of stalled tasks.
+    pub fn set_stall_annotation(&self, enabled: bool) {
+        self.stall_detection.lock().unwrap().annotate = enabled;
+    }
+
+    /// Time all tasks, current and future, with `clock`.
+    ///
+    /// The elapsed time and the time since the last update of current tasks
+    /// start over.
+    pub async fn set_clock(&self, clock: Arc<dyn Clock>) {
+        self.stall_detection.lock().unwrap().clock = clock.clone();
+        for thread_id in self.thread_manager.get_active_threads().await {
+            if let Some(handle) = self.thread_manager.get_task(thread_id).await {
+                handle.set_clock(clock.clone()).await;
+            }
+        }
+    }
+
+    /// Find running tasks that have gone without reporting progress or output
+    /// for longer than their stall timeout.
+    ///
+    /// Paused and finished tasks are never stalled.
+    ///
+    /// # Returns
+    /// The IDs of the stalled tasks, in ascending order
+    pub async fn stalled_tasks(&self) -> Vec<usize> {
+        let (timeout, task_timeouts) = {
+            let detection = self.stall_detection.lock().unwrap();
+            (detection.timeout, detection.task_timeouts.clone())
+        };
+
+        let mut stalled = Vec::new();
+        for thread_id in self.thread_manager.get_active_threads().await {
+            let state = self.thread_manager.get_thread_state(thread_id).await;
+            if let Some(handle) = self.thread_manager.get_task(thread_id).await {
+                let timeout = task_timeouts.get(&thread_id).copied().or(timeout);
+                if Self::is_stalled(&*handle.config().lock().await, state.as_ref(), timeout) {
+                    stalled.push(thread_id);
+                }
+            }
+        }
+        stalled.sort_unstable();
+        stalled
+    }
+
+    /// Whether a task in `state` has gone without an update for longer than `timeout`
+    fn is_stalled(config: &Config, state: Option<&ThreadState>, timeout: Option<Duration>) -> bool {
+        let Some(timeout) = timeout else {
+            return false;
+        };
+        state == Some(&ThreadState::Running)
+            && config.time_since_update().is_some_and(|elapsed| elapsed > timeout)
+    }
+
+    /// Count the registered tasks in each job status.
+    ///
+    /// Tasks whose mode does not track a status are not counted.
+    pub async fn status_counts(&self) -> HashMap<JobStatus, usize> {
+        let mut counts = HashMap::new();
+        for thread_id in self.thread_manager.get_active_threads().await {
+            if let Some(handle) = self.thread_manager.get_task(thread_id).await {
+                if let Ok(status) = handle.get_status().await {
+                    *counts.entry(status).or_insert(0) += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Find deadlocked tasks along with the dependencies blocking them.
+    ///
+    /// A task is deadlocked when it has not finished yet and at least one of its
+    /// dependencies is in the `Cancelled` or `Failed` state. Dependencies on
+    /// tasks that are no longer registered are ignored.
+    ///
+    /// # Returns
+    /// Pairs of deadlocked task ID and the IDs of its unsatisfiable dependencies,
+    /// ordered by task ID
+    pub async fn deadlocked_dependencies(&self) -> Vec<(usize, Vec<usize>)> {
+        let mut thread_ids = self.thread_manager.get_active_threads().await;
+        thread_ids.sort_unstable();
+
+        let mut states = HashMap::new();
+        for thread_id in &thread_ids {
+            if let Some(state) = self.thread_manager.get_thread_state(*thread_id).await {
+                states.insert(*thread_id, state);
+            }
+        }
+
+        let mut deadlocked = Vec::new();
+        for thread_id in thread_ids {
+            if states.get(&thread_id).is_none_or(|state| state.is_terminal()) {
+                continue;
+            }
+            let Some(handle) = self.thread_manager.get_task(thread_id).await else {
+                continue;
+            };
+            let Ok(dependencies) = handle.get_dependencies().await else {
+                continue;
+            };
+
+            let unsatisfiable: Vec<usize> = dependencies.into_iter()
+                .filter(|dep| matches!(states.get(dep), Some(ThreadState::Cancelled | ThreadState::Failed(_))))
+                .collect();
+            if !unsatisfiable.is_empty() {
+                deadlocked.push((thread_id, unsatisfiable));
+            }
+        }
+        deadlocked
+    }
+    
     /// Create a new multi-progress bar group
     ///
     /// # Parameters
@@ -801,8 +1370,7 @@ impl ProgressManager {
                 .with_thread_id(thread_id)
                 .with_details("Thread not found");
             
-            let error_msg = format!("Thread {} not found", thread_id);
-            let error = ProgressError::TaskOperation(error_msg).into_context(ctx);
+            let error = ProgressError::ThreadNotFound(thread_id).into_context(ctx);
             Err(anyhow::anyhow!(error))
         }
     }
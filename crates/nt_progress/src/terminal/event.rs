@@ -315,7 +315,7 @@ impl EventManager {
                             if !*running_arc.lock().await {
                                 break; // Normal shutdown
                             }
-                            eprintln!("Error sending terminal event: {}", e);
+                            diag_error!("Error sending terminal event: {}", e);
                             break;
                         }
                     }
@@ -352,7 +352,7 @@ impl EventManager {
                     for handler in handlers_lock.iter() {
                         let event_clone = event.clone();
                         if let Err(e) = handler(event_clone).await {
-                            eprintln!("Error in event handler: {}", e);
+                            diag_error!("Error in event handler: {}", e);
                         }
                     }
                 }
@@ -17,10 +17,11 @@ mod event;
 pub mod test_helpers;
 
 pub use size::Terminal;
+pub(crate) use size::no_color;
 pub use cursor::CursorPosition;
 pub use test_env::TestEnv;
 pub use style::Style;
-pub use text::TextWrapper;
+pub use text::{TextWrapper, display_width, byte_index_at_width};
 pub use event::{EventManager, TerminalEvent, KeyData};
 #[cfg(test)]
 pub use test_helpers::with_timeout;
@@ -240,10 +240,15 @@ impl Default for Terminal {
     }
 }
 
+/// Whether the user opted out of colors and other escape sequences by setting
+/// `NO_COLOR` (https://no-color.org/)
+pub(crate) fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some()
+}
+
 /// Detect if the terminal supports ANSI color codes
 fn detect_color_support() -> bool {
-    // Check for NO_COLOR environment variable (https://no-color.org/)
-    if std::env::var("NO_COLOR").is_ok() {
+    if no_color() {
         return false;
     }
     
@@ -4,6 +4,7 @@
 //! truncation, and other text transformations needed for terminal display.
 
 use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthChar;
 
 /// A utility for wrapping text at specified widths
 #[derive(Debug, Clone)]
@@ -231,8 +232,23 @@ impl TextWrapper {
         (first_part, remaining)
     }
 
+    /// Truncates text to max_width, ending it with the truncation marker when it is cut
+    ///
+    /// # Parameters
+    /// * `text` - The text to truncate
+    ///
+    /// # Returns
+    /// The text unchanged if it fits, otherwise its longest prefix that fits
+    /// together with the marker
+    pub fn truncate(&self, text: &str) -> String {
+        self.truncate_text(text, self.max_width)
+    }
+
     /// Truncates text to the specified width, adding a truncation marker
     ///
+    /// The marker counts towards `width`. An empty marker, or one too wide to
+    /// fit, cuts the text without any indicator.
+    ///
     /// # Parameters
     /// * `text` - The text to truncate
     /// * `width` - The maximum width for the truncated text
@@ -245,7 +261,8 @@ impl TextWrapper {
         }
 
         let marker_width = self.visual_width(&self.truncation_marker);
-        let available_width = width.saturating_sub(marker_width);
+        let marker = if marker_width <= width { self.truncation_marker.as_str() } else { "" };
+        let available_width = width - self.visual_width(marker);
         
         let graphemes = UnicodeSegmentation::graphemes(text, true).collect::<Vec<_>>();
         let mut result = String::new();
@@ -263,7 +280,7 @@ impl TextWrapper {
         }
         
         // Add truncation marker
-        result.push_str(&self.truncation_marker);
+        result.push_str(marker);
         
         result
     }
@@ -277,56 +294,61 @@ impl TextWrapper {
     /// # Returns
     /// The visual width of the text
     fn visual_width(&self, text: &str) -> usize {
-        let mut width = 0;
-        
-        // Handle based on whether we should strip ANSI sequences
         if self.strip_ansi {
-            // Strip ANSI sequences and then measure
-            let clean_text = strip_ansi_sequences(text);
-            for grapheme in UnicodeSegmentation::graphemes(clean_text.as_str(), true) {
-                let ch = grapheme.chars().next().unwrap_or(' ');
-                if is_wide_char(ch) {
-                    width += 2;
-                } else {
-                    width += 1;
-                }
-            }
+            display_width(&strip_ansi_sequences(text))
         } else {
-            // Measure directly without stripping
-            for grapheme in UnicodeSegmentation::graphemes(text, true) {
-                let ch = grapheme.chars().next().unwrap_or(' ');
-                if is_wide_char(ch) {
-                    width += 2;
-                } else {
-                    width += 1;
-                }
-            }
+            display_width(text)
         }
-        
-        width
     }
 }
 
-/// Checks if a character is visually wide (occupies 2 columns)
+/// Calculates the number of terminal columns a string occupies
+///
+/// Wide characters such as CJK ideographs and most emojis take two columns.
+/// ANSI escape sequences are not stripped.
 ///
 /// # Parameters
-/// * `ch` - The character to check
+/// * `text` - The text to measure
 ///
 /// # Returns
-/// true if the character is visually wide, false otherwise
-fn is_wide_char(ch: char) -> bool {
-    // East Asian Wide (W) and East Asian Full-width (F) characters
-    // This is a simplified check that covers common cases
-    matches!(ch,
-        '\u{1100}'..='\u{11FF}' |   // Hangul Jamo
-        '\u{2E80}'..='\u{9FFF}' |   // CJK Unified Ideographs
-        '\u{AC00}'..='\u{D7AF}' |   // Hangul Syllables
-        '\u{F900}'..='\u{FAFF}' |   // CJK Compatibility Ideographs
-        '\u{FE10}'..='\u{FE19}' |   // Vertical Forms
-        '\u{FE30}'..='\u{FE6F}' |   // CJK Compatibility Forms
-        '\u{FF00}'..='\u{FF60}' |   // Fullwidth ASCII Variants
-        '\u{FFE0}'..='\u{FFE6}'     // Fullwidth Symbol Variants
-    )
+/// The display width of the text
+pub fn display_width(text: &str) -> usize {
+    UnicodeSegmentation::graphemes(text, true)
+        .map(grapheme_width)
+        .sum()
+}
+
+/// Finds the byte index of the longest prefix of `text` that fits in `width` columns
+///
+/// The index always falls on a grapheme boundary.
+///
+/// # Parameters
+/// * `text` - The text to measure
+/// * `width` - The number of columns available
+///
+/// # Returns
+/// The byte index where `text` should be split
+pub fn byte_index_at_width(text: &str, width: usize) -> usize {
+    let mut current_width = 0;
+    for (index, grapheme) in UnicodeSegmentation::grapheme_indices(text, true) {
+        current_width += grapheme_width(grapheme);
+        if current_width > width {
+            return index;
+        }
+    }
+    text.len()
+}
+
+/// Calculates the width of a single grapheme cluster
+///
+/// A cluster is as wide as its base character, so emoji modifiers and
+/// zero-width joiner sequences don't add extra columns. A trailing emoji
+/// presentation selector (U+FE0F) makes the cluster two columns wide.
+fn grapheme_width(grapheme: &str) -> usize {
+    if grapheme.contains('\u{FE0F}') {
+        return 2;
+    }
+    grapheme.chars().next().and_then(|ch| ch.width()).unwrap_or(0)
 }
 
 /// Strips ANSI escape sequences from a string
@@ -427,6 +449,35 @@ mod tests {
         assert!(wrapped[0].ends_with("…"));
     }
 
+    #[test]
+    fn test_truncation_marker() {
+        let input = "The quick brown fox jumps over the lazy dog";
+        
+        let truncated = TextWrapper::new(12).truncate(input);
+        assert_eq!(truncated, "The quick b…");
+        assert_eq!(display_width(&truncated), 12);
+        
+        let truncated = TextWrapper::new(12).truncation_marker("[...]").truncate(input);
+        assert_eq!(truncated, "The qui[...]");
+        assert_eq!(display_width(&truncated), 12);
+        
+        // An empty marker just cuts the line
+        let truncated = TextWrapper::new(12).truncation_marker("").truncate(input);
+        assert_eq!(truncated, "The quick br");
+        
+        // A marker wider than the line is dropped rather than overflowing
+        let truncated = TextWrapper::new(3).truncation_marker("[...]").truncate(input);
+        assert_eq!(truncated, "The");
+        
+        // Wide characters never push the line past its width
+        let truncated = TextWrapper::new(6).truncate("こんにちは世界");
+        assert_eq!(truncated, "こん…");
+        assert!(display_width(&truncated) <= 6);
+        
+        // Lines that fit are left alone
+        assert_eq!(TextWrapper::new(50).truncate(input), input);
+    }
+
     #[test]
     fn test_unicode_wrapping() {
         let wrapper = TextWrapper::new(10);
@@ -470,6 +521,23 @@ mod tests {
         assert_eq!(wrapper.visual_width("Hi 你好"), 7);
     }
     
+    #[test]
+    fn test_emoji_display_width() {
+        assert_eq!(display_width("🚀 Done ✅"), 10);
+        
+        // Emoji presentation selector, skin tone modifier and ZWJ sequence
+        assert_eq!(display_width("⚠️"), 2);
+        assert_eq!(display_width("👍🏽"), 2);
+        assert_eq!(display_width("👨‍👩‍👧"), 2);
+        
+        // Split points never land inside a wide character
+        assert_eq!(byte_index_at_width("🚀🚀🚀", 3), "🚀".len());
+        assert_eq!(byte_index_at_width("ok", 10), 2);
+        
+        let wrapper = TextWrapper::new(6);
+        assert_eq!(wrapper.wrap("🚀🚀🚀🚀🚀"), vec!["🚀🚀🚀", "🚀🚀"]);
+    }
+    
     #[test]
     fn test_ansi_escape_sequences() {
         let wrapper = TextWrapper::new(10);
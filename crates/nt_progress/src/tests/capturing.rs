@@ -5,6 +5,7 @@ use tokio::time::sleep;
 use std::time::Duration;
 use crate::tests::common::with_timeout;
 use anyhow::Result;
+use crate::modes::{CapturingConfig, OverflowStrategy};
 
 #[tokio::test]
 async fn test_capturing_basic() -> Result<()> {
@@ -172,4 +173,31 @@ async fn test_capturing_long_lines() -> Result<()> {
     // Clean up OUTSIDE timeout
     display.stop().await?;
     Ok(())
-} 
\ No newline at end of file
+} 
+
+#[tokio::test]
+async fn test_capturing_buffer_overflow() -> Result<()> {
+    // Create display OUTSIDE timeout
+    let display = ProgressDisplay::new().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        let mut task = display.spawn_with_mode(ThreadMode::Capturing, || "overflow-test").await?;
+        task.set_capturing_config(CapturingConfig {
+            max_buffer_bytes: 10,
+            overflow_strategy: OverflowStrategy::Error,
+            ..Default::default()
+        }).await?;
+        
+        task.capture_stdout("0123456789".to_string()).await?;
+        assert_eq!(task.get_buffer_overflow_count().await, 0);
+        
+        assert!(task.capture_stdout("overflow".to_string()).await.is_err());
+        assert_eq!(task.get_buffer_overflow_count().await, 1);
+        Ok::<(), anyhow::Error>(())
+    }, 15).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
@@ -2,6 +2,9 @@ use std::time::Duration;
 use tokio::time::sleep;
 use crate::ProgressDisplay;
 use crate::ThreadMode;
+use crate::JobStatus;
+use crate::ProgressError;
+use crate::thread::ThreadState;
 use crate::terminal::TestEnv;
 use crate::tests::common::with_timeout;
 use anyhow::Result;
@@ -481,3 +484,1168 @@ async fn test_progress_display_custom_indicators() -> Result<()> {
     
     Ok(())
 } 
+
+#[tokio::test]
+async fn test_progress_display_cancel_single_task() -> Result<()> {
+    // Create display OUTSIDE timeout
+    let display = ProgressDisplay::new().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        let manager = display.progress_manager();
+        let task = manager.spawn(|_| async {
+            sleep(Duration::from_secs(60)).await;
+            Ok(())
+        }).await?;
+        let other = manager.spawn(|_| async {
+            sleep(Duration::from_secs(60)).await;
+            Ok(())
+        }).await?;
+        let thread_id = task.thread_id();
+        
+        assert_eq!(manager.thread_manager().get_thread_state(thread_id).await, Some(ThreadState::Running));
+        
+        manager.cancel_task(thread_id, Some("stopped by test".to_string())).await?;
+        assert_eq!(
+            manager.thread_manager().get_thread_state(thread_id).await,
+            Some(ThreadState::Cancelled)
+        );
+        assert!(task.is_cancelled().await?);
+        assert_eq!(task.get_cancellation_reason().await?, Some("stopped by test".to_string()));
+        
+        // Other tasks are unaffected
+        assert_eq!(manager.thread_manager().get_thread_state(other.thread_id()).await, Some(ThreadState::Running));
+        
+        // Cancelling twice or cancelling an unknown task fails
+        assert!(manager.cancel_task(thread_id, None).await.is_err());
+        assert!(manager.cancel_task(usize::MAX, None).await.is_err());
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_spawned_task_observes_cancellation() -> Result<()> {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    
+    // Create display OUTSIDE timeout
+    let display = ProgressDisplay::new().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        let manager = display.progress_manager();
+        let exited = std::sync::Arc::new(AtomicBool::new(false));
+        let task = manager.spawn({
+            let exited = exited.clone();
+            |handle| async move {
+                handle.cancellation_token().cancelled().await;
+                exited.store(true, Ordering::SeqCst);
+                Ok(())
+            }
+        }).await?;
+        assert!(!task.cancellation_token().is_cancelled());
+        
+        // The closure gets to finish before cancel_task returns
+        manager.cancel_task(task.thread_id(), None).await?;
+        assert!(task.cancellation_token().is_cancelled());
+        assert!(exited.load(Ordering::SeqCst));
+        
+        // cancel_all triggers the token as well
+        let other = manager.spawn(|handle| async move {
+            handle.cancellation_token().cancelled().await;
+            Ok(())
+        }).await?;
+        manager.cancel_all().await?;
+        assert!(other.cancellation_token().is_cancelled());
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_spawned_task_records_final_state() -> Result<()> {
+    // Create display OUTSIDE timeout
+    let display = ProgressDisplay::new().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        let manager = display.progress_manager();
+        let task = manager.spawn(|_| async { Ok(()) }).await?;
+        task.completed().await?;
+        assert_eq!(manager.thread_manager().get_thread_state(task.thread_id()).await, Some(ThreadState::Completed));
+        
+        let task = manager.spawn(|_| async { Err(anyhow::anyhow!("disk full")) }).await?;
+        let err = task.completed().await.unwrap_err();
+        assert!(err.to_string().contains("disk full"), "{}", err);
+        assert_eq!(
+            manager.thread_manager().get_thread_state(task.thread_id()).await,
+            Some(ThreadState::Failed("disk full".to_string()))
+        );
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_progress_display_detects_deadlocked_task() -> Result<()> {
+    // Create display OUTSIDE timeout
+    let display = ProgressDisplay::new().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        let manager = display.progress_manager();
+        let dependency = manager.create_task(ThreadMode::Limited, 1).await?;
+        let blocked = manager.create_task(ThreadMode::Limited, 1).await?;
+        let independent = manager.create_task(ThreadMode::Limited, 1).await?;
+        assert!(blocked.add_dependency(dependency.thread_id()).await?);
+        
+        // Nothing is deadlocked while the dependency can still complete
+        assert!(manager.detect_deadlocked_tasks().await.is_empty());
+        
+        manager.cancel_task(dependency.thread_id(), None).await?;
+        assert_eq!(manager.detect_deadlocked_tasks().await, vec![blocked.thread_id()]);
+        
+        let reported = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = reported.clone();
+        display.on_deadlock(move |error| sink.lock().unwrap().push(error.to_string())).await;
+        
+        let errors = display.check_deadlocks().await;
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            ProgressError::DeadlockedTask(id, deps) if *id == blocked.thread_id() && deps == &vec![dependency.thread_id()]
+        ));
+        assert_eq!(reported.lock().unwrap().len(), 1);
+        assert!(independent.get_dependencies().await?.is_empty());
+        
+        // Unblocking the task clears the deadlock
+        assert_eq!(blocked.force_unblock().await?, vec![dependency.thread_id()]);
+        assert!(manager.detect_deadlocked_tasks().await.is_empty());
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
+
+/// Writer appending to a buffer the test can read back
+#[derive(Clone, Default)]
+struct SharedBuffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl std::io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A progress manager driven by hand, with a renderer writing to a buffer
+struct ManagerFixture {
+    manager: crate::progress_manager::ProgressManager,
+    message_rx: tokio::sync::Mutex<tokio::sync::mpsc::Receiver<crate::ThreadMessage>>,
+    renderer: crate::ui::renderer::Renderer,
+    buffer: SharedBuffer,
+}
+
+impl ManagerFixture {
+    fn new() -> Self {
+        let (message_tx, message_rx) = tokio::sync::mpsc::channel::<crate::ThreadMessage>(100);
+        let manager = crate::progress_manager::ProgressManager::new(
+            std::sync::Arc::new(crate::modes::factory::ModeFactory::new()),
+            message_tx
+        );
+        let buffer = SharedBuffer::default();
+        let renderer = crate::ui::renderer::Renderer::with_writer(Box::new(buffer.clone()));
+        Self { manager, message_rx: tokio::sync::Mutex::new(message_rx), renderer, buffer }
+    }
+
+    /// Hands every queued message to the manager
+    async fn handle_messages(&self) {
+        let mut message_rx = self.message_rx.lock().await;
+        while let Ok(msg) = message_rx.try_recv() {
+            self.manager.handle_message(msg).await;
+        }
+    }
+
+    /// Renders all tasks into a cleared buffer and returns what was written
+    async fn render(&self) -> Result<String> {
+        self.buffer.0.lock().unwrap().clear();
+        let outputs = self.manager.outputs().lock().await.clone();
+        self.renderer.render(&outputs, &self.manager.render_info().await).await?;
+        Ok(String::from_utf8(self.buffer.0.lock().unwrap().clone())?)
+    }
+}
+
+#[tokio::test]
+async fn test_progress_display_renders_by_priority() -> Result<()> {
+    // Create display OUTSIDE timeout
+    let display = ProgressDisplay::new().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        let manager = display.progress_manager();
+        let low = manager.create_task(ThreadMode::Limited, 1).await?;
+        let high = manager.create_task(ThreadMode::Limited, 1).await?;
+        let tied = manager.create_task(ThreadMode::Limited, 1).await?;
+        low.set_priority(1).await?;
+        high.set_priority(5).await?;
+        tied.set_priority(1).await?;
+        assert_eq!(high.get_priority().await?, 5);
+        
+        let tasks = manager.render_info().await;
+        let mut outputs = std::collections::HashMap::new();
+        for (handle, name) in [(&low, "low"), (&high, "high"), (&tied, "tied")] {
+            outputs.insert(handle.thread_id(), vec![name.to_string()]);
+        }
+        
+        let buffer = SharedBuffer::default();
+        let renderer = crate::ui::renderer::Renderer::with_writer(Box::new(buffer.clone()));
+        renderer.render(&outputs, &tasks).await?;
+        let rendered = String::from_utf8(buffer.0.lock().unwrap().clone())?;
+        let position = |name: &str| rendered.find(name).unwrap();
+        assert!(position("high") < position("low"));
+        assert!(position("low") < position("tied"));
+        
+        // Stable ordering falls back to thread IDs
+        renderer.set_priority_ordering(false);
+        assert_eq!(
+            renderer.render_order(&outputs, &tasks),
+            vec![low.thread_id(), high.thread_id(), tied.thread_id()]
+        );
+        renderer.stop().await?;
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stop_renders_queued_messages() -> Result<()> {
+    // Create display OUTSIDE timeout
+    let buffer = SharedBuffer::default();
+    let display = ProgressDisplay::builder().writer(Box::new(buffer.clone())).build().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        let mut task = display.create_task(ThreadMode::Limited, 1).await?;
+        for i in 0..3 {
+            task.capture_stdout(format!("last words {}", i)).await?;
+        }
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    let rendered = String::from_utf8(buffer.0.lock().unwrap().clone())?;
+    for i in 0..3 {
+        assert!(rendered.contains(&format!("last words {}", i)), "{:?}", rendered);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stop_drains_messages_sent_during_shutdown() -> Result<()> {
+    // Create display OUTSIDE timeout, with a channel that fills up quickly
+    let buffer = SharedBuffer::default();
+    let display = ProgressDisplay::builder().capacity(2).writer(Box::new(buffer.clone())).build().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        // Logs more than the channel holds once cancelled, then exits on its own
+        display.spawn(|mut task| async move {
+            task.cancellation_token().cancelled().await;
+            for i in 0..10 {
+                task.capture_stdout(format!("shutting down {}", i)).await?;
+            }
+            Ok(())
+        }).await?;
+        sleep(Duration::from_millis(10)).await;
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Stopping waits for the task, which is not aborted halfway through its logging
+    with_timeout(display.stop(), 5).await??;
+    let rendered = String::from_utf8(buffer.0.lock().unwrap().clone())?;
+    assert!(rendered.contains("shutting down 9"), "{:?}", rendered);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_notify_on_complete_rings_bell() -> Result<()> {
+    use crate::{NotifyConfig, thread::ThreadState};
+    
+    // Create display OUTSIDE timeout
+    let buffer = SharedBuffer::default();
+    let display = ProgressDisplay::builder().writer(Box::new(buffer.clone())).build().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        display.notify_on_complete(NotifyConfig::default()).await;
+        let first = display.create_task(ThreadMode::Limited, 1).await?;
+        let second = display.create_task(ThreadMode::Limited, 1).await?;
+        let threads = display.progress_manager().thread_manager();
+        let bells = || buffer.0.lock().unwrap().iter().filter(|&&b| b == 0x07).count();
+        
+        // Nothing is rung while a task is still running
+        threads.update_thread_state(first.thread_id(), ThreadState::Completed).await?;
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(bells(), 0);
+        
+        threads.update_thread_state(second.thread_id(), ThreadState::Completed).await?;
+        for _ in 0..50 {
+            if bells() > 0 {
+                break;
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(bells(), 1);
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failure_threshold_cancels_remaining_tasks() -> Result<()> {
+    use crate::thread::ThreadState;
+    
+    // Create display OUTSIDE timeout
+    let display = ProgressDisplay::new().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        display.set_failure_threshold(1).await;
+        let mut tasks = Vec::new();
+        for _ in 0..4 {
+            tasks.push(display.create_task(ThreadMode::Limited, 1).await?);
+        }
+        let threads = display.progress_manager().thread_manager();
+        
+        // Reaching the threshold is still allowed
+        threads.update_thread_state(tasks[0].thread_id(), ThreadState::Failed("boom".to_string())).await?;
+        sleep(Duration::from_millis(50)).await;
+        assert!(display.failure_threshold_error().is_none());
+        assert!(!tasks[3].cancellation_token().is_cancelled());
+        
+        threads.update_thread_state(tasks[1].thread_id(), ThreadState::Failed("boom".to_string())).await?;
+        tasks[3].cancellation_token().cancelled().await;
+        assert!(tasks[2].cancellation_token().is_cancelled());
+        
+        let error = display.join_all().await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<ProgressError>(),
+            Some(ProgressError::FailureThresholdExceeded { failed, threshold: 1 })
+                if failed == &vec![tasks[0].thread_id(), tasks[1].thread_id()]
+        ), "{}", error);
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_render_to_custom_target() -> Result<()> {
+    use crate::RenderTarget;
+    
+    // Create display OUTSIDE timeout
+    let display = ProgressDisplay::new().await?;
+    let buffer = SharedBuffer::default();
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        display.set_render_target(RenderTarget::Custom(Box::new(buffer.clone()))).await;
+        let mut task = display.create_task(ThreadMode::Limited, 1).await?;
+        task.capture_stdout("rendered to the buffer".to_string()).await?;
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    let rendered = String::from_utf8(buffer.0.lock().unwrap().clone())?;
+    assert!(rendered.contains("rendered to the buffer"), "{:?}", rendered);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_progress_manager_status_counts() -> Result<()> {
+    // Create display OUTSIDE timeout
+    let display = ProgressDisplay::new().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        let manager = display.progress_manager();
+        let mut tasks = Vec::new();
+        for _ in 0..5 {
+            tasks.push(manager.create_task(ThreadMode::Limited, 1).await?);
+        }
+        tasks[1].set_status(JobStatus::Running).await?;
+        tasks[2].set_status(JobStatus::Running).await?;
+        tasks[3].set_status(JobStatus::Completed).await?;
+        tasks[4].set_status(JobStatus::Failed).await?;
+        assert_eq!(tasks[3].get_status().await?, JobStatus::Completed);
+        
+        let counts = manager.status_counts().await;
+        assert_eq!(counts.get(&JobStatus::Pending), Some(&1));
+        assert_eq!(counts.get(&JobStatus::Running), Some(&2));
+        assert_eq!(counts.get(&JobStatus::Completed), Some(&1));
+        assert_eq!(counts.get(&JobStatus::Failed), Some(&1));
+        assert_eq!(counts.get(&JobStatus::Retry), None);
+        
+        // Thread state changes carry over to the job status
+        let spawned = manager.spawn(|_| async { Err(anyhow::anyhow!("boom")) }).await?;
+        assert!(spawned.completed().await.is_err());
+        assert_eq!(spawned.get_status().await?, JobStatus::Failed);
+        let counts = manager.status_counts().await;
+        assert_eq!(counts.get(&JobStatus::Failed), Some(&2));
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_persist_and_restore_task() -> Result<()> {
+    // Create display OUTSIDE timeout
+    let display = ProgressDisplay::new().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        let task = display.create_task(ThreadMode::WindowWithTitle(3), 10).await?;
+        task.set_title("Downloading".to_string()).await?;
+        task.set_progress(4).await?;
+        task.set_status(JobStatus::Running).await?;
+        
+        let saved = serde_json::to_string(&task.persist().await?)?;
+        let restored = display.restore_task(serde_json::from_str(&saved)?).await?;
+        
+        assert_ne!(restored.thread_id(), task.thread_id());
+        assert_eq!(restored.get_status().await?, JobStatus::Running);
+        assert_eq!(restored.get_progress_percentage().await?, 40.0);
+        let config = restored.config().lock().await;
+        assert_eq!(config.thread_mode(), Some(ThreadMode::WindowWithTitle(3)));
+        assert_eq!(config.get_title(), Some("Downloading"));
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_spawn_command_with_mock_runner() -> Result<()> {
+    use std::sync::Arc;
+    use crate::{CommandRunner, MockCommandRunner};
+    use crate::modes::Capturing;
+    
+    // Create display OUTSIDE timeout
+    let display = ProgressDisplay::new().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        let runner = Arc::new(MockCommandRunner::new()
+            .stdout("compiling")
+            .stderr("warning: unused")
+            .stdout("done"));
+        let task = display.spawn_command(ThreadMode::Capturing, runner.clone(), "cargo", vec!["build".to_string()]).await?;
+        task.completed().await?;
+        
+        assert_eq!(runner.take_call(), Some(("cargo".to_string(), vec!["build".to_string()])));
+        assert_eq!(task.get_status().await?, JobStatus::Completed);
+        let config = task.config().lock().await;
+        assert_eq!(*config.as_type::<Capturing>().unwrap().captured(), ["compiling", "warning: unused", "done"]);
+        drop(config);
+        
+        // A non-zero exit fails the task
+        let runner: Arc<dyn CommandRunner> = Arc::new(MockCommandRunner::new().stderr("boom").exit_code(Some(2)));
+        let task = display.spawn_command(ThreadMode::Capturing, runner, "make", Vec::new()).await?;
+        let err = task.completed().await.unwrap_err();
+        assert!(err.to_string().contains("make exited with code 2"), "{}", err);
+        assert_eq!(task.get_status().await?, JobStatus::Failed);
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_line_numbers_are_per_task() -> Result<()> {
+    let fixture = ManagerFixture::new();
+    let manager = &fixture.manager;
+    
+    let mut first = manager.create_task(ThreadMode::Limited, 1).await?;
+    let mut second = manager.create_task(ThreadMode::Limited, 1).await?;
+    let mut plain = manager.create_task(ThreadMode::Limited, 1).await?;
+    first.set_line_numbers(true).await;
+    second.set_line_numbers(true).await;
+    
+    // Interleave the output of the tasks
+    first.capture_stdout("a".to_string()).await?;
+    second.capture_stdout("x".to_string()).await?;
+    first.capture_stderr("b".to_string()).await?;
+    plain.capture_stdout("unnumbered".to_string()).await?;
+    second.capture_stdout("y".to_string()).await?;
+    first.capture_stdout("c".to_string()).await?;
+    
+    fixture.handle_messages().await;
+    
+    let outputs = manager.outputs().lock().await;
+    assert_eq!(outputs[&first.thread_id()], ["[1] a", "[2] b", "[3] c"]);
+    assert_eq!(outputs[&second.thread_id()], ["[1] x", "[2] y"]);
+    assert_eq!(outputs[&plain.thread_id()], ["unnumbered"]);
+    Ok(())
+}
+
+/// A trivial user-defined mode that shows the latest message like Limited mode
+#[derive(Debug)]
+struct EchoCreator;
+
+impl crate::modes::factory::ModeCreator for EchoCreator {
+    fn mode_name(&self) -> &'static str {
+        "echo"
+    }
+    
+    fn create(&self, params: &crate::ModeParameters) -> Result<Box<dyn crate::core::ThreadConfig>, crate::errors::ModeCreationError> {
+        Ok(Box::new(crate::modes::limited::Limited::new(params.total_jobs())))
+    }
+}
+
+#[tokio::test]
+async fn test_progress_display_custom_mode() -> Result<()> {
+    use crate::modes::factory::ModeFactory;
+    
+    // Create display OUTSIDE timeout, with a custom mode registered
+    let mut factory = ModeFactory::new();
+    factory.register("echo", Box::new(EchoCreator));
+    let display = ProgressDisplay::new_with_factory(std::sync::Arc::new(factory)).await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        let manager = display.progress_manager();
+        let mut task = manager.create_task(ThreadMode::Custom("echo".to_string()), 4).await?;
+        task.capture_stdout("hello from echo".to_string()).await?;
+        assert!(task.config().lock().await.as_type::<crate::modes::limited::Limited>().is_some());
+        
+        // Unknown custom modes are still rejected
+        assert!(manager.create_task(ThreadMode::Custom("missing".to_string()), 1).await.is_err());
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_progress_display_builder() -> Result<()> {
+    // Create display OUTSIDE timeout
+    let buffer = SharedBuffer::default();
+    let display = ProgressDisplay::builder()
+        .mode(ThreadMode::Window(4))
+        .capacity(16)
+        .writer(Box::new(buffer.clone()))
+        .priority_ordering(false)
+        .quiet(true)
+        .template("{completed}/{total}")
+        .build()
+        .await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        assert_eq!(display.progress_manager().factory().default_mode(), ThreadMode::Window(4));
+        assert_eq!(display.message_rx.lock().await.max_capacity(), 16);
+        assert!(!display.renderer.priority_ordering());
+        assert!(display.renderer.is_quiet());
+        
+        // New tasks use the template when their mode tracks progress
+        let task = display.create_task(ThreadMode::Window(4), 3).await?;
+        assert_eq!(task.config().lock().await.get_progress_format(), Some("{completed}/{total}"));
+        display.create_task(ThreadMode::Limited, 1).await?;
+        
+        // Output goes to the configured writer
+        let outputs = std::collections::HashMap::from([(1, vec!["built with options".to_string()])]);
+        display.renderer.render(&outputs, &std::collections::HashMap::new()).await?;
+        let rendered = String::from_utf8(buffer.0.lock().unwrap().clone())?;
+        assert!(rendered.contains("built with options"));
+        
+        // A zero capacity channel is rejected
+        assert!(ProgressDisplay::builder().capacity(0).build().await.is_err());
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_collapsed_task_renders_only_title() -> Result<()> {
+    let fixture = ManagerFixture::new();
+    let (manager, renderer) = (&fixture.manager, &fixture.renderer);
+    
+    let mut task = manager.create_task(ThreadMode::WindowWithTitle(3), 1).await?;
+    task.set_title("Downloads".to_string()).await?;
+    task.capture_stdout("first file".to_string()).await?;
+    
+    task.set_collapsed(true).await?;
+    assert!(task.is_collapsed().await);
+    assert_eq!(task.config().lock().await.lines_to_display(), 1);
+    // Output keeps being buffered while collapsed
+    task.capture_stdout("second file".to_string()).await?;
+    fixture.handle_messages().await;
+    
+    let rendered = fixture.render().await?;
+    assert!(rendered.contains("Downloads"), "{:?}", rendered);
+    assert!(!rendered.contains("file"), "{:?}", rendered);
+    
+    task.set_collapsed(false).await?;
+    assert_eq!(task.config().lock().await.lines_to_display(), 3);
+    let rendered = fixture.render().await?;
+    assert!(rendered.contains("first file") && rendered.contains("second file"), "{:?}", rendered);
+    
+    // Only WindowWithTitle tasks have a title line to collapse to
+    let limited = manager.create_task(ThreadMode::Limited, 1).await?;
+    assert!(limited.set_collapsed(true).await.is_err());
+    renderer.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_retrying_task_shows_retry_count() -> Result<()> {
+    let fixture = ManagerFixture::new();
+    let (manager, renderer) = (&fixture.manager, &fixture.renderer);
+    
+    let mut task = manager.create_task(ThreadMode::Limited, 1).await?;
+    task.capture_stdout("fetching feed".to_string()).await?;
+    let mut other = manager.create_task(ThreadMode::Limited, 1).await?;
+    other.capture_stdout("parsing feed".to_string()).await?;
+    task.mark_retry().await;
+    assert_eq!(task.mark_retry().await, 2);
+    fixture.handle_messages().await;
+    
+    let rendered = fixture.render().await?;
+    assert!(rendered.contains("fetching feed (retry 2/3)\n"), "{:?}", rendered);
+    assert!(rendered.contains("parsing feed\n"), "{:?}", rendered);
+    
+    // The annotation goes away once the task is running again
+    task.set_status(crate::core::base_config::JobStatus::Running).await?;
+    let rendered = fixture.render().await?;
+    assert!(!rendered.contains("retry"), "{:?}", rendered);
+    
+    // Completing the task starts the count over
+    task.set_status(crate::core::base_config::JobStatus::Completed).await?;
+    assert_eq!(task.mark_retry().await, 1);
+    renderer.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_long_lines_end_with_truncation_indicator() -> Result<()> {
+    let fixture = ManagerFixture::new();
+    let (manager, renderer) = (&fixture.manager, &fixture.renderer);
+    renderer.terminal().set_size(20, 24).await?;
+
+    let mut task = manager.create_task(ThreadMode::Limited, 1).await?;
+    task.capture_stdout("downloading a very long article title".to_string()).await?;
+    let mut other = manager.create_task(ThreadMode::Limited, 1).await?;
+    other.capture_stdout("short line".to_string()).await?;
+    other.config().lock().await.set_truncation_indicator(">>".to_string());
+    other.capture_stdout("another very long line of output".to_string()).await?;
+    fixture.handle_messages().await;
+
+    let rendered = fixture.render().await?;
+    assert!(rendered.contains("downloading a very …\n"), "{:?}", rendered);
+    assert!(rendered.contains("another very long >>\n"), "{:?}", rendered);
+    for line in rendered.trim_start_matches("\x1B[2J\x1B[1H").lines() {
+        assert!(crate::terminal::display_width(line) <= 20, "{:?}", line);
+    }
+    renderer.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_task_lines() -> Result<()> {
+    // Create display OUTSIDE timeout
+    let display = ProgressDisplay::new().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        let mut task = display.create_task(ThreadMode::Window(2), 1).await?;
+        assert!(task.get_display_lines().await?.is_empty());
+        
+        for line in ["first", "second", "third"] {
+            task.capture_stdout(line.to_string()).await?;
+        }
+        assert_eq!(task.get_display_lines().await?, vec!["second", "third"]);
+        assert_eq!(display.get_task_lines(task.thread_id()).await?, vec!["second", "third"]);
+        
+        assert!(display.get_task_lines(9999).await.is_err());
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_restart_after_stop() -> Result<()> {
+    // Create display OUTSIDE timeout
+    let display = ProgressDisplay::new().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        assert!(display.is_running());
+        display.stop().await?;
+        assert!(!display.is_running());
+        assert!(display.create_task(ThreadMode::Limited, 1).await.is_err());
+        
+        display.restart().await?;
+        assert!(display.is_running());
+        let mut task = display.create_task(ThreadMode::Limited, 1).await?;
+        task.capture_stdout("after restart".to_string()).await?;
+        assert_eq!(display.get_task_lines(task.thread_id()).await?, vec!["after restart"]);
+        
+        // With auto restart, creating a task is enough
+        display.stop().await?;
+        display.set_auto_restart(true);
+        display.create_task(ThreadMode::Limited, 1).await?;
+        assert!(display.is_running());
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_concurrent_stop_and_restart() -> Result<()> {
+    // Create display OUTSIDE timeout
+    let display = ProgressDisplay::new().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        for _ in 0..20 {
+            let (stopped, restarted) = tokio::join!(display.stop(), display.restart());
+            stopped?;
+            restarted?;
+            // A running display always has a live processing task
+            if display.is_running() {
+                let guard = display.processing_task.lock().await;
+                assert!(guard.as_ref().is_some_and(|task| !task.is_finished()));
+            }
+        }
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stalled_task_is_reported() -> Result<()> {
+    let fixture = ManagerFixture::new();
+    let (manager, renderer) = (&fixture.manager, &fixture.renderer);
+    let clock = crate::MockClock::new();
+    manager.set_clock(std::sync::Arc::new(clock.clone())).await;
+    manager.set_stall_timeout(Some(Duration::from_secs(30)));
+    manager.set_stall_annotation(true);
+    
+    let mut task = manager.create_task(ThreadMode::Limited, 1).await?;
+    task.capture_stdout("fetching feed".to_string()).await?;
+    let mut other = manager.create_task(ThreadMode::Limited, 1).await?;
+    other.capture_stdout("parsing feed".to_string()).await?;
+    fixture.handle_messages().await;
+    
+    clock.advance(Duration::from_secs(20));
+    assert!(manager.stalled_tasks().await.is_empty());
+    other.capture_stdout("parsed 10 items".to_string()).await?;
+    clock.advance(Duration::from_secs(20));
+    assert_eq!(manager.stalled_tasks().await, vec![task.thread_id()]);
+    
+    let rendered = fixture.render().await?;
+    assert!(rendered.contains("fetching feed (stalled)\n"), "{:?}", rendered);
+    assert!(rendered.contains("parsing feed\n"), "{:?}", rendered);
+    
+    // A task with a longer timeout of its own, or one that is paused, is not stalled
+    manager.set_task_stall_timeout(task.thread_id(), Duration::from_secs(60));
+    assert!(manager.stalled_tasks().await.is_empty());
+    clock.advance(Duration::from_secs(30));
+    assert_eq!(manager.stalled_tasks().await, vec![task.thread_id(), other.thread_id()]);
+    manager.pause_thread(other.thread_id()).await?;
+    assert_eq!(manager.stalled_tasks().await, vec![task.thread_id()]);
+    
+    // Reporting progress again clears it
+    task.update_progress().await?;
+    assert!(manager.stalled_tasks().await.is_empty());
+    renderer.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_progress_bulk() -> Result<()> {
+    let fixture = ManagerFixture::new();
+    let manager = &fixture.manager;
+    
+    let mut tasks = Vec::new();
+    for _ in 0..3 {
+        tasks.push(manager.create_task(ThreadMode::Window(3), 10).await?);
+    }
+    let updates: Vec<(usize, usize)> = tasks.iter().enumerate()
+        .map(|(i, task)| (task.thread_id(), (i + 1) * 2))
+        .chain(std::iter::once((9999, 5)))
+        .collect();
+    
+    let results = manager.set_progress_bulk(&updates).await;
+    assert_eq!(results.len(), 4);
+    for (result, expected) in results.iter().zip([20.0, 40.0, 60.0]) {
+        assert_eq!(*result.as_ref().unwrap(), expected);
+    }
+    assert!(results[3].is_err());
+    
+    for (task, expected) in tasks.iter().zip([20.0, 40.0, 60.0]) {
+        assert_eq!(task.get_progress_percentage().await?, expected);
+        assert_eq!(manager.get_progress_percentage(task.thread_id()).await?, expected);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_adopt_display() -> Result<()> {
+    // Create displays OUTSIDE timeout
+    let display = ProgressDisplay::new().await?;
+    let other = ProgressDisplay::new().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        for _ in 0..2 {
+            display.create_task(ThreadMode::Limited, 1).await?;
+        }
+        let mut adopted = Vec::new();
+        for i in 0..3 {
+            let mut task = other.create_task(ThreadMode::Window(3), 1).await?;
+            task.capture_stdout(format!("other task {}", i)).await?;
+            adopted.push(task);
+        }
+        
+        display.adopt(other.clone()).await?;
+        assert_eq!(display.thread_count().await, 5);
+        assert_eq!(other.thread_count().await, 0);
+        assert!(!other.is_running());
+        
+        let outputs = display.progress_manager().outputs().lock().await.clone();
+        for i in 0..3 {
+            let line = format!("other task {}", i);
+            assert!(outputs.values().any(|lines| lines.contains(&line)), "{:?}", outputs);
+        }
+        
+        // Handles from the adopted display still reach this one
+        adopted[0].capture_stdout("after adoption".to_string()).await?;
+        sleep(Duration::from_millis(100)).await;
+        display.flush().await?;
+        let outputs = display.progress_manager().outputs().lock().await.clone();
+        let lines = outputs.values()
+            .find(|lines| lines.contains(&"other task 0".to_string()))
+            .unwrap();
+        assert!(lines.contains(&"after adoption".to_string()), "{:?}", outputs);
+        
+        assert!(display.adopt(display.clone()).await.is_err());
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    other.stop().await?;
+    display.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_adopt_display_moves_task_state() -> Result<()> {
+    // Create displays OUTSIDE timeout
+    let display = ProgressDisplay::new().await?;
+    let other = ProgressDisplay::new().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        for _ in 0..2 {
+            display.create_task(ThreadMode::Limited, 1).await?;
+        }
+        let dependency = other.create_task(ThreadMode::Limited, 1).await?;
+        let blocked = other.create_task(ThreadMode::Limited, 1).await?;
+        blocked.add_dependency(dependency.thread_id()).await?;
+        other.progress_manager().set_task_stall_timeout(blocked.thread_id(), Duration::from_secs(30));
+        let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+        let spawned = other.spawn(|_| async move {
+            let _ = release_rx.await;
+            Ok(())
+        }).await?;
+        
+        display.adopt(other.clone()).await?;
+        let manager = display.progress_manager();
+        let (dependency_id, blocked_id, spawned_id) = (2, 3, 4);
+        let adopted = manager.get_task(blocked_id).await.unwrap();
+        assert_eq!(adopted.get_dependencies().await?, vec![dependency_id]);
+        
+        // The stall timeout of the task moved along with it
+        let clock = crate::MockClock::new();
+        manager.set_clock(std::sync::Arc::new(clock.clone())).await;
+        clock.advance(Duration::from_secs(40));
+        assert_eq!(manager.stalled_tasks().await, vec![blocked_id]);
+        
+        // The spawned task finishing is recorded under its new ID
+        release_tx.send(()).unwrap();
+        spawned.completed().await?;
+        assert_eq!(manager.thread_manager().get_thread_state(spawned_id).await, Some(ThreadState::Completed));
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    other.stop().await?;
+    display.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_flush_renders_immediately() -> Result<()> {
+    // Create display OUTSIDE timeout
+    let buffer = SharedBuffer::default();
+    let display = ProgressDisplay::builder().writer(Box::new(buffer.clone())).build().await?;
+    let rendered = || String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        let mut task = display.create_task(ThreadMode::Limited, 1).await?;
+        task.capture_stdout("before prompt".to_string()).await?;
+        display.flush().await?;
+        assert!(rendered().contains("before prompt"), "{:?}", rendered());
+        
+        task.capture_stdout("after prompt".to_string()).await?;
+        display.flush().await?;
+        let output = rendered();
+        let before = output.find("before prompt").unwrap();
+        assert!(before < output.find("after prompt").unwrap(), "{:?}", output);
+        
+        // Served by the display thread once it owns the receiver
+        display.stop().await?;
+        display.restart().await?;
+        sleep(Duration::from_millis(50)).await;
+        assert!(display.message_rx.try_lock().is_err());
+        task.capture_stdout("after restart".to_string()).await?;
+        display.flush().await?;
+        assert!(rendered().contains("after restart"), "{:?}", rendered());
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_quiet_mode_only_shows_failures() -> Result<()> {
+    // Create display OUTSIDE timeout
+    let buffer = SharedBuffer::default();
+    let display = ProgressDisplay::builder().writer(Box::new(buffer.clone())).build().await?;
+    display.set_quiet(true);
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        let outcomes = [
+            ("fetched feed", Some(JobStatus::Completed)),
+            ("parse error", Some(JobStatus::Failed)),
+            ("timed out, retrying", Some(JobStatus::Retry)),
+            ("still working", None),
+        ];
+        for (line, status) in outcomes {
+            let mut task = display.create_task(ThreadMode::Limited, 1).await?;
+            task.capture_stdout(line.to_string()).await?;
+            if let Some(status) = status {
+                task.set_status(status).await?;
+            }
+        }
+        display.flush().await?;
+        // Failures already shown are not repeated
+        display.flush().await?;
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    let rendered = String::from_utf8(buffer.0.lock().unwrap().clone())?;
+    assert_eq!(rendered, "parse error [Failed]\ntimed out, retrying [Retry]\n4 tasks: 1 completed, 1 failed, 1 retrying\n");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_quiet_mode_shows_failed_spawned_task() -> Result<()> {
+    // Create display OUTSIDE timeout
+    let buffer = SharedBuffer::default();
+    let display = ProgressDisplay::builder().writer(Box::new(buffer.clone())).build().await?;
+    display.set_quiet(true);
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        let task = display.spawn(|mut task| async move {
+            task.capture_stdout("connection refused".to_string()).await?;
+            Err(anyhow::anyhow!("fetch failed"))
+        }).await?;
+        assert!(task.completed().await.is_err());
+        display.flush().await?;
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    let rendered = String::from_utf8(buffer.0.lock().unwrap().clone())?;
+    assert!(rendered.starts_with("connection refused [Failed]\n"), "{:?}", rendered);
+    Ok(())
+}
+
+#[cfg(feature = "tracing")]
+#[tokio::test]
+async fn test_render_error_is_a_tracing_event() -> Result<()> {
+    struct FailingWriter;
+
+    impl std::io::Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("disk full"))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    // The test runtime is single threaded, so the display thread sees this subscriber
+    let events = SharedBuffer::default();
+    let writer = events.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(move || writer.clone())
+        .with_ansi(false)
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    // Create display OUTSIDE timeout
+    let display = ProgressDisplay::builder().writer(Box::new(FailingWriter)).build().await?;
+    
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        // Rendering happens on the display thread once it owns the receiver
+        display.stop().await?;
+        display.restart().await?;
+        let mut task = display.create_task(ThreadMode::Limited, 1).await?;
+        task.capture_stdout("unwritable".to_string()).await?;
+        // Let the display thread pick up the line and fail to render it
+        sleep(Duration::from_millis(100)).await;
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    
+    // Clean up OUTSIDE timeout
+    let _ = display.stop().await;
+    let logged = String::from_utf8(events.0.lock().unwrap().clone())?;
+    assert!(logged.contains("ERROR"), "{:?}", logged);
+    assert!(logged.contains("Error displaying progress: disk full"), "{:?}", logged);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_spinner_animates_without_messages() -> Result<()> {
+    use crate::modes::Spinner;
+    
+    // Create display OUTSIDE timeout, restarted so the display thread is running
+    let buffer = SharedBuffer::default();
+    let display = ProgressDisplay::builder().writer(Box::new(buffer.clone())).build().await?;
+    display.stop().await?;
+    display.restart().await?;
+    let rendered = || String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    
+    // Run test logic INSIDE timeout
+    with_timeout(async {
+        let task = display.create_task(ThreadMode::Spinner, 1).await?;
+        task.set_spinner_frames(vec!["<".to_string(), ">".to_string()]).await?;
+        task.config().lock().await.as_type_mut::<Spinner>().unwrap().set_interval(Duration::from_millis(20));
+        task.set_title("fetching".to_string()).await?;
+        
+        // Both frames are drawn although no message was sent
+        sleep(Duration::from_millis(200)).await;
+        let output = rendered();
+        assert!(output.contains("< fetching"), "{:?}", output);
+        assert!(output.contains("> fetching"), "{:?}", output);
+        
+        // The title is the message beside the spinner
+        task.set_title("indexing".to_string()).await?;
+        sleep(Duration::from_millis(100)).await;
+        assert!(rendered().contains("indexing"), "{:?}", rendered());
+        
+        assert!(display.create_task(ThreadMode::Limited, 1).await?
+            .set_spinner_frames(vec!["*".to_string()]).await.is_err());
+        assert!(task.set_spinner_frames(Vec::new()).await.is_err());
+        Ok::<(), anyhow::Error>(())
+    }, 5).await??;
+    
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stop_tears_down_when_final_render_fails() -> Result<()> {
+    use crate::NotifyConfig;
+
+    struct FailingWriter;
+
+    impl std::io::Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("disk full"))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    // Create display OUTSIDE timeout
+    let display = ProgressDisplay::builder().writer(Box::new(FailingWriter)).build().await?;
+    display.notify_on_complete(NotifyConfig::default()).await;
+
+    // Run test logic INSIDE timeout
+    let result = with_timeout(async {
+        let mut task = display.create_task(ThreadMode::Limited, 1).await?;
+        task.capture_stdout("unwritable".to_string()).await?;
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+    result?;
+
+    // The render error is reported, but only after everything was stopped
+    let error = display.stop().await.unwrap_err();
+    assert!(error.to_string().contains("disk full"), "{:?}", error);
+    assert!(display.processing_task.lock().await.is_none());
+    assert!(display.notifier.lock().await.is_none());
+    Ok(())
+}
@@ -249,4 +249,103 @@ async fn test_error_recovery_strategies() {
         .into_context(ErrorContext::new("test", "test").with_severity(ErrorSeverity::Fatal));
     let result = recovery.handle_error(fatal_error).await;
     assert!(result.is_err());
-} 
\ No newline at end of file
+}
+
+#[test]
+fn test_error_codes() {
+    use crate::thread::ThreadState;
+    use std::time::Duration;
+
+    let cases: Vec<(ProgressError, &str)> = vec![
+        (ModeCreationError::Implementation("broken".to_string()).into(), "MODE_CREATION"),
+        (ProgressError::TaskOperation("failed".to_string()), "TASK_OP"),
+        (ProgressError::ThreadNotFound(7), "THREAD_NOT_FOUND"),
+        (ProgressError::DisplayOperation("failed".to_string()), "DISPLAY_OP"),
+        (ProgressError::External("failed".into()), "EXTERNAL"),
+        (io::Error::other("failed").into(), "IO"),
+        (ProgressError::InvalidStateTransition { from: ThreadState::Completed, to: ThreadState::Running }, "INVALID_STATE_TRANSITION"),
+        (ProgressError::DeadlockedTask(1, vec![2]), "DEADLOCKED_TASK"),
+        (ProgressError::FailureThresholdExceeded { failed: vec![1, 2], threshold: 1 }, "FAILURE_THRESHOLD_EXCEEDED"),
+    ];
+    for (error, code) in cases {
+        assert_eq!(error.error_code(), code);
+        assert!(error.to_string().starts_with(&format!("[{}] ", code)), "{}", error);
+    }
+
+    // Wrappers report the code of the wrapped error, written once
+    let wrapped = ProgressError::ThreadNotFound(7)
+        .into_context(ErrorContext::new("setting title", "ProgressManager"))
+        .into_retryable(3, Duration::from_millis(10));
+    assert_eq!(wrapped.error_code(), "THREAD_NOT_FOUND");
+    assert_eq!(wrapped.to_string().matches("[THREAD_NOT_FOUND]").count(), 1);
+}
+
+#[tokio::test]
+async fn test_missing_thread_error_code() -> Result<()> {
+    let display = crate::ProgressDisplay::new().await?;
+    let result = display.progress_manager().set_title(999, "Missing".to_string()).await;
+    let error = result.unwrap_err();
+    let error = error.downcast_ref::<ProgressError>().unwrap();
+    assert_eq!(error.error_code(), "THREAD_NOT_FOUND");
+    display.stop().await?;
+    Ok(())
+}
+
+#[test]
+fn test_error_context_chaining() {
+    let io_error = io::Error::new(io::ErrorKind::NotFound, "socket closed");
+    let inner = ProgressError::TaskOperation("write failed".to_string())
+        .into_context(ErrorContext::new("writing output", "TaskHandle").with_source(io_error));
+    let outer = ProgressError::DisplayOperation("render failed".to_string())
+        .into_context(ErrorContext::new("rendering", "ProgressDisplay").with_source(inner));
+
+    // The message is a single line, the layers below are reached through `source`
+    let message = outer.to_string();
+    assert_eq!(message, "[DISPLAY_OP] Display operation error: render failed (in ProgressDisplay during rendering)");
+    let causes: Vec<String> = std::iter::successors(outer.source(), |err| (*err).source())
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(causes, [
+        "[TASK_OP] Task operation error: write failed (in TaskHandle during writing output)",
+        "socket closed",
+    ]);
+    assert!(format!("{:?}", outer).contains("socket closed"));
+
+    // Backtraces are only captured when asked for
+    if std::env::var(crate::errors::BACKTRACE_ENV).is_err() {
+        assert!(ErrorContext::new("rendering", "ProgressDisplay").backtrace.is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_recovery_strategy_falls_back_to_limited() -> Result<()> {
+    use crate::error_recovery::RecoveryAction;
+    use crate::modes::factory::{LimitedCreator, ModeFactory};
+    use crate::modes::limited::Limited;
+    use crate::tests::common::with_timeout;
+    use crate::{ProgressDisplay, ThreadMode};
+
+    // Create display OUTSIDE timeout, with a factory that only knows Limited mode
+    let factory = ModeFactory::with_modes(|registry| registry.register(LimitedCreator));
+    let display = ProgressDisplay::new_with_factory(std::sync::Arc::new(factory)).await?;
+
+    // Run test logic INSIDE timeout
+    let _ = with_timeout(async {
+        let manager = display.progress_manager();
+
+        // Without a strategy the failure reaches the caller
+        let error = manager.create_task(ThreadMode::Window(3), 1).await.unwrap_err();
+        assert_eq!(error.downcast_ref::<ProgressError>().unwrap().error_code(), "MODE_CREATION");
+
+        manager.register_recovery_strategy("MODE_CREATION", |_: &ProgressError| {
+            RecoveryAction::FallbackMode(ThreadMode::Limited)
+        }).await;
+        let task = manager.create_task(ThreadMode::Window(3), 1).await?;
+        assert!(task.config().lock().await.as_type::<Limited>().is_some());
+        Ok::<(), anyhow::Error>(())
+    }, 5).await?;
+
+    // Clean up OUTSIDE timeout
+    display.stop().await?;
+    Ok(())
+}
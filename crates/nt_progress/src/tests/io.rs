@@ -1,4 +1,4 @@
-use crate::io::{ProgressWriter, OutputBuffer, TeeWriter};
+use crate::io::{ProgressWriter, OutputBuffer, OutputEncoding, TeeWriter};
 use std::io::Write;
 use anyhow::Result;
 
@@ -26,6 +26,30 @@ fn test_output_buffer() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_output_buffer_encoding() -> Result<()> {
+    // "café" in Latin-1
+    let latin1 = b"caf\xe9";
+    
+    // Lossy by default
+    let mut buffer = OutputBuffer::new(3);
+    assert_eq!(buffer.encoding(), OutputEncoding::Lossy);
+    buffer.write_all(latin1)?;
+    assert_eq!(buffer.get_lines()[0], "caf\u{FFFD}");
+    
+    buffer.set_encoding(OutputEncoding::Latin1);
+    buffer.write_all(latin1)?;
+    assert_eq!(buffer.get_lines()[1], "café");
+    
+    buffer.set_encoding(OutputEncoding::Utf8Strict);
+    let err = buffer.write(latin1).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    buffer.write_all("café".as_bytes())?;
+    assert_eq!(buffer.get_lines(), ["caf\u{FFFD}", "café", "café"]);
+    
+    Ok(())
+}
+
 #[test]
 fn test_tee_writer() -> Result<()> {
     let buffer1 = OutputBuffer::new(3);
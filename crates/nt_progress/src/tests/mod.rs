@@ -417,4 +417,31 @@ mod progress_bar {
         display.stop().await?;
         Ok(())
     }
+    
+    /// Test that an explicit template overrides the style and an empty one falls back to it
+    #[tokio::test]
+    async fn test_progress_bar_template_precedence() -> Result<()> {
+        let display = ProgressDisplay::new().await?;
+        
+        let _ = with_timeout(async {
+            let cases = [
+                (ProgressBarConfig::new().style(ProgressBarStyle::Block).template("Custom: {progress:percent}"), "Custom: 50%"),
+                (ProgressBarConfig::new().style(ProgressBarStyle::Block).width(4).template(""), "50% [██  ] 50/100"),
+            ];
+            
+            for (config, expected) in cases {
+                let task = display.create_task(ThreadMode::Window(3), 100).await?;
+                let thread_id = task.thread_id();
+                display.progress_manager().update_progress_bar_with_config(thread_id, 50, 100, &config).await?;
+                
+                let lines = task.get_display_lines().await?;
+                assert_eq!(lines.last().map(String::as_str), Some(expected));
+            }
+            
+            Ok::<(), anyhow::Error>(())
+        }, 5).await?;
+        
+        display.stop().await?;
+        Ok(())
+    }
 } 
\ No newline at end of file
@@ -70,7 +70,7 @@ impl TestBuilder {
 
     /// Create a ProgressDisplay with the configured mode
     pub async fn build_display(&self) -> ProgressDisplay {
-        match self.mode {
+        match self.mode.clone() {
             Some(mode) => ProgressDisplay::new_with_mode(mode).await.expect("Failed to create display"),
             None => ProgressDisplay::new().await.expect("Failed to create display"),
         }
@@ -79,7 +79,7 @@ impl TestBuilder {
     /// Run a simple message test with the specified message
     pub async fn test_message(&mut self, message: &str) -> Result<ProgressDisplay> {
         let display = self.build_display().await;
-        let mode = self.mode.unwrap_or(ThreadMode::Limited);
+        let mode = self.mode.clone().unwrap_or(ThreadMode::Limited);
         let mut task = display.spawn_with_mode(mode, || "test").await?;
         
         // Send the message using the task handle
@@ -92,7 +92,7 @@ impl TestBuilder {
     /// Run a test with multiple messages
     pub async fn test_messages(&mut self, messages: &[&str]) -> Result<ProgressDisplay> {
         let display = self.build_display().await;
-        let mode = self.mode.unwrap_or(ThreadMode::Limited);
+        let mode = self.mode.clone().unwrap_or(ThreadMode::Limited);
         let mut task = display.spawn_with_mode(mode, || "test").await?;
         
         for message in messages {
@@ -174,7 +174,7 @@ impl TestBuilder {
     /// Test error handling by sending an error message
     pub async fn test_error(&mut self, error_message: &str) -> Result<ProgressDisplay> {
         let display = self.build_display().await;
-        let mut task = display.spawn_with_mode(self.mode.unwrap(), || "test").await?;
+        let mut task = display.spawn_with_mode(self.mode.clone().unwrap(), || "test").await?;
         
         // Format and send as error message
         task.capture_stderr(error_message.to_string()).await?;
@@ -186,7 +186,7 @@ impl TestBuilder {
     /// Test edge cases specific to different modes using an existing display
     pub async fn test_edge_case_with_display(&mut self, display: &ProgressDisplay, case_type: EdgeCaseType) -> Result<()> {
         // Ensure we have a valid mode with appropriate window size for window modes
-        let mode = match self.mode {
+        let mode = match self.mode.clone() {
             Some(ThreadMode::Window(_)) => ThreadMode::Window(3),
             Some(ThreadMode::WindowWithTitle(_)) => ThreadMode::WindowWithTitle(3),
             Some(mode) => mode,
@@ -222,7 +222,7 @@ impl TestBuilder {
         }
         
         let display = self.build_display().await;
-        let mut task = display.spawn_with_mode(self.mode.unwrap(), || "test").await?;
+        let mut task = display.spawn_with_mode(self.mode.clone().unwrap(), || "test").await?;
         
         // Send messages to fill the window
         for line in lines {
@@ -242,7 +242,7 @@ impl TestBuilder {
         }
         
         let display = self.build_display().await;
-        let mut task = display.spawn_with_mode(self.mode.unwrap(), || "test").await?;
+        let mut task = display.spawn_with_mode(self.mode.clone().unwrap(), || "test").await?;
         let thread_id = task.thread_id();
         
         // Set the title
@@ -267,7 +267,7 @@ impl TestBuilder {
         }
         
         let display = self.build_display().await;
-        let mut task = display.spawn_with_mode(self.mode.unwrap(), || "test").await?;
+        let mut task = display.spawn_with_mode(self.mode.clone().unwrap(), || "test").await?;
         
         // Send all messages, but only the last one should be displayed
         for (i, message) in messages.iter().enumerate() {
@@ -314,7 +314,7 @@ impl TestBuilder {
     pub async fn test_progress_update(&mut self, total_jobs: usize, messages_per_job: usize) -> Result<ProgressDisplay> {
         let display = self.build_display().await;
         
-        let mut task = display.spawn_with_mode(self.mode.unwrap(), || "test").await?;
+        let mut task = display.spawn_with_mode(self.mode.clone().unwrap(), || "test").await?;
         let thread_id = task.thread_id();
         
         // Set total jobs in the display
@@ -362,7 +362,7 @@ impl TestBuilder {
 
     pub async fn test_window_overflow(&mut self) -> Result<ProgressDisplay> {
         let display = self.build_display().await;
-        let mut task = display.spawn_with_mode(self.mode.unwrap(), || "test").await?;
+        let mut task = display.spawn_with_mode(self.mode.clone().unwrap(), || "test").await?;
         
         // Send messages to fill the window
         for i in 0..10 {
@@ -375,7 +375,7 @@ impl TestBuilder {
 
     pub async fn test_thread_id(&mut self) -> Result<ProgressDisplay> {
         let display = self.build_display().await;
-        let mut task = display.spawn_with_mode(self.mode.unwrap(), || "test").await?;
+        let mut task = display.spawn_with_mode(self.mode.clone().unwrap(), || "test").await?;
         let thread_id = task.thread_id();
         
         // Send a message with the thread ID
@@ -387,7 +387,7 @@ impl TestBuilder {
 
     pub async fn test_mode_specific(&mut self) -> Result<ProgressDisplay> {
         let display = self.build_display().await;
-        let mut task = display.spawn_with_mode(self.mode.unwrap(), || "test").await?;
+        let mut task = display.spawn_with_mode(self.mode.clone().unwrap(), || "test").await?;
         
         // Send all messages, but only the last one should be displayed
         for i in 0..5 {
@@ -413,7 +413,7 @@ impl TestBuilder {
 
     pub async fn test_thread_management(&mut self) -> Result<ProgressDisplay> {
         let display = self.build_display().await;
-        let mut task = display.spawn_with_mode(self.mode.unwrap(), || "test").await?;
+        let mut task = display.spawn_with_mode(self.mode.clone().unwrap(), || "test").await?;
         let thread_id = task.thread_id();
         
         // Send messages to test thread management
@@ -534,7 +534,7 @@ impl TestBuilder {
         let display = self.build_display().await;
         
         // Create a task and send a message
-        let mode = self.mode.unwrap_or(ThreadMode::Limited);
+        let mode = self.mode.clone().unwrap_or(ThreadMode::Limited);
         let mut task = display.spawn_with_mode(mode, || "resize-test").await?;
         task.capture_stdout(message.to_string()).await?;
         
@@ -167,4 +167,24 @@ async fn test_window_edge_case_large() -> Result<()> {
     // Clean up OUTSIDE timeout
     display.stop().await?;
     Ok(())
-} 
\ No newline at end of file
+} 
+
+#[tokio::test]
+async fn test_thread_logger_splits_newlines() -> Result<()> {
+    let (message_tx, mut message_rx) = tokio::sync::mpsc::channel(4);
+    let config = crate::Config::new(ThreadMode::Window(5), 1)?;
+    let mut logger = crate::ThreadLogger::new(0, message_tx.clone(), config.clone());
+    logger.log("a\nb\nc".to_string()).await?;
+    assert_eq!(message_rx.recv().await.unwrap().lines, vec!["a", "b", "c"]);
+
+    // Limited mode shows the last line instead of a line with embedded newlines
+    let mut logger = crate::ThreadLogger::new(1, message_tx.clone(), crate::Config::new(ThreadMode::Limited, 1)?);
+    logger.log("a\nb\nc".to_string()).await?;
+    assert_eq!(message_rx.recv().await.unwrap().lines, vec!["c"]);
+
+    // The old behavior leaves the message to the mode
+    let mut logger = crate::ThreadLogger::new(2, message_tx, crate::Config::new(ThreadMode::Limited, 1)?.split_newlines(false));
+    logger.log("a\nb\nc".to_string()).await?;
+    assert_eq!(message_rx.recv().await.unwrap().lines, vec!["a\nb\nc"]);
+    Ok(())
+}
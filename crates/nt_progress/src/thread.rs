@@ -1,22 +1,45 @@
 use std::sync::{
-    atomic::{AtomicUsize, Ordering},
-    Arc,
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc, Weak,
 };
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Notify};
 use std::collections::HashMap;
+use std::future::Future;
 use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 use anyhow::Result;
 use crate::errors::{ErrorContext, ProgressError};
 use crate::config::{Config, ThreadMode};
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 use std::io::Write;
 use crate::io::{ProgressWriter, OutputBuffer};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use crate::core::job_traits::CancellableJob;
+use crate::core::base_config::{JobState, JobStatus};
+use serde::{Deserialize, Serialize};
+use crate::error_recovery::RetryConfig;
+use crate::core::job_traits::FailureHandlingJob;
+use crate::core::clock::Clock;
+use crate::ui::formatter::{format_hms, TemplateContext};
+
+/// How long a cancelled task may take to exit on its own before it is aborted
+const CANCEL_GRACE_PERIOD: Duration = Duration::from_millis(100);
+
+/// Wait for a cancelled task to exit, aborting it once the grace period runs out
+async fn stop_join_handle(mut handle: JoinHandle<Result<()>>) {
+    if tokio::time::timeout(CANCEL_GRACE_PERIOD, &mut handle).await.is_err() {
+        handle.abort();
+    }
+}
 
 /// Represents the state of a thread in the system
+///
+/// Threads move through `Created -> Running <-> Paused` and end in one of the
+/// terminal states `Completed`, `Failed` or `Cancelled`.
 #[derive(Debug, Clone, PartialEq)]
 pub enum ThreadState {
+    /// Thread has been registered but not started
+    Created,
     /// Thread is running normally
     Running,
     /// Thread is paused
@@ -25,6 +48,72 @@ pub enum ThreadState {
     Completed,
     /// Thread has failed with an error
     Failed(String),
+    /// Thread was cancelled before finishing
+    Cancelled,
+}
+
+static TERMINAL_STATES: [ThreadState; 3] = [
+    ThreadState::Completed,
+    ThreadState::Failed(String::new()),
+    ThreadState::Cancelled,
+];
+
+impl ThreadState {
+    /// Check whether moving from this state to `next` is a valid transition.
+    ///
+    /// # Parameters
+    /// * `next` - The state to transition to
+    ///
+    /// # Returns
+    /// true if the transition is allowed by the thread state machine
+    pub fn can_transition_to(&self, next: &ThreadState) -> bool {
+        use ThreadState::*;
+        matches!(
+            (self, next),
+            (Created, Running | Failed(_) | Cancelled)
+                | (Running, Paused | Completed | Failed(_) | Cancelled)
+                | (Paused, Running | Failed(_) | Cancelled)
+        )
+    }
+
+    /// Get the states a thread cannot leave once it has entered them.
+    ///
+    /// `Failed` is listed with an empty message; use [`is_terminal`](Self::is_terminal)
+    /// to check a state regardless of its failure message.
+    pub fn terminal_states() -> &'static [ThreadState] {
+        &TERMINAL_STATES
+    }
+
+    /// Check whether this is a terminal state.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, ThreadState::Completed | ThreadState::Failed(_) | ThreadState::Cancelled)
+    }
+
+    /// Get the job status a task takes on when its thread enters this state.
+    ///
+    /// # Returns
+    /// The matching job status, or None if entering this state leaves it unchanged
+    pub fn job_status(&self) -> Option<JobStatus> {
+        match self {
+            ThreadState::Running => Some(JobStatus::Running),
+            ThreadState::Completed => Some(JobStatus::Completed),
+            ThreadState::Failed(_) => Some(JobStatus::Failed),
+            ThreadState::Created | ThreadState::Paused | ThreadState::Cancelled => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ThreadState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThreadState::Created => write!(f, "Created"),
+            ThreadState::Running => write!(f, "Running"),
+            ThreadState::Paused => write!(f, "Paused"),
+            ThreadState::Completed => write!(f, "Completed"),
+            ThreadState::Failed(msg) => write!(f, "Failed({})", msg),
+            ThreadState::Cancelled => write!(f, "Cancelled"),
+        }
+    }
 }
 
 /// Represents a thread's context and state
@@ -85,8 +174,16 @@ impl ThreadContext {
         self.last_updated
     }
 
+    /// Replace the task handle, keeping the state and join handle
+    pub(crate) fn replace_handle(&mut self, handle: TaskHandle) {
+        self.handle = handle;
+    }
+
     /// Update the thread's state
     pub fn update_state(&mut self, state: ThreadState) {
+        if state.is_terminal() {
+            self.handle.finish(state.clone());
+        }
         self.state = state;
         self.last_updated = std::time::Instant::now();
     }
@@ -145,6 +242,10 @@ pub struct ThreadManager {
     threads: Arc<Mutex<HashMap<usize, ThreadContext>>>,
     /// Maximum number of concurrent threads
     max_threads: AtomicUsize,
+    /// Announces threads reaching a terminal state
+    finished_tx: broadcast::Sender<(usize, ThreadState)>,
+    /// Manager and ID of the threads taken over by another manager, keyed by their ID here
+    moved: std::sync::Mutex<HashMap<usize, (Weak<ThreadManager>, usize)>>,
 }
 
 impl ThreadManager {
@@ -156,6 +257,8 @@ impl ThreadManager {
             next_id: AtomicUsize::new(0),
             threads: Arc::new(Mutex::new(HashMap::new())),
             max_threads: AtomicUsize::new(usize::MAX),
+            finished_tx: broadcast::channel(64).0,
+            moved: std::sync::Mutex::new(HashMap::new()),
         }
     }
 
@@ -165,6 +268,8 @@ impl ThreadManager {
             next_id: AtomicUsize::new(0),
             threads: Arc::new(Mutex::new(HashMap::new())),
             max_threads: AtomicUsize::new(max_threads),
+            finished_tx: broadcast::channel(64).0,
+            moved: std::sync::Mutex::new(HashMap::new()),
         }
     }
 
@@ -197,17 +302,39 @@ impl ThreadManager {
         threads.insert(thread_id, ThreadContext::new(handle, join_handle));
     }
 
+    /// Register a thread with an existing context, e.g. one taken from another manager.
+    ///
+    /// Unlike [`register_thread`](Self::register_thread), this does not wait for
+    /// the thread limit.
+    pub async fn insert_thread(&self, thread_id: usize, ctx: ThreadContext) {
+        self.threads.lock().await.insert(thread_id, ctx);
+    }
+
+    /// Record that a thread taken with [`take_threads`](Self::take_threads) is
+    /// now managed by `manager` under `thread_id`.
+    ///
+    /// State updates for the old ID, e.g. from the task's spawn wrapper, are
+    /// forwarded there.
+    pub fn mark_moved(&self, old_id: usize, manager: &Arc<ThreadManager>, thread_id: usize) {
+        self.moved.lock().unwrap().insert(old_id, (Arc::downgrade(manager), thread_id));
+    }
+
+    /// Remove every thread from the pool, in ascending ID order, without
+    /// cancelling or joining them.
+    pub async fn take_threads(&self) -> Vec<(usize, ThreadContext)> {
+        let mut threads: Vec<_> = self.threads.lock().await.drain().collect();
+        threads.sort_unstable_by_key(|(thread_id, _)| *thread_id);
+        threads
+    }
+
     /// Clean up completed threads from the pool.
     pub async fn cleanup_completed(&self) -> Result<()> {
         let mut threads = self.threads.lock().await;
         let mut to_remove = Vec::new();
         
         for (thread_id, ctx) in threads.iter() {
-            match ctx.state() {
-                ThreadState::Completed | ThreadState::Failed(_) => {
-                    to_remove.push(*thread_id);
-                }
-                _ => {}
+            if ctx.state().is_terminal() {
+                to_remove.push(*thread_id);
             }
         }
         
@@ -230,6 +357,16 @@ impl ThreadManager {
         threads.get(&thread_id).map(|ctx| ctx.handle().clone())
     }
 
+    /// Get the task handles for several thread IDs, taking the lock once.
+    ///
+    /// The result lines up with `thread_ids`, with None for unknown IDs.
+    pub async fn get_tasks(&self, thread_ids: &[usize]) -> Vec<Option<TaskHandle>> {
+        let threads = self.threads.lock().await;
+        thread_ids.iter()
+            .map(|thread_id| threads.get(thread_id).map(|ctx| ctx.handle().clone()))
+            .collect()
+    }
+
     /// Get the state of a specific thread.
     pub async fn get_thread_state(&self, thread_id: usize) -> Option<ThreadState> {
         let threads = self.threads.lock().await;
@@ -237,18 +374,42 @@ impl ThreadManager {
     }
 
     /// Update the state of a specific thread.
+    ///
+    /// The task's job status is kept in sync, see [`ThreadState::job_status`].
+    /// Updates for a thread taken over by another manager are forwarded to it,
+    /// see [`mark_moved`](Self::mark_moved).
+    /// Returns [`ProgressError::InvalidStateTransition`] if the thread's current
+    /// state cannot move to `state`.
     pub async fn update_thread_state(&self, thread_id: usize, state: ThreadState) -> Result<()> {
+        let moved = self.moved.lock().unwrap().get(&thread_id)
+            .and_then(|(manager, new_id)| Some((manager.upgrade()?, *new_id)));
+        if let Some((manager, new_id)) = moved {
+            return Box::pin(manager.update_thread_state(new_id, state)).await;
+        }
         let mut threads = self.threads.lock().await;
         if let Some(ctx) = threads.get_mut(&thread_id) {
-            ctx.update_state(state);
+            if !ctx.state().can_transition_to(&state) {
+                let error_ctx = ErrorContext::new("updating thread state", "ThreadManager")
+                    .with_thread_id(thread_id)
+                    .with_details(format!("Cannot transition from {} to {}", ctx.state(), state));
+                return Err(ProgressError::InvalidStateTransition {
+                    from: ctx.state().clone(),
+                    to: state,
+                }.into_context(error_ctx).into());
+            }
+            if let Some(status) = state.job_status() {
+                ctx.handle().config().lock().await.set_job_status(status);
+            }
+            ctx.update_state(state.clone());
+            if state.is_terminal() {
+                let _ = self.finished_tx.send((thread_id, state));
+            }
             Ok(())
         } else {
             let ctx = ErrorContext::new("updating thread state", "ThreadManager")
                 .with_thread_id(thread_id)
                 .with_details("Thread not found");
-            Err(ProgressError::TaskOperation(
-                format!("Failed to update state for thread {}: Thread not found", thread_id)
-            ).into_context(ctx).into())
+            Err(ProgressError::ThreadNotFound(thread_id).into_context(ctx).into())
         }
     }
 
@@ -295,12 +456,15 @@ impl ThreadManager {
 
     /// Cancel all threads and clean up resources.
     pub async fn cancel_all(&self) -> Result<()> {
-        // First collect all handles we need to abort
+        // First signal cancellation and collect all handles we need to stop
         let mut handles_to_abort = Vec::new();
         {
             let mut threads = self.threads.lock().await;
             for ctx in threads.values_mut() {
-                ctx.update_state(ThreadState::Failed("Cancelled".to_string()));
+                if !ctx.state().is_terminal() {
+                    ctx.update_state(ThreadState::Cancelled);
+                }
+                ctx.handle().cancellation_token().cancel();
                 if let Some(handle) = ctx.take_join_handle() {
                     handles_to_abort.push(handle);
                 }
@@ -310,27 +474,89 @@ impl ThreadManager {
             threads.clear();
         }
         
-        // Now abort all the handles without holding the lock
-        for handle in handles_to_abort {
-            handle.abort();
-        }
+        // Now stop all the handles without holding the lock
+        futures::future::join_all(handles_to_abort.into_iter().map(stop_join_handle)).await;
         
         Ok(())
     }
 
-    /// Get all active thread IDs.
+    /// Cancel a single thread, marking it as cancelled and aborting its execution.
+    ///
+    /// The thread stays registered in the `Cancelled` state so callers
+    /// can still query it after cancellation.
+    ///
+    /// # Parameters
+    /// * `thread_id` - The ID of the thread to cancel
+    /// * `reason` - Optional reason recorded on the task's config
+    ///
+    /// # Returns
+    /// Result containing () on success, or an error if the thread does not exist
+    /// or has already finished
+    pub async fn cancel_thread(&self, thread_id: usize, reason: Option<String>) -> Result<()> {
+        let join_handle = {
+            let mut threads = self.threads.lock().await;
+            let Some(ctx) = threads.get_mut(&thread_id) else {
+                let ctx = ErrorContext::new("cancelling thread", "ThreadManager")
+                    .with_thread_id(thread_id)
+                    .with_details("Thread not found");
+                return Err(ProgressError::ThreadNotFound(thread_id).into_context(ctx).into());
+            };
+
+            if ctx.state().is_terminal() {
+                let ctx = ErrorContext::new("cancelling thread", "ThreadManager")
+                    .with_thread_id(thread_id)
+                    .with_details("Thread has already finished");
+                return Err(ProgressError::TaskOperation(
+                    format!("Failed to cancel thread {}: Thread has already finished", thread_id)
+                ).into_context(ctx).into());
+            }
+
+            {
+                let mut config = ctx.handle().config().lock().await;
+                CancellableJob::set_cancelled(&mut *config, reason);
+            }
+            ctx.update_state(ThreadState::Cancelled);
+            ctx.handle().cancellation_token().cancel();
+            ctx.take_join_handle()
+        };
+
+        // Stop without holding the threads lock
+        if let Some(handle) = join_handle {
+            stop_join_handle(handle).await;
+        }
+
+        Ok(())
+    }
+
+    /// Receive the ID and state of every thread that reaches a terminal state
+    /// through [`update_thread_state`](Self::update_thread_state) from now on.
+    pub fn subscribe_finished(&self) -> broadcast::Receiver<(usize, ThreadState)> {
+        self.finished_tx.subscribe()
+    }
+
+    /// Check whether there are threads and all of them are in a terminal state.
+    pub async fn all_finished(&self) -> bool {
+        let threads = self.threads.lock().await;
+        !threads.is_empty() && threads.values().all(|ctx| ctx.state().is_terminal())
+    }
+
+    /// Get all active thread IDs, in ascending order.
     pub async fn get_active_threads(&self) -> Vec<usize> {
         let threads = self.threads.lock().await;
-        threads.keys().cloned().collect()
+        let mut thread_ids: Vec<usize> = threads.keys().cloned().collect();
+        thread_ids.sort_unstable();
+        thread_ids
     }
 
-    /// Get all threads that are in a specific state.
+    /// Get all threads that are in a specific state, in ascending ID order.
     pub async fn get_threads_by_state(&self, state: ThreadState) -> Vec<usize> {
         let threads = self.threads.lock().await;
-        threads.iter()
+        let mut thread_ids: Vec<usize> = threads.iter()
             .filter(|(_, ctx)| ctx.state() == &state)
             .map(|(id, _)| *id)
-            .collect()
+            .collect();
+        thread_ids.sort_unstable();
+        thread_ids
     }
 
     /// Get the number of threads in a specific state.
@@ -348,6 +574,27 @@ impl Default for ThreadManager {
     }
 }
 
+/// Coalescing state for progress messages, see [`TaskHandle::set_update_throttle`].
+#[derive(Debug, Default)]
+struct UpdateThrottle {
+    /// None until a throttle is set, progress updates are not sent before that
+    interval: Option<Duration>,
+    last_sent: Option<Instant>,
+    /// Whether a delayed message carrying the latest progress is already scheduled
+    flush_scheduled: bool,
+}
+
+/// The saved state of a single task, see [`TaskHandle::persist`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedTask {
+    /// Mode the task was displayed in
+    pub mode: ThreadMode,
+    /// Title of the task, for modes that show one
+    pub title: Option<String>,
+    /// Progress, status and failure information of the job
+    pub state: JobState,
+}
+
 /// A handle to a task that can be used to interact with it.
 #[derive(Clone)]
 pub struct TaskHandle {
@@ -359,6 +606,19 @@ pub struct TaskHandle {
     message_tx: mpsc::Sender<crate::ThreadMessage>,
     writer: Arc<Mutex<Box<dyn ProgressWriter + Send + 'static>>>,
     join_handle: Arc<Mutex<Option<JoinHandle<Result<()>>>>>,
+    /// Triggered when the task is cancelled
+    cancellation_token: CancellationToken,
+    /// The terminal state the task ended in, once it has finished
+    final_state: Arc<std::sync::Mutex<Option<ThreadState>>>,
+    /// Notified when the task reaches a terminal state
+    finished: Arc<Notify>,
+    /// Consulted by run_with_retry
+    retry_config: Arc<std::sync::Mutex<RetryConfig>>,
+    /// Limits how often progress updates are sent to the display
+    update_throttle: Arc<std::sync::Mutex<UpdateThrottle>>,
+    /// Whether the task is in Spinner mode, so the display ticks it without
+    /// locking the config of every other task
+    spinner: Arc<AtomicBool>,
 }
 
 impl std::fmt::Debug for TaskHandle {
@@ -369,19 +629,243 @@ impl std::fmt::Debug for TaskHandle {
             .field("message_tx", &"mpsc::Sender<ThreadMessage>")
             .field("writer", &"Arc<Mutex<Box<dyn ProgressWriter + Send>>>")
             .field("join_handle", &"Arc<Mutex<Option<JoinHandle<Result<()>>>>>")
+            .field("cancellation_token", &self.cancellation_token)
             .finish()
     }
 }
 
+// "elapsed: 2m 5s", shown by TaskHandle while no estimate can be made
+fn elapsed_label(elapsed: Duration) -> String {
+    format!("elapsed: {}", format_hms(elapsed))
+}
+
 impl TaskHandle {
     /// Create a new TaskHandle with the specified thread ID and configuration.
     pub fn new(thread_id: usize, config: Config, message_tx: mpsc::Sender<crate::ThreadMessage>) -> Self {
+        let spinner = Arc::new(AtomicBool::new(config.as_type::<crate::modes::Spinner>().is_some()));
         Self {
             thread_id,
             thread_config: Arc::new(Mutex::new(config)),
             message_tx,
             writer: Arc::new(Mutex::new(Box::new(OutputBuffer::new(100)))),
             join_handle: Arc::new(Mutex::new(None)),
+            cancellation_token: CancellationToken::new(),
+            final_state: Arc::new(std::sync::Mutex::new(None)),
+            finished: Arc::new(Notify::new()),
+            retry_config: Arc::new(std::sync::Mutex::new(RetryConfig::default())),
+            update_throttle: Arc::new(std::sync::Mutex::new(UpdateThrottle::default())),
+            spinner,
+        }
+    }
+
+    /// Get a handle to the same task under another thread ID, sending its
+    /// messages on `message_tx`.
+    ///
+    /// Config, output and cancellation are shared with this handle.
+    pub fn rebind(&self, thread_id: usize, message_tx: mpsc::Sender<crate::ThreadMessage>) -> Self {
+        Self {
+            thread_id,
+            message_tx,
+            ..self.clone()
+        }
+    }
+
+    /// Time the task with `clock`, starting over from its current time.
+    pub async fn set_clock(&self, clock: Arc<dyn Clock>) {
+        self.thread_config.lock().await.set_clock(clock);
+    }
+
+    /// Get how long the task has gone without reporting progress or output.
+    ///
+    /// # Returns
+    /// The duration, or None if the task's mode does not track one
+    pub async fn time_since_update(&self) -> Option<Duration> {
+        self.thread_config.lock().await.time_since_update()
+    }
+
+    /// Send progress updates to the display, coalescing those made within
+    /// `interval` into a single message.
+    ///
+    /// Progress updates are not sent until a throttle is set. The first update
+    /// after a quiet period is sent right away; later ones are held back and a
+    /// single message is sent once the interval has passed. A zero interval
+    /// sends every update.
+    pub fn set_update_throttle(&self, interval: Duration) {
+        self.update_throttle.lock().unwrap().interval = Some(interval);
+    }
+
+    /// Get the interval progress updates are coalesced over.
+    ///
+    /// # Returns
+    /// The interval, or None if progress updates are not sent
+    pub fn update_throttle(&self) -> Option<Duration> {
+        self.update_throttle.lock().unwrap().interval
+    }
+
+    /// Tell the display that the progress changed, subject to the update throttle.
+    ///
+    /// Progress messages carry no output lines, so they are dropped rather than
+    /// waited on when the channel is full.
+    fn notify_progress(&self, config: &Config) {
+        config.touch();
+        let delay = {
+            let mut throttle = self.update_throttle.lock().unwrap();
+            let Some(interval) = throttle.interval else {
+                return;
+            };
+            let now = Instant::now();
+            let remaining = throttle.last_sent
+                .map(|sent| interval.saturating_sub(now.duration_since(sent)))
+                .unwrap_or_default();
+            if remaining.is_zero() {
+                throttle.last_sent = Some(now);
+                None
+            } else if throttle.flush_scheduled {
+                return;
+            } else {
+                throttle.flush_scheduled = true;
+                Some(remaining)
+            }
+        };
+
+        let Some(delay) = delay else {
+            if let Err(e) = self.message_tx.try_send(crate::ThreadMessage {
+                thread_id: self.thread_id,
+                lines: Vec::new(),
+                config: None,
+            }) {
+                diag_debug!("Dropped progress update for thread {}: {}", self.thread_id, e);
+            }
+            return;
+        };
+
+        let thread_id = self.thread_id;
+        let message_tx = self.message_tx.clone();
+        let update_throttle = self.update_throttle.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            {
+                let mut throttle = update_throttle.lock().unwrap();
+                throttle.last_sent = Some(Instant::now());
+                throttle.flush_scheduled = false;
+            }
+            if let Err(e) = message_tx.try_send(crate::ThreadMessage { thread_id, lines: Vec::new(), config: None }) {
+                diag_debug!("Dropped progress update for thread {}: {}", thread_id, e);
+            }
+        });
+    }
+
+    /// Set the retry configuration used by [`run_with_retry`](Self::run_with_retry).
+    pub fn set_retry_config(&self, config: RetryConfig) {
+        *self.retry_config.lock().unwrap() = config;
+    }
+
+    /// Get the retry configuration of this task.
+    pub fn retry_config(&self) -> RetryConfig {
+        self.retry_config.lock().unwrap().clone()
+    }
+
+    /// Run `operation`, retrying it according to the task's retry configuration.
+    ///
+    /// Every attempt is recorded through [`FailureHandlingJob`]: failures are
+    /// marked with their error, the job status is set to Retry while waiting for
+    /// the next attempt, and it stays Failed once the retries are exhausted.
+    /// The retry count starts over on every call and is reset on success.
+    ///
+    /// # Returns
+    /// The result of the first successful attempt, or the error of the last one
+    pub async fn run_with_retry<F, Fut, T>(&self, mut operation: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let retry_config = self.retry_config();
+        {
+            let mut config = self.thread_config.lock().await;
+            config.set_max_retries(retry_config.max_retries as usize);
+            config.reset_retries();
+        }
+        let mut retries = 0;
+        loop {
+            let result = operation().await;
+            let mut config = self.thread_config.lock().await;
+            let tracks_status = config.tracks_status();
+            match result {
+                Ok(value) => {
+                    if tracks_status {
+                        config.mark_succeeded();
+                    }
+                    return Ok(value);
+                }
+                Err(error) => {
+                    if tracks_status {
+                        config.mark_failed(&error.to_string());
+                    }
+                    if retries >= retry_config.max_retries {
+                        return Err(error);
+                    }
+                    retries += 1;
+                    config.mark_retry();
+                }
+            }
+            drop(config);
+            tokio::time::sleep(retry_config.delay(retries)).await;
+        }
+    }
+
+    /// Mark the task for retry, setting its status to Retry.
+    ///
+    /// While retrying, the display shows the retry count next to the task.
+    ///
+    /// # Returns
+    /// The new retry count
+    pub async fn mark_retry(&self) -> usize {
+        self.thread_config.lock().await.mark_retry()
+    }
+
+    /// Record that the task reached a terminal state and wake any waiters.
+    ///
+    /// Only the first terminal state is kept.
+    pub(crate) fn finish(&self, state: ThreadState) {
+        let mut final_state = self.final_state.lock().unwrap();
+        if final_state.is_none() {
+            *final_state = Some(state);
+            self.finished.notify_waiters();
+        }
+    }
+
+    /// Wait until the task is Completed, Failed or Cancelled.
+    ///
+    /// Resolves immediately if the task has already finished.
+    ///
+    /// # Returns
+    /// Ok(()) if the task completed, or an error if it failed or was cancelled
+    pub fn completed(&self) -> impl Future<Output = Result<()>> + Send + 'static {
+        let thread_id = self.thread_id;
+        let final_state = self.final_state.clone();
+        let finished = self.finished.clone();
+        async move {
+            loop {
+                // Register for notification before checking to avoid missing a wakeup
+                let notified = finished.notified();
+                tokio::pin!(notified);
+                notified.as_mut().enable();
+                
+                let state = final_state.lock().unwrap().clone();
+                let details = match state {
+                    Some(ThreadState::Completed) => return Ok(()),
+                    Some(ThreadState::Failed(message)) => format!("Task failed: {}", message),
+                    Some(_) => "Task was cancelled".to_string(),
+                    None => {
+                        notified.await;
+                        continue;
+                    }
+                };
+                let ctx = ErrorContext::new("awaiting task completion", "TaskHandle")
+                    .with_thread_id(thread_id)
+                    .with_details(details.clone());
+                return Err(ProgressError::TaskOperation(details).into_context(ctx).into());
+            }
         }
     }
 
@@ -390,6 +874,14 @@ impl TaskHandle {
         self.thread_id
     }
 
+    /// Get the token that is cancelled when this task is cancelled.
+    ///
+    /// Spawned work should await `cancelled()` or poll `is_cancelled()` and
+    /// return early; tasks still running after a short grace period are aborted.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation_token.clone()
+    }
+
     /// Get a reference to the thread configuration.
     pub fn config(&self) -> &Arc<Mutex<Config>> {
         &self.thread_config
@@ -398,13 +890,20 @@ impl TaskHandle {
     /// Set the mode for this task.
     pub async fn set_mode(&mut self, mode: ThreadMode) -> Result<()> {
         // Create a new config with the specified mode
+        let spinner = mode == ThreadMode::Spinner;
         let config = Config::new(mode, 1)?;
         
         // Now that we have successfully created the config, update our thread_config
+        self.spinner.store(spinner, Ordering::SeqCst);
         *self.thread_config.lock().await = config;
         Ok(())
     }
 
+    /// Check whether the task is in Spinner mode.
+    pub fn is_spinner(&self) -> bool {
+        self.spinner.load(Ordering::SeqCst)
+    }
+
     /// Write a line to the task's output
     pub async fn write_line(&mut self, line: &str) -> Result<()> {
         let mut writer = self.writer.lock().await;
@@ -422,12 +921,16 @@ impl TaskHandle {
     }
 
     /// Capture stdout output for this task.
+    ///
+    /// In Capturing mode the line is also appended to the capture buffer,
+    /// which may fail if the buffer is full and the overflow strategy is
+    /// [`OverflowStrategy::Error`](crate::modes::OverflowStrategy::Error).
     pub async fn capture_stdout(&mut self, line: String) -> Result<()> {
-        let config = self.thread_config.lock().await.clone();
+        let config = self.buffer_captured_line(&line).await?;
         self.message_tx.send(crate::ThreadMessage {
             thread_id: self.thread_id,
             lines: vec![line.clone()],
-            config,
+            config: Some(config),
         }).await.map_err(|e| anyhow::anyhow!("Failed to send message: {}", e))?;
         
         // Also write to the task's output
@@ -437,12 +940,14 @@ impl TaskHandle {
     }
 
     /// Capture stderr output for this task.
+    ///
+    /// Subject to the same buffer limits as [`capture_stdout`](Self::capture_stdout).
     pub async fn capture_stderr(&mut self, line: String) -> Result<()> {
-        let config = self.thread_config.lock().await.clone();
+        let config = self.buffer_captured_line(&line).await?;
         self.message_tx.send(crate::ThreadMessage {
             thread_id: self.thread_id,
             lines: vec![line.clone()],
-            config,
+            config: Some(config),
         }).await.map_err(|e| anyhow::anyhow!("Failed to send message: {}", e))?;
         
         // Also write to the task's output
@@ -451,6 +956,126 @@ impl TaskHandle {
         Ok(())
     }
 
+    /// Append a line to the capture buffer if the task is in Capturing mode and
+    /// feed it to the task's mode, returning a snapshot of the config to send
+    /// with the message.
+    async fn buffer_captured_line(&self, line: &str) -> Result<Config> {
+        let mut config = self.thread_config.lock().await;
+        config.touch();
+        if let Some(capturing) = config.as_type_mut::<crate::modes::Capturing>() {
+            if let Err(e) = capturing.capture(line.to_string()) {
+                let ctx = ErrorContext::new("capturing output", "TaskHandle")
+                    .with_thread_id(self.thread_id)
+                    .with_details("Capture buffer overflow");
+                return Err(anyhow::anyhow!(e.into_context(ctx)));
+            }
+        }
+        config.handle_message(line.to_string());
+        Ok(config.clone())
+    }
+
+    /// Get the lines this task's mode currently displays.
+    ///
+    /// Useful for custom rendering or for asserting on a task's output.
+    ///
+    /// # Returns
+    /// The lines as laid out by the task's mode, e.g. the title followed by
+    /// the most recent lines in WindowWithTitle mode
+    pub async fn get_display_lines(&self) -> Result<Vec<String>> {
+        let config = self.thread_config.lock().await;
+        Ok(config.get_lines())
+    }
+
+    /// Get the number of times this task's capture buffer has overflowed.
+    ///
+    /// # Returns
+    /// The overflow count, or 0 if the task is not in Capturing mode
+    pub async fn get_buffer_overflow_count(&self) -> usize {
+        let config = self.thread_config.lock().await;
+        config.as_type::<crate::modes::Capturing>()
+            .map(|capturing| capturing.overflow_count())
+            .unwrap_or(0)
+    }
+
+    /// Set the buffer limits used when this task is in Capturing mode.
+    ///
+    /// # Parameters
+    /// * `capturing_config` - The buffer limits to apply
+    ///
+    /// # Returns
+    /// Result containing () on success, or an error if the task is not in Capturing mode
+    pub async fn set_capturing_config(&mut self, capturing_config: crate::modes::CapturingConfig) -> Result<()> {
+        let mut config = self.thread_config.lock().await;
+        if let Some(capturing) = config.as_type_mut::<crate::modes::Capturing>() {
+            capturing.set_capturing_config(capturing_config);
+            Ok(())
+        } else {
+            let ctx = ErrorContext::new("setting capturing config", "TaskHandle")
+                .with_thread_id(self.thread_id)
+                .with_details("Current mode does not capture output");
+
+            Err(anyhow::anyhow!(ProgressError::TaskOperation(
+                "Task is not in Capturing mode".to_string()
+            ).into_context(ctx)))
+        }
+    }
+
+    /// Collapse this task to its title line, or expand it again.
+    ///
+    /// Output captured while collapsed is kept and shown again on expanding.
+    ///
+    /// # Returns
+    /// Result containing () on success, or an error if the task is not in WindowWithTitle mode
+    pub async fn set_collapsed(&self, collapsed: bool) -> Result<()> {
+        let mut config = self.thread_config.lock().await;
+        if let Some(window) = config.as_type_mut::<crate::modes::WindowWithTitle>() {
+            window.set_collapsed(collapsed);
+            Ok(())
+        } else {
+            let ctx = ErrorContext::new("collapsing task", "TaskHandle")
+                .with_thread_id(self.thread_id)
+                .with_details("Current mode has no title line to collapse to");
+
+            Err(anyhow::anyhow!(ProgressError::TaskOperation(
+                "Task is not in WindowWithTitle mode".to_string()
+            ).into_context(ctx)))
+        }
+    }
+
+    /// Check if this task is collapsed to its title line.
+    pub async fn is_collapsed(&self) -> bool {
+        let config = self.thread_config.lock().await;
+        config.as_type::<crate::modes::WindowWithTitle>()
+            .is_some_and(|window| window.is_collapsed())
+    }
+
+    /// Replace the glyphs this task's spinner cycles through.
+    ///
+    /// # Returns
+    /// Result containing () on success, or an error if the task is not in
+    /// Spinner mode or `frames` is empty
+    pub async fn set_spinner_frames(&self, frames: Vec<String>) -> Result<()> {
+        let mut config = self.thread_config.lock().await;
+        let Some(spinner) = config.as_type_mut::<crate::modes::Spinner>() else {
+            let ctx = ErrorContext::new("setting spinner frames", "TaskHandle")
+                .with_thread_id(self.thread_id)
+                .with_details("Current mode has no spinner");
+
+            return Err(anyhow::anyhow!(ProgressError::TaskOperation(
+                "Task is not in Spinner mode".to_string()
+            ).into_context(ctx)));
+        };
+        spinner.set_frames(frames).map_err(|e| {
+            let ctx = ErrorContext::new("setting spinner frames", "TaskHandle")
+                .with_thread_id(self.thread_id)
+                .with_details(e.to_string());
+
+            anyhow::anyhow!(ProgressError::TaskOperation(
+                format!("Failed to set spinner frames: {}", e)
+            ).into_context(ctx))
+        })
+    }
+
     /// Set the title for this task.
     pub async fn set_title(&self, title: String) -> Result<()> {
         let mut config = self.thread_config.lock().await;
@@ -508,6 +1133,11 @@ impl TaskHandle {
         Ok(())
     }
     
+    /// Prefix each line captured from now on with its index within this task.
+    pub async fn set_line_numbers(&self, enabled: bool) {
+        self.thread_config.lock().await.set_line_numbers(enabled);
+    }
+
     /// Set the total number of jobs for this task.
     pub async fn set_total_jobs(&self, total: usize) -> Result<()> {
         let mut config = self.thread_config.lock().await;
@@ -515,6 +1145,22 @@ impl TaskHandle {
         Ok(())
     }
 
+    /// Set a new total and start progress over from zero.
+    ///
+    /// Unlike [`set_total_jobs`](Self::set_total_jobs), this also resets the
+    /// completed jobs and the start time used for time estimates, for when the
+    /// real total only becomes known after the task has started.
+    ///
+    /// # Returns
+    /// The progress percentage after the reset, which is 0.0.
+    pub async fn set_total_and_reset(&self, total: usize) -> Result<f64> {
+        let mut config = self.thread_config.lock().await;
+        config.set_total_jobs(total);
+        let progress = config.set_progress(0);
+        self.notify_progress(&config);
+        Ok(progress)
+    }
+
     /// Get the current progress percentage.
     ///
     /// # Returns
@@ -530,7 +1176,9 @@ impl TaskHandle {
     /// The updated progress percentage as a value between 0.0 and 100.0.
     pub async fn update_progress(&self) -> Result<f64> {
         let mut config = self.thread_config.lock().await;
-        Ok(config.update_progress())
+        let progress = config.update_progress();
+        self.notify_progress(&config);
+        Ok(progress)
     }
     
     /// Set the progress to a specific number of completed jobs.
@@ -542,7 +1190,9 @@ impl TaskHandle {
     /// The updated progress percentage as a value between 0.0 and 100.0.
     pub async fn set_progress(&self, completed: usize) -> Result<f64> {
         let mut config = self.thread_config.lock().await;
-        Ok(config.set_progress(completed))
+        let progress = config.set_progress(completed);
+        self.notify_progress(&config);
+        Ok(progress)
     }
     
     /// Set the progress display format.
@@ -655,6 +1305,31 @@ impl TaskHandle {
         Ok(config.get_elapsed_time())
     }
 
+    /// Get the elapsed time as a human-readable string, e.g. "elapsed: 45s".
+    pub async fn elapsed_string(&self) -> String {
+        elapsed_label(self.thread_config.lock().await.get_elapsed_time())
+    }
+
+    /// Get the estimated time remaining as a human-readable string, e.g.
+    /// "2m 13s remaining", or the [elapsed string](Self::elapsed_string) while
+    /// no estimate can be made.
+    pub async fn elapsed_eta_string(&self) -> String {
+        let config = self.thread_config.lock().await;
+        match config.get_estimated_time_remaining() {
+            Some(eta) => format!("{} remaining", format_hms(eta)),
+            None => elapsed_label(config.get_elapsed_time()),
+        }
+    }
+
+    /// Get a template context holding the task's elapsed time and estimate,
+    /// as set by [`TemplateContext::set_times`].
+    pub async fn time_context(&self) -> TemplateContext {
+        let config = self.thread_config.lock().await;
+        let mut ctx = TemplateContext::new();
+        ctx.set_times(config.get_elapsed_time(), config.get_estimated_time_remaining());
+        ctx
+    }
+
     /// Join this task, waiting for it to complete.
     /// 
     /// This will block the current thread until the task is complete.
@@ -681,6 +1356,8 @@ impl TaskHandle {
             // Using CancellableJob trait method
             CancellableJob::set_cancelled(&mut *config, Some("Task cancelled by user".to_string()));
         }
+        self.cancellation_token.cancel();
+        self.finish(ThreadState::Cancelled);
         
         // Then abort the task's execution
         if let Some(handle) = self.join_handle.lock().await.take() {
@@ -705,6 +1382,8 @@ impl TaskHandle {
             // Using CancellableJob trait method
             CancellableJob::set_cancelled(&mut *config, Some(reason));
         }
+        self.cancellation_token.cancel();
+        self.finish(ThreadState::Cancelled);
         
         // Then abort the task's execution
         if let Some(handle) = self.join_handle.lock().await.take() {
@@ -1038,6 +1717,109 @@ impl TaskHandle {
         Ok(config.get_child_job_ids())
     }
 
+    /// Make this task depend on another job.
+    ///
+    /// # Parameters
+    /// * `job_id` - The ID of the job this task waits for
+    ///
+    /// # Returns
+    /// true if the dependency was added, false if it already existed
+    pub async fn add_dependency(&self, job_id: usize) -> Result<bool> {
+        let mut config = self.thread_config.lock().await;
+        Ok(config.add_dependency(job_id))
+    }
+
+    /// Get the job status of this task.
+    ///
+    /// # Returns
+    /// The current status, or an error if the task's mode does not track one
+    pub async fn get_status(&self) -> Result<JobStatus> {
+        let config = self.thread_config.lock().await;
+        config.job_status().ok_or_else(|| {
+            let ctx = ErrorContext::new("getting job status", "TaskHandle")
+                .with_thread_id(self.thread_id)
+                .with_details("Mode does not track a job status");
+            ProgressError::TaskOperation("Job status not available".to_string()).into_context(ctx).into()
+        })
+    }
+
+    /// Set the job status of this task.
+    ///
+    /// # Parameters
+    /// * `status` - The new status
+    pub async fn set_status(&self, status: JobStatus) -> Result<()> {
+        let mut config = self.thread_config.lock().await;
+        config.set_job_status(status);
+        Ok(())
+    }
+
+    /// Save the state of this task so it can be restored into a new task.
+    ///
+    /// The job state is captured with [`PersistentJob::job_state`](crate::core::job_traits::PersistentJob::job_state).
+    ///
+    /// # Returns
+    /// The persisted task, or an error if the task uses a user-defined mode
+    pub async fn persist(&self) -> Result<PersistedTask> {
+        let mut config = self.thread_config.lock().await;
+        let thread_id = self.thread_id;
+        let persisted = config.thread_mode().and_then(|mode| {
+            let title = config.get_title().map(str::to_string);
+            let state = config.job_state(|| format!("task-{}", thread_id))?;
+            Some(PersistedTask { mode, title, state })
+        });
+        persisted.ok_or_else(|| {
+            let ctx = ErrorContext::new("persisting task", "TaskHandle")
+                .with_thread_id(thread_id)
+                .with_details("Only built-in modes can be persisted");
+            ProgressError::TaskOperation("Task cannot be persisted".to_string()).into_context(ctx).into()
+        })
+    }
+
+    /// Get the priority of this task.
+    ///
+    /// # Returns
+    /// The priority, higher values being more important
+    pub async fn get_priority(&self) -> Result<u32> {
+        let config = self.thread_config.lock().await;
+        Ok(config.get_priority())
+    }
+
+    /// Set the priority of this task.
+    ///
+    /// # Parameters
+    /// * `priority` - The new priority, higher values being more important
+    pub async fn set_priority(&self, priority: u32) -> Result<()> {
+        let mut config = self.thread_config.lock().await;
+        config.set_priority(priority);
+        Ok(())
+    }
+
+    /// Get the IDs of the jobs this task depends on.
+    ///
+    /// # Returns
+    /// A vector of job IDs
+    pub async fn get_dependencies(&self) -> Result<Vec<usize>> {
+        let config = self.thread_config.lock().await;
+        Ok(config.get_dependencies())
+    }
+
+    /// Drop every dependency of this task so it no longer waits on anything.
+    ///
+    /// Meant for recovering a task reported as deadlocked: a handle cannot see
+    /// the state of other tasks, so all dependencies are cleared, not just the
+    /// ones that can no longer complete.
+    ///
+    /// # Returns
+    /// The IDs of the dependencies that were removed
+    pub async fn force_unblock(&self) -> Result<Vec<usize>> {
+        let mut config = self.thread_config.lock().await;
+        let dependencies = config.get_dependencies();
+        for job_id in &dependencies {
+            config.remove_dependency(*job_id);
+        }
+        Ok(dependencies)
+    }
+
     /// Pause this task.
     ///
     /// When a task is paused, it will stop incrementing its progress counter
@@ -1089,4 +1871,270 @@ impl TaskHandle {
         // For the test only - return dummy value to avoid breaking tests
         Ok(if self.is_paused().await? { 5 } else { 6 })
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (from, to, whether the transition is allowed)
+    fn transition_cases() -> Vec<(ThreadState, ThreadState, bool)> {
+        use ThreadState::*;
+        let failed = || Failed("error".to_string());
+        vec![
+            (Created, Running, true),
+            (Created, failed(), true),
+            (Created, Cancelled, true),
+            (Created, Paused, false),
+            (Created, Completed, false),
+            (Running, Paused, true),
+            (Running, Completed, true),
+            (Running, failed(), true),
+            (Running, Cancelled, true),
+            (Running, Created, false),
+            (Running, Running, false),
+            (Paused, Running, true),
+            (Paused, Cancelled, true),
+            (Paused, Completed, false),
+            (Completed, Running, false),
+            (Completed, failed(), false),
+            (failed(), Running, false),
+            (Cancelled, Created, false),
+        ]
+    }
+
+    #[test]
+    fn test_thread_state_transitions() {
+        for (from, to, allowed) in transition_cases() {
+            assert_eq!(from.can_transition_to(&to), allowed, "unexpected result for {} -> {}", from, to);
+        }
+    }
+
+    #[test]
+    fn test_terminal_states() {
+        let terminal = ThreadState::terminal_states();
+        assert_eq!(terminal.len(), 3);
+        assert!(terminal.contains(&ThreadState::Completed));
+        assert!(terminal.contains(&ThreadState::Cancelled));
+        assert!(terminal.iter().all(|s| s.is_terminal()));
+        assert!(ThreadState::Failed("any message".to_string()).is_terminal());
+        assert!(!ThreadState::Running.is_terminal());
+    }
+
+    #[tokio::test]
+    async fn test_update_thread_state_validates_transitions() -> Result<()> {
+        let (message_tx, _message_rx) = mpsc::channel(10);
+
+        for (from, to, allowed) in transition_cases() {
+            let manager = ThreadManager::new();
+            let thread_id = manager.next_thread_id();
+            let handle = TaskHandle::new(thread_id, Config::new(ThreadMode::Limited, 1)?, message_tx.clone());
+            manager.register_thread(thread_id, handle, tokio::spawn(async { Ok(()) })).await;
+            manager.threads.lock().await.get_mut(&thread_id).unwrap().update_state(from.clone());
+
+            let result = manager.update_thread_state(thread_id, to.clone()).await;
+            if allowed {
+                assert!(result.is_ok(), "{} -> {} should succeed", from, to);
+                assert_eq!(manager.get_thread_state(thread_id).await, Some(to));
+            } else {
+                let err = result.unwrap_err().to_string();
+                assert!(err.contains("Invalid thread state transition"), "{}", err);
+                assert_eq!(manager.get_thread_state(thread_id).await, Some(from));
+            }
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_active_threads_are_sorted() -> Result<()> {
+        let (message_tx, _message_rx) = mpsc::channel(10);
+        let manager = ThreadManager::new();
+        let thread_ids: Vec<usize> = (0..32).map(|_| manager.next_thread_id()).collect();
+        for &thread_id in thread_ids.iter().rev() {
+            let handle = TaskHandle::new(thread_id, Config::new(ThreadMode::Limited, 1)?, message_tx.clone());
+            manager.register_thread(thread_id, handle, tokio::spawn(async { Ok(()) })).await;
+        }
+
+        assert_eq!(manager.get_active_threads().await, thread_ids);
+        assert_eq!(manager.get_threads_by_state(ThreadState::Running).await, thread_ids);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_task_handle_completed() -> Result<()> {
+        let manager = Arc::new(ThreadManager::new());
+        let (message_tx, _message_rx) = mpsc::channel(10);
+
+        let thread_id = manager.next_thread_id();
+        let handle = TaskHandle::new(thread_id, Config::new(ThreadMode::Limited, 1)?, message_tx.clone());
+        manager.register_thread(thread_id, handle.clone(), tokio::spawn(async { Ok(()) })).await;
+
+        // Another task drives the job to done while we wait
+        let driver = tokio::spawn({
+            let manager = manager.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                manager.update_thread_state(thread_id, ThreadState::Completed).await
+            }
+        });
+        tokio::time::timeout(Duration::from_secs(2), handle.completed()).await??;
+        driver.await??;
+
+        // Finished tasks resolve immediately
+        handle.completed().await?;
+
+        // Failed tasks resolve with the failure
+        let failed_id = manager.next_thread_id();
+        let failed = TaskHandle::new(failed_id, Config::new(ThreadMode::Limited, 1)?, message_tx);
+        manager.register_thread(failed_id, failed.clone(), tokio::spawn(async { Ok(()) })).await;
+        let waiter = tokio::spawn(failed.completed());
+        manager.update_thread_state(failed_id, ThreadState::Failed("disk full".to_string())).await?;
+        let err = tokio::time::timeout(Duration::from_secs(2), waiter).await??.unwrap_err();
+        assert!(err.to_string().contains("disk full"), "{}", err);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_throttle_coalesces_progress() -> Result<()> {
+        let (message_tx, mut message_rx) = mpsc::channel(1000);
+        let handle = TaskHandle::new(0, Config::new(ThreadMode::WindowWithTitle(3), 1000)?, message_tx);
+
+        // Progress updates are not sent until a throttle is set
+        assert_eq!(handle.update_throttle(), None);
+        handle.update_progress().await?;
+        assert!(message_rx.try_recv().is_err());
+
+        handle.set_update_throttle(Duration::from_millis(50));
+
+        let start = Instant::now();
+        for completed in 1..=500 {
+            handle.set_progress(completed).await?;
+        }
+        let elapsed = start.elapsed();
+        tokio::time::sleep(Duration::from_millis(120)).await;
+
+        let mut messages = Vec::new();
+        while let Ok(message) = message_rx.try_recv() {
+            messages.push(message);
+        }
+        let bound = elapsed.as_millis() as usize / 50 + 2;
+        assert!(!messages.is_empty() && messages.len() <= bound, "{} messages, expected at most {}", messages.len(), bound);
+        assert!(messages.iter().all(|message| message.lines.is_empty() && message.config.is_none()));
+
+        // Without a throttle every update is sent
+        handle.set_update_throttle(Duration::ZERO);
+        handle.update_progress().await?;
+        handle.update_progress().await?;
+        assert!(message_rx.try_recv().is_ok() && message_rx.try_recv().is_ok());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_total_and_reset() -> Result<()> {
+        let (message_tx, _message_rx) = mpsc::channel(10);
+        let handle = TaskHandle::new(0, Config::new(ThreadMode::Window(3), 10)?, message_tx);
+
+        for _ in 0..4 {
+            handle.update_progress().await?;
+        }
+        assert_eq!(handle.get_progress_percentage().await?, 40.0);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // The real total turns out to be larger, so progress starts over
+        assert_eq!(handle.set_total_and_reset(20).await?, 0.0);
+        assert_eq!(handle.get_progress_percentage().await?, 0.0);
+        assert!(handle.get_elapsed_time().await? < Duration::from_millis(50));
+
+        assert_eq!(handle.update_progress().await?, 5.0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_elapsed_eta_string() -> Result<()> {
+        use crate::core::clock::MockClock;
+        use crate::core::job_traits::HasBaseConfig;
+        use crate::ui::formatter::ProgressTemplate;
+
+        let (message_tx, _message_rx) = mpsc::channel(10);
+        let handle = TaskHandle::new(0, Config::new(ThreadMode::Window(3), 10)?, message_tx);
+        let clock = MockClock::new();
+        handle.thread_config.lock().await.base_config_mut().set_clock(Arc::new(clock.clone()));
+
+        // No estimate before any progress
+        assert_eq!(handle.elapsed_string().await, "elapsed: 0s");
+        assert_eq!(handle.elapsed_eta_string().await, "elapsed: 0s");
+        assert!(!handle.time_context().await.has("eta"));
+
+        // One job in one second leaves nine seconds for the other nine
+        clock.advance(Duration::from_secs(1));
+        handle.update_progress().await?;
+        clock.advance(Duration::from_secs(124));
+        assert_eq!(handle.elapsed_string().await, "elapsed: 2m 5s");
+        assert_eq!(handle.elapsed_eta_string().await, "9s remaining");
+
+        let rendered = ProgressTemplate::new("{elapsed:duration} in, {eta:duration} left")
+            .render(&handle.time_context().await)?;
+        assert_eq!(rendered, "2m 5s in, 9s left");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_run_with_retry_respects_config() -> Result<()> {
+        let (message_tx, _message_rx) = mpsc::channel(10);
+        let handle = TaskHandle::new(0, Config::new(ThreadMode::Limited, 1)?, message_tx);
+        handle.set_retry_config(RetryConfig {
+            max_retries: 1,
+            retry_delay: Duration::from_millis(1),
+            use_exponential_backoff: false,
+            base_delay: Duration::from_millis(1),
+        });
+
+        let attempts = AtomicUsize::new(0);
+        let result: Result<()> = handle.run_with_retry(|| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            async { Err(anyhow::anyhow!("still broken")) }
+        }).await;
+        assert!(result.unwrap_err().to_string().contains("still broken"));
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        assert_eq!(handle.get_status().await?, JobStatus::Failed);
+        {
+            let config = handle.config().lock().await;
+            assert_eq!(config.get_failure_count(), 2);
+            assert_eq!(config.get_error_message(), Some("still broken".to_string()));
+            assert_eq!(config.get_retry_count(), 1);
+        }
+
+        // Retries are counted from zero again on the next call
+        let result: Result<()> = handle.run_with_retry(|| async { Err(anyhow::anyhow!("still broken")) }).await;
+        assert!(result.is_err());
+        assert_eq!(handle.config().lock().await.get_retry_count(), 1);
+
+        // A later success stops retrying and clears the failures
+        attempts.store(0, Ordering::SeqCst);
+        let value = handle.run_with_retry(|| {
+            let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+            async move { if attempt == 0 { Err(anyhow::anyhow!("flaky")) } else { Ok(attempt) } }
+        }).await?;
+        assert_eq!(value, 1);
+        assert_eq!(handle.get_status().await?, JobStatus::Running);
+        assert_eq!(handle.config().lock().await.get_failure_count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_retry_config_delays() {
+        let mut config = RetryConfig {
+            max_retries: 3,
+            retry_delay: Duration::from_millis(50),
+            use_exponential_backoff: false,
+            base_delay: Duration::from_millis(100),
+        };
+        assert_eq!(config.delay(1), Duration::from_millis(50));
+        assert_eq!(config.delay(5), Duration::from_millis(50));
+
+        config.use_exponential_backoff = true;
+        assert_eq!(config.delay(1), Duration::from_millis(200));
+        assert_eq!(config.delay(2), Duration::from_millis(400));
+        assert_eq!(config.delay(3), Duration::from_millis(800));
+    }
+}
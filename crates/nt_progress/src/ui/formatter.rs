@@ -9,6 +9,52 @@ use crate::errors::{ProgressError, ContextExt};
 use crate::terminal::Color;
 use crossterm::style::{SetForegroundColor, ResetColor};
 use std::str::FromStr;
+use std::time::Duration;
+
+/// Write `label` over the middle of `bar`, leaving ANSI escape sequences intact.
+///
+/// The bar is returned unchanged if the label does not fit between its first
+/// and last visible characters.
+fn overlay_centered(bar: &str, label: &str) -> String {
+    let mut visible = 0;
+    let mut in_escape = false;
+    for c in bar.chars() {
+        if c == '\x1B' {
+            in_escape = true;
+        } else if in_escape {
+            in_escape = !c.is_ascii_alphabetic();
+        } else {
+            visible += 1;
+        }
+    }
+    let label: Vec<char> = label.chars().collect();
+    if label.len() + 2 > visible {
+        return bar.to_string();
+    }
+    
+    let start = (visible - label.len()) / 2;
+    let mut result = String::with_capacity(bar.len());
+    let mut index = 0;
+    in_escape = false;
+    for c in bar.chars() {
+        if c == '\x1B' {
+            in_escape = true;
+        } else if in_escape {
+            in_escape = !c.is_ascii_alphabetic();
+        } else {
+            // The label is written whole so escape sequences never split it
+            if index == start {
+                result.extend(label.iter());
+            } else if index < start || index >= start + label.len() {
+                result.push(c);
+            }
+            index += 1;
+            continue;
+        }
+        result.push(c);
+    }
+    result
+}
 
 /// Template variable types that can be interpolated into templates
 #[derive(Debug, Clone)]
@@ -71,6 +117,20 @@ impl From<bool> for TemplateVar {
     }
 }
 
+/// Format a duration as hours, minutes and seconds, dropping leading zero
+/// units (e.g. "12h 33m 51s", "2m 13s" or "45s").
+pub fn format_hms(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}h {}m {}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
 /// A container for template variables
 #[derive(Debug, Clone, Default)]
 pub struct TemplateContext {
@@ -104,12 +164,47 @@ impl TemplateContext {
     pub fn has(&self, key: &str) -> bool {
         self.vars.contains_key(key)
     }
+    
+    /// Set the time variables of a task.
+    ///
+    /// `elapsed` and `eta` hold seconds, so `{eta:duration}` renders them like
+    /// "2m 13s". `eta` is removed while no estimate can be made, so
+    /// `{?eta}...{/}` can hide the text depending on it.
+    pub fn set_times(&mut self, elapsed: Duration, eta: Option<Duration>) -> &mut Self {
+        self.set("elapsed", elapsed.as_secs_f64());
+        match eta {
+            Some(eta) => {
+                self.set("eta", eta.as_secs_f64());
+            }
+            None => {
+                self.vars.remove("eta");
+            }
+        }
+        self
+    }
+}
+
+// Which part of a template `ProgressTemplate::render_block` is rendering
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Block {
+    Top,
+    Then,
+    Else,
+}
+
+// The tag that ended a conditional branch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockEnd {
+    Else,
+    End,
 }
 
 /// A template for formatting task progress messages
 #[derive(Debug, Clone)]
 pub struct ProgressTemplate {
     template: String,
+    /// Whether `link` formats emit OSC 8 hyperlinks or plain text
+    hyperlinks: bool,
 }
 
 impl ProgressTemplate {
@@ -128,16 +223,23 @@ impl ProgressTemplate {
     /// - `{var:format}` - Interpolate `var` with the specified format
     /// - `{?condition}content{/}` - Include `content` only if `condition` is truthy
     /// - `{!condition}content{/}` - Include `content` only if `condition` is falsy
+    /// - `{?condition}truthy{:}falsy{/}` - Include `truthy` or `falsy` depending on `condition`
+    ///
+    /// A condition is truthy when it is set to a non-empty string, a non-zero
+    /// number or `true`. Conditionals can be nested inside either branch, and a
+    /// `{:}` outside of one is kept as literal text.
     ///
     /// # Available Formats
     ///
     /// - `{var:bar}` - Render `var` as a progress bar
     /// - `{var:percent}` - Render `var` as a percentage (e.g., "50%")
     /// - `{var:ratio}` - Render `var` as a ratio (e.g., "5/10")
+    /// - `{var:duration}` - Render `var` seconds as a duration (e.g., "12h 33m 51s", or "12:33:51" with `:compact`)
     /// - `{var:pad:N}` - Pad `var` to length N with spaces
     /// - `{var:lpad:N}` - Left-pad `var` to length N with spaces
     /// - `{var:rpad:N}` - Right-pad `var` to length N with spaces
     /// - `{var:color:name}` - Apply color to `var` (supported colors: black, red, green, yellow, blue, magenta, cyan, white, reset)
+    /// - `{var:link:url}` - Render `var` as a terminal hyperlink to `url`, which is either a literal URL or the name of a context variable
     ///
     /// # Examples
     ///
@@ -169,9 +271,19 @@ impl ProgressTemplate {
     pub fn new(template: impl Into<String>) -> Self {
         Self {
             template: template.into(),
+            hyperlinks: !crate::terminal::no_color(),
         }
     }
     
+    /// Enable or disable hyperlinks
+    ///
+    /// Hyperlinks are enabled unless `NO_COLOR` is set. When disabled, `link`
+    /// formats render only the link text.
+    pub fn with_hyperlinks(mut self, enabled: bool) -> Self {
+        self.hyperlinks = enabled;
+        self
+    }
+    
     /// Render the template with the given context
     ///
     /// # Parameters
@@ -180,55 +292,91 @@ impl ProgressTemplate {
     /// # Returns
     /// A Result containing the rendered string or an error
     pub fn render(&self, context: &TemplateContext) -> Result<String, ProgressError> {
-        // Create a buffer for building the result
-        let mut result = String::with_capacity(self.template.len() * 2);
-        
-        // Parse the template and render it
         let chars: Vec<char> = self.template.chars().collect();
         let mut i = 0;
-        
-        while i < chars.len() {
-            if chars[i] == '{' {
-                if i + 1 < chars.len() && chars[i + 1] == '{' {
+        let (result, _) = self.render_block(&chars, &mut i, context, Block::Top, true)?;
+        Ok(result)
+    }
+    
+    // Render from `i` until the end of the template or of the `block`, leaving `i`
+    // past the tag that ended it. Tags of an inactive block are parsed but not
+    // rendered, so formats in an untaken branch can't fail.
+    fn render_block(
+        &self,
+        chars: &[char],
+        i: &mut usize,
+        context: &TemplateContext,
+        block: Block,
+        active: bool,
+    ) -> Result<(String, Option<BlockEnd>), ProgressError> {
+        let mut result = String::with_capacity(chars.len() * 2);
+        
+        while *i < chars.len() {
+            if chars[*i] == '{' {
+                if *i + 1 < chars.len() && chars[*i + 1] == '{' {
                     // Escaped opening brace {{ -> {
                     result.push('{');
-                    i += 2;
+                    *i += 2;
                     continue;
                 }
                 
                 // Look for the closing brace
-                let mut j = i + 1;
+                let mut j = *i + 1;
                 while j < chars.len() && chars[j] != '}' {
                     j += 1;
                 }
                 
                 if j < chars.len() {
                     // Found a complete tag
-                    let tag = chars[i + 1..j].iter().collect::<String>();
+                    let tag = chars[*i + 1..j].iter().collect::<String>();
+                    *i = j + 1;
                     
-                    if let Some(rendered) = self.render_tag(&tag, context)
-                        .with_context("rendering template tag", "ProgressTemplate")? {
-                        result.push_str(&rendered);
+                    match tag.as_str() {
+                        ":" if block == Block::Then => return Ok((result, Some(BlockEnd::Else))),
+                        // Outside a truthy branch there is nothing to separate
+                        ":" => result.push_str("{:}"),
+                        "/" if block != Block::Top => return Ok((result, Some(BlockEnd::End))),
+                        _ if tag.len() > 1 && (tag.starts_with('?') || tag.starts_with('!')) => {
+                            let truthy = context.get(tag[1..].trim()).is_some_and(TemplateVar::is_truthy);
+                            let taken = truthy == tag.starts_with('?');
+                            
+                            let (then, end) = self.render_block(chars, i, context, Block::Then, active && taken)?;
+                            if taken {
+                                result.push_str(&then);
+                            }
+                            if end == Some(BlockEnd::Else) {
+                                let (otherwise, _) = self.render_block(chars, i, context, Block::Else, active && !taken)?;
+                                if !taken {
+                                    result.push_str(&otherwise);
+                                }
+                            }
+                        }
+                        _ if active => {
+                            if let Some(rendered) = self.render_tag(&tag, context)
+                                .with_context("rendering template tag", "ProgressTemplate")? {
+                                result.push_str(&rendered);
+                            }
+                        }
+                        _ => {}
                     }
-                    
-                    i = j + 1;
                 } else {
                     // Unclosed tag, treat as literal
                     result.push('{');
-                    i += 1;
+                    *i += 1;
                 }
-            } else if chars[i] == '}' && i + 1 < chars.len() && chars[i + 1] == '}' {
+            } else if chars[*i] == '}' && *i + 1 < chars.len() && chars[*i + 1] == '}' {
                 // Escaped closing brace }} -> }
                 result.push('}');
-                i += 2;
+                *i += 2;
             } else {
                 // Normal character
-                result.push(chars[i]);
-                i += 1;
+                result.push(chars[*i]);
+                *i += 1;
             }
         }
         
-        Ok(result)
+        // An unclosed conditional runs to the end of the template
+        Ok((result, None))
     }
     
     // Process a single template tag
@@ -280,8 +428,10 @@ impl ProgressTemplate {
             "bar" => self.format_bar(var, params, context),
             "percent" => self.format_percent(var, params, context),
             "ratio" => self.format_ratio(var, params, context),
+            "duration" => self.format_duration(var, params),
             "pad" | "lpad" | "rpad" => self.format_padding(var, format, params, context),
             "color" => self.format_color(var, params, context),
+            "link" => self.format_link(var, params, context),
             _ => Ok(Some(var.as_string())),
         }
     }
@@ -314,6 +464,7 @@ impl ProgressTemplate {
         let mut custom_params = Vec::new();
         let mut width = 10; // Default width
         let mut smooth_animation = false;
+        let mut label = false;
         
         // Process remaining parameters
         for i in 1..format_parts.len() {
@@ -331,12 +482,18 @@ impl ProgressTemplate {
                 continue;
             }
             
+            // Check for the percentage label flag
+            if param == "label" {
+                label = true;
+                continue;
+            }
+            
             // Otherwise it's a custom parameter for the indicator
             custom_params.push(param);
         }
         
         // Match on indicator type
-        match indicator_type {
+        let bar = match indicator_type {
             "bar" => {
                 self.format_bar_indicator(progress, &custom_params, width, smooth_animation)
             }
@@ -371,7 +528,13 @@ impl ProgressTemplate {
                 // Default to standard bar
                 self.format_bar_indicator(progress, &[], width, smooth_animation)
             }
+        }?;
+        
+        if !label {
+            return Ok(bar);
         }
+        let percent = format!("{}%", (progress * 100.0).round() as usize);
+        Ok(bar.map(|bar| overlay_centered(&bar, &percent)))
     }
     
     /// Format a traditional bar indicator "[====    ]"
@@ -676,6 +839,29 @@ impl ProgressTemplate {
         Ok(Some(format!("{}%", percent)))
     }
     
+    // Format a variable holding seconds as a duration, dropping leading zero units
+    fn format_duration(
+        &self,
+        var: &TemplateVar,
+        format_parts: &[&str],
+    ) -> Result<Option<String>, ProgressError> {
+        let seconds = match var {
+            TemplateVar::Number(n) => n.max(0.0) as u64,
+            _ => {
+                return Err(ProgressError::DisplayOperation(
+                    "Duration format requires a number".to_string(),
+                ))
+            }
+        };
+        
+        if format_parts.first() == Some(&"compact") {
+            let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+            return Ok(Some(format!("{:02}:{:02}:{:02}", hours, minutes, seconds)));
+        }
+        
+        Ok(Some(format_hms(Duration::from_secs(seconds))))
+    }
+    
     // Format a variable as a ratio (numerator/denominator)
     fn format_ratio(
         &self,
@@ -793,6 +979,36 @@ impl ProgressTemplate {
         Ok(Some(format!("\x1B[{}m{}\x1B[0m", color_code, text)))
     }
     
+    // Format a variable as an OSC 8 terminal hyperlink
+    fn format_link(
+        &self,
+        var: &TemplateVar,
+        format_parts: &[&str],
+        context: &TemplateContext,
+    ) -> Result<Option<String>, ProgressError> {
+        if format_parts.is_empty() {
+            return Err(ProgressError::DisplayOperation(
+                "Link format requires a URL".to_string(),
+            ));
+        }
+        
+        // URLs contain ':' so the remaining parts are joined back together
+        let target = format_parts.join(":");
+        let url = match context.get(&target) {
+            Some(url) => url.as_string(),
+            None => target,
+        };
+        
+        let text = var.as_string();
+        if !self.hyperlinks {
+            return Ok(Some(text));
+        }
+        
+        // A control character such as ESC or BEL would end the sequence early
+        let url: String = url.chars().filter(|c| !c.is_control()).collect();
+        Ok(Some(format!("\x1B]8;;{}\x1B\\{}\x1B]8;;\x1B\\", url, text)))
+    }
+    
     /// Format a custom indicator defined by the user
     fn format_custom_indicator(
         &self,
@@ -1333,6 +1549,42 @@ mod tests {
         assert_eq!(result, "Hello, world!");
     }
     
+    #[test]
+    fn test_conditionals() {
+        let mut ctx = TemplateContext::new();
+        ctx.set("done", true)
+           .set("name", "job")
+           .set("count", 0.0)
+           .set("empty", "");
+        let render = |template: &str| ProgressTemplate::new(template).render(&ctx).unwrap();
+        
+        // Without an else-branch
+        assert_eq!(render("{?done}finished {name}{/}!"), "finished job!");
+        assert_eq!(render("{!done}running{/}!"), "!");
+        assert_eq!(render("{?missing}set{/}{!missing}unset{/}"), "unset");
+        assert_eq!(render("{?count}some{/}{?empty}text{/}"), "");
+        
+        // With one
+        assert_eq!(render("{?done}done{:}running{/}"), "done");
+        assert_eq!(render("{!done}running{:}done{/}"), "done");
+        assert_eq!(render("{?count}{count}{:}none{/} left"), "none left");
+        
+        // Nested in either branch
+        assert_eq!(render("{?done}{?count}a{:}b{/}-{?name}c{/}{:}d{/}"), "b-c");
+        assert_eq!(render("{?count}a{:}{!empty}{?name}{name}{:}e{/}{/}{/}"), "job");
+        
+        // An unclosed conditional runs to the end
+        assert_eq!(render("{?count}hidden"), "");
+        
+        // `{:}` outside a truthy branch is literal
+        assert_eq!(render("a{:}b"), "a{:}b");
+        assert_eq!(render("{?count}x{:}y{:}z{/}"), "y{:}z");
+        
+        // Formats in the untaken branch are not evaluated
+        assert_eq!(render("{?done}ok{:}{name:duration}{/}"), "ok");
+        assert!(ProgressTemplate::new("{!done}ok{:}{name:duration}{/}").render(&ctx).is_err());
+    }
+    
     #[test]
     fn test_progress_bar() {
         let template = ProgressTemplate::new("{progress:bar:10}");
@@ -1343,6 +1595,29 @@ mod tests {
         assert_eq!(result, "[=====     ]");
     }
     
+    #[test]
+    fn test_progress_bar_label() {
+        let template = ProgressTemplate::new("{progress:bar:bar:10:label}");
+        let mut ctx = TemplateContext::new();
+        ctx.set("progress", 0.5);
+        assert_eq!(template.render(&ctx).unwrap(), "[===50%    ]");
+        
+        ctx.set("progress", 1.0);
+        assert_eq!(template.render(&ctx).unwrap(), "[===100%===]");
+        
+        // Colors are kept around the label
+        let template = ProgressTemplate::new("{progress:bar:bar:=:-:green:red:6:label}");
+        ctx.set("progress", 0.5);
+        let result = template.render(&ctx).unwrap();
+        assert!(result.starts_with('['));
+        assert!(result.contains("50%"));
+        assert!(result.contains("\x1B["));
+        
+        // A label wider than the bar is left out
+        let template = ProgressTemplate::new("{progress:bar:bar:2:label}");
+        assert_eq!(template.render(&ctx).unwrap(), "[= ]");
+    }
+    
     #[test]
     fn test_percentage_format() {
         let template = ProgressTemplate::new("{progress:percent}");
@@ -1364,6 +1639,43 @@ mod tests {
         assert_eq!(result, "7/10");
     }
     
+    #[test]
+    fn test_duration_format() {
+        let template = ProgressTemplate::new("{elapsed:duration}");
+        let compact = ProgressTemplate::new("{elapsed:duration:compact}");
+        let mut ctx = TemplateContext::new();
+        
+        for (seconds, expected, clock) in [
+            (45231.0, "12h 33m 51s", "12:33:51"),
+            (2031.0, "33m 51s", "00:33:51"),
+            (51.0, "51s", "00:00:51"),
+            (3600.0, "1h 0m 0s", "01:00:00"),
+            (0.0, "0s", "00:00:00"),
+            (-5.0, "0s", "00:00:00"),
+        ] {
+            ctx.set("elapsed", seconds);
+            assert_eq!(template.render(&ctx).unwrap(), expected);
+            assert_eq!(compact.render(&ctx).unwrap(), clock);
+        }
+        
+        ctx.set("elapsed", "soon");
+        assert!(template.render(&ctx).is_err());
+    }
+    
+    #[test]
+    fn test_set_times() {
+        let template = ProgressTemplate::new("{elapsed:duration} elapsed{?eta}, {eta:duration} left ({eta:duration:compact}){/}");
+        let mut ctx = TemplateContext::new();
+        
+        ctx.set_times(Duration::from_secs(45), Some(Duration::from_secs(133)));
+        assert_eq!(template.render(&ctx).unwrap(), "45s elapsed, 2m 13s left (00:02:13)");
+        
+        // A lost estimate is cleared rather than left stale
+        ctx.set_times(Duration::from_secs(3725), None);
+        assert!(!ctx.has("eta"));
+        assert_eq!(template.render(&ctx).unwrap(), "1h 2m 5s elapsed");
+    }
+    
     #[test]
     fn test_padding_formats() {
         let template = ProgressTemplate::new("'{text:lpad:10}' '{text:rpad:10}' '{text:pad:10}'");
@@ -1399,6 +1711,40 @@ mod tests {
         assert!(result.contains("World"), "Result should contain the variable value");
     }
     
+    #[test]
+    fn test_link_format() {
+        let mut ctx = TemplateContext::new();
+        ctx.set("file", "report.pdf")
+           .set("file_url", "file:///tmp/report.pdf");
+        
+        let template = ProgressTemplate::new("Saved {file:link:https://example.com/report.pdf}").with_hyperlinks(true);
+        assert_eq!(
+            template.render(&ctx).unwrap(),
+            "Saved \x1B]8;;https://example.com/report.pdf\x1B\\report.pdf\x1B]8;;\x1B\\"
+        );
+        
+        // The URL can come from a context variable
+        let template = ProgressTemplate::new("{file:link:file_url}").with_hyperlinks(true);
+        assert_eq!(
+            template.render(&ctx).unwrap(),
+            "\x1B]8;;file:///tmp/report.pdf\x1B\\report.pdf\x1B]8;;\x1B\\"
+        );
+        
+        // Control characters cannot end the sequence early
+        ctx.set("file_url", "https://example.com/\x1B\\evil\x07?q=1");
+        assert_eq!(
+            template.render(&ctx).unwrap(),
+            "\x1B]8;;https://example.com/\\evil?q=1\x1B\\report.pdf\x1B]8;;\x1B\\"
+        );
+        
+        // Plain text when hyperlinks are disabled
+        let template = ProgressTemplate::new("{file:link:file_url}").with_hyperlinks(false);
+        assert_eq!(template.render(&ctx).unwrap(), "report.pdf");
+        
+        // A URL is required
+        assert!(ProgressTemplate::new("{file:link}").render(&ctx).is_err());
+    }
+    
     #[test]
     fn test_color_name_parsing() {
         assert_eq!(ColorName::from_str("red"), Some(ColorName::Red));
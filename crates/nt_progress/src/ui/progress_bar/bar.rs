@@ -1,5 +1,7 @@
 use std::time::{Duration, Instant};
 use super::config::ProgressBarConfig;
+use crate::errors::ProgressError;
+use crate::ui::formatter::{ProgressTemplate, TemplateContext};
 
 /// A progress bar that tracks progress and allows for customized display
 #[derive(Debug, Clone)]
@@ -107,10 +109,33 @@ impl ProgressBar {
         self.speed
     }
     
+    /// Get the current value (numerator)
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Get the total value (denominator)
+    pub fn total(&self) -> usize {
+        self.total
+    }
+    
     /// Get the template for this progress bar
     pub fn template(&self) -> String {
         self.config.build_template()
     }
+
+    /// Render the template with the current progress
+    pub fn render(&self) -> Result<String, ProgressError> {
+        let mut ctx = TemplateContext::new();
+        ctx.set("progress", self.progress)
+           .set("completed", self.current)
+           .set("total", self.total)
+           .set("percent", format!("{}%", self.percentage()));
+        if let Some(prefix) = &self.config.prefix {
+            ctx.set("prefix", prefix.clone());
+        }
+        ProgressTemplate::new(self.template()).render(&ctx)
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,19 @@
 use super::style::ProgressBarStyle;
 
+/// Where the percentage label is placed relative to the bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LabelPosition {
+    /// Before the bar
+    #[default]
+    Left,
+    /// After the bar
+    Right,
+    /// Centered over the bar, left out if the bar is too narrow to hold it
+    Inside,
+    /// Not shown
+    None,
+}
+
 /// Configuration for a progress bar display
 #[derive(Debug, Clone)]
 pub struct ProgressBarConfig {
@@ -9,11 +23,16 @@ pub struct ProgressBarConfig {
     pub width: usize,
     /// Whether to show percentage
     pub show_percentage: bool,
+    /// Where the percentage is shown
+    pub label_position: LabelPosition,
     /// Whether to show the fraction (e.g. "5/10")
     pub show_fraction: bool,
     /// The prefix to display before the progress bar
     pub prefix: Option<String>,
-    /// The template for formatting the progress display
+    /// The template for formatting the progress display.
+    ///
+    /// Takes precedence over the template derived from the style and the
+    /// other display options; an empty or blank template falls back to it.
     pub template: Option<String>,
     /// Characters to use for the filled portion (default depends on style)
     pub fill_char: Option<char>,
@@ -35,7 +54,7 @@ pub struct ProgressBarConfig {
     pub show_eta: bool,
     /// Whether to show speed
     pub show_speed: bool,
-    /// Format for displaying the ETA (e.g., "ETA: {eta}")
+    /// Format for displaying the ETA (e.g., "ETA: {eta:duration}")
     pub eta_format: Option<String>,
     /// Format for displaying the speed (e.g., "{speed}/s")
     pub speed_format: Option<String>,
@@ -55,6 +74,7 @@ impl Default for ProgressBarConfig {
             style: ProgressBarStyle::default(),
             width: 20,
             show_percentage: true,
+            label_position: LabelPosition::default(),
             show_fraction: true,
             prefix: None,
             template: None,
@@ -68,7 +88,7 @@ impl Default for ProgressBarConfig {
             use_spinner: false,
             show_eta: false,
             show_speed: false,
-            eta_format: Some("ETA: {eta}".to_string()),
+            eta_format: Some("ETA: {eta:duration}".to_string()),
             speed_format: Some("{speed} {unit}/s".to_string()),
             speed_unit: "items".to_string(),
             left_bracket: None,
@@ -102,6 +122,15 @@ impl ProgressBarConfig {
         self
     }
 
+    /// Set where the percentage is shown
+    ///
+    /// [`LabelPosition::Inside`] overlays the percentage on the bar, so a
+    /// percentage color does not apply to it.
+    pub fn label_position(mut self, position: LabelPosition) -> Self {
+        self.label_position = position;
+        self
+    }
+
     /// Set whether to show the fraction
     pub fn show_fraction(mut self, show: bool) -> Self {
         self.show_fraction = show;
@@ -114,7 +143,9 @@ impl ProgressBarConfig {
         self
     }
 
-    /// Set a custom template for formatting
+    /// Set a custom template for formatting, overriding the style-derived one.
+    ///
+    /// An empty template keeps the style-derived template.
     pub fn template(mut self, template: impl Into<String>) -> Self {
         self.template = Some(template.into());
         self
@@ -209,10 +240,13 @@ impl ProgressBarConfig {
         self
     }
 
-    /// Create a template string based on the current configuration
+    /// Create a template string based on the current configuration.
+    ///
+    /// A non-blank explicit template is returned as is; otherwise the template
+    /// is derived from the style and the other display options.
     pub fn build_template(&self) -> String {
-        if let Some(template) = &self.template {
-            return template.clone();
+        if let Some(template) = self.template.as_deref().filter(|t| !t.trim().is_empty()) {
+            return template.to_string();
         }
 
         let mut parts = Vec::new();
@@ -229,13 +263,13 @@ impl ProgressBarConfig {
         }
 
         // Add percentage if enabled with optional color
-        if self.show_percentage {
-            let percentage = if let Some(color) = &self.percentage_color {
-                format!("{{progress:percent:{}}}", color)
-            } else {
-                "{progress:percent}".to_string()
-            };
-            parts.push(percentage);
+        let percentage = match &self.percentage_color {
+            Some(color) => format!("{{progress:percent:{}}}", color),
+            None => "{progress:percent}".to_string(),
+        };
+        let label_position = if self.show_percentage { self.label_position } else { LabelPosition::None };
+        if label_position == LabelPosition::Left {
+            parts.push(percentage.clone());
         }
 
         // Add the bar with appropriate style and width
@@ -300,7 +334,15 @@ impl ProgressBarConfig {
             bar_params.push("smooth".to_string());
         }
         
+        if label_position == LabelPosition::Inside {
+            bar_params.push("label".to_string());
+        }
+        
         parts.push(format!("{{progress:bar:{}:{}}}", bar_params.join(":"), self.width));
+        
+        if label_position == LabelPosition::Right {
+            parts.push(percentage);
+        }
 
         // Add fraction if enabled with optional color
         if self.show_fraction {
@@ -314,7 +356,7 @@ impl ProgressBarConfig {
         
         // Add ETA if enabled
         if self.show_eta {
-            let eta_format = self.eta_format.as_deref().unwrap_or("ETA: {eta}");
+            let eta_format = self.eta_format.as_deref().unwrap_or("ETA: {eta:duration}");
             parts.push(eta_format.to_string());
         }
         
@@ -390,6 +432,21 @@ mod tests {
         let config = ProgressBarConfig::new().template("{task}: {progress:bar:bar:10} ({progress:percent})");
         assert_eq!(config.build_template(), "{task}: {progress:bar:bar:10} ({progress:percent})");
     }
+
+    #[test]
+    fn test_template_precedence() {
+        // An explicit template wins over the style, whichever is set first
+        let config = ProgressBarConfig::new().style(ProgressBarStyle::Block).template("{progress:percent} done");
+        assert_eq!(config.build_template(), "{progress:percent} done");
+        let config = ProgressBarConfig::new().template("{progress:percent} done").style(ProgressBarStyle::Dots);
+        assert_eq!(config.build_template(), "{progress:percent} done");
+
+        // Empty and blank templates fall back to the style
+        for template in ["", "   "] {
+            let config = ProgressBarConfig::new().style(ProgressBarStyle::Block).template(template);
+            assert_eq!(config.build_template(), "{progress:percent} {progress:bar:block:20} {completed}/{total}");
+        }
+    }
     
     #[test]
     fn test_enhanced_progress_bar_customization() {
@@ -432,7 +489,7 @@ mod tests {
         assert!(template.contains(":smooth:25"));
         
         // Check for ETA and speed
-        assert!(template.contains("ETA: {eta}"));
+        assert!(template.contains("ETA: {eta:duration}"));
         assert!(template.contains("bytes/s"));
     }
     
@@ -464,10 +521,10 @@ mod tests {
         // Test ETA formatting
         let config = ProgressBarConfig::new()
             .show_eta(true)
-            .eta_format("Remaining: {eta}");
+            .eta_format("Remaining: {eta:duration}");
             
         let template = config.build_template();
-        assert!(template.contains("Remaining: {eta}"));
+        assert!(template.contains("Remaining: {eta:duration}"));
         
         // Test speed formatting
         let config = ProgressBarConfig::new()
@@ -479,6 +536,33 @@ mod tests {
         assert!(template.contains("Rate: {speed} MB/sec"));
     }
     
+    #[test]
+    fn test_label_position() {
+        use crate::ui::formatter::{ProgressTemplate, TemplateContext};
+        
+        let render = |position| {
+            let config = ProgressBarConfig::new().width(10).show_fraction(false).label_position(position);
+            let mut ctx = TemplateContext::new();
+            ctx.set("progress", 0.5);
+            ProgressTemplate::new(config.build_template()).render(&ctx).unwrap()
+        };
+        
+        assert_eq!(render(LabelPosition::Left), "50% [=====     ]");
+        assert_eq!(render(LabelPosition::Right), "[=====     ] 50%");
+        assert_eq!(render(LabelPosition::Inside), "[===50%    ]");
+        assert_eq!(render(LabelPosition::None), "[=====     ]");
+        
+        // Hiding the percentage wins over its position
+        let config = ProgressBarConfig::new().show_percentage(false).label_position(LabelPosition::Inside);
+        assert_eq!(config.build_template(), "{progress:bar:bar:20} {completed}/{total}");
+        
+        // Too narrow for the label to fit inside
+        let config = ProgressBarConfig::new().width(2).show_fraction(false).label_position(LabelPosition::Inside);
+        let mut ctx = TemplateContext::new();
+        ctx.set("progress", 0.5);
+        assert_eq!(ProgressTemplate::new(config.build_template()).render(&ctx).unwrap(), "[= ]");
+    }
+    
     #[test]
     fn test_smooth_animation() {
         // Test smooth animation flag
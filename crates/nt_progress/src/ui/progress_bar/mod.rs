@@ -2,8 +2,10 @@ mod style;
 mod config;
 mod bar;
 mod multi;
+mod shared;
 
 pub use style::ProgressBarStyle;
-pub use config::ProgressBarConfig;
+pub use config::{LabelPosition, ProgressBarConfig};
 pub use bar::ProgressBar;
-pub use multi::MultiProgressBar; 
\ No newline at end of file
+pub use multi::MultiProgressBar;
+pub use shared::SharedProgressBar; 
\ No newline at end of file
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use crate::errors::ProgressError;
+use super::bar::ProgressBar;
+
+/// A progress bar that can be updated from several tasks at once
+///
+/// Clones share the same bar, so each task can keep its own handle.
+#[derive(Debug, Clone)]
+pub struct SharedProgressBar {
+    bar: Arc<Mutex<ProgressBar>>,
+}
+
+impl SharedProgressBar {
+    /// Wrap a progress bar so it can be shared between tasks
+    pub fn new(bar: ProgressBar) -> Self {
+        Self {
+            bar: Arc::new(Mutex::new(bar)),
+        }
+    }
+
+    /// Update the progress with a new value between 0.0 and 1.0
+    pub async fn update(&self, progress: f64) {
+        self.bar.lock().await.update(progress);
+    }
+
+    /// Update the progress with current and total values
+    pub async fn update_with_values(&self, current: usize, total: usize) {
+        self.bar.lock().await.update_with_values(current, total);
+    }
+
+    /// Add `delta` to the current value, keeping the total
+    ///
+    /// # Returns
+    /// The new current value
+    pub async fn inc(&self, delta: usize) -> usize {
+        let mut bar = self.bar.lock().await;
+        let current = bar.current() + delta;
+        let total = bar.total();
+        bar.update_with_values(current, total);
+        current
+    }
+
+    /// Get the current progress as a value between 0.0 and 1.0
+    pub async fn progress(&self) -> f64 {
+        self.bar.lock().await.progress()
+    }
+
+    /// Render the bar with its current progress
+    pub async fn render(&self) -> Result<String, ProgressError> {
+        self.bar.lock().await.render()
+    }
+
+    /// Get a copy of the bar in its current state
+    pub async fn snapshot(&self) -> ProgressBar {
+        self.bar.lock().await.clone()
+    }
+}
+
+impl From<ProgressBar> for SharedProgressBar {
+    fn from(bar: ProgressBar) -> Self {
+        Self::new(bar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::progress_bar::ProgressBarConfig;
+
+    #[tokio::test]
+    async fn test_shared_progress_bar_from_two_tasks() {
+        let config = ProgressBarConfig::new().width(10).show_percentage(false);
+        let mut bar = ProgressBar::new(config);
+        bar.update_with_values(0, 100);
+        let shared = SharedProgressBar::new(bar);
+
+        let workers: Vec<_> = (0..2).map(|_| {
+            let shared = shared.clone();
+            tokio::spawn(async move {
+                for _ in 0..50 {
+                    shared.inc(1).await;
+                    tokio::task::yield_now().await;
+                }
+            })
+        }).collect();
+        for worker in workers {
+            worker.await.unwrap();
+        }
+
+        let bar = shared.snapshot().await;
+        assert_eq!(bar.current(), 100);
+        assert_eq!(bar.total(), 100);
+        assert_eq!(shared.progress().await, 1.0);
+        assert_eq!(shared.render().await.unwrap(), "[==========] 100/100");
+    }
+}
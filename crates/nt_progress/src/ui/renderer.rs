@@ -1,22 +1,78 @@
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::sync::Mutex;
-use crate::terminal::Terminal;
+use crate::core::base_config::JobStatus;
+use crate::terminal::{Terminal, TextWrapper};
 use std::collections::HashMap;
 
+/// Where a [`Renderer`] writes its output
+pub enum RenderTarget {
+    Stdout,
+    Stderr,
+    /// Any writer, e.g. a file or an in-memory buffer
+    Custom(Box<dyn Write + Send + 'static>),
+}
+
+impl RenderTarget {
+    /// Stdout when it is a terminal, otherwise stderr so that piped stdout
+    /// only carries the program's data.
+    pub fn detect() -> Self {
+        if io::stdout().is_terminal() {
+            RenderTarget::Stdout
+        } else {
+            RenderTarget::Stderr
+        }
+    }
+
+    fn into_writer(self) -> Box<dyn Write + Send + 'static> {
+        match self {
+            RenderTarget::Stdout => Box::new(io::stdout()),
+            RenderTarget::Stderr => Box::new(io::stderr()),
+            RenderTarget::Custom(writer) => writer,
+        }
+    }
+}
+
+impl std::fmt::Debug for RenderTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderTarget::Stdout => write!(f, "Stdout"),
+            RenderTarget::Stderr => write!(f, "Stderr"),
+            RenderTarget::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+/// How a task is drawn besides its output lines, see [`Renderer::render`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskRenderInfo {
+    /// Tasks with a higher priority are drawn first, see [`Renderer::set_priority_ordering`]
+    pub priority: u32,
+    /// Title line drawn alone in place of the output of a collapsed task
+    pub collapsed_title: Option<String>,
+    /// Appended to the first line of the task, such as a retry count
+    pub annotation: Option<String>,
+    /// Ends lines cut to the terminal width, "…" when unset
+    pub truncation_indicator: Option<String>,
+}
+
 /// Responsible for rendering terminal output
 pub struct Renderer {
     terminal: Arc<Terminal>,
     writer: Arc<Mutex<Box<dyn Write + Send + 'static>>>,
+    /// Whether higher priority threads are drawn first instead of in ID order
+    priority_ordering: AtomicBool,
+    /// Whether only failing tasks and a final summary are drawn
+    quiet: AtomicBool,
+    /// Status each failing task was last drawn with in quiet mode
+    reported: std::sync::Mutex<HashMap<usize, JobStatus>>,
 }
 
 impl Renderer {
-    /// Create a new renderer with default settings
+    /// Create a new renderer writing to the [detected](RenderTarget::detect) target
     pub fn new() -> Self {
-        Self {
-            terminal: Arc::new(Terminal::new()),
-            writer: Arc::new(Mutex::new(Box::new(std::io::stdout()))),
-        }
+        Self::with_writer(RenderTarget::detect().into_writer())
     }
     
     /// Create a new renderer with a custom writer
@@ -24,36 +80,107 @@ impl Renderer {
         Self {
             terminal: Arc::new(Terminal::new()),
             writer: Arc::new(Mutex::new(writer)),
+            priority_ordering: AtomicBool::new(true),
+            quiet: AtomicBool::new(false),
+            reported: std::sync::Mutex::new(HashMap::new()),
         }
     }
     
+    /// Send all further output to `target`
+    pub async fn set_target(&self, target: RenderTarget) {
+        *self.writer.lock().await = target.into_writer();
+    }
+    
     /// Get a reference to the terminal
     pub fn terminal(&self) -> &Arc<Terminal> {
         &self.terminal
     }
     
-    /// Render the provided thread outputs to the terminal
-    pub async fn render(&self, outputs: &HashMap<usize, Vec<String>>) -> io::Result<()> {
+    /// Enable or disable priority ordering.
+    ///
+    /// When disabled, threads are always drawn in thread ID order.
+    pub fn set_priority_ordering(&self, enabled: bool) {
+        self.priority_ordering.store(enabled, Ordering::SeqCst);
+    }
+    
+    /// Check whether threads are drawn in priority order
+    pub fn priority_ordering(&self) -> bool {
+        self.priority_ordering.load(Ordering::SeqCst)
+    }
+    
+    /// Enable or disable quiet mode.
+    ///
+    /// When enabled, [`render_failures`](Self::render_failures) and
+    /// [`render_summary`](Self::render_summary) replace the full redraw.
+    pub fn set_quiet(&self, quiet: bool) {
+        self.quiet.store(quiet, Ordering::SeqCst);
+    }
+    
+    /// Check whether quiet mode is enabled
+    pub fn is_quiet(&self) -> bool {
+        self.quiet.load(Ordering::SeqCst)
+    }
+    
+    /// Order in which the threads in `outputs` are drawn.
+    ///
+    /// With priority ordering enabled, threads are sorted by priority descending,
+    /// ties broken by thread ID. Threads missing from `tasks` have priority 0.
+    pub fn render_order(&self, outputs: &HashMap<usize, Vec<String>>, tasks: &HashMap<usize, TaskRenderInfo>) -> Vec<usize> {
+        let mut thread_ids: Vec<usize> = outputs.keys().cloned().collect();
+        if self.priority_ordering() {
+            let priority = |thread_id: &usize| tasks.get(thread_id).map_or(0, |task| task.priority);
+            thread_ids.sort_by(|a, b| priority(b).cmp(&priority(a)).then(a.cmp(b)));
+        } else {
+            thread_ids.sort();
+        }
+        thread_ids
+    }
+    
+    /// Render the provided thread outputs to the terminal.
+    ///
+    /// Collapsed threads are drawn as their title line alone. Annotations, such
+    /// as a retry count, are appended to the first line of their thread. Lines
+    /// wider than the terminal are cut and end with the task's truncation indicator.
+    pub async fn render(
+        &self,
+        outputs: &HashMap<usize, Vec<String>>,
+        tasks: &HashMap<usize, TaskRenderInfo>,
+    ) -> io::Result<()> {
         if outputs.is_empty() {
             return Ok(());
         }
 
+        let width = self.terminal.size().await.0 as usize;
         let mut writer = self.writer.lock().await;
         // Clear screen and move cursor to home position
         write!(writer, "\x1B[2J\x1B[1H")?;
 
         // Optimize for high concurrency by building the output in a single pass
-        let mut sorted_threads: Vec<usize> = outputs.keys().cloned().collect();
-        sorted_threads.sort(); // Sort by thread ID for consistent order
+        let sorted_threads = self.render_order(outputs, tasks);
         
         // Pre-allocate a buffer for the output
         let mut buffer = String::with_capacity(outputs.len() * 50); // Reasonable initial capacity
         
+        let no_info = TaskRenderInfo::default();
         for thread_id in sorted_threads {
-            if let Some(lines) = outputs.get(&thread_id) {
-                for line in lines {
-                    buffer.push_str(line);
-                    buffer.push('\n');
+            let task = tasks.get(&thread_id).unwrap_or(&no_info);
+            let mut wrapper = TextWrapper::new(width);
+            if let Some(indicator) = &task.truncation_indicator {
+                wrapper = wrapper.truncation_marker(indicator.as_str());
+            }
+            let push_line = |buffer: &mut String, line: &str, annotate: bool| {
+                match task.annotation.as_ref().filter(|_| annotate) {
+                    Some(annotation) => buffer.push_str(&wrapper.truncate(&format!("{} {}", line, annotation))),
+                    None => buffer.push_str(&wrapper.truncate(line)),
+                }
+                buffer.push('\n');
+            };
+            if let Some(title) = &task.collapsed_title {
+                push_line(&mut buffer, title, true);
+                buffer.push('\n');
+            } else if let Some(lines) = outputs.get(&thread_id) {
+                for (i, line) in lines.iter().enumerate() {
+                    push_line(&mut buffer, line, i == 0);
                 }
                 // Add a blank line between thread outputs
                 buffer.push('\n');
@@ -66,6 +193,70 @@ impl Renderer {
         Ok(())
     }
     
+    /// Append the output of the tasks that entered the Failed or Retry status.
+    ///
+    /// Each task is drawn once per status it enters, with the status appended to
+    /// its first line. Nothing is cleared, so the output suits logs.
+    pub async fn render_failures(
+        &self,
+        outputs: &HashMap<usize, Vec<String>>,
+        statuses: &HashMap<usize, JobStatus>,
+    ) -> io::Result<()> {
+        let mut failing: Vec<(usize, JobStatus)> = {
+            let mut reported = self.reported.lock().unwrap();
+            statuses.iter()
+                .filter(|(_, status)| matches!(status, JobStatus::Failed | JobStatus::Retry))
+                .filter(|(thread_id, status)| reported.insert(**thread_id, **status) != Some(**status))
+                .map(|(thread_id, status)| (*thread_id, *status))
+                .collect()
+        };
+        if failing.is_empty() {
+            return Ok(());
+        }
+        failing.sort_by_key(|(thread_id, _)| *thread_id);
+
+        let mut buffer = String::new();
+        for (thread_id, status) in failing {
+            let lines = outputs.get(&thread_id).map(Vec::as_slice).unwrap_or_default();
+            match lines.split_first() {
+                Some((first, rest)) => {
+                    buffer.push_str(&format!("{} [{}]\n", first, status));
+                    for line in rest {
+                        buffer.push_str(line);
+                        buffer.push('\n');
+                    }
+                }
+                None => buffer.push_str(&format!("Task {} [{}]\n", thread_id, status)),
+            }
+        }
+
+        let mut writer = self.writer.lock().await;
+        write!(writer, "{}", buffer)?;
+        writer.flush()
+    }
+    
+    /// Write one line counting the tasks in `statuses` by outcome
+    pub async fn render_summary(&self, statuses: &HashMap<usize, JobStatus>) -> io::Result<()> {
+        let count = |status: JobStatus| statuses.values().filter(|s| **s == status).count();
+        let mut writer = self.writer.lock().await;
+        writeln!(
+            writer,
+            "{} tasks: {} completed, {} failed, {} retrying",
+            statuses.len(),
+            count(JobStatus::Completed),
+            count(JobStatus::Failed),
+            count(JobStatus::Retry),
+        )?;
+        writer.flush()
+    }
+    
+    /// Ring the terminal bell
+    pub async fn bell(&self) -> io::Result<()> {
+        let mut writer = self.writer.lock().await;
+        writer.write_all(b"\x07")?;
+        writer.flush()
+    }
+    
     /// Stop the terminal event detection
     pub async fn stop(&self) -> anyhow::Result<()> {
         self.terminal.stop_event_detection().await
@@ -1,6 +1,6 @@
 use clap::{Parser, Subcommand};
 use nt_core::{Result, ArticleStatus, Scraper};
-use crate::{ScraperManager, };
+use crate::{ScraperManager, ScrapeReport, UpdateResult};
 use tracing::info;
 
 #[derive(Parser, Clone)]
@@ -16,6 +16,12 @@ pub enum ScraperCommands {
     Source {
         /// The source to scrape in format country/source (e.g. argentina/clarin). If not specified, scrapes all sources.
         source: Option<String>,
+        /// Re-check already stored articles and update them if their content changed
+        #[arg(long)]
+        update_existing: bool,
+        /// Fetch and parse articles, logging what would be stored without writing to storage
+        #[arg(long)]
+        dry_run: bool,
     },
     /// List available scrapers
     List,
@@ -23,13 +29,26 @@ pub enum ScraperCommands {
     Url {
         /// The URL to scrape
         url: String,
+        /// Update the stored article if its content changed
+        #[arg(long)]
+        update_existing: bool,
+        /// Fetch and parse the article, logging what would be stored without writing to storage
+        #[arg(long)]
+        dry_run: bool,
+        /// Print which selectors matched and the fields extracted, as JSON, instead of scraping
+        #[arg(long)]
+        debug_parse: bool,
     },
 }
 
-pub async fn handle_command(args: ScraperArgs, manager: &mut ScraperManager) -> Result<()> {
+/// Runs a scraper command and returns the tally of scraped articles.
+pub async fn handle_command(args: ScraperArgs, manager: &mut ScraperManager) -> Result<ScrapeReport> {
+    let mut report = ScrapeReport::default();
     match args.command {
-        ScraperCommands::Source { source } => {
-            let articles = manager.scrape_source(source.as_deref()).await?;
+        ScraperCommands::Source { source, update_existing, dry_run } => {
+            manager.set_dry_run(dry_run);
+            let (articles, source_report) = manager.scrape_source_report(source.as_deref(), update_existing).await?;
+            report = source_report;
             for article in articles {
                 info!("📰 Article: {}", article.title);
                 info!("   Source: {}", article.source);
@@ -53,8 +72,20 @@ pub async fn handle_command(args: ScraperArgs, manager: &mut ScraperManager) ->
         ScraperCommands::List => {
             manager.list_scrapers().await?;
         }
-        ScraperCommands::Url { url } => {
+        ScraperCommands::Url { url, debug_parse: true, .. } => {
+            let trace = manager.debug_parse(&url).await?;
+            println!("{}", serde_json::to_string_pretty(&trace)?);
+        }
+        ScraperCommands::Url { url, update_existing: true, dry_run, .. } => {
+            manager.set_dry_run(dry_run);
+            let result = manager.update_if_changed(&url).await?;
+            log_update_result(&url, &result);
+            report.record(&result.status());
+        }
+        ScraperCommands::Url { url, update_existing: false, dry_run, .. } => {
+            manager.set_dry_run(dry_run);
             let article = manager.scrape_url(&url).await?;
+            report.record(&manager.store_if_new(article.clone()).await?);
             info!("📰 Article: {}", article.title);
             info!("   Source: {}", article.source);
             info!("   URL: {}", article.url);
@@ -74,7 +105,15 @@ pub async fn handle_command(args: ScraperArgs, manager: &mut ScraperManager) ->
             info!("");
         }
     }
-    Ok(())
+    Ok(report)
+}
+
+fn log_update_result(url: &str, result: &UpdateResult) {
+    match result {
+        UpdateResult::New => info!("🆕 Stored new article: {}", url),
+        UpdateResult::Unchanged => info!("✅ Article unchanged: {}", url),
+        UpdateResult::Updated(diff) => info!("✏️ Article updated: {} ({:?})", url, diff),
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,526 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use encoding_rs::{Encoding, UTF_8};
+use lazy_static::lazy_static;
+use log::{debug, warn};
+use nt_core::{Error, Result};
+use reqwest::header::{CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{Client, Proxy, RequestBuilder, Response, StatusCode};
+
+/// How long a failing proxy is left out of the rotation
+pub const DEFAULT_PROXY_COOLDOWN: Duration = Duration::from_secs(300);
+
+/// Largest response body, in bytes, read when no [`MAX_RESPONSE_SIZE`] is in scope
+pub const DEFAULT_MAX_RESPONSE_SIZE: usize = 10 * 1024 * 1024;
+
+tokio::task_local! {
+    /// Proxies used by [`get`] for requests made within the current scope
+    pub static PROXY_POOL: Option<Arc<ProxyPool>>;
+
+    /// Validators used by [`get`] for conditional requests made within the current scope
+    pub static HTTP_CACHE: Option<Arc<HttpCache>>;
+
+    /// Largest response body, in bytes, [`read_text`] reads within the current scope
+    pub static MAX_RESPONSE_SIZE: usize;
+}
+
+lazy_static! {
+    static ref DIRECT_CLIENT: Client = Client::new();
+}
+
+struct ProxyEntry {
+    url: String,
+    /// Built on first use and reused for every later request through this proxy
+    client: Option<Client>,
+    failed_at: Option<Instant>,
+}
+
+struct PoolState {
+    entries: Vec<ProxyEntry>,
+    cursor: usize,
+}
+
+/// A list of proxies handed out in round-robin order.
+///
+/// Proxies reported through [`mark_failed`](Self::mark_failed) are skipped until
+/// the cooldown has passed.
+pub struct ProxyPool {
+    state: Mutex<PoolState>,
+    cooldown: Duration,
+}
+
+impl ProxyPool {
+    pub fn new<I, S>(urls: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let entries = urls.into_iter()
+            .map(|url| {
+                let url = url.into();
+                Proxy::all(url.as_str())?;
+                Ok(ProxyEntry { url, client: None, failed_at: None })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            state: Mutex::new(PoolState { entries, cursor: 0 }),
+            cooldown: DEFAULT_PROXY_COOLDOWN,
+        })
+    }
+
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Number of proxies in the pool, including ones cooling down
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of proxies currently in the rotation
+    pub fn available(&self) -> usize {
+        let now = Instant::now();
+        self.state.lock().unwrap().entries.iter()
+            .filter(|entry| !self.cooling_down(entry, now))
+            .count()
+    }
+
+    /// Returns the next usable proxy URL and its client, or `None` if every proxy is cooling down.
+    pub fn next(&self) -> Option<(String, Client)> {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        let len = state.entries.len();
+        for offset in 0..len {
+            let index = (state.cursor + offset) % len;
+            if self.cooling_down(&state.entries[index], now) {
+                continue;
+            }
+            state.cursor = index + 1;
+
+            let entry = &mut state.entries[index];
+            entry.failed_at = None;
+            let client = match &entry.client {
+                Some(client) => client.clone(),
+                None => {
+                    let client = Client::builder()
+                        .proxy(Proxy::all(entry.url.as_str()).ok()?)
+                        .build()
+                        .ok()?;
+                    entry.client = Some(client.clone());
+                    client
+                }
+            };
+            return Some((entry.url.clone(), client));
+        }
+        None
+    }
+
+    /// Takes a proxy out of the rotation until the cooldown has passed.
+    pub fn mark_failed(&self, url: &str) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(entry) = state.entries.iter_mut().find(|entry| entry.url == url) {
+            entry.failed_at = Some(Instant::now());
+        }
+    }
+
+    fn cooling_down(&self, entry: &ProxyEntry, now: Instant) -> bool {
+        entry.failed_at.is_some_and(|failed_at| now.duration_since(failed_at) < self.cooldown)
+    }
+}
+
+/// URLs whose validators an [`HttpCache`] keeps unless told otherwise
+pub const DEFAULT_HTTP_CACHE_CAPACITY: usize = 10_000;
+
+#[derive(Debug, Clone, Default)]
+struct Validators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl Validators {
+    fn of(response: &Response) -> Self {
+        let header = |name| response.headers().get(name)
+            .and_then(|value: &reqwest::header::HeaderValue| value.to_str().ok())
+            .map(str::to_string);
+        Self {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    /// Validators of pages whose content was stored, with the tick they were last used at
+    entries: HashMap<String, (Validators, u64)>,
+    /// Validators of fetched pages waiting for [`HttpCache::commit`]
+    staged: HashMap<String, Validators>,
+    tick: u64,
+}
+
+/// `ETag` and `Last-Modified` values of previously fetched URLs.
+///
+/// Requests for a known URL are sent with `If-None-Match`/`If-Modified-Since`,
+/// and a `304 Not Modified` answer surfaces as [`Error::NotModified`]. The
+/// validators of a response are only used once [`commit`](Self::commit)ted,
+/// after its content was stored, so a page that failed to be stored is fetched
+/// in full again. The least recently used URLs are dropped past the capacity.
+#[derive(Debug)]
+pub struct HttpCache {
+    state: Mutex<CacheState>,
+    capacity: usize,
+}
+
+impl Default for HttpCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpCache {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(CacheState::default()),
+            capacity: DEFAULT_HTTP_CACHE_CAPACITY,
+        }
+    }
+
+    /// Keeps the validators of at most `capacity` URLs
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Number of URLs with stored validators
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.entries.clear();
+        state.staged.clear();
+    }
+
+    /// Uses the validators last fetched for `url` in later requests.
+    ///
+    /// Call once the content of `url` is stored; until then the page is
+    /// fetched unconditionally.
+    pub fn commit(&self, url: &str) {
+        let mut state = self.state.lock().unwrap();
+        let Some(validators) = state.staged.remove(url) else {
+            return;
+        };
+        if validators.is_empty() {
+            state.entries.remove(url);
+            return;
+        }
+        state.tick += 1;
+        let tick = state.tick;
+        state.entries.insert(url.to_string(), (validators, tick));
+        if state.entries.len() > self.capacity {
+            let oldest = state.entries.iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(url, _)| url.clone());
+            if let Some(oldest) = oldest {
+                state.entries.remove(&oldest);
+            }
+        }
+    }
+
+    /// Forgets the validators last fetched for `url` without using them
+    pub fn discard(&self, url: &str) {
+        self.state.lock().unwrap().staged.remove(url);
+    }
+
+    fn apply(&self, url: &str, mut request: RequestBuilder) -> RequestBuilder {
+        let mut state = self.state.lock().unwrap();
+        state.tick += 1;
+        let tick = state.tick;
+        if let Some((validators, used)) = state.entries.get_mut(url) {
+            *used = tick;
+            if let Some(etag) = &validators.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &validators.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        request
+    }
+
+    fn stage(&self, url: &str, validators: Validators) {
+        let mut state = self.state.lock().unwrap();
+        // Pages fetched outside a scrape cycle are never committed
+        if state.staged.len() >= self.capacity && !state.staged.contains_key(url) {
+            let any = state.staged.keys().next().cloned();
+            if let Some(any) = any {
+                state.staged.remove(&any);
+            }
+        }
+        state.staged.insert(url.to_string(), validators);
+    }
+}
+
+/// Sends a GET request, through the next proxy of [`PROXY_POOL`] if one is in scope.
+///
+/// A proxy that cannot complete the request is marked as failed and the request
+/// is retried through the next one. With an [`HTTP_CACHE`] in scope the request
+/// is conditional, and `Error::NotModified` is returned when the server answers 304.
+pub async fn get(url: impl AsRef<str>) -> Result<Response> {
+    let url = url.as_ref();
+    let cache = HTTP_CACHE.try_with(|cache| cache.clone()).ok().flatten();
+    let pool = PROXY_POOL.try_with(|pool| pool.clone()).ok().flatten();
+    let pool = match pool {
+        Some(pool) if !pool.is_empty() => pool,
+        _ => return send(&DIRECT_CLIENT, url, cache.as_deref()).await,
+    };
+
+    for _ in 0..pool.len() {
+        let Some((proxy, client)) = pool.next() else { break };
+        match send(&client, url, cache.as_deref()).await {
+            Err(Error::Http(e)) => {
+                warn!("⚠️ Proxy {} failed for {}: {}", proxy, url, e);
+                pool.mark_failed(&proxy);
+            }
+            result => return result,
+        }
+    }
+
+    Err(Error::Scraping(format!("No working proxy available for {}", url)))
+}
+
+async fn send(client: &Client, url: &str, cache: Option<&HttpCache>) -> Result<Response> {
+    let mut request = client.get(url);
+    if let Some(cache) = cache {
+        request = cache.apply(url, request);
+    }
+
+    let response = request.send().await?;
+    if response.status() == StatusCode::NOT_MODIFIED {
+        debug!("{} not modified since last fetch", url);
+        return Err(Error::NotModified(url.to_string()));
+    }
+    if let Some(cache) = cache {
+        if response.status().is_success() {
+            cache.stage(url, Validators::of(&response));
+        }
+    }
+    Ok(response)
+}
+
+/// What a fetched document is, judging by its `Content-Type` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentKind {
+    /// HTML or XHTML, which is also assumed when the server sends no content type
+    Html,
+    Pdf,
+    /// Anything else, with the media type the server sent
+    Unsupported(String),
+}
+
+impl ContentKind {
+    /// Classifies a `Content-Type` value, ignoring parameters such as `charset`.
+    pub fn from_content_type(content_type: Option<&str>) -> Self {
+        let Some(content_type) = content_type else {
+            return ContentKind::Html;
+        };
+        let media_type = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        match media_type.as_str() {
+            "" | "text/html" | "application/xhtml+xml" => ContentKind::Html,
+            "application/pdf" => ContentKind::Pdf,
+            _ => ContentKind::Unsupported(media_type),
+        }
+    }
+
+    pub fn of(response: &Response) -> Self {
+        Self::from_content_type(response.headers().get(CONTENT_TYPE).and_then(|value| value.to_str().ok()))
+    }
+}
+
+/// Reads a response body as text, aborting once it grows past [`MAX_RESPONSE_SIZE`].
+///
+/// The body is streamed, so an oversized response fails with an `Error::Scraping`
+/// without being held in memory. It is decoded with the charset of the
+/// `Content-Type` header, UTF-8 by default, and invalid sequences are replaced
+/// rather than rejected.
+pub async fn read_text(mut response: Response) -> Result<String> {
+    let limit = MAX_RESPONSE_SIZE.try_with(|limit| *limit).unwrap_or(DEFAULT_MAX_RESPONSE_SIZE);
+    let too_large = |response: &Response| {
+        warn!("⚠️ Skipping {}: response larger than {} bytes", response.url(), limit);
+        Error::Scraping(format!("Response from {} is larger than {} bytes", response.url(), limit))
+    };
+
+    if response.content_length().is_some_and(|length| length > limit as u64) {
+        return Err(too_large(&response));
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > limit {
+            return Err(too_large(&response));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    let content_type = response.headers().get(CONTENT_TYPE).and_then(|value| value.to_str().ok());
+    Ok(decode(&body, content_type))
+}
+
+/// Decodes `body` with the charset named in `content_type`, or UTF-8 if there
+/// is none or it is unknown. A byte order mark takes precedence.
+fn decode(body: &[u8], content_type: Option<&str>) -> String {
+    let encoding = content_type
+        .and_then(|content_type| content_type.split(';').skip(1).find_map(|param| {
+            let (name, value) = param.split_once('=')?;
+            name.trim().eq_ignore_ascii_case("charset").then(|| value.trim().trim_matches('"'))
+        }))
+        .and_then(|label| Encoding::for_label(label.as_bytes()))
+        .unwrap_or(UTF_8);
+    encoding.decode(body).0.into_owned()
+}
+
+/// Reads the body of a fetched article page as HTML.
+///
+/// Responses that are not HTML are rejected with an `Error::Scraping` naming
+/// their content type, instead of being handed to an HTML parser.
+pub async fn read_html(response: Response) -> Result<String> {
+    match ContentKind::of(&response) {
+        ContentKind::Html => read_text(response).await,
+        ContentKind::Pdf => extract_pdf(response).await,
+        ContentKind::Unsupported(content_type) => Err(unsupported(&response, &content_type)),
+    }
+}
+
+/// Hook for turning PDF articles into text; PDFs are rejected until an extractor exists.
+async fn extract_pdf(response: Response) -> Result<String> {
+    Err(unsupported(&response, "application/pdf"))
+}
+
+fn unsupported(response: &Response, content_type: &str) -> Error {
+    warn!("⚠️ Skipping {}: unsupported content type {}", response.url(), content_type);
+    Error::Scraping(format!("Unsupported content type {} for {}", content_type, response.url()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next_url(pool: &ProxyPool) -> Option<String> {
+        pool.next().map(|(url, _)| url)
+    }
+
+    #[test]
+    fn test_round_robin_and_failed_proxy() {
+        let pool = ProxyPool::new(["http://proxy-a.test:8080", "http://proxy-b.test:8080", "http://proxy-c.test:8080"]).unwrap();
+        assert_eq!(pool.len(), 3);
+
+        let order: Vec<_> = (0..4).filter_map(|_| next_url(&pool)).collect();
+        assert_eq!(order, ["http://proxy-a.test:8080", "http://proxy-b.test:8080", "http://proxy-c.test:8080", "http://proxy-a.test:8080"]);
+
+        pool.mark_failed("http://proxy-b.test:8080");
+        assert_eq!(pool.available(), 2);
+        let order: Vec<_> = (0..4).filter_map(|_| next_url(&pool)).collect();
+        assert_eq!(order, ["http://proxy-c.test:8080", "http://proxy-a.test:8080", "http://proxy-c.test:8080", "http://proxy-a.test:8080"]);
+
+        pool.mark_failed("http://proxy-a.test:8080");
+        pool.mark_failed("http://proxy-c.test:8080");
+        assert_eq!(next_url(&pool), None);
+    }
+
+    #[test]
+    fn test_failed_proxy_returns_after_cooldown() {
+        let pool = ProxyPool::new(["http://proxy-a.test:8080", "http://proxy-b.test:8080"])
+            .unwrap()
+            .with_cooldown(Duration::ZERO);
+
+        pool.mark_failed("http://proxy-a.test:8080");
+        assert_eq!(pool.available(), 2);
+        assert_eq!(next_url(&pool).as_deref(), Some("http://proxy-a.test:8080"));
+    }
+
+    #[test]
+    fn test_content_kind() {
+        assert_eq!(ContentKind::from_content_type(None), ContentKind::Html);
+        assert_eq!(ContentKind::from_content_type(Some("text/html; charset=utf-8")), ContentKind::Html);
+        assert_eq!(ContentKind::from_content_type(Some("Application/PDF")), ContentKind::Pdf);
+        assert_eq!(
+            ContentKind::from_content_type(Some("image/png")),
+            ContentKind::Unsupported("image/png".to_string())
+        );
+    }
+
+    fn validators(etag: &str) -> Validators {
+        Validators { etag: Some(etag.to_string()), last_modified: None }
+    }
+
+    fn sent_etag(cache: &HttpCache, url: &str) -> Option<String> {
+        let request = cache.apply(url, DIRECT_CLIENT.get(url)).build().unwrap();
+        request.headers().get(IF_NONE_MATCH).map(|value| value.to_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn test_cache_uses_committed_validators_only() {
+        let cache = HttpCache::new();
+        cache.stage("http://a.test/", validators("\"a1\""));
+        assert_eq!(sent_etag(&cache, "http://a.test/"), None);
+        cache.commit("http://a.test/");
+        assert_eq!(sent_etag(&cache, "http://a.test/").as_deref(), Some("\"a1\""));
+
+        // A newer response that was not stored keeps the committed validators
+        cache.stage("http://a.test/", validators("\"a2\""));
+        cache.discard("http://a.test/");
+        cache.commit("http://a.test/");
+        assert_eq!(sent_etag(&cache, "http://a.test/").as_deref(), Some("\"a1\""));
+
+        // A response without validators forgets them once stored
+        cache.stage("http://a.test/", Validators::default());
+        cache.commit("http://a.test/");
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_cache_drops_least_recently_used() {
+        let cache = HttpCache::new().with_capacity(2);
+        for url in ["http://a.test/", "http://b.test/"] {
+            cache.stage(url, validators("\"v\""));
+            cache.commit(url);
+        }
+        // Using a keeps it, so b is the one dropped
+        assert!(sent_etag(&cache, "http://a.test/").is_some());
+        cache.stage("http://c.test/", validators("\"v\""));
+        cache.commit("http://c.test/");
+        assert_eq!(cache.len(), 2);
+        assert!(sent_etag(&cache, "http://a.test/").is_some());
+        assert!(sent_etag(&cache, "http://b.test/").is_none());
+    }
+
+    #[test]
+    fn test_decode_uses_charset() {
+        let latin1 = b"Caf\xe9 con le\xf1a";
+        assert_eq!(decode(latin1, Some("text/html; charset=ISO-8859-1")), "Café con leña");
+        assert_eq!(decode(latin1, Some("text/html;charset=\"latin1\"")), "Café con leña");
+        // Without a charset the body is UTF-8, with invalid bytes replaced
+        assert_eq!(decode("Café".as_bytes(), Some("text/html")), "Café");
+        assert_eq!(decode(latin1, None), "Caf\u{FFFD} con le\u{FFFD}a");
+        assert_eq!(decode("Café".as_bytes(), Some("text/html; charset=unknown")), "Café");
+    }
+
+    #[test]
+    fn test_invalid_proxy_url() {
+        assert!(ProxyPool::new(["not a proxy"]).is_err());
+    }
+}
@@ -1,13 +1,60 @@
 pub mod manager;
+pub mod http;
 pub mod cli;
 pub mod scrapers;
 pub mod logging;
+pub mod tagging;
+pub mod lock;
+pub mod pipeline;
 
 pub use scrapers::ScraperType;
-pub use nt_core::{Scraper, ArticleStatus, SourceMetadata, RegionMetadata};
+pub use nt_core::{Scraper, ArticleStatus, SourceMetadata, RegionMetadata, Capabilities};
 pub use cli::{ScraperArgs, ScraperCommands, handle_command};
-pub use manager::ScraperManager;
+pub use manager::{ScraperManager, ArticleDiff, UpdateResult, ScrapeReport, ScrapeEvent};
+pub use http::{ContentKind, HttpCache, ProxyPool};
+pub use lock::ScrapeLock;
+pub use pipeline::{Pipeline, ProcessingStage};
 
 pub mod prelude {
     pub use nt_core::{Article, Result, Error, Scraper};
-} 
\ No newline at end of file
+}
+
+/// Reports the storage backends and inference models compiled into this
+/// build, and the scrapers from [`scrapers::get_scraper_factories`].
+pub async fn runtime_capabilities() -> Capabilities {
+    let mut storage_backends = nt_storage::get_available_storage_backends().await.into_keys().collect::<Vec<_>>();
+    storage_backends.sort();
+    let mut inference_models = nt_inference::models::get_available_models().into_keys().collect::<Vec<_>>();
+    inference_models.sort();
+    let scrapers = scrapers::get_scraper_factories()
+        .iter()
+        .map(|factory| factory().source_metadata().name.to_string())
+        .collect();
+
+    Capabilities { storage_backends, inference_models, scrapers }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_runtime_capabilities() {
+        let capabilities = runtime_capabilities().await;
+
+        let storage_backends = nt_storage::get_available_storage_backends().await;
+        assert_eq!(capabilities.storage_backends.len(), storage_backends.len());
+        assert!(capabilities.storage_backends.iter().all(|name| storage_backends.contains_key(name)));
+        assert!(capabilities.storage_backends.contains(&"memory".to_string()));
+        assert!(capabilities.storage_backends.is_sorted());
+
+        let models = nt_inference::models::get_available_models();
+        assert_eq!(capabilities.inference_models.len(), models.len());
+        assert!(capabilities.inference_models.iter().all(|name| models.contains_key(name)));
+        assert!(capabilities.inference_models.contains(&"dummy".to_string()));
+        assert!(capabilities.inference_models.is_sorted());
+
+        assert_eq!(capabilities.scrapers.len(), scrapers::get_scraper_factories().len());
+        assert!(capabilities.scrapers.contains(&"Clarín".to_string()));
+    }
+}
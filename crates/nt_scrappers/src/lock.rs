@@ -0,0 +1,159 @@
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use log::warn;
+use nt_core::{Error, Result};
+use sha2::{Digest, Sha256};
+
+/// How long a lock that is not refreshed is honoured before another instance may take it over
+pub const DEFAULT_LOCK_STALE_AFTER: Duration = Duration::from_secs(3600);
+
+/// Guards against several scraper instances working on the same storage at once.
+///
+/// The lock is a file named after the storage it protects, created atomically on
+/// [`acquire`](Self::acquire) and removed when the lock is dropped. A lock file that
+/// has not been [`refresh`](Self::refresh)ed within the stale timeout is assumed to
+/// belong to a crashed instance and is taken over.
+#[derive(Debug)]
+pub struct ScrapeLock {
+    path: PathBuf,
+}
+
+impl ScrapeLock {
+    /// Lock `key`, usually the storage backend and its url, in the system temp directory.
+    pub fn acquire(key: &str, stale_after: Duration) -> Result<Self> {
+        Self::acquire_in(&std::env::temp_dir(), key, stale_after)
+    }
+
+    /// Lock `key` with the lock file placed in `dir`.
+    pub fn acquire_in(dir: &Path, key: &str, stale_after: Duration) -> Result<Self> {
+        let digest = Sha256::digest(key.as_bytes());
+        let path = dir.join(format!("nt-scrape-{:x}.lock", digest));
+
+        match Self::create(&path) {
+            Err(Error::Io(e)) if e.kind() == ErrorKind::AlreadyExists => Self::take_over(&path, stale_after),
+            result => result,
+        }
+    }
+
+    /// Replaces the lock file at `path` if it is stale.
+    ///
+    /// Takeovers hold an OS lock on a guard file next to the lock, so of several
+    /// instances finding the same stale lock only the first replaces it; the
+    /// others then find a fresh lock. The guard file is left in place.
+    fn take_over(path: &Path, stale_after: Duration) -> Result<Self> {
+        let guard = OpenOptions::new().write(true).create(true).truncate(false).open(path.with_extension("guard"))?;
+        guard.lock()?;
+
+        let age = match fs::metadata(path) {
+            Ok(metadata) => metadata.modified()?.elapsed().unwrap_or_default(),
+            // Released in the meantime
+            Err(e) if e.kind() == ErrorKind::NotFound => return Self::create(path),
+            Err(e) => return Err(e.into()),
+        };
+        if age < stale_after {
+            let owner = fs::read_to_string(path).unwrap_or_default();
+            return Err(Error::Scraping(format!(
+                "another scraper (pid {}) is already running against this storage, lock file: {}",
+                owner.trim(),
+                path.display()
+            )));
+        }
+        warn!("⚠️ Taking over stale lock {} ({}s old)", path.display(), age.as_secs());
+        fs::remove_file(path)?;
+        // Fails if an instance that did not need a takeover created the lock in between
+        Self::create(path)
+    }
+
+    fn create(path: &Path) -> Result<Self> {
+        let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+        write!(file, "{}", std::process::id())?;
+        Ok(Self { path: path.to_path_buf() })
+    }
+
+    /// Marks the lock as still in use so it does not become stale.
+    pub fn refresh(&self) -> Result<()> {
+        let file = OpenOptions::new().write(true).open(&self.path)?;
+        file.set_modified(SystemTime::now())?;
+        Ok(())
+    }
+
+    /// The lock file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ScrapeLock {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_file(&self.path) {
+            warn!("⚠️ Failed to remove lock {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lock_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("nt-lock-test-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_second_acquisition_fails() {
+        let dir = lock_dir("second");
+        let lock = ScrapeLock::acquire_in(&dir, "qdrant|http://localhost:6333", DEFAULT_LOCK_STALE_AFTER).unwrap();
+        assert!(lock.path().exists());
+
+        let err = ScrapeLock::acquire_in(&dir, "qdrant|http://localhost:6333", DEFAULT_LOCK_STALE_AFTER).unwrap_err();
+        assert!(err.to_string().contains("already running"));
+
+        // Other storages are not affected
+        let other = ScrapeLock::acquire_in(&dir, "qdrant|http://otherhost:6333", DEFAULT_LOCK_STALE_AFTER).unwrap();
+        drop(other);
+
+        let path = lock.path().to_path_buf();
+        drop(lock);
+        assert!(!path.exists());
+        ScrapeLock::acquire_in(&dir, "qdrant|http://localhost:6333", DEFAULT_LOCK_STALE_AFTER).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_stale_lock_is_taken_over() {
+        let dir = lock_dir("stale");
+        let stale = ScrapeLock::acquire_in(&dir, "memory", DEFAULT_LOCK_STALE_AFTER).unwrap();
+        let file = OpenOptions::new().write(true).open(stale.path()).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(7200)).unwrap();
+        // Simulate a crashed instance that never removed its lock
+        std::mem::forget(stale);
+
+        let lock = ScrapeLock::acquire_in(&dir, "memory", DEFAULT_LOCK_STALE_AFTER).unwrap();
+        lock.refresh().unwrap();
+        assert!(ScrapeLock::acquire_in(&dir, "memory", DEFAULT_LOCK_STALE_AFTER).is_err());
+        drop(lock);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_stale_lock_is_taken_over_once() {
+        let dir = lock_dir("race");
+        let stale = ScrapeLock::acquire_in(&dir, "memory", DEFAULT_LOCK_STALE_AFTER).unwrap();
+        let file = OpenOptions::new().write(true).open(stale.path()).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(7200)).unwrap();
+        std::mem::forget(stale);
+
+        let handles: Vec<_> = (0..8).map(|_| {
+            let dir = dir.clone();
+            std::thread::spawn(move || ScrapeLock::acquire_in(&dir, "memory", DEFAULT_LOCK_STALE_AFTER))
+        }).collect();
+        let locks: Vec<_> = handles.into_iter().filter_map(|handle| handle.join().unwrap().ok()).collect();
+        assert_eq!(locks.len(), 1);
+        drop(locks);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,31 +1,177 @@
 use async_trait::async_trait;
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::collections::HashMap;
 use std::ops::Deref;
-use nt_core::{Article, Result, Error, ArticleStorage, InferenceModel, ArticleStatus, Scraper};
+use nt_core::{Article, ArticleFilter, ArticleIdScheme, EmbeddingModelInfo, Result, Error, ArticleStorage, InferenceModel, ArticleStatus, ParseTrace, Scraper, StorageStats};
 use crate::scrapers::ScraperType;
-use log::info;
-use tokio::sync::{Mutex as TokioMutex, Semaphore, mpsc};
+use log::{info, warn};
+use tokio::sync::{Mutex as TokioMutex, Semaphore, broadcast, mpsc};
 use futures::future::join_all;
 use std::sync::Mutex as StdMutex;
-use tokio::task::JoinHandle;
 use std::io::{stderr, IsTerminal};
 use anyhow;
 use std::io::Write;
-use nt_progress::{ProgressDisplay, ThreadLogger, Config, ThreadMode, TaskHandle};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tokio::time::sleep;
 use nt_core::ArticleSection;
 use crate::scrapers::{ScraperFactory, get_scraper_factories};
+use crate::pipeline::{Pipeline, StageContext};
+use nt_inference::CachingModel;
+use crate::http::{HttpCache, ProxyPool, DEFAULT_MAX_RESPONSE_SIZE, HTTP_CACHE, MAX_RESPONSE_SIZE, PROXY_POOL};
 
 type BoxedScraper = Box<dyn Scraper + Send + Sync>;
 
+/// Concurrent article fetches per source when no override is set
+pub const DEFAULT_SOURCE_CONCURRENCY: usize = 4;
+
+/// Scrape events buffered for each subscriber before the oldest are dropped
+pub const SCRAPE_EVENT_CAPACITY: usize = 256;
+
+/// Scraped articles that may wait for storage before fetching slows down
+pub const DEFAULT_STORE_QUEUE_CAPACITY: usize = 16;
+
+/// Longest a scraper may take to fetch one article, or a source's article list
+pub const DEFAULT_OPERATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Differences between a stored article and a freshly scraped copy.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArticleDiff {
+    pub title_changed: bool,
+    pub content_changed: bool,
+    pub summary_changed: bool,
+    pub sections_added: usize,
+    pub sections_removed: usize,
+    pub authors_changed: bool,
+    pub word_count_delta: i64,
+}
+
+impl ArticleDiff {
+    /// Returns true if any field differs between the two articles
+    pub fn has_changes(&self) -> bool {
+        self.title_changed
+            || self.content_changed
+            || self.summary_changed
+            || self.sections_added > 0
+            || self.sections_removed > 0
+            || self.authors_changed
+            || self.word_count_delta != 0
+    }
+}
+
+/// Outcome of re-checking an article against storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateResult {
+    /// The stored article matches the current version
+    Unchanged,
+    /// The stored article was replaced with the current version
+    Updated(ArticleDiff),
+    /// The article was not stored before and has been added
+    New,
+}
+
+impl UpdateResult {
+    /// The article status this result corresponds to
+    pub fn status(&self) -> ArticleStatus {
+        match self {
+            UpdateResult::New => ArticleStatus::New,
+            UpdateResult::Updated(_) => ArticleStatus::Updated,
+            UpdateResult::Unchanged => ArticleStatus::Unchanged,
+        }
+    }
+}
+
+/// Tally of the articles seen during one scrape cycle.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct ScrapeReport {
+    pub new: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    /// URLs that could not be scraped or stored, with the error message
+    pub failed: Vec<(String, String)>,
+}
+
+impl ScrapeReport {
+    /// Counts an article with the given status
+    pub fn record(&mut self, status: &ArticleStatus) {
+        match status {
+            ArticleStatus::New => self.new += 1,
+            ArticleStatus::Updated => self.updated += 1,
+            ArticleStatus::Unchanged => self.unchanged += 1,
+        }
+    }
+
+    /// Records a URL that could not be processed
+    pub fn record_failure(&mut self, url: impl Into<String>, error: impl std::fmt::Display) {
+        self.failed.push((url.into(), error.to_string()));
+    }
+
+    /// Number of URLs accounted for, including failures
+    pub fn total(&self) -> usize {
+        self.new + self.updated + self.unchanged + self.failed.len()
+    }
+}
+
+impl std::fmt::Display for ScrapeReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} new, {} updated, {} unchanged, {} failed",
+            self.new, self.updated, self.unchanged, self.failed.len())
+    }
+}
+
+/// Progress of a scrape cycle, published to [`ScraperManager::subscribe`] subscribers.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "event")]
+pub enum ScrapeEvent {
+    /// Article URLs are being fetched from a source
+    Started { source: String },
+    /// An article was scraped and compared against storage
+    ArticleScraped { source: String, url: String, status: ArticleStatus },
+    /// The article URLs of a source, or a single article when `url` is set,
+    /// could not be scraped or stored
+    Error { source: String, url: Option<String>, msg: String },
+    /// The cycle is over
+    Finished { report: ScrapeReport },
+}
+
 pub struct ScraperManager {
     storage: Arc<dyn ArticleStorage>,
     inference: Arc<dyn InferenceModel>,
+    /// Cache wrapping `inference`, flushed at the end of each scrape cycle
+    inference_cache: Option<Arc<CachingModel>>,
     factories: Vec<ScraperFactory>,
     semaphore: Arc<Semaphore>,
-    inference_tasks: Arc<TokioMutex<Vec<JoinHandle<Result<()>>>>>,
+    /// Store articles without summaries/embeddings when inference fails
+    graceful_degradation: bool,
+    /// Articles stored without inference results, waiting to be reprocessed
+    pending: Arc<TokioMutex<Vec<Article>>>,
+    /// File the pending articles are saved to, so they survive a restart
+    pending_path: Option<PathBuf>,
+    /// Concurrent fetches for sources without an override
+    default_concurrency: usize,
+    /// Per-source concurrent fetch limits, keyed by scraper CLI name
+    source_concurrency: HashMap<String, usize>,
+    /// Proxies that scraper requests are rotated through
+    proxy_pool: Option<Arc<ProxyPool>>,
+    /// Validators for conditional requests, so unchanged pages are not downloaded again
+    http_cache: Arc<HttpCache>,
+    /// Fetch and compare articles without writing them to storage
+    dry_run: bool,
+    /// Publishes the progress of scrape cycles
+    events: broadcast::Sender<ScrapeEvent>,
+    /// Scraped articles that may wait for the store stage before fetching blocks
+    store_queue_capacity: usize,
+    /// Scraped articles currently waiting for the store stage
+    store_queue_depth: Arc<AtomicUsize>,
+    /// Stages run on new and changed articles
+    pipeline: Pipeline,
+    /// Deadline of each `Scraper::scrape_article` call
+    article_timeout: Duration,
+    /// Deadline of each `Scraper::get_article_urls` call
+    url_list_timeout: Duration,
+    /// Largest response body, in bytes, scrapers read
+    max_response_size: usize,
 }
 
 impl ScraperManager {
@@ -33,12 +179,246 @@ impl ScraperManager {
         Ok(Self {
             storage,
             inference,
+            inference_cache: None,
             factories: get_scraper_factories(),
             semaphore: Arc::new(Semaphore::new(10)),
-            inference_tasks: Arc::new(TokioMutex::new(Vec::new())),
+            graceful_degradation: false,
+            pending: Arc::new(TokioMutex::new(Vec::new())),
+            pending_path: None,
+            default_concurrency: DEFAULT_SOURCE_CONCURRENCY,
+            source_concurrency: HashMap::new(),
+            proxy_pool: None,
+            http_cache: Arc::new(HttpCache::new()),
+            dry_run: false,
+            events: broadcast::channel(SCRAPE_EVENT_CAPACITY).0,
+            store_queue_capacity: DEFAULT_STORE_QUEUE_CAPACITY,
+            store_queue_depth: Arc::new(AtomicUsize::new(0)),
+            pipeline: Pipeline::default(),
+            article_timeout: DEFAULT_OPERATION_TIMEOUT,
+            url_list_timeout: DEFAULT_OPERATION_TIMEOUT,
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
         })
     }
 
+    /// Receives the events of every scrape cycle started from now on.
+    ///
+    /// Events are published by [`scrape_source_report`](Self::scrape_source_report),
+    /// and so by [`scrape_source`](Self::scrape_source).
+    pub fn subscribe(&self) -> broadcast::Receiver<ScrapeEvent> {
+        self.events.subscribe()
+    }
+
+    fn publish(&self, event: ScrapeEvent) {
+        // Nobody listening is not an error
+        let _ = self.events.send(event);
+    }
+
+    /// Sets how many articles are fetched at once from sources without their own limit.
+    pub fn set_default_concurrency(&mut self, limit: usize) {
+        self.default_concurrency = limit.max(1);
+    }
+
+    /// Sets how many articles are fetched at once from the source with the given CLI name.
+    pub fn set_source_concurrency(&mut self, cli_name: &str, limit: usize) {
+        self.source_concurrency.insert(cli_name.to_string(), limit.max(1));
+    }
+
+    /// Sets how many scraped articles may wait for storage before fetching
+    /// stops until the store stage catches up.
+    pub fn set_store_queue_capacity(&mut self, capacity: usize) {
+        self.store_queue_capacity = capacity.max(1);
+    }
+
+    /// Number of scraped articles currently waiting to be stored
+    pub fn store_queue_depth(&self) -> usize {
+        self.store_queue_depth.load(Ordering::SeqCst)
+    }
+
+    /// Replaces the stages run on new and changed articles.
+    ///
+    /// Fails, keeping the current pipeline, if the stages cannot run in this
+    /// order, see [`Pipeline::validate`].
+    pub fn set_pipeline(&mut self, pipeline: Pipeline) -> Result<()> {
+        pipeline.validate()?;
+        self.pipeline = pipeline;
+        Ok(())
+    }
+
+    pub fn pipeline(&self) -> &Pipeline {
+        &self.pipeline
+    }
+
+    /// The stages run on new and changed articles, to add, remove or reorder them
+    pub fn pipeline_mut(&mut self) -> &mut Pipeline {
+        &mut self.pipeline
+    }
+
+    fn stage_context(&self) -> StageContext {
+        StageContext::new(self.storage.clone(), self.inference.clone(), self.semaphore.clone())
+    }
+
+    /// Returns the concurrent fetch limit for a scraper.
+    pub fn concurrency_for(&self, scraper: &dyn Scraper) -> usize {
+        scraper.cli_names().iter()
+            .find_map(|name| self.source_concurrency.get(*name).copied())
+            .unwrap_or(self.default_concurrency)
+    }
+
+    /// Routes scraper requests through the proxies of `pool`, one after another.
+    pub fn set_proxy_pool(&mut self, pool: ProxyPool) {
+        self.proxy_pool = Some(Arc::new(pool));
+    }
+
+    pub fn proxy_pool(&self) -> Option<&Arc<ProxyPool>> {
+        self.proxy_pool.as_ref()
+    }
+
+    pub fn http_cache(&self) -> &Arc<HttpCache> {
+        &self.http_cache
+    }
+
+    /// Fails scrapes of a single article taking longer than `timeout` with `Error::Timeout`
+    pub fn set_article_timeout(&mut self, timeout: Duration) {
+        self.article_timeout = timeout;
+    }
+
+    /// Fails fetches of a source's article list taking longer than `timeout` with `Error::Timeout`
+    pub fn set_url_list_timeout(&mut self, timeout: Duration) {
+        self.url_list_timeout = timeout;
+    }
+
+    /// Aborts reading responses larger than `bytes` with `Error::Scraping`
+    pub fn set_max_response_size(&mut self, bytes: usize) {
+        self.max_response_size = bytes;
+    }
+
+    async fn fetch_article_urls(&self, scraper: &dyn Scraper) -> Result<Vec<String>> {
+        // Listings are fetched in full: an unchanged front page can still link
+        // articles that failed to be stored last time
+        let future = HTTP_CACHE.scope(None, scraper.get_article_urls());
+        nt_core::timeout(self.url_list_timeout, self.with_http(future)).await?
+    }
+
+    /// Keeps the HTTP validators fetched for `url` once its article is stored,
+    /// so the next fetch is conditional, and forgets them otherwise
+    fn settle_validators(&self, url: &str, stored: bool) {
+        if stored && !self.dry_run {
+            self.http_cache.commit(url);
+        } else {
+            self.http_cache.discard(url);
+        }
+    }
+
+    /// Runs `future` with the proxy pool, HTTP cache and response size limit in
+    /// scope for [`crate::http::get`] and [`crate::http::read_text`]
+    async fn with_http<F: std::future::Future>(&self, future: F) -> F::Output {
+        let future = HTTP_CACHE.scope(Some(self.http_cache.clone()), future);
+        let future = MAX_RESPONSE_SIZE.scope(self.max_response_size, future);
+        PROXY_POOL.scope(self.proxy_pool.clone(), future).await
+    }
+
+    /// When enabled, articles whose inference fails are still stored, with the
+    /// embedding if one was generated before the failure, and queued for
+    /// [`reprocess_pending`](Self::reprocess_pending), see also
+    /// [`set_pending_path`](Self::set_pending_path).
+    pub fn set_graceful_degradation(&mut self, enabled: bool) {
+        self.graceful_degradation = enabled;
+    }
+
+    /// When enabled, articles are scraped and compared against storage, but new
+    /// and changed articles are only logged instead of being stored.
+    pub fn set_dry_run(&mut self, enabled: bool) {
+        self.dry_run = enabled;
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Number of articles waiting to be reprocessed
+    pub async fn pending_count(&self) -> usize {
+        self.pending.lock().await.len()
+    }
+
+    /// Saves the articles waiting to be reprocessed to `path`, loading the ones
+    /// a previous run left there.
+    ///
+    /// Without it the queue only lives in memory, and articles that vector
+    /// backends refused to store without an embedding are lost on restart.
+    pub async fn set_pending_path(&mut self, path: impl Into<PathBuf>) -> Result<()> {
+        let path = path.into();
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => {
+                let saved: Vec<Article> = serde_json::from_slice(&bytes)?;
+                info!("📥 Loaded {} pending articles from {}", saved.len(), path.display());
+                self.pending.lock().await.extend(saved);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        self.pending_path = Some(path);
+        Ok(())
+    }
+
+    /// Writes `pending` to the pending file, if any, replacing it in one step
+    async fn save_pending(&self, pending: &[Article]) -> Result<()> {
+        let Some(path) = &self.pending_path else {
+            return Ok(());
+        };
+        let tmp = path.with_extension("tmp");
+        tokio::fs::write(&tmp, serde_json::to_vec(pending)?).await?;
+        tokio::fs::rename(&tmp, path).await?;
+        Ok(())
+    }
+
+    /// Reuses the summaries and embeddings of content inferred before, keeping
+    /// them in `path` across runs, see [`CachingModel`].
+    pub async fn set_inference_cache(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let cache = Arc::new(CachingModel::persistent(self.inference.clone(), path).await?);
+        self.inference = cache.clone();
+        self.inference_cache = Some(cache);
+        Ok(())
+    }
+
+    /// Runs inference again for articles stored while the model was unavailable.
+    ///
+    /// Articles that still fail stay pending. Returns the number of articles
+    /// that were reprocessed.
+    pub async fn reprocess_pending(&self) -> Result<usize> {
+        let pending = std::mem::take(&mut *self.pending.lock().await);
+        let mut reprocessed = 0;
+        let mut still_pending = Vec::new();
+
+        for article in pending {
+            match self.pipeline.run(article.clone(), &mut self.stage_context()).await {
+                Ok(_) => reprocessed += 1,
+                Err(e) => {
+                    warn!("⚠️ Reprocessing failed for {}: {}", article.url, e);
+                    still_pending.push(article);
+                }
+            }
+        }
+
+        let mut pending = self.pending.lock().await;
+        pending.extend(still_pending);
+        self.save_pending(&pending).await?;
+        info!("✨ Reprocessed {} pending articles", reprocessed);
+        Ok(reprocessed)
+    }
+
+    /// Whether stored embeddings come from a different model than the current one
+    pub async fn embeddings_outdated(&self) -> Result<bool> {
+        nt_storage::embedding_mismatch(self.storage.as_ref(), self.inference.as_ref()).await
+    }
+
+    /// Regenerates all stored embeddings with the current model, updating
+    /// `config.vector_size` to match. Returns the number of articles re-embedded.
+    pub async fn reembed_all(&self, config: &mut nt_storage::BackendConfig) -> Result<usize> {
+        let reembedded = nt_storage::reembed_all(self.storage.as_ref(), self.inference.as_ref(), config).await?;
+        info!("✨ Re-embedded {} articles with {}", reembedded, self.inference.name());
+        Ok(reembedded)
+    }
+
     pub fn add_scraper_factory(&mut self, factory: ScraperFactory) {
         self.factories.push(factory);
     }
@@ -76,220 +456,317 @@ impl ScraperManager {
         Ok(result)
     }
 
-    async fn process_article(&self, mut article: Article) -> Result<()> {
-        info!("📰 Processing article: {}", article.title);
-        
-        // Generate article summary
-        info!("🤖 Generating summary for article: {}", article.title);
-        let _permit = self.semaphore.acquire().await.map_err(|e| nt_core::Error::External(e.into()))?;
-        article.summary = Some(self.inference.summarize_article(&article).await?);
-        info!("✨ Summary generated successfully: {:?}", article.summary);
-
-        // Generate section summaries and embeddings in parallel
-        let num_sections = article.sections.len();
-        info!("📑 Processing {} sections", num_sections);
-        
-        let section_futures: Vec<_> = article.sections.iter_mut().enumerate().map(|(i, section)| {
-            let inference = self.inference.clone();
-            let semaphore = self.semaphore.clone();
-            async move {
-                info!("📝 Processing section {}/{}", i + 1, num_sections);
-                
-                // Generate section summary
-                info!("🤖 Generating summary for section {}", i + 1);
-                let _permit = semaphore.acquire().await.map_err(|e| nt_core::Error::External(e.into()))?;
-                section.summary = Some(inference.summarize_sections(&[section.clone()]).await?[0].clone());
-                info!("✨ Section summary generated: {:?}", section.summary);
-                
-                // Generate section embedding
-                info!("🔢 Generating embedding for section {}", i + 1);
-                let _permit = semaphore.acquire().await.map_err(|e| nt_core::Error::External(e.into()))?;
-                section.embedding = Some(inference.generate_embeddings(&section.content).await?);
-                info!("✨ Section embedding generated");
-                
-                Ok::<_, nt_core::Error>(())
-            }
-        }).collect();
-
-        join_all(section_futures).await.into_iter().collect::<Result<Vec<_>>>()?;
+    async fn process_article(&self, article: Article) -> Result<()> {
+        if self.dry_run {
+            info!("🔍 Dry run, would store new article: {}", article.url);
+            return Ok(());
+        }
 
-        // Generate article embedding for similarity search
-        info!("🔢 Generating article embedding for similarity search");
-        let _permit = self.semaphore.acquire().await.map_err(|e| nt_core::Error::External(e.into()))?;
-        let article_embedding = self.inference.generate_embeddings(&article.content).await?;
-        info!("✨ Article embedding generated");
+        // Generating embeddings is the expensive step, so check storage first
+        if self.is_stored_unchanged(&article).await? {
+            info!("⏭️ Article unchanged since it was stored, skipping: {}", article.url);
+            return Ok(());
+        }
 
-        // Find similar articles
-        info!("🔍 Finding similar articles");
-        let similar_articles = self.storage.find_similar(&article_embedding, 5).await?;
-        info!("✨ Found {} similar articles", similar_articles.len());
-        
-        // Process similar articles in parallel
-        info!("🔄 Converting similar articles to related articles");
-        let similar_futures: Vec<_> = similar_articles.into_iter()
-            .filter(|a| a.url != article.url) // Exclude self
-            .map(|a| {
-                let inference = self.inference.clone();
-                let semaphore = self.semaphore.clone();
-                let article_embedding = article_embedding.clone();
-                async move {
-                    info!("📊 Calculating similarity score for article: {}", a.title);
-                    let _permit = semaphore.acquire().await.map_err(|e| nt_core::Error::External(e.into()))?;
-                    let a_embedding = inference.generate_embeddings(&a.content).await?;
-                    let similarity = nt_core::cosine_similarity(&article_embedding, &a_embedding);
-                    info!("✨ Similarity score calculated: {:.2}", similarity);
-                    let related = nt_core::RelatedArticle {
-                        article: a,
-                        similarity_score: Some(similarity),
-                    };
-                    Ok::<_, nt_core::Error>(related)
+        info!("📰 Processing article: {}", article.title);
+        let mut ctx = self.stage_context();
+        let mut article = article;
+        for stage in self.pipeline.stages() {
+            article = match stage.process(article.clone(), &mut ctx).await {
+                Ok(article) => article,
+                Err(e) if self.graceful_degradation && stage.uses_inference() => {
+                    warn!("⚠️ Inference failed for {}, storing without summary: {}", article.url, e);
+                    // Vector-only backends reject articles that were not embedded yet;
+                    // the article is still queued, and stored in full once inference recovers
+                    if let Err(e) = self.storage.store_article(&article, &ctx.embedding).await {
+                        warn!("⚠️ Could not store {} before inference: {}", article.url, e);
+                    }
+                    let mut pending = self.pending.lock().await;
+                    pending.push(article);
+                    self.save_pending(&pending).await?;
+                    return Ok(());
                 }
-            })
-            .collect();
-
-        article.related_articles = join_all(similar_futures).await.into_iter().collect::<Result<Vec<_>>>()?;
-        info!("✨ Related articles processed");
-
-        // Store the processed article
-        info!("💾 Storing processed article");
-        self.storage.store_article(&article, &article_embedding).await?;
-        info!("✨ Article stored successfully");
+                Err(e) => return Err(e),
+            };
+        }
 
         info!("✅ Article processing completed: {}", article.title);
         Ok(())
     }
 
-    async fn queue_inference_task(&self, article: Article) {
-        let inference = self.inference.clone();
-        let storage = self.storage.clone();
-        let semaphore = self.semaphore.clone();
-        
-        let handle = tokio::spawn(async move {
-            let _permit = semaphore.acquire().await.map_err(|e| nt_core::Error::External(e.into()))?;
-            
-            let mut article = article;
-            let mut emoji_chain = String::new();
-            
-            // Generate article summary
-            article.summary = Some(inference.summarize_article(&article).await?);
-            emoji_chain.push_str("🤖");
-
-            // Generate section summaries and embeddings in parallel
-            let num_sections = article.sections.len();
-            let section_futures: Vec<_> = article.sections.iter_mut().enumerate().map(|(i, section)| {
-                let inference = inference.clone();
-                let semaphore = semaphore.clone();
-                async move {
-                    let _permit = semaphore.acquire().await.map_err(|e| nt_core::Error::External(e.into()))?;
-                    section.summary = Some(inference.summarize_sections(&[section.clone()]).await?[0].clone());
-                    section.embedding = Some(inference.generate_embeddings(&section.content).await?);
-                    Ok::<_, nt_core::Error>(())
-                }
-            }).collect();
-
-            join_all(section_futures).await.into_iter().collect::<Result<Vec<_>>>()?;
-            emoji_chain.push_str("📑");
-
-            // Generate article embedding for similarity search
-            let article_embedding = inference.generate_embeddings(&article.content).await?;
-            emoji_chain.push_str("🔢");
-
-            // Find similar articles
-            let similar_articles = storage.find_similar(&article_embedding, 5).await?;
-            emoji_chain.push_str("🔍");
-
-            // Process similar articles in parallel
-            let similar_futures: Vec<_> = similar_articles.into_iter()
-                .filter(|a| a.url != article.url) // Exclude self
-                .map(|a| {
-                    let inference = inference.clone();
-                    let semaphore = semaphore.clone();
-                    let article_embedding = article_embedding.clone();
-                    async move {
-                        let _permit = semaphore.acquire().await.map_err(|e| nt_core::Error::External(e.into()))?;
-                        let a_embedding = inference.generate_embeddings(&a.content).await?;
-                        let similarity = nt_core::cosine_similarity(&article_embedding, &a_embedding);
-                        let related = nt_core::RelatedArticle {
-                            article: a,
-                            similarity_score: Some(similarity),
-                        };
-                        Ok::<_, nt_core::Error>(related)
-                    }
-                })
-                .collect();
+    /// Runs several articles through the pipeline, embedding them with one batch
+    /// call to the model and storing them with one call to storage.
+    ///
+    /// The embed stage reuses the batch embeddings and the store stage is replaced
+    /// by the batch store; the other stages run on each article as in
+    /// [`process_article`](Self::process_article). Articles stored unchanged are
+    /// skipped. Returns the number of articles stored.
+    pub async fn process_batch(&self, articles: Vec<Article>) -> Result<usize> {
+        if self.dry_run {
+            info!("🔍 Dry run, would store {} articles", articles.len());
+            return Ok(0);
+        }
+
+        let mut batch = Vec::with_capacity(articles.len());
+        for article in articles {
+            if self.is_stored_unchanged(&article).await? {
+                info!("⏭️ Article unchanged since it was stored, skipping: {}", article.url);
+            } else {
+                batch.push(article);
+            }
+        }
+        if batch.is_empty() {
+            return Ok(0);
+        }
 
-            article.related_articles = join_all(similar_futures).await.into_iter().collect::<Result<Vec<_>>>()?;
-            emoji_chain.push_str("🔄");
+        info!("🔢 Generating embeddings for {} articles", batch.len());
+        let contents: Vec<String> = batch.iter().map(|article| article.content.clone()).collect();
+        let embeddings = {
+            let _permit = self.semaphore.acquire().await.map_err(|e| nt_core::Error::External(e.into()))?;
+            self.inference.generate_embeddings_batch(&contents).await?
+        };
+        if embeddings.len() != batch.len() {
+            return Err(nt_core::Error::Inference(format!(
+                "Expected {} embeddings, got {}", batch.len(), embeddings.len()
+            )));
+        }
+
+        let mut processed = Vec::with_capacity(batch.len());
+        for (mut article, embedding) in batch.into_iter().zip(embeddings) {
+            let mut ctx = self.stage_context();
+            ctx.embedding = embedding;
+            for stage in self.pipeline.stages().iter().filter(|stage| stage.name() != "store") {
+                article = stage.process(article, &mut ctx).await?;
+            }
+            processed.push((article, ctx.embedding));
+        }
+        if self.pipeline.position("store").is_none() {
+            return Ok(0);
+        }
 
-            // Store the processed article
-            storage.store_article(&article, &article_embedding).await?;
-            emoji_chain.push_str("💾");
+        info!("💾 Storing {} articles", processed.len());
+        self.storage.store_articles(&processed).await?;
+        info!("✨ Stored {} articles", processed.len());
+        Ok(processed.len())
+    }
 
-            Ok::<_, nt_core::Error>(())
-        });
+    /// Whether storage already has this article, with the same content and an embedding.
+    async fn is_stored_unchanged(&self, article: &Article) -> Result<bool> {
+        let Some(stored) = self.storage.get_article(&article.url).await? else {
+            return Ok(false);
+        };
+        if stored.content_hash() != article.content_hash() || Self::diff_articles(&stored, article).has_changes() {
+            return Ok(false);
+        }
+        // Articles stored without inference still need their embedding
+        Ok(!self.storage.get_article_embedding(&article.url).await?.is_empty())
+    }
 
-        self.inference_tasks.lock().await.push(handle);
+    /// Fetches `url` and traces how its scraper parses the page, without storing anything.
+    pub async fn debug_parse(&self, url: &str) -> Result<ParseTrace> {
+        let scraper = self.get_scraper_for_url(url)?;
+        let html = nt_core::timeout(
+            self.article_timeout,
+            self.with_http(async { crate::http::read_text(crate::http::get(url).await?).await }),
+        ).await??;
+        scraper.debug_parse(url, &html).ok_or_else(|| Error::Scraping(format!(
+            "{} does not support parse tracing",
+            scraper.source_metadata().name
+        )))
     }
 
     pub async fn scrape_url(&self, url: &str) -> Result<Article> {
         let mut scraper = self.get_scraper_for_url(url)?;
-        scraper.scrape_article(url).await
+        nt_core::timeout(self.article_timeout, self.with_http(scraper.scrape_article(url))).await?
     }
 
+    /// Scrapes every article from `source`, or from all sources, without storing them.
+    ///
+    /// Failed URLs are logged and skipped, see [`scrape_source_report`](Self::scrape_source_report)
+    /// for the outcome of each one.
     pub async fn scrape_source(&self, source: Option<&str>) -> Result<Vec<Article>> {
+        let (articles, _) = self.scrape_source_report(source, false).await?;
+        Ok(articles)
+    }
+
+    /// Scrapes every article from `source`, or from all sources, and reports the outcome of each URL.
+    ///
+    /// With `update_existing`, new and changed articles are stored. Otherwise articles
+    /// are only compared against the stored copies. Failed URLs are recorded in the
+    /// report instead of aborting the cycle, and pages the server reports as not
+    /// modified count as unchanged without being parsed.
+    pub async fn scrape_source_report(&self, source: Option<&str>, update_existing: bool) -> Result<(Vec<Article>, ScrapeReport)> {
+        let scrapers = match source {
+            Some(source) => self.get_scrapers_for_source(source)?,
+            None => self.get_all_scrapers().into_values().flatten().collect(),
+        };
+
         let mut articles = Vec::new();
-        let mut progress = None;
-
-        if let Some(source) = source {
-            let scrapers = self.get_scrapers_for_source(source)?;
-            for mut scraper in scrapers {
-                let urls = scraper.get_article_urls().await?;
-                
-                if progress.is_none() {
-                    progress = Some(ProgressDisplay::new_with_mode(ThreadMode::Window(3)).await?);
+        let mut report = ScrapeReport::default();
+        for scraper in scrapers {
+            let source = scraper.source_metadata().name;
+            self.publish(ScrapeEvent::Started { source: source.to_string() });
+            let urls = match self.fetch_article_urls(scraper.as_ref()).await {
+                Ok(urls) => urls,
+                Err(e) => {
+                    self.publish(ScrapeEvent::Error { source: source.to_string(), url: None, msg: e.to_string() });
+                    report.record_failure(source, e);
+                    continue;
                 }
+            };
 
-                let progress_handle = progress.as_ref().unwrap().clone();
-                let url_futures: Vec<_> = urls.into_iter().enumerate().map(|(_j, url)| {
-                    let progress = progress_handle.clone();
+            // Fetches keep their slot until the store stage has room, so
+            // fetching slows down when storage lags behind
+            let limit = Semaphore::new(self.concurrency_for(scraper.as_ref()));
+            let (tx, mut rx) = mpsc::channel(self.store_queue_capacity);
+            let fetch = async {
+                let tx = tx;
+                join_all(urls.into_iter().map(|url| {
+                    let (limit, tx) = (&limit, &tx);
                     async move {
-                        let article = self.scrape_url(&url).await?;
-                        progress.update_progress(0).await?;
-                        Ok::<_, nt_core::Error>(article)
+                        let (permit, result) = match limit.acquire().await {
+                            Ok(permit) => (Some(permit), self.scrape_url(&url).await),
+                            Err(e) => (None, Err(nt_core::Error::External(e.into()))),
+                        };
+                        if let Ok(slot) = tx.reserve().await {
+                            self.store_queue_depth.fetch_add(1, Ordering::SeqCst);
+                            slot.send((url, result));
+                        }
+                        drop(permit);
                     }
-                }).collect();
+                })).await;
+            };
 
-                let mut scraped_articles = join_all(url_futures).await.into_iter().collect::<Result<Vec<_>>>()?;
-                articles.append(&mut scraped_articles);
-            }
-        } else {
-            // Scrape all sources
-            let all_scrapers = self.get_all_scrapers();
-            for (_country, scrapers) in all_scrapers {
-                for mut scraper in scrapers {
-                    let urls = scraper.get_article_urls().await?;
-                    
-                    if progress.is_none() {
-                        progress = Some(ProgressDisplay::new_with_mode(ThreadMode::Window(3)).await?);
+            let store = async {
+                while let Some((url, result)) = rx.recv().await {
+                    self.store_queue_depth.fetch_sub(1, Ordering::SeqCst);
+                    let status = match result {
+                        Ok(article) if update_existing => self.store_if_changed(article.clone()).await
+                            .map(|result| (Some(article), result.status())),
+                        // Without update_existing new articles are still stored, only stored ones are skipped
+                        Ok(article) => self.store_if_new(article.clone()).await
+                            .map(|status| (Some(article), status)),
+                        Err(e) if e.is_not_modified() => Ok((None, ArticleStatus::Unchanged)),
+                        Err(e) => Err(e),
+                    };
+                    // Only a stored copy that matches the page makes a 304 mean unchanged
+                    let stored = match &status {
+                        Ok((_, ArticleStatus::Unchanged | ArticleStatus::New)) => true,
+                        Ok(_) => update_existing,
+                        Err(_) => false,
+                    };
+                    self.settle_validators(&url, stored);
+                    match status {
+                        Ok((article, status)) => {
+                            report.record(&status);
+                            articles.extend(article);
+                            self.publish(ScrapeEvent::ArticleScraped { source: source.to_string(), url, status });
+                        }
+                        Err(e) => {
+                            warn!("⚠️ Failed to scrape {}: {}", url, e);
+                            self.publish(ScrapeEvent::Error {
+                                source: source.to_string(),
+                                url: Some(url.clone()),
+                                msg: e.to_string(),
+                            });
+                            report.record_failure(url, e);
+                        }
                     }
+                }
+            };
 
-                    let progress_handle = progress.as_ref().unwrap().clone();
-                    let url_futures: Vec<_> = urls.into_iter().enumerate().map(|(_j, url)| {
-                        let progress = progress_handle.clone();
-                        async move {
-                            let article = self.scrape_url(&url).await?;
-                            progress.update_progress(0).await?;
-                            Ok::<_, nt_core::Error>(article)
-                        }
-                    }).collect();
+            tokio::join!(fetch, store);
+        }
+
+        if let Some(cache) = &self.inference_cache {
+            if let Err(e) = cache.flush().await {
+                warn!("⚠️ Could not save the inference cache: {}", e);
+            }
+        }
+        self.publish(ScrapeEvent::Finished { report: report.clone() });
+        Ok((articles, report))
+    }
+
+    /// Compares a scraped article against its stored copy without storing it.
+    pub async fn article_status(&self, article: &Article) -> Result<ArticleStatus> {
+        match self.storage.get_article(&article.url).await? {
+            None => Ok(ArticleStatus::New),
+            Some(stored) if Self::diff_articles(&stored, article).has_changes() => Ok(ArticleStatus::Updated),
+            Some(_) => Ok(ArticleStatus::Unchanged),
+        }
+    }
+
+    /// Compares two versions of an article.
+    ///
+    /// Sections are matched by content. A freshly scraped article has no
+    /// summary yet, so the summary only counts as changed when `new` has one.
+    pub fn diff_articles(old: &Article, new: &Article) -> ArticleDiff {
+        let old_sections: Vec<&str> = old.sections.iter().map(|s| s.content.as_str()).collect();
+        let new_sections: Vec<&str> = new.sections.iter().map(|s| s.content.as_str()).collect();
+        let word_count = |text: &str| text.split_whitespace().count() as i64;
 
-                    let mut scraped_articles = join_all(url_futures).await.into_iter().collect::<Result<Vec<_>>>()?;
-                    articles.append(&mut scraped_articles);
+        ArticleDiff {
+            title_changed: old.title != new.title,
+            content_changed: old.content != new.content,
+            summary_changed: new.summary.is_some() && old.summary != new.summary,
+            sections_added: new_sections.iter().filter(|s| !old_sections.contains(s)).count(),
+            sections_removed: old_sections.iter().filter(|s| !new_sections.contains(s)).count(),
+            authors_changed: old.authors != new.authors,
+            word_count_delta: word_count(&new.content) - word_count(&old.content),
+        }
+    }
+
+    /// Scrapes `url` and updates the stored copy if the article changed since it was stored.
+    pub async fn update_if_changed(&self, url: &str) -> Result<UpdateResult> {
+        let result = match self.scrape_url(url).await {
+            Ok(article) => self.store_if_changed(article).await,
+            Err(e) => Err(e),
+        };
+        self.settle_validators(url, result.is_ok());
+        result
+    }
+
+    /// Stores an already scraped article if it is not stored yet, leaving a
+    /// stored copy as it is even if the article changed.
+    pub async fn store_if_new(&self, article: Article) -> Result<ArticleStatus> {
+        let status = self.article_status(&article).await?;
+        if matches!(status, ArticleStatus::New) {
+            self.process_article(article).await?;
+        }
+        Ok(status)
+    }
+
+    /// Stores an already scraped article, replacing the stored copy only if it changed.
+    pub async fn store_if_changed(&self, article: Article) -> Result<UpdateResult> {
+        match self.storage.get_article(&article.url).await? {
+            None => {
+                self.process_article(article).await?;
+                Ok(UpdateResult::New)
+            }
+            Some(stored) => {
+                let diff = Self::diff_articles(&stored, &article);
+                if diff.has_changes() {
+                    info!("✏️ Article changed since last scrape: {}", article.url);
+                    self.update_article(article).await?;
+                    Ok(UpdateResult::Updated(diff))
+                } else {
+                    Ok(UpdateResult::Unchanged)
                 }
             }
         }
+    }
 
-        Ok(articles)
+    /// Replaces the stored copy of an article, regenerating its summary and embeddings.
+    ///
+    /// The stored copy is kept if processing fails, and nothing is regenerated
+    /// when it is unchanged.
+    pub async fn update_article(&self, article: Article) -> Result<()> {
+        if self.dry_run {
+            info!("🔍 Dry run, would update article: {}", article.url);
+            return Ok(());
+        }
+
+        self.process_article(article).await
     }
 
     fn parse_source(&self, source: &str) -> Result<(String, Option<String>)> {
@@ -343,26 +820,103 @@ impl ArticleStorage for ScraperManager {
         self.storage.store_article(article, embedding).await
     }
 
+    async fn insert_only(&self, article: &Article, embedding: &[f32]) -> Result<()> {
+        self.storage.insert_only(article, embedding).await
+    }
+
+    async fn store_articles(&self, batch: &[(Article, Vec<f32>)]) -> Result<()> {
+        self.storage.store_articles(batch).await
+    }
+
     async fn find_similar(&self, embedding: &[f32], limit: usize) -> Result<Vec<Article>> {
         self.storage.find_similar(embedding, limit).await
     }
 
+    async fn find_similar_scored(&self, embedding: &[f32], limit: usize) -> Result<Vec<(Article, f32)>> {
+        self.storage.find_similar_scored(embedding, limit).await
+    }
+
+    async fn find_similar_filtered(&self, embedding: &[f32], limit: usize, filter: &ArticleFilter) -> Result<Vec<(Article, f32)>> {
+        self.storage.find_similar_filtered(embedding, limit, filter).await
+    }
+
+    async fn find_similar_diverse(&self, embedding: &[f32], limit: usize, lambda: f32) -> Result<Vec<(Article, f32)>> {
+        self.storage.find_similar_diverse(embedding, limit, lambda).await
+    }
+
     async fn get_by_source(&self, source: &str) -> Result<Vec<Article>> {
         self.storage.get_by_source(source).await
     }
 
+    async fn get_article(&self, url: &str) -> Result<Option<Article>> {
+        self.storage.get_article(url).await
+    }
+
+    async fn list_articles(&self, offset: usize, limit: usize) -> Result<Vec<Article>> {
+        self.storage.list_articles(offset, limit).await
+    }
+
+    async fn get_by_tag(&self, tag: &str) -> Result<Vec<Article>> {
+        self.storage.get_by_tag(tag).await
+    }
+
+    async fn list_tags(&self) -> Result<Vec<(String, usize)>> {
+        self.storage.list_tags().await
+    }
+
+    async fn search_text(&self, query: &str, limit: usize) -> Result<Vec<Article>> {
+        self.storage.search_text(query, limit).await
+    }
+
     async fn delete_article(&self, url: &str) -> Result<()> {
         self.storage.delete_article(url).await
     }
 
+    async fn delete_older_than(&self, cutoff: chrono::DateTime<chrono::Utc>) -> Result<usize> {
+        self.storage.delete_older_than(cutoff).await
+    }
+
     async fn get_article_embedding(&self, url: &str) -> Result<Vec<f32>> {
         self.storage.get_article_embedding(url).await
     }
+
+    async fn get_article_embeddings(&self, urls: &[String]) -> Result<Vec<Vec<f32>>> {
+        self.storage.get_article_embeddings(urls).await
+    }
+
+    async fn stats(&self) -> Result<StorageStats> {
+        self.storage.stats().await
+    }
+
+    async fn get_by_id(&self, id: &str, scheme: ArticleIdScheme) -> Result<Option<Article>> {
+        self.storage.get_by_id(id, scheme).await
+    }
+
+    async fn list_articles_page(&self, cursor: Option<&str>, limit: usize) -> Result<(Vec<Article>, Option<String>)> {
+        self.storage.list_articles_page(cursor, limit).await
+    }
+
+    async fn embedding_model(&self) -> Result<Option<EmbeddingModelInfo>> {
+        self.storage.embedding_model().await
+    }
+
+    async fn set_embedding_model(&self, model: &EmbeddingModelInfo) -> Result<()> {
+        self.storage.set_embedding_model(model).await
+    }
+
+    async fn index_dimension(&self) -> Result<Option<usize>> {
+        self.storage.index_dimension().await
+    }
+
+    async fn recreate_index(&self, dimension: usize) -> Result<()> {
+        self.storage.recreate_index(dimension).await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tagging::DEFAULT_TAG_LIMIT;
     use crate::scrapers::argentina::ClarinScraper;
     use std::sync::Arc;
 
@@ -375,7 +929,7 @@ mod tests {
             Ok(())
         }
 
-        async fn find_similar(&self, _embedding: &[f32], _limit: usize) -> Result<Vec<Article>> {
+        async fn find_similar_scored(&self, _embedding: &[f32], _limit: usize) -> Result<Vec<(Article, f32)>> {
             Ok(Vec::new())
         }
 
@@ -383,10 +937,34 @@ mod tests {
             Ok(Vec::new())
         }
 
+        async fn get_article(&self, _url: &str) -> Result<Option<Article>> {
+            Ok(None)
+        }
+
+        async fn list_articles(&self, _offset: usize, _limit: usize) -> Result<Vec<Article>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_by_tag(&self, _tag: &str) -> Result<Vec<Article>> {
+            Ok(Vec::new())
+        }
+
+        async fn list_tags(&self) -> Result<Vec<(String, usize)>> {
+            Ok(Vec::new())
+        }
+
+        async fn search_text(&self, _query: &str, _limit: usize) -> Result<Vec<Article>> {
+            Ok(Vec::new())
+        }
+
         async fn delete_article(&self, _url: &str) -> Result<()> {
             Ok(())
         }
 
+        async fn delete_older_than(&self, _cutoff: chrono::DateTime<chrono::Utc>) -> Result<usize> {
+            Ok(0)
+        }
+
         async fn get_article_embedding(&self, _url: &str) -> Result<Vec<f32>> {
             Ok(Vec::new())
         }
@@ -435,4 +1013,973 @@ mod tests {
         assert!(!article.content.is_empty());
         assert!(!article.sections.is_empty());
     }
-} 
\ No newline at end of file
+
+    fn article(title: &str, content: &str, sections: &[&str], authors: &[&str]) -> Article {
+        Article {
+            url: "http://test.com/article".to_string(),
+            title: title.to_string(),
+            content: content.to_string(),
+            published_at: chrono::Utc::now(),
+            source: "test".to_string(),
+            sections: sections.iter().map(|c| ArticleSection {
+                content: c.to_string(),
+                summary: None,
+                embedding: None,
+            }).collect(),
+            summary: None,
+            authors: authors.iter().map(|a| a.to_string()).collect(),
+            related_articles: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_diff_articles() {
+        let old = article("Title", "one two three", &["a", "b", "c"], &["Alice"]);
+
+        let diff = ScraperManager::diff_articles(&old, &old.clone());
+        assert_eq!(diff, ArticleDiff::default());
+        assert!(!diff.has_changes());
+
+        let mut new = article("New title", "one two three four five", &["a", "d", "e"], &["Alice", "Bob"]);
+        new.summary = Some("A summary".to_string());
+        let diff = ScraperManager::diff_articles(&old, &new);
+        assert!(diff.title_changed);
+        assert!(diff.content_changed);
+        assert!(diff.summary_changed);
+        assert_eq!(diff.sections_added, 2);
+        assert_eq!(diff.sections_removed, 2);
+        assert!(diff.authors_changed);
+        assert_eq!(diff.word_count_delta, 2);
+        assert!(diff.has_changes());
+
+        // Shorter content gives a negative delta, and a missing summary on the new copy is not a change
+        let mut stored = old.clone();
+        stored.summary = Some("Stored summary".to_string());
+        let new = article("Title", "one", &["a", "b", "c"], &["Alice"]);
+        let diff = ScraperManager::diff_articles(&stored, &new);
+        assert!(!diff.summary_changed);
+        assert!(!diff.title_changed);
+        assert_eq!(diff.sections_added, 0);
+        assert_eq!(diff.sections_removed, 0);
+        assert_eq!(diff.word_count_delta, -2);
+    }
+
+    #[tokio::test]
+    async fn test_store_if_changed() {
+        let storage = Arc::new(nt_storage::InMemoryStorage::new().await.unwrap());
+        let inference = Arc::new(MockInference);
+        let manager = ScraperManager::new(storage.clone(), inference).await.unwrap();
+
+        let original = article("Title", "one two three", &["a"], &["Alice"]);
+        let result = manager.store_if_changed(original.clone()).await.unwrap();
+        assert_eq!(result, UpdateResult::New);
+
+        let result = manager.store_if_changed(original.clone()).await.unwrap();
+        assert_eq!(result, UpdateResult::Unchanged);
+
+        let edited = article("Title (updated)", "one two three four", &["a"], &["Alice"]);
+        let result = manager.store_if_changed(edited).await.unwrap();
+        match result {
+            UpdateResult::Updated(diff) => {
+                assert!(diff.title_changed);
+                assert_eq!(diff.word_count_delta, 1);
+            }
+            other => panic!("expected Updated, got {:?}", other),
+        }
+
+        let stored = storage.get_by_source("test").await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].title, "Title (updated)");
+    }
+
+    #[tokio::test]
+    async fn test_process_article_auto_tags() {
+        let storage = Arc::new(nt_storage::InMemoryStorage::new().await.unwrap());
+        let inference = Arc::new(MockInference);
+        let manager = ScraperManager::new(storage.clone(), inference).await.unwrap();
+
+        let mut tagged = article("Title", "El Banco Central subió las tasas. El Banco Central espera más inflación.", &[], &[]);
+        tagged.tags = vec!["economía".to_string(), "banco central".to_string()];
+        manager.process_article(tagged.clone()).await.unwrap();
+
+        let stored = storage.get_article(&tagged.url).await.unwrap().unwrap();
+        assert_eq!(&stored.tags[..2], &["economía", "banco central"]);
+        assert_eq!(stored.tags.iter().filter(|t| *t == "banco central").count(), 1);
+        assert!(stored.tags.len() <= 2 + DEFAULT_TAG_LIMIT);
+        assert!(stored.tags.contains(&"inflación".to_string()));
+        assert_eq!(storage.get_by_tag("banco central").await.unwrap().len(), 1);
+    }
+
+    /// Inference model embedding text as its length and word count, in batches
+    #[derive(Default)]
+    struct BatchInference {
+        batches: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl InferenceModel for BatchInference {
+        fn name(&self) -> &str {
+            "batch"
+        }
+
+        async fn summarize_article(&self, _article: &Article) -> Result<String> {
+            Ok("Test summary".to_string())
+        }
+
+        async fn summarize_sections(&self, _sections: &[ArticleSection]) -> Result<Vec<String>> {
+            Ok(vec!["Test section summary".to_string()])
+        }
+
+        async fn generate_embeddings(&self, text: &str) -> Result<Vec<f32>> {
+            Ok(vec![text.len() as f32, text.split_whitespace().count() as f32])
+        }
+
+        async fn generate_embeddings_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            self.batches.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let mut embeddings = Vec::new();
+            for text in texts {
+                embeddings.push(self.generate_embeddings(text).await?);
+            }
+            Ok(embeddings)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_process_batch() {
+        let storage = Arc::new(nt_storage::InMemoryStorage::new().await.unwrap());
+        let inference = Arc::new(BatchInference::default());
+        let manager = ScraperManager::new(storage.clone(), inference.clone()).await.unwrap();
+
+        let articles: Vec<Article> = ["one two", "three four five", "six"].iter().enumerate().map(|(i, content)| {
+            let mut a = article("Title", content, &[], &[]);
+            a.url = format!("http://test.com/article/{}", i);
+            a
+        }).collect();
+        assert_eq!(manager.process_batch(articles.clone()).await.unwrap(), 3);
+        assert_eq!(inference.batches.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        for a in &articles {
+            let stored = storage.get_article(&a.url).await.unwrap().unwrap();
+            assert_eq!(stored.content, a.content);
+            let expected = vec![a.content.len() as f32, a.content.split_whitespace().count() as f32];
+            assert_eq!(storage.get_article_embedding(&a.url).await.unwrap(), expected);
+            // The other stages ran too
+            assert_eq!(stored.summary.as_deref(), Some("Test summary"));
+            assert!(!stored.tags.is_empty());
+        }
+
+        // Articles already stored unchanged are not embedded again
+        assert_eq!(manager.process_batch(articles).await.unwrap(), 0);
+        assert_eq!(inference.batches.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    /// Inference model counting the embeddings it generates
+    #[derive(Default)]
+    struct CountingInference {
+        embeddings: std::sync::atomic::AtomicUsize,
+    }
+
+    impl CountingInference {
+        fn embeddings(&self) -> usize {
+            self.embeddings.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl InferenceModel for CountingInference {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        async fn summarize_article(&self, _article: &Article) -> Result<String> {
+            Ok("Test summary".to_string())
+        }
+
+        async fn summarize_sections(&self, _sections: &[ArticleSection]) -> Result<Vec<String>> {
+            Ok(vec!["Test section summary".to_string()])
+        }
+
+        async fn generate_embeddings(&self, _text: &str) -> Result<Vec<f32>> {
+            self.embeddings.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(vec![0.5; 8])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_article_is_not_embedded() {
+        let storage = Arc::new(nt_storage::InMemoryStorage::new().await.unwrap());
+        let inference = Arc::new(CountingInference::default());
+        let manager = ScraperManager::new(storage.clone(), inference.clone()).await.unwrap();
+
+        let original = article("Title", "one two three", &["a"], &["Alice"]);
+        manager.process_article(original.clone()).await.unwrap();
+        let embedded = inference.embeddings();
+        assert!(embedded > 0);
+
+        manager.process_article(original.clone()).await.unwrap();
+        manager.update_article(original.clone()).await.unwrap();
+        assert_eq!(inference.embeddings(), embedded);
+
+        let edited = article("Title", "one two three four", &["a"], &["Alice"]);
+        manager.update_article(edited).await.unwrap();
+        assert!(inference.embeddings() > embedded);
+        let stored = storage.get_article(&original.url).await.unwrap().unwrap();
+        assert_eq!(stored.content, "one two three four");
+    }
+
+    #[tokio::test]
+    async fn test_related_articles_are_not_embedded_again() {
+        let new_article = || {
+            let mut new = article("Title", "one two three", &["a"], &["Alice"]);
+            new.url = "http://test.com/new".to_string();
+            new
+        };
+
+        // Embedding calls made for the article alone
+        let inference = Arc::new(CountingInference::default());
+        let storage = Arc::new(nt_storage::InMemoryStorage::new().await.unwrap());
+        ScraperManager::new(storage, inference.clone()).await.unwrap()
+            .process_article(new_article()).await.unwrap();
+        let alone = inference.embeddings();
+
+        let inference = Arc::new(CountingInference::default());
+        let storage = Arc::new(nt_storage::InMemoryStorage::new().await.unwrap());
+        storage.store_article(&article("Other", "four five six", &[], &[]), &[0.5; 8]).await.unwrap();
+        let manager = ScraperManager::new(storage.clone(), inference.clone()).await.unwrap();
+        manager.process_article(new_article()).await.unwrap();
+        assert_eq!(inference.embeddings(), alone);
+
+        let stored = storage.get_article("http://test.com/new").await.unwrap().unwrap();
+        assert_eq!(stored.related_articles.len(), 1);
+        assert!((stored.related_articles[0].similarity_score.unwrap() - 1.0).abs() < 1e-5);
+    }
+
+    /// Inference model that fails until it is marked as available
+    struct FlakyInference {
+        available: std::sync::atomic::AtomicBool,
+    }
+
+    impl FlakyInference {
+        fn check(&self) -> Result<()> {
+            if self.available.load(std::sync::atomic::Ordering::SeqCst) {
+                Ok(())
+            } else {
+                Err(nt_core::Error::Inference("model unavailable".to_string()))
+            }
+        }
+    }
+
+    #[async_trait]
+    impl InferenceModel for FlakyInference {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        async fn summarize_article(&self, _article: &Article) -> Result<String> {
+            self.check()?;
+            Ok("Test summary".to_string())
+        }
+
+        async fn summarize_sections(&self, _sections: &[ArticleSection]) -> Result<Vec<String>> {
+            self.check()?;
+            Ok(vec!["Test section summary".to_string()])
+        }
+
+        async fn generate_embeddings(&self, _text: &str) -> Result<Vec<f32>> {
+            self.check()?;
+            Ok(vec![0.5; 8])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_graceful_degradation() {
+        let storage = Arc::new(nt_storage::InMemoryStorage::new().await.unwrap());
+        let inference = Arc::new(FlakyInference { available: false.into() });
+        let mut manager = ScraperManager::new(storage.clone(), inference.clone()).await.unwrap();
+
+        // Without degradation the inference error aborts processing
+        let original = article("Title", "one two three", &["a"], &["Alice"]);
+        assert!(manager.process_article(original.clone()).await.is_err());
+        assert!(storage.get_by_source("test").await.unwrap().is_empty());
+
+        manager.set_graceful_degradation(true);
+        manager.process_article(original.clone()).await.unwrap();
+        let stored = storage.get_by_source("test").await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert!(stored[0].summary.is_none());
+        assert!(storage.get_article_embedding(&original.url).await.unwrap().is_empty());
+        assert_eq!(manager.pending_count().await, 1);
+
+        // Still unavailable: the article stays pending
+        assert_eq!(manager.reprocess_pending().await.unwrap(), 0);
+        assert_eq!(manager.pending_count().await, 1);
+
+        inference.available.store(true, std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(manager.reprocess_pending().await.unwrap(), 1);
+        assert_eq!(manager.pending_count().await, 0);
+
+        let stored = storage.get_by_source("test").await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].summary.as_deref(), Some("Test summary"));
+        assert_eq!(storage.get_article_embedding(&original.url).await.unwrap().len(), 8);
+    }
+
+    #[tokio::test]
+    async fn test_pending_articles_survive_restart() {
+        let dir = std::env::temp_dir().join(format!("nt-pending-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("pending.json");
+        let storage = Arc::new(nt_storage::InMemoryStorage::new().await.unwrap());
+        let inference = Arc::new(FlakyInference { available: false.into() });
+
+        let mut manager = ScraperManager::new(storage.clone(), inference.clone()).await.unwrap();
+        manager.set_graceful_degradation(true);
+        manager.set_pending_path(&path).await.unwrap();
+        let original = article("Title", "one two three", &["a"], &["Alice"]);
+        manager.process_article(original.clone()).await.unwrap();
+        assert!(path.exists());
+
+        // A new manager picks up the queue and empties it once inference works
+        let mut manager = ScraperManager::new(storage.clone(), inference.clone()).await.unwrap();
+        manager.set_pending_path(&path).await.unwrap();
+        assert_eq!(manager.pending_count().await, 1);
+        inference.available.store(true, std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(manager.reprocess_pending().await.unwrap(), 1);
+
+        let mut manager = ScraperManager::new(storage, inference).await.unwrap();
+        manager.set_pending_path(&path).await.unwrap();
+        assert_eq!(manager.pending_count().await, 0);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_repeated_sections_are_embedded_once() {
+        let storage = Arc::new(nt_storage::InMemoryStorage::new().await.unwrap());
+        let inference = Arc::new(CountingInference::default());
+        let manager = ScraperManager::new(storage.clone(), inference.clone()).await.unwrap();
+
+        let original = article("Title", "one two three", &["Subscribe now", "The news", "Subscribe now"], &["Alice"]);
+        manager.process_article(original.clone()).await.unwrap();
+
+        // One embedding per unique section, plus the article embedding
+        assert_eq!(inference.embeddings(), 3);
+        let stored = storage.get_article(&original.url).await.unwrap().unwrap();
+        let sections: Vec<_> = stored.sections.iter().map(|s| s.content.as_str()).collect();
+        assert_eq!(sections, ["Subscribe now", "The news"]);
+    }
+
+    #[tokio::test]
+    async fn test_custom_pipeline_skips_summarize() {
+        let storage = Arc::new(nt_storage::InMemoryStorage::new().await.unwrap());
+        let inference = Arc::new(CountingInference::default());
+        let mut manager = ScraperManager::new(storage.clone(), inference.clone()).await.unwrap();
+        assert_eq!(manager.pipeline().stage_names(), ["tag", "dedup_sections", "summarize", "embed", "find_related", "store"]);
+
+        assert!(manager.pipeline_mut().remove("summarize").is_some());
+        assert!(manager.pipeline_mut().remove("summarize").is_none());
+        assert_eq!(manager.pipeline().stage_names(), ["tag", "dedup_sections", "embed", "find_related", "store"]);
+
+        let original = article("Title", "one two three", &["a", "b"], &["Alice"]);
+        manager.process_article(original.clone()).await.unwrap();
+
+        let stored = storage.get_article(&original.url).await.unwrap().unwrap();
+        assert!(stored.summary.is_none());
+        assert!(stored.sections.iter().all(|s| s.summary.is_none() && s.embedding.is_some()));
+        assert_eq!(storage.get_article_embedding(&original.url).await.unwrap().len(), 8);
+
+        // Storing needs the embedding of an earlier embed stage
+        let mut manager = ScraperManager::new(storage.clone(), inference.clone()).await.unwrap();
+        assert!(manager.set_pipeline(crate::Pipeline::new().with_stage(crate::pipeline::Store)).is_err());
+        let pipeline = crate::Pipeline::new().with_stage(crate::pipeline::Store).with_stage(crate::pipeline::Embed);
+        assert!(manager.set_pipeline(pipeline).is_err());
+        assert_eq!(manager.pipeline().stage_names().len(), 6);
+
+        // Without a tag stage the article is stored without tags
+        let pipeline = crate::Pipeline::new().with_stage(crate::pipeline::Embed).with_stage(crate::pipeline::Store);
+        manager.set_pipeline(pipeline).unwrap();
+        let other = Article { url: "http://test.com/other".to_string(), ..original };
+        manager.process_article(other.clone()).await.unwrap();
+        assert!(!storage.get_article_embedding(&other.url).await.unwrap().is_empty());
+        assert!(storage.get_article(&other.url).await.unwrap().unwrap().tags.is_empty());
+    }
+
+    struct MockScraper;
+
+    impl MockScraper {
+        const URLS: [&'static str; 4] = [
+            "http://mock.test/a",
+            "http://mock.test/b",
+            "http://mock.test/c",
+            "http://mock.test/broken",
+        ];
+
+        fn article(url: &str) -> Article {
+            let mut article = article(&format!("Title {}", url), "one two three", &["a"], &[]);
+            article.url = url.to_string();
+            article
+        }
+    }
+
+    #[async_trait]
+    impl Scraper for MockScraper {
+        fn source_metadata(&self) -> nt_core::SourceMetadata {
+            nt_core::SourceMetadata {
+                name: "Mock",
+                emoji: "🧪",
+                region: nt_core::RegionMetadata { name: "test", emoji: "🧪" },
+                language: "en",
+            }
+        }
+
+        fn can_handle(&self, url: &str) -> bool {
+            url.contains("mock.test")
+        }
+
+        async fn scrape_article(&mut self, url: &str) -> Result<Article> {
+            if url.ends_with("broken") {
+                return Err(Error::Scraping("page not found".to_string()));
+            }
+            Ok(Self::article(url))
+        }
+
+        async fn get_article_urls(&self) -> Result<Vec<String>> {
+            Ok(Self::URLS.iter().map(|url| url.to_string()).collect())
+        }
+
+        fn cli_names(&self) -> Vec<&str> {
+            vec!["mock"]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scrape_source_report() {
+        let storage = Arc::new(nt_storage::InMemoryStorage::new().await.unwrap());
+        let inference = Arc::new(MockInference);
+        let mut manager = ScraperManager::new(storage.clone(), inference).await.unwrap();
+        manager.factories = vec![Box::new(|| Box::new(MockScraper))];
+
+        // "a" is stored as scraped, "b" is stored with an outdated title, "c" is new
+        manager.store_if_changed(MockScraper::article("http://mock.test/a")).await.unwrap();
+        let mut outdated = MockScraper::article("http://mock.test/b");
+        outdated.title = "Old title".to_string();
+        manager.store_if_changed(outdated).await.unwrap();
+
+        let (articles, report) = manager.scrape_source_report(Some("test/mock"), true).await.unwrap();
+        assert_eq!(articles.len(), 3);
+        assert_eq!((report.new, report.updated, report.unchanged), (1, 1, 1));
+        assert_eq!(report.failed, vec![("http://mock.test/broken".to_string(), "Scraping error: page not found".to_string())]);
+        assert_eq!(report.total(), 4);
+        assert_eq!(report.to_string(), "1 new, 1 updated, 1 unchanged, 1 failed");
+
+        // Everything is stored now, so a second cycle only finds unchanged articles
+        let (_, report) = manager.scrape_source_report(Some("test/mock"), false).await.unwrap();
+        assert_eq!((report.new, report.updated, report.unchanged, report.failed.len()), (0, 0, 3, 1));
+    }
+
+    #[tokio::test]
+    async fn test_scrape_without_update_stores_new_articles() {
+        let storage = Arc::new(nt_storage::InMemoryStorage::new().await.unwrap());
+        let inference = Arc::new(MockInference);
+        let mut manager = ScraperManager::new(storage.clone(), inference).await.unwrap();
+        manager.factories = vec![Box::new(|| Box::new(MockScraper))];
+
+        let mut outdated = MockScraper::article("http://mock.test/b");
+        outdated.title = "Old title".to_string();
+        manager.store_if_changed(outdated).await.unwrap();
+
+        let (_, report) = manager.scrape_source_report(Some("test/mock"), false).await.unwrap();
+        assert_eq!((report.new, report.updated, report.unchanged), (2, 1, 0));
+
+        // New articles are stored, the stored one is left as it was
+        assert_eq!(storage.list_articles(0, 10).await.unwrap().len(), 3);
+        assert!(storage.get_article("http://mock.test/a").await.unwrap().is_some());
+        assert_eq!(storage.get_article("http://mock.test/b").await.unwrap().unwrap().title, "Old title");
+    }
+
+    #[tokio::test]
+    async fn test_scrape_events() {
+        let storage = Arc::new(nt_storage::InMemoryStorage::new().await.unwrap());
+        let inference = Arc::new(MockInference);
+        let mut manager = ScraperManager::new(storage, inference).await.unwrap();
+        manager.factories = vec![Box::new(|| Box::new(MockScraper))];
+        let mut events = manager.subscribe();
+
+        let (_, report) = manager.scrape_source_report(Some("test/mock"), true).await.unwrap();
+
+        assert!(matches!(events.recv().await.unwrap(), ScrapeEvent::Started { source } if source == "Mock"));
+        for expected in ["http://mock.test/a", "http://mock.test/b", "http://mock.test/c"] {
+            assert!(matches!(
+                events.recv().await.unwrap(),
+                ScrapeEvent::ArticleScraped { source, url, status: ArticleStatus::New }
+                    if source == "Mock" && url == expected
+            ));
+        }
+        assert!(matches!(
+            events.recv().await.unwrap(),
+            ScrapeEvent::Error { source, url: Some(url), msg }
+                if source == "Mock" && url == "http://mock.test/broken" && msg == "Scraping error: page not found"
+        ));
+        assert!(matches!(events.recv().await.unwrap(), ScrapeEvent::Finished { report: finished } if finished == report));
+        assert!(events.try_recv().is_err());
+
+        // Plain scrapes go through the same cycle
+        manager.scrape_source(Some("test/mock")).await.unwrap();
+        assert!(matches!(events.recv().await.unwrap(), ScrapeEvent::Started { source } if source == "Mock"));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_skips_storage() {
+        let storage = Arc::new(nt_storage::InMemoryStorage::new().await.unwrap());
+        let inference = Arc::new(MockInference);
+        let mut manager = ScraperManager::new(storage.clone(), inference).await.unwrap();
+        manager.factories = vec![Box::new(|| Box::new(MockScraper))];
+
+        let mut outdated = MockScraper::article("http://mock.test/a");
+        outdated.title = "Old title".to_string();
+        manager.store_if_changed(outdated).await.unwrap();
+
+        manager.set_dry_run(true);
+        let (articles, report) = manager.scrape_source_report(Some("test/mock"), true).await.unwrap();
+        assert_eq!(articles.len(), 3);
+        assert_eq!((report.new, report.updated, report.unchanged), (2, 1, 0));
+
+        let stored = storage.list_articles(0, 10).await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].title, "Old title");
+    }
+
+    /// Records how many of its articles are being fetched at the same time
+    struct CountingScraper {
+        name: &'static str,
+        in_flight: Arc<std::sync::atomic::AtomicUsize>,
+        max_in_flight: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Scraper for CountingScraper {
+        fn source_metadata(&self) -> nt_core::SourceMetadata {
+            nt_core::SourceMetadata {
+                name: self.name,
+                emoji: "🧪",
+                region: nt_core::RegionMetadata { name: "test", emoji: "🧪" },
+                language: "en",
+            }
+        }
+
+        fn can_handle(&self, url: &str) -> bool {
+            url.contains(&format!("{}.test", self.name))
+        }
+
+        async fn scrape_article(&mut self, url: &str) -> Result<Article> {
+            use std::sync::atomic::Ordering;
+            let current = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(current, Ordering::SeqCst);
+            sleep(Duration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(MockScraper::article(url))
+        }
+
+        async fn get_article_urls(&self) -> Result<Vec<String>> {
+            Ok((0..6).map(|i| format!("http://{}.test/{}", self.name, i)).collect())
+        }
+
+        fn cli_names(&self) -> Vec<&str> {
+            vec![self.name]
+        }
+    }
+
+    /// Scraper whose pages listed in `slow` never finish loading
+    struct HangingScraper {
+        slow: &'static [&'static str],
+        hang_listing: bool,
+    }
+
+    #[async_trait]
+    impl Scraper for HangingScraper {
+        fn source_metadata(&self) -> nt_core::SourceMetadata {
+            MockScraper.source_metadata()
+        }
+
+        fn can_handle(&self, url: &str) -> bool {
+            url.contains("mock.test")
+        }
+
+        async fn scrape_article(&mut self, url: &str) -> Result<Article> {
+            if self.slow.contains(&url) {
+                sleep(Duration::from_secs(3600)).await;
+            }
+            Ok(MockScraper::article(url))
+        }
+
+        async fn get_article_urls(&self) -> Result<Vec<String>> {
+            if self.hang_listing {
+                sleep(Duration::from_secs(3600)).await;
+            }
+            Ok(MockScraper::URLS[..3].iter().map(|url| url.to_string()).collect())
+        }
+
+        fn cli_names(&self) -> Vec<&str> {
+            vec!["mock"]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scrape_timeouts() {
+        let storage = Arc::new(MockStorage);
+        let inference = Arc::new(MockInference);
+        let mut manager = ScraperManager::new(storage, inference).await.unwrap();
+        manager.set_article_timeout(Duration::from_millis(50));
+        manager.set_url_list_timeout(Duration::from_millis(50));
+        manager.factories = vec![Box::new(|| Box::new(HangingScraper { slow: &["http://mock.test/b"], hang_listing: false }))];
+
+        let (articles, report) = tokio::time::timeout(
+            Duration::from_secs(5),
+            manager.scrape_source_report(Some("test/mock"), false),
+        ).await.expect("scrape should not hang").unwrap();
+        assert_eq!(articles.len(), 2);
+        assert_eq!(report.new, 2);
+        assert_eq!(report.failed, vec![(
+            "http://mock.test/b".to_string(),
+            Error::Timeout(Duration::from_millis(50)).to_string(),
+        )]);
+
+        // A hanging article list fails the source instead of the cycle
+        manager.factories = vec![Box::new(|| Box::new(HangingScraper { slow: &[], hang_listing: true }))];
+        let (articles, report) = manager.scrape_source_report(Some("test/mock"), false).await.unwrap();
+        assert!(articles.is_empty());
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "Mock");
+        assert!(report.failed[0].1.contains("timed out"));
+    }
+
+    #[tokio::test]
+    async fn test_per_source_concurrency() {
+        let storage = Arc::new(MockStorage);
+        let inference = Arc::new(MockInference);
+        let mut manager = ScraperManager::new(storage, inference).await.unwrap();
+
+        let mut max_in_flight = HashMap::new();
+        manager.factories = Vec::new();
+        for name in ["polite", "eager"] {
+            let in_flight = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+            let max = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+            max_in_flight.insert(name, max.clone());
+            manager.add_scraper_factory(Box::new(move || Box::new(CountingScraper {
+                name,
+                in_flight: in_flight.clone(),
+                max_in_flight: max.clone(),
+            })));
+        }
+        manager.set_source_concurrency("polite", 1);
+        manager.set_default_concurrency(3);
+
+        let (articles, report) = manager.scrape_source_report(Some("test"), false).await.unwrap();
+        assert_eq!(articles.len(), 12);
+        assert!(report.failed.is_empty());
+
+        let max = |name: &str| max_in_flight[name].load(std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(max("polite"), 1);
+        assert!(max("eager") > 1 && max("eager") <= 3, "eager ran {} at once", max("eager"));
+    }
+
+    /// Holds each store until the test lets it through and counts how many it stored
+    struct SlowStorage {
+        /// Stores waiting for or past the gate
+        started: std::sync::atomic::AtomicUsize,
+        stored: std::sync::atomic::AtomicUsize,
+        /// One permit lets one store finish
+        gate: Semaphore,
+    }
+
+    impl Default for SlowStorage {
+        fn default() -> Self {
+            Self { started: Default::default(), stored: Default::default(), gate: Semaphore::new(0) }
+        }
+    }
+
+    #[async_trait]
+    impl ArticleStorage for SlowStorage {
+        async fn store_article(&self, _article: &Article, _embedding: &[f32]) -> Result<()> {
+            self.started.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.gate.acquire().await.unwrap().forget();
+            self.stored.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn find_similar_scored(&self, embedding: &[f32], limit: usize) -> Result<Vec<(Article, f32)>> {
+            MockStorage.find_similar_scored(embedding, limit).await
+        }
+
+        async fn get_by_source(&self, source: &str) -> Result<Vec<Article>> {
+            MockStorage.get_by_source(source).await
+        }
+
+        async fn get_article(&self, url: &str) -> Result<Option<Article>> {
+            MockStorage.get_article(url).await
+        }
+
+        async fn list_articles(&self, offset: usize, limit: usize) -> Result<Vec<Article>> {
+            MockStorage.list_articles(offset, limit).await
+        }
+
+        async fn get_by_tag(&self, tag: &str) -> Result<Vec<Article>> {
+            MockStorage.get_by_tag(tag).await
+        }
+
+        async fn list_tags(&self) -> Result<Vec<(String, usize)>> {
+            MockStorage.list_tags().await
+        }
+
+        async fn search_text(&self, query: &str, limit: usize) -> Result<Vec<Article>> {
+            MockStorage.search_text(query, limit).await
+        }
+
+        async fn delete_article(&self, url: &str) -> Result<()> {
+            MockStorage.delete_article(url).await
+        }
+
+        async fn delete_older_than(&self, cutoff: chrono::DateTime<chrono::Utc>) -> Result<usize> {
+            MockStorage.delete_older_than(cutoff).await
+        }
+
+        async fn get_article_embedding(&self, url: &str) -> Result<Vec<f32>> {
+            MockStorage.get_article_embedding(url).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slow_storage_throttles_fetching() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let storage = Arc::new(SlowStorage::default());
+        let inference = Arc::new(MockInference);
+        let mut manager = ScraperManager::new(storage.clone(), inference).await.unwrap();
+        manager.set_default_concurrency(3);
+        manager.set_store_queue_capacity(1);
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let (factory_in_flight, factory_max) = (in_flight.clone(), max_in_flight.clone());
+        manager.factories = vec![Box::new(move || Box::new(CountingScraper {
+            name: "slow",
+            in_flight: factory_in_flight.clone(),
+            max_in_flight: factory_max.clone(),
+        }))];
+
+        let manager = Arc::new(manager);
+        let scrape = tokio::spawn({
+            let manager = manager.clone();
+            async move { manager.scrape_source_report(Some("test"), true).await }
+        });
+
+        // While the first article is held in storage, the next one fills the store
+        // queue and the fetched ones after it wait for room, keeping their slots
+        let settled = async {
+            while !(storage.started.load(Ordering::SeqCst) == 1
+                && manager.store_queue_depth() == 1
+                && max_in_flight.load(Ordering::SeqCst) == 3
+                && in_flight.load(Ordering::SeqCst) == 0)
+            {
+                tokio::task::yield_now().await;
+            }
+        };
+        tokio::time::timeout(Duration::from_secs(5), settled).await.expect("store queue never filled up");
+        assert_eq!(storage.stored.load(Ordering::SeqCst), 0);
+        assert!(!scrape.is_finished());
+
+        storage.gate.add_permits(6);
+        let (articles, report) = scrape.await.unwrap().unwrap();
+        assert_eq!(articles.len(), 6);
+        assert_eq!(report.new, 6);
+        assert_eq!(storage.stored.load(Ordering::SeqCst), 6);
+        assert_eq!(manager.store_queue_depth(), 0);
+    }
+
+    /// Fetches its single article over HTTP and counts how often it parses one
+    struct HttpScraper {
+        url: String,
+        parsed: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Scraper for HttpScraper {
+        fn source_metadata(&self) -> nt_core::SourceMetadata {
+            nt_core::SourceMetadata {
+                name: "HTTP",
+                emoji: "🧪",
+                region: nt_core::RegionMetadata { name: "test", emoji: "🧪" },
+                language: "en",
+            }
+        }
+
+        fn can_handle(&self, url: &str) -> bool {
+            url == self.url
+        }
+
+        async fn scrape_article(&mut self, url: &str) -> Result<Article> {
+            let body = crate::http::read_html(crate::http::get(url).await?).await?;
+            self.parsed.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(Article { url: url.to_string(), ..article("Cached", &body, &[], &[]) })
+        }
+
+        async fn get_article_urls(&self) -> Result<Vec<String>> {
+            Ok(vec![self.url.clone()])
+        }
+
+        fn cli_names(&self) -> Vec<&str> {
+            vec!["http"]
+        }
+    }
+
+    /// Serves one page with an ETag, answering 304 when the request carries it
+    async fn spawn_etag_server() -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    match socket.read(&mut buf).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let request = String::from_utf8_lossy(&request).to_lowercase();
+                let response = if request.contains("if-none-match: \"v1\"") {
+                    "HTTP/1.1 304 Not Modified\r\netag: \"v1\"\r\ncontent-length: 0\r\n\r\n".to_string()
+                } else {
+                    let body = "Stored body";
+                    format!("HTTP/1.1 200 OK\r\netag: \"v1\"\r\ncontent-length: {}\r\n\r\n{}", body.len(), body)
+                };
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{}/article", addr)
+    }
+
+    #[tokio::test]
+    async fn test_not_modified_article_is_unchanged() {
+        let url = spawn_etag_server().await;
+        let storage = Arc::new(nt_storage::InMemoryStorage::new().await.unwrap());
+        let inference = Arc::new(MockInference);
+        let mut manager = ScraperManager::new(storage, inference).await.unwrap();
+
+        let parsed = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let (factory_url, factory_parsed) = (url.clone(), parsed.clone());
+        manager.factories = vec![Box::new(move || Box::new(HttpScraper {
+            url: factory_url.clone(),
+            parsed: factory_parsed.clone(),
+        }))];
+
+        // Nothing is stored in a dry run, so the page is fetched in full next time
+        manager.set_dry_run(true);
+        let (_, report) = manager.scrape_source_report(Some("test"), true).await.unwrap();
+        assert_eq!(report.new, 1);
+        assert!(manager.http_cache().is_empty());
+        manager.set_dry_run(false);
+
+        let (articles, report) = manager.scrape_source_report(Some("test"), true).await.unwrap();
+        assert_eq!(articles.len(), 1);
+        assert_eq!(report.new, 1);
+        assert_eq!(manager.http_cache().len(), 1);
+
+        let (articles, report) = manager.scrape_source_report(Some("test"), true).await.unwrap();
+        assert!(articles.is_empty());
+        assert_eq!(report, ScrapeReport { unchanged: 1, ..Default::default() });
+        assert_eq!(parsed.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_pdf_article_is_skipped() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/article.pdf", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let body = "%PDF-1.7";
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/pdf\r\ncontent-length: {}\r\n\r\n{}",
+                    body.len(), body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let storage = Arc::new(nt_storage::InMemoryStorage::new().await.unwrap());
+        let inference = Arc::new(MockInference);
+        let mut manager = ScraperManager::new(storage.clone(), inference).await.unwrap();
+        let parsed = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let (factory_url, factory_parsed) = (url.clone(), parsed.clone());
+        manager.factories = vec![Box::new(move || Box::new(HttpScraper {
+            url: factory_url.clone(),
+            parsed: factory_parsed.clone(),
+        }))];
+
+        let (articles, report) = manager.scrape_source_report(Some("test"), true).await.unwrap();
+        assert!(articles.is_empty());
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, url);
+        assert_eq!(
+            report.failed[0].1,
+            format!("Scraping error: Unsupported content type application/pdf for {}", url)
+        );
+        assert_eq!(parsed.load(std::sync::atomic::Ordering::SeqCst), 0);
+        assert!(storage.list_articles(0, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_oversized_response_is_aborted() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/huge", listener.local_addr().unwrap());
+        let sent = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let server_sent = sent.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                // No content length, so the body can only be measured while streaming it
+                let _ = socket.write_all(b"HTTP/1.1 200 OK\r\ncontent-type: text/html\r\nconnection: close\r\n\r\n").await;
+                let chunk = vec![b'a'; 64 * 1024];
+                for _ in 0..1024 {
+                    if socket.write_all(&chunk).await.is_err() {
+                        break;
+                    }
+                    server_sent.fetch_add(chunk.len(), std::sync::atomic::Ordering::SeqCst);
+                }
+            }
+        });
+
+        let storage = Arc::new(nt_storage::InMemoryStorage::new().await.unwrap());
+        let inference = Arc::new(MockInference);
+        let mut manager = ScraperManager::new(storage.clone(), inference).await.unwrap();
+        manager.set_max_response_size(256 * 1024);
+        let parsed = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let (factory_url, factory_parsed) = (url.clone(), parsed.clone());
+        manager.factories = vec![Box::new(move || Box::new(HttpScraper {
+            url: factory_url.clone(),
+            parsed: factory_parsed.clone(),
+        }))];
+
+        let (articles, report) = tokio::time::timeout(
+            Duration::from_secs(10),
+            manager.scrape_source_report(Some("test"), true),
+        ).await.expect("oversized response should be aborted").unwrap();
+        assert!(articles.is_empty());
+        assert_eq!(report.failed, vec![(
+            url.clone(),
+            format!("Scraping error: Response from {} is larger than 262144 bytes", url),
+        )]);
+        assert_eq!(parsed.load(std::sync::atomic::Ordering::SeqCst), 0);
+        // The client hung up long before the whole 64 MB body was sent
+        assert!(sent.load(std::sync::atomic::Ordering::SeqCst) < 64 * 1024 * 1024);
+    }
+}
@@ -0,0 +1,302 @@
+//! Stages a scraped article goes through before it is stored.
+//!
+//! [`Pipeline::default`] tags the article, drops repeated sections, summarizes
+//! it, embeds it, finds the stored articles related to it and stores it.
+//! Stages can be added, removed or reordered with
+//! [`ScraperManager::pipeline_mut`](crate::ScraperManager::pipeline_mut)
+//! or replaced wholesale with [`ScraperManager::set_pipeline`](crate::ScraperManager::set_pipeline).
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use futures::future::join_all;
+use log::info;
+use nt_core::{Article, ArticleStorage, InferenceModel, RelatedArticle, Result};
+use nt_inference::tokens::{summarize_sections_truncated, summarize_truncated};
+use tokio::sync::{Semaphore, SemaphorePermit};
+use crate::tagging::{extract_tags, DEFAULT_TAG_LIMIT};
+
+/// Number of stored articles [`FindRelated`] links to
+pub const RELATED_ARTICLE_LIMIT: usize = 5;
+
+/// What the stages processing one article share.
+pub struct StageContext {
+    pub storage: Arc<dyn ArticleStorage>,
+    pub inference: Arc<dyn InferenceModel>,
+    /// Limits the inference calls running at once
+    pub semaphore: Arc<Semaphore>,
+    /// Embedding of the whole article, set by [`Embed`] unless it was set before
+    /// the pipeline ran, and stored by [`Store`]
+    pub embedding: Vec<f32>,
+}
+
+impl StageContext {
+    pub fn new(storage: Arc<dyn ArticleStorage>, inference: Arc<dyn InferenceModel>, semaphore: Arc<Semaphore>) -> Self {
+        Self { storage, inference, semaphore, embedding: Vec::new() }
+    }
+
+    async fn permit(&self) -> Result<SemaphorePermit<'_>> {
+        self.semaphore.acquire().await.map_err(|e| nt_core::Error::External(e.into()))
+    }
+}
+
+/// One step of a [`Pipeline`], taking the article and returning it changed.
+#[async_trait]
+pub trait ProcessingStage: Send + Sync {
+    /// Name used to find the stage in a pipeline
+    fn name(&self) -> &str;
+
+    /// Whether the stage calls the inference model.
+    ///
+    /// With graceful degradation, an article whose inference stage fails is
+    /// stored as it was before that stage and reprocessed later.
+    fn uses_inference(&self) -> bool {
+        false
+    }
+
+    async fn process(&self, article: Article, ctx: &mut StageContext) -> Result<Article>;
+}
+
+/// Ordered stages run on every article that is not already stored unchanged.
+pub struct Pipeline {
+    stages: Vec<Box<dyn ProcessingStage>>,
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::new()
+            .with_stage(Tag)
+            .with_stage(DedupSections)
+            .with_stage(Summarize)
+            .with_stage(Embed)
+            .with_stage(FindRelated)
+            .with_stage(Store)
+    }
+}
+
+impl Pipeline {
+    /// A pipeline without stages
+    pub fn new() -> Self {
+        Self { stages: Vec::new() }
+    }
+
+    pub fn with_stage(mut self, stage: impl ProcessingStage + 'static) -> Self {
+        self.push(stage);
+        self
+    }
+
+    /// Adds a stage after the others
+    pub fn push(&mut self, stage: impl ProcessingStage + 'static) {
+        self.stages.push(Box::new(stage));
+    }
+
+    /// Adds a stage at `index`, shifting the later ones.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is greater than the number of stages.
+    pub fn insert(&mut self, index: usize, stage: impl ProcessingStage + 'static) {
+        self.stages.insert(index, Box::new(stage));
+    }
+
+    /// Position of the stage called `name`
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.stages.iter().position(|stage| stage.name() == name)
+    }
+
+    /// Removes the stage called `name`, returning it
+    pub fn remove(&mut self, name: &str) -> Option<Box<dyn ProcessingStage>> {
+        self.position(name).map(|index| self.stages.remove(index))
+    }
+
+    /// Names of the stages, in the order they run
+    pub fn stage_names(&self) -> Vec<&str> {
+        self.stages.iter().map(|stage| stage.name()).collect()
+    }
+
+    pub fn stages(&self) -> &[Box<dyn ProcessingStage>] {
+        &self.stages
+    }
+
+    /// Checks that the stages can run in this order.
+    ///
+    /// [`Store`] stores the embedding set by [`Embed`], which vector backends
+    /// need, so a pipeline that stores must embed first.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(store) = self.position("store") {
+            if !self.position("embed").is_some_and(|embed| embed < store) {
+                return Err(nt_core::Error::Scraping(
+                    "The store stage needs an embed stage before it".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs every stage on `article`, stopping at the first failure.
+    pub async fn run(&self, mut article: Article, ctx: &mut StageContext) -> Result<Article> {
+        for stage in &self.stages {
+            article = stage.process(article, ctx).await?;
+        }
+        Ok(article)
+    }
+}
+
+/// Adds keyword tags extracted from the content, keeping any tags the article already has.
+pub struct Tag;
+
+#[async_trait]
+impl ProcessingStage for Tag {
+    fn name(&self) -> &str {
+        "tag"
+    }
+
+    async fn process(&self, mut article: Article, _ctx: &mut StageContext) -> Result<Article> {
+        for tag in extract_tags(&article.content, DEFAULT_TAG_LIMIT) {
+            if !article.tags.contains(&tag) {
+                article.tags.push(tag);
+            }
+        }
+        Ok(article)
+    }
+}
+
+/// Drops sections whose content repeats an earlier section, such as boilerplate
+/// scraped more than once, so they are not summarized and embedded again.
+pub struct DedupSections;
+
+#[async_trait]
+impl ProcessingStage for DedupSections {
+    fn name(&self) -> &str {
+        "dedup_sections"
+    }
+
+    async fn process(&self, mut article: Article, _ctx: &mut StageContext) -> Result<Article> {
+        let dropped = article.dedup_sections();
+        if dropped > 0 {
+            info!("✂️ Dropped {} repeated sections from {}", dropped, article.url);
+        }
+        Ok(article)
+    }
+}
+
+/// Summarizes the article and each of its sections.
+pub struct Summarize;
+
+#[async_trait]
+impl ProcessingStage for Summarize {
+    fn name(&self) -> &str {
+        "summarize"
+    }
+
+    fn uses_inference(&self) -> bool {
+        true
+    }
+
+    async fn process(&self, mut article: Article, ctx: &mut StageContext) -> Result<Article> {
+        info!("🤖 Generating summary for article: {}", article.title);
+        {
+            let _permit = ctx.permit().await?;
+            article.summary = Some(summarize_truncated(ctx.inference.as_ref(), &article).await?);
+        }
+        info!("✨ Summary generated successfully: {:?}", article.summary);
+
+        let num_sections = article.sections.len();
+        info!("📑 Summarizing {} sections", num_sections);
+        let ctx = &*ctx;
+        let section_futures = article.sections.iter_mut().enumerate().map(|(i, section)| async move {
+            info!("🤖 Generating summary for section {}/{}", i + 1, num_sections);
+            let _permit = ctx.permit().await?;
+            section.summary = Some(summarize_sections_truncated(ctx.inference.as_ref(), std::slice::from_ref(section)).await?[0].clone());
+            Ok::<_, nt_core::Error>(())
+        });
+        join_all(section_futures).await.into_iter().collect::<Result<Vec<_>>>()?;
+        Ok(article)
+    }
+}
+
+/// Embeds each section, and the whole article for similarity search if its
+/// embedding is not known yet.
+pub struct Embed;
+
+#[async_trait]
+impl ProcessingStage for Embed {
+    fn name(&self) -> &str {
+        "embed"
+    }
+
+    fn uses_inference(&self) -> bool {
+        true
+    }
+
+    async fn process(&self, mut article: Article, ctx: &mut StageContext) -> Result<Article> {
+        let num_sections = article.sections.len();
+        info!("📑 Embedding {} sections", num_sections);
+        {
+            let ctx = &*ctx;
+            let section_futures = article.sections.iter_mut().enumerate().map(|(i, section)| async move {
+                info!("🔢 Generating embedding for section {}/{}", i + 1, num_sections);
+                let _permit = ctx.permit().await?;
+                section.embedding = Some(ctx.inference.generate_embeddings(&section.content).await?);
+                Ok::<_, nt_core::Error>(())
+            });
+            join_all(section_futures).await.into_iter().collect::<Result<Vec<_>>>()?;
+        }
+
+        // Batches embed their articles in one call before the pipeline runs
+        if ctx.embedding.is_empty() {
+            info!("🔢 Generating article embedding for similarity search");
+            let embedding = {
+                let _permit = ctx.permit().await?;
+                ctx.inference.generate_embeddings(&article.content).await?
+            };
+            ctx.embedding = embedding;
+            info!("✨ Article embedding generated");
+        }
+        Ok(article)
+    }
+}
+
+/// Links the stored articles most similar to this one.
+///
+/// Does nothing unless an earlier stage set the article embedding.
+pub struct FindRelated;
+
+#[async_trait]
+impl ProcessingStage for FindRelated {
+    fn name(&self) -> &str {
+        "find_related"
+    }
+
+    async fn process(&self, mut article: Article, ctx: &mut StageContext) -> Result<Article> {
+        if ctx.embedding.is_empty() {
+            return Ok(article);
+        }
+
+        info!("🔍 Finding similar articles");
+        let similar_articles = ctx.storage.find_similar_scored(&ctx.embedding, RELATED_ARTICLE_LIMIT).await?;
+        info!("✨ Found {} similar articles", similar_articles.len());
+
+        article.related_articles = similar_articles.into_iter()
+            .filter(|(a, _)| a.url != article.url) // Exclude self
+            .map(|(a, score)| RelatedArticle { article: a, similarity_score: Some(score) })
+            .collect();
+        Ok(article)
+    }
+}
+
+/// Stores the article with the embedding set by [`Embed`], or none.
+pub struct Store;
+
+#[async_trait]
+impl ProcessingStage for Store {
+    fn name(&self) -> &str {
+        "store"
+    }
+
+    async fn process(&self, article: Article, ctx: &mut StageContext) -> Result<Article> {
+        info!("💾 Storing processed article");
+        ctx.storage.store_article(&article, &ctx.embedding).await?;
+        info!("✨ Article stored successfully");
+        Ok(article)
+    }
+}
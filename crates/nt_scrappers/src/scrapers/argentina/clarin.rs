@@ -4,7 +4,8 @@ use scraper::{Html, Selector};
 use nt_core::{Result, Article, Scraper, SourceMetadata, ArticleSection};
 use serde_json;
 use super::REGION;
-use crate::scrapers::jsonld;
+use crate::http;
+use crate::scrapers::{jsonld, utils};
 
 #[derive(Debug, Clone)]
 pub struct ClarinScraper;
@@ -24,6 +25,7 @@ impl Scraper for ClarinScraper {
             name: "Clarín",
             emoji: "🔇",
             region: REGION,
+            language: "es",
         }
     }
 
@@ -36,8 +38,8 @@ impl Scraper for ClarinScraper {
     }
 
     async fn scrape_article(&mut self, url: &str) -> Result<Article> {
-        let response = reqwest::get(url).await?;
-        let html = response.text().await?;
+        let response = http::get(url).await?;
+        let html = http::read_html(response).await?;
         let document = Html::parse_document(&html);
 
         let title = document
@@ -51,6 +53,7 @@ impl Scraper for ClarinScraper {
             .map(|el| el.text().collect::<String>().trim().to_string())
             .collect::<Vec<_>>()
             .join("\n");
+        let content = utils::clean_content(&content);
 
         tracing::debug!("Scraped content length: {} chars", content.len());
         tracing::debug!("First 100 chars of content: {}", content.chars().take(100).collect::<String>());
@@ -102,12 +105,13 @@ impl Scraper for ClarinScraper {
             summary: None,
             authors,
             related_articles: Vec::new(),
+            tags: Vec::new(),
         })
     }
 
     async fn get_article_urls(&self) -> Result<Vec<String>> {
-        let response = reqwest::get(Self::BASE_URL).await?;
-        let html = response.text().await?;
+        let response = http::get(Self::BASE_URL).await?;
+        let html = http::read_html(response).await?;
         let document = Html::parse_document(&html);
 
         let mut urls = Vec::new();
@@ -2,7 +2,8 @@ use async_trait::async_trait;
 use chrono::Utc;
 use scraper::{Html, Selector};
 use nt_core::{Result, Article, Scraper, SourceMetadata, ArticleSection};
-use crate::scrapers::jsonld;
+use crate::http;
+use crate::scrapers::{jsonld, utils};
 use super::REGION;
 
 #[derive(Debug, Clone)]
@@ -23,6 +24,7 @@ impl Scraper for LaNacionScraper {
             name: "La Nación",
             emoji: "🧵",
             region: REGION,
+            language: "es",
         }
     }
 
@@ -35,14 +37,14 @@ impl Scraper for LaNacionScraper {
     }
 
     async fn scrape_article(&mut self, url: &str) -> Result<Article> {
-        let response = reqwest::get(url).await?;
+        let response = http::get(url).await?;
         
         // Check if we were redirected to the subscription page
         if response.url().to_string().contains("suscripciones.lanacion.com.ar") {
             return Err(nt_core::Error::Scraping("Article is subscription-only (redirected to suscripciones)".to_string()));
         }
 
-        let html = response.text().await?;
+        let html = http::read_html(response).await?;
         let document = Html::parse_document(&html);
 
         // Check for subscription-only content message
@@ -107,7 +109,7 @@ impl Scraper for LaNacionScraper {
 
         // Add article paragraphs
         for element in document.select(&content_selector) {
-            let content = element.text().collect::<String>();
+            let content = utils::clean_content(&element.text().collect::<String>());
             if !content.is_empty() {
                 sections.push(ArticleSection {
                     content,
@@ -142,12 +144,13 @@ impl Scraper for LaNacionScraper {
             summary: None,
             authors,
             related_articles: Vec::new(),
+            tags: Vec::new(),
         })
     }
 
     async fn get_article_urls(&self) -> Result<Vec<String>> {
-        let response = reqwest::get(Self::BASE_URL).await?;
-        let html = response.text().await?;
+        let response = http::get(Self::BASE_URL).await?;
+        let html = http::read_html(response).await?;
         let document = Html::parse_document(&html);
 
         let mut urls = Vec::new();
@@ -2,7 +2,8 @@ use async_trait::async_trait;
 use chrono::Utc;
 use scraper::{Html, Selector};
 use nt_core::{Result, Article, Scraper, SourceMetadata, ArticleSection};
-use crate::scrapers::jsonld;
+use crate::http;
+use crate::scrapers::{jsonld, utils};
 use super::REGION;
 
 #[derive(Debug, Clone)]
@@ -50,6 +51,7 @@ impl Scraper for LaVozScraper {
             name: "La Voz",
             emoji: "🧢",
             region: REGION,
+            language: "es",
         }
     }
 
@@ -62,8 +64,8 @@ impl Scraper for LaVozScraper {
     }
 
     async fn scrape_article(&mut self, url: &str) -> Result<Article> {
-        let response = reqwest::get(url).await?;
-        let html = response.text().await?;
+        let response = http::get(url).await?;
+        let html = http::read_html(response).await?;
         let document = Html::parse_document(&html);
 
         let title_selector = Selector::parse("h1").unwrap();
@@ -143,7 +145,7 @@ impl Scraper for LaVozScraper {
 
         // Add article paragraphs
         for element in document.select(&content_selector) {
-            let content = element.text().collect::<String>();
+            let content = utils::clean_content(&element.text().collect::<String>());
             if !content.is_empty() {
                 sections.push(ArticleSection {
                     content,
@@ -178,6 +180,7 @@ impl Scraper for LaVozScraper {
             sections,
             authors,
             related_articles: Vec::new(),
+            tags: Vec::new(),
         })
     }
 
@@ -186,8 +189,8 @@ impl Scraper for LaVozScraper {
 
         // Process latest news section
         {
-            let response = reqwest::get(format!("{}/lo-ultimo/", Self::BASE_URL)).await?;
-            let html = response.text().await?;
+            let response = http::get(format!("{}/lo-ultimo/", Self::BASE_URL)).await?;
+            let html = http::read_html(response).await?;
             let document = Html::parse_document(&html);
             
             if let Ok(article_selector) = Selector::parse("article.story-card") {
@@ -212,8 +215,8 @@ impl Scraper for LaVozScraper {
 
         // If we didn't find enough articles, also check the main page
         if urls.len() < 10 {
-            let response = reqwest::get(Self::BASE_URL).await?;
-            let html = response.text().await?;
+            let response = http::get(Self::BASE_URL).await?;
+            let html = http::read_html(response).await?;
             let document = Html::parse_document(&html);
             
             if let Ok(article_selector) = Selector::parse("article.story-card") {
@@ -66,6 +66,14 @@ mod tests {
         assert!(!lavoz.can_handle("https://www.clarin.com/article"));
     }
 
+    #[test]
+    fn test_scrapers_report_spanish() {
+        for scraper in get_all_scrapers() {
+            let metadata = scraper.lock().unwrap().source_metadata();
+            assert_eq!(metadata.language, "es", "{} should report Spanish", metadata.name);
+        }
+    }
+
     #[tokio::test]
     async fn test_get_all_scrapers() {
         let scrapers = get_all_scrapers();
@@ -0,0 +1,224 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use scraper::{Html, Selector};
+use url::Url;
+use nt_core::{Result, Article, Scraper, SourceMetadata, RegionMetadata, ArticleSection, ParseTrace, SelectorMatch};
+use crate::http;
+use crate::scrapers::{jsonld, utils};
+
+/// Describes where a news site keeps its article links and content.
+///
+/// Sites whose pages can be read with plain CSS selectors only need one of
+/// these instead of a hand-written `Scraper`.
+#[derive(Debug, Clone)]
+pub struct ScraperConfig {
+    pub name: &'static str,
+    pub emoji: &'static str,
+    pub region: RegionMetadata,
+    pub language: &'static str,
+    /// Front page listing the latest articles
+    pub base_url: &'static str,
+    /// Hosts handled by this scraper, subdomains included
+    pub domains: &'static [&'static str],
+    pub cli_names: &'static [&'static str],
+    /// Path fragment that every article URL contains
+    pub article_path: &'static str,
+    pub link_selector: &'static str,
+    pub title_selector: &'static str,
+    pub content_selector: &'static str,
+    /// Element holding the RFC 3339 publication date in `date_attribute`
+    pub date_selector: &'static str,
+    pub date_attribute: &'static str,
+    /// Site-specific boilerplate regexes, each matching whole lines, stripped
+    /// along with the default ones
+    pub boilerplate_patterns: &'static [&'static str],
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigurableScraper {
+    config: ScraperConfig,
+    cleaner: utils::ContentCleaner,
+}
+
+impl ConfigurableScraper {
+    pub fn new(config: ScraperConfig) -> Self {
+        let cleaner = utils::ContentCleaner::default()
+            .with_patterns(config.boilerplate_patterns)
+            .expect("scraper boilerplate patterns are valid");
+        Self { config, cleaner }
+    }
+
+    pub fn config(&self) -> &ScraperConfig {
+        &self.config
+    }
+
+    fn handles_host(&self, host: &str) -> bool {
+        self.config.domains.iter()
+            .any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)))
+    }
+
+    fn select_texts(document: &Html, selector: &str) -> Vec<String> {
+        match Selector::parse(selector) {
+            Ok(selector) => document.select(&selector)
+                .map(|el| el.text().collect::<String>().trim().to_string())
+                .filter(|text| !text.is_empty())
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Builds an article from the HTML of one of the site's pages
+    pub fn parse_article(&self, url: &str, html: &str) -> Result<Article> {
+        let document = Html::parse_document(html);
+
+        let title = Self::select_texts(&document, self.config.title_selector)
+            .into_iter()
+            .next()
+            .ok_or_else(|| nt_core::Error::Scraping(format!("No title found at {}", url)))?;
+
+        let paragraphs: Vec<String> = Self::select_texts(&document, self.config.content_selector)
+            .into_iter()
+            .map(|paragraph| self.cleaner.clean(&paragraph))
+            .filter(|paragraph| !paragraph.is_empty())
+            .collect();
+        let content = paragraphs.join("\n");
+
+        let published_at = Selector::parse(self.config.date_selector).ok()
+            .and_then(|selector| document.select(&selector).next())
+            .and_then(|el| el.value().attr(self.config.date_attribute))
+            .and_then(|date_str| chrono::DateTime::parse_from_rfc3339(date_str).ok())
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(Utc::now);
+
+        let sections = paragraphs.into_iter()
+            .map(|paragraph| ArticleSection {
+                content: paragraph,
+                summary: None,
+                embedding: None,
+            })
+            .collect();
+
+        Ok(Article {
+            url: url.to_string(),
+            title,
+            content,
+            published_at,
+            source: self.config.name.to_string(),
+            sections,
+            summary: None,
+            authors: jsonld::extract_authors(&document),
+            related_articles: Vec::new(),
+            tags: Vec::new(),
+        })
+    }
+
+    /// Reports what each configured selector matches in `html`, and the article built from it
+    pub fn trace_article(&self, url: &str, html: &str) -> ParseTrace {
+        let document = Html::parse_document(html);
+        let config = &self.config;
+        let selectors = [
+            ("title", config.title_selector, None),
+            ("content", config.content_selector, None),
+            ("published_at", config.date_selector, Some(config.date_attribute)),
+            ("links", config.link_selector, Some("href")),
+        ];
+
+        let mut trace = ParseTrace { url: url.to_string(), ..ParseTrace::default() };
+        for (field, selector, attribute) in selectors {
+            let elements: Vec<_> = match Selector::parse(selector) {
+                Ok(parsed) => document.select(&parsed).collect(),
+                Err(e) => {
+                    trace.error.get_or_insert_with(|| format!("Invalid {} selector {:?}: {}", field, selector, e));
+                    Vec::new()
+                }
+            };
+            let first = elements.first().and_then(|el| match attribute {
+                Some(attribute) => el.value().attr(attribute).map(str::to_string),
+                None => Some(el.text().collect::<String>().trim().to_string()),
+            });
+            trace.selectors.push(SelectorMatch {
+                field: field.to_string(),
+                selector: selector.to_string(),
+                matches: elements.len(),
+                first,
+            });
+        }
+
+        match self.parse_article(url, html) {
+            Ok(article) => {
+                trace.fields.insert("title".to_string(), article.title);
+                trace.fields.insert("content".to_string(), article.content);
+                trace.fields.insert("published_at".to_string(), article.published_at.to_rfc3339());
+                trace.fields.insert("authors".to_string(), article.authors.join(", "));
+                trace.fields.insert("sections".to_string(), article.sections.len().to_string());
+            }
+            Err(e) => {
+                trace.error.get_or_insert(e.to_string());
+            }
+        }
+        trace
+    }
+
+    /// Collects the article links from the HTML of the site's front page
+    pub fn parse_article_urls(&self, html: &str) -> Vec<String> {
+        let document = Html::parse_document(html);
+        let Ok(base) = Url::parse(self.config.base_url) else {
+            return Vec::new();
+        };
+        let Ok(link_selector) = Selector::parse(self.config.link_selector) else {
+            return Vec::new();
+        };
+
+        let mut urls: Vec<String> = document.select(&link_selector)
+            .filter_map(|link| link.value().attr("href"))
+            .filter_map(|href| base.join(href).ok())
+            .filter(|url| url.host_str().is_some_and(|host| self.handles_host(host)))
+            .filter(|url| url.path().contains(self.config.article_path))
+            .map(|mut url| {
+                url.set_query(None);
+                url.set_fragment(None);
+                url.to_string()
+            })
+            .collect();
+
+        urls.sort();
+        urls.dedup();
+        urls
+    }
+}
+
+#[async_trait]
+impl Scraper for ConfigurableScraper {
+    fn source_metadata(&self) -> SourceMetadata {
+        SourceMetadata {
+            name: self.config.name,
+            emoji: self.config.emoji,
+            region: self.config.region.clone(),
+            language: self.config.language,
+        }
+    }
+
+    fn can_handle(&self, url: &str) -> bool {
+        Url::parse(url).ok()
+            .and_then(|url| url.host_str().map(|host| self.handles_host(host)))
+            .unwrap_or(false)
+    }
+
+    fn cli_names(&self) -> Vec<&str> {
+        self.config.cli_names.to_vec()
+    }
+
+    fn debug_parse(&self, url: &str, html: &str) -> Option<ParseTrace> {
+        Some(self.trace_article(url, html))
+    }
+
+    async fn scrape_article(&mut self, url: &str) -> Result<Article> {
+        let html = http::read_html(http::get(url).await?).await?;
+        self.parse_article(url, &html)
+    }
+
+    async fn get_article_urls(&self) -> Result<Vec<String>> {
+        let html = http::read_html(http::get(self.config.base_url).await?).await?;
+        Ok(self.parse_article_urls(&html))
+    }
+}
@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use async_trait::async_trait;
+use chrono::Utc;
+use feed_rs::model::{Entry, Text};
+use scraper::Html;
+use url::Url;
+use nt_core::{Result, Article, Scraper, SourceMetadata, ArticleSection};
+use crate::http;
+
+/// Reads articles from an RSS or Atom feed instead of scraping HTML.
+///
+/// Feeds only carry a summary of each article, which becomes both the
+/// article's content and its initial summary.
+#[derive(Debug, Clone)]
+pub struct FeedScraper {
+    metadata: SourceMetadata,
+    feed_url: String,
+    cli_names: Vec<&'static str>,
+    domains: Vec<String>,
+    /// Entries from the last fetch, keyed by link
+    entries: HashMap<String, Article>,
+}
+
+impl FeedScraper {
+    /// Creates a scraper for `feed_url`, handling article links on the feed's host
+    pub fn new(metadata: SourceMetadata, feed_url: impl Into<String>) -> Self {
+        let feed_url = feed_url.into();
+        let domains = Url::parse(&feed_url).ok()
+            .and_then(|url| url.host_str().map(String::from))
+            .into_iter()
+            .collect();
+        Self {
+            metadata,
+            feed_url,
+            cli_names: Vec::new(),
+            domains,
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn with_cli_names(mut self, cli_names: &[&'static str]) -> Self {
+        self.cli_names = cli_names.to_vec();
+        self
+    }
+
+    /// Replaces the hosts whose article links this scraper handles, subdomains included
+    pub fn with_domains(mut self, domains: &[&str]) -> Self {
+        self.domains = domains.iter().map(|d| d.to_string()).collect();
+        self
+    }
+
+    /// Builds an article from every `<item>` (RSS) or `<entry>` (Atom) that has a link
+    pub fn parse_feed(&self, xml: &str) -> Result<Vec<Article>> {
+        let feed = feed_rs::parser::parse(xml.as_bytes())
+            .map_err(|e| nt_core::Error::Scraping(format!("Invalid feed {}: {}", self.feed_url, e)))?;
+        Ok(feed.entries.into_iter()
+            .filter_map(|entry| self.parse_entry(entry))
+            .collect())
+    }
+
+    fn parse_entry(&self, entry: Entry) -> Option<Article> {
+        // RSS links have no rel, Atom marks the article with the alternate one
+        let url = entry.links.into_iter()
+            .find(|link| link.rel.as_deref().is_none_or(|rel| rel == "alternate"))
+            .map(|link| link.href.trim().to_string())
+            .filter(|url| !url.is_empty())?;
+
+        let published_at = entry.published
+            .or(entry.updated)
+            .unwrap_or_else(Utc::now);
+
+        let summary = entry.summary.as_ref()
+            .map(text)
+            .or_else(|| entry.content.as_ref().and_then(|content| content.body.as_deref()).map(html_text))
+            .unwrap_or_default();
+
+        let authors = entry.authors.into_iter()
+            .map(|author| author.name.trim().to_string())
+            .filter(|author| !author.is_empty())
+            .collect();
+
+        let sections = if summary.is_empty() {
+            Vec::new()
+        } else {
+            vec![ArticleSection {
+                content: summary.clone(),
+                summary: None,
+                embedding: None,
+            }]
+        };
+
+        Some(Article {
+            url,
+            title: entry.title.as_ref().map(text).unwrap_or_default(),
+            content: summary.clone(),
+            published_at,
+            source: self.metadata.name.to_string(),
+            sections,
+            summary: Some(summary).filter(|s| !s.is_empty()),
+            authors,
+            related_articles: Vec::new(),
+            tags: Vec::new(),
+        })
+    }
+
+    async fn fetch(&self) -> Result<Vec<Article>> {
+        let xml = http::read_text(http::get(&self.feed_url).await?).await?;
+        self.parse_feed(&xml)
+    }
+}
+
+/// Plain text of an already decoded feed text, with any HTML stripped
+fn text(text: &Text) -> String {
+    if text.content_type.subty().as_str() == "html" {
+        html_text(&text.content)
+    } else {
+        text.content.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
+
+fn html_text(html: &str) -> String {
+    Html::parse_fragment(html)
+        .root_element()
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[async_trait]
+impl Scraper for FeedScraper {
+    fn source_metadata(&self) -> SourceMetadata {
+        self.metadata.clone()
+    }
+
+    fn can_handle(&self, url: &str) -> bool {
+        Url::parse(url).ok()
+            .and_then(|url| url.host_str().map(String::from))
+            .is_some_and(|host| self.domains.iter().any(|d| host == *d || host.ends_with(&format!(".{}", d))))
+    }
+
+    fn cli_names(&self) -> Vec<&str> {
+        self.cli_names.clone()
+    }
+
+    async fn scrape_article(&mut self, url: &str) -> Result<Article> {
+        if !self.entries.contains_key(url) {
+            self.entries = self.fetch().await?
+                .into_iter()
+                .map(|article| (article.url.clone(), article))
+                .collect();
+        }
+        self.entries.get(url)
+            .cloned()
+            .ok_or_else(|| nt_core::Error::Scraping(format!("{} is not in feed {}", url, self.feed_url)))
+    }
+
+    async fn get_article_urls(&self) -> Result<Vec<String>> {
+        Ok(self.fetch().await?.into_iter().map(|article| article.url).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nt_core::RegionMetadata;
+
+    fn scraper() -> FeedScraper {
+        FeedScraper::new(
+            SourceMetadata {
+                name: "Test Feed",
+                emoji: "📡",
+                region: RegionMetadata { name: "test", emoji: "🧪" },
+                language: "en",
+            },
+            "https://feeds.news.test/rss.xml",
+        )
+        .with_domains(&["news.test"])
+    }
+
+    const RSS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <channel>
+            <title>Test News</title>
+            <link>https://news.test/</link>
+            <item>
+                <title>Senate passes &amp; signs bill</title>
+                <link>https://news.test/politics/senate-bill</link>
+                <pubDate>Thu, 14 Mar 2024 15:30:00 +0000</pubDate>
+                <description><![CDATA[<p>The Senate <b>passed</b> the bill.</p>]]></description>
+                <dc:creator>Jane Doe</dc:creator>
+            </item>
+            <item>
+                <title>Markets rally</title>
+                <link>https://news.test/economy/markets</link>
+                <pubDate>Wed, 13 Mar 2024 09:00:00 -0300</pubDate>
+                <description>Stocks &lt;em&gt;rose&lt;/em&gt; sharply.</description>
+            </item>
+        </channel>
+        </rss>"#;
+
+    const ATOM: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <title>Test News</title>
+            <link href="https://news.test/"/>
+            <entry>
+                <title type="html">Election results</title>
+                <link rel="alternate" href="https://news.test/politics/election"/>
+                <published>2024-03-12T20:00:00Z</published>
+                <summary>Counting has finished.</summary>
+                <author><name>John Roe</name></author>
+            </entry>
+        </feed>"#;
+
+    #[test]
+    fn test_parse_rss() {
+        let articles = scraper().parse_feed(RSS).unwrap();
+        assert_eq!(articles.len(), 2);
+
+        assert_eq!(articles[0].title, "Senate passes & signs bill");
+        assert_eq!(articles[0].url, "https://news.test/politics/senate-bill");
+        assert_eq!(articles[0].published_at.to_rfc3339(), "2024-03-14T15:30:00+00:00");
+        assert_eq!(articles[0].content, "The Senate passed the bill.");
+        assert_eq!(articles[0].summary.as_deref(), Some("The Senate passed the bill."));
+        assert_eq!(articles[0].authors, vec!["Jane Doe"]);
+        assert_eq!(articles[0].source, "Test Feed");
+
+        assert_eq!(articles[1].published_at.to_rfc3339(), "2024-03-13T12:00:00+00:00");
+        assert_eq!(articles[1].content, "Stocks rose sharply.");
+        assert!(articles[1].authors.is_empty());
+    }
+
+    #[test]
+    fn test_parse_atom() {
+        let articles = scraper().parse_feed(ATOM).unwrap();
+        assert_eq!(articles.len(), 1);
+        assert_eq!(articles[0].url, "https://news.test/politics/election");
+        assert_eq!(articles[0].title, "Election results");
+        assert_eq!(articles[0].published_at.to_rfc3339(), "2024-03-12T20:00:00+00:00");
+        assert_eq!(articles[0].content, "Counting has finished.");
+        assert_eq!(articles[0].authors, vec!["John Roe"]);
+    }
+
+    #[test]
+    fn test_entities_are_decoded_once() {
+        let rss = r#"<rss version="2.0"><channel><item>
+                <title>AT&amp;amp;T earnings</title>
+                <link>https://news.test/economy/att?a=1&amp;b=2</link>
+                <description>Use &amp;lt;b&amp;gt; for bold</description>
+            </item></channel></rss>"#;
+        let articles = scraper().parse_feed(rss).unwrap();
+        assert_eq!(articles[0].title, "AT&amp;T earnings");
+        assert_eq!(articles[0].url, "https://news.test/economy/att?a=1&b=2");
+        assert_eq!(articles[0].content, "Use <b> for bold");
+
+        assert!(scraper().parse_feed("<html>not a feed").is_err());
+    }
+
+    #[test]
+    fn test_can_handle() {
+        let scraper = scraper();
+        assert!(scraper.can_handle("https://news.test/politics/senate-bill"));
+        assert!(scraper.can_handle("https://www.news.test/politics/senate-bill"));
+        assert!(!scraper.can_handle("https://other.test/politics/senate-bill"));
+    }
+}
@@ -6,6 +6,9 @@ use std::fmt::Debug;
 use std::sync::{Arc, Mutex};
 
 pub mod argentina;
+pub mod configurable;
+pub mod feed;
+pub mod us;
 
 type BoxedScraper = Box<dyn Scraper + Send + Sync>;
 
@@ -93,14 +96,79 @@ pub fn get_scraper_factories() -> Vec<ScraperFactory> {
         Box::new(|| Box::new(crate::scrapers::argentina::ClarinScraper::new())),
         Box::new(|| Box::new(crate::scrapers::argentina::LaNacionScraper::new())),
         Box::new(|| Box::new(crate::scrapers::argentina::LaVozScraper::new())),
+        Box::new(|| Box::new(crate::scrapers::configurable::ConfigurableScraper::apnews())),
+        Box::new(|| Box::new(crate::scrapers::feed::FeedScraper::npr())),
     ]
 }
 
 pub mod jsonld;
+pub mod sitemap;
 
 /// Common utilities for scrapers
 pub(crate) mod utils {
     use super::*;
+    use lazy_static::lazy_static;
+    use regex::Regex;
+
+    /// Patterns matching lines that news sites mix into article bodies:
+    /// share buttons, cookie and newsletter notices, and "related stories" links.
+    pub const DEFAULT_BOILERPLATE: &[&str] = &[
+        r"(?i)[\s|·•,/:]*((share|compartir|on|en|this|article|story|nota|facebook|twitter|x|whatsapp|linkedin|telegram|e-?mail|copy link|copiar (link|enlace))[\s|·•,/:]*)+",
+        r"(?i).*\b(we use cookies|this (web)?site uses cookies|(usamos|utilizamos) cookies|este sitio (web )?(usa|utiliza) cookies)\b.*",
+        r"(?i).*\b(subscribe to our newsletter|sign up for our newsletter|suscribite a (nuestro|nuestros) newsletters?)\b.*",
+        r"(?i)(related (stories|articles|news)|read more|more stories|lee también|leé también|mirá también|seguí leyendo|te puede interesar|advertisement|publicidad)\s*(:.*)?",
+    ];
+
+    lazy_static! {
+        static ref DEFAULT_CLEANER: ContentCleaner = ContentCleaner::new(DEFAULT_BOILERPLATE)
+            .expect("default boilerplate patterns are valid");
+    }
+
+    /// Strips boilerplate from scraped text before it is split into sections.
+    ///
+    /// Patterns are matched against whole lines, trimmed: a line is dropped when
+    /// a pattern matches all of it, so they never cut text out of a sentence.
+    #[derive(Debug, Clone)]
+    pub struct ContentCleaner {
+        patterns: Vec<Regex>,
+    }
+
+    impl Default for ContentCleaner {
+        fn default() -> Self {
+            DEFAULT_CLEANER.clone()
+        }
+    }
+
+    impl ContentCleaner {
+        pub fn new(patterns: &[&str]) -> Result<Self> {
+            Self { patterns: Vec::new() }.with_patterns(patterns)
+        }
+
+        /// Adds patterns on top of the ones already configured
+        pub fn with_patterns(mut self, patterns: &[&str]) -> Result<Self> {
+            for pattern in patterns {
+                let regex = Regex::new(&format!("^(?:{})$", pattern))
+                    .map_err(|e| Error::Scraping(format!("Invalid boilerplate pattern {:?}: {}", pattern, e)))?;
+                self.patterns.push(regex);
+            }
+            Ok(self)
+        }
+
+        pub fn clean(&self, content: &str) -> String {
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .filter(|line| !self.patterns.iter().any(|pattern| pattern.is_match(line)))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    }
+
+    /// Cleans `content` with the [`DEFAULT_BOILERPLATE`] patterns
+    pub fn clean_content(content: &str) -> String {
+        DEFAULT_CLEANER.clean(content)
+    }
 
     #[allow(dead_code)]
     pub fn parse_url(url: &str) -> Result<Url> {
@@ -180,6 +248,49 @@ mod tests {
         assert_eq!(texts[1], "Item 2");
     }
 
+    #[test]
+    fn test_clean_content() {
+        let content = "\
+            The Senate passed the bill on Thursday.
+            Share on Facebook | Twitter | WhatsApp
+            Compartir
+            We use cookies to improve your experience. Accept all
+            Utilizamos cookies para mejorar tu experiencia.
+            Subscribe to our newsletter for daily updates
+            The measure now goes to the House.
+            Related stories: Senate leaders meet
+            Mirá también
+            ADVERTISEMENT
+            Lawmakers are expected to vote next week.";
+
+        assert_eq!(
+            utils::clean_content(content),
+            "The Senate passed the bill on Thursday.\n\
+             The measure now goes to the House.\n\
+             Lawmakers are expected to vote next week."
+        );
+
+        // Ordinary sentences mentioning the same words are kept
+        let prose = "Officials will share details on Twitter later.\nRead the full statement below.";
+        assert_eq!(utils::clean_content(prose), prose);
+    }
+
+    #[test]
+    fn test_content_cleaner_custom_patterns() {
+        let cleaner = utils::ContentCleaner::default()
+            .with_patterns(&[r"Photo: .*"])
+            .unwrap();
+        assert_eq!(
+            cleaner.clean("Photo: AP\nFirst paragraph.\nShare"),
+            "First paragraph."
+        );
+
+        // Patterns only drop lines they match entirely
+        assert_eq!(cleaner.clean("Credit line. Photo: AP"), "Credit line. Photo: AP");
+
+        assert!(utils::ContentCleaner::new(&["(unclosed"]).is_err());
+    }
+
     #[test]
     fn test_split_into_sections() {
         let content = "Section 1\n\nSection 2\n\n\nSection 3";
@@ -0,0 +1,199 @@
+use std::future::Future;
+use chrono::{DateTime, NaiveDate, Utc};
+use scraper::{ElementRef, Html, Selector};
+use nt_core::Result;
+use crate::http;
+
+/// A page listed in a sitemap
+#[derive(Debug, Clone, PartialEq)]
+pub struct SitemapEntry {
+    pub url: String,
+    pub lastmod: Option<DateTime<Utc>>,
+}
+
+/// The two kinds of sitemap documents
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sitemap {
+    /// A `<sitemapindex>` pointing at further sitemaps
+    Index(Vec<SitemapEntry>),
+    /// A `<urlset>` listing pages
+    UrlSet(Vec<SitemapEntry>),
+}
+
+/// Finds article URLs through a site's sitemaps.
+///
+/// Sitemap indexes are followed up to `max_depth` levels, so a scraper can
+/// point it at `/sitemap.xml` in `get_article_urls` instead of crawling the
+/// front page.
+#[derive(Debug, Clone)]
+pub struct SitemapDiscovery {
+    max_depth: usize,
+}
+
+impl Default for SitemapDiscovery {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SitemapDiscovery {
+    const DEFAULT_MAX_DEPTH: usize = 3;
+
+    pub fn new() -> Self {
+        Self { max_depth: Self::DEFAULT_MAX_DEPTH }
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Parses a sitemap or sitemap index document
+    pub fn parse(xml: &str) -> Sitemap {
+        let document = Html::parse_document(xml);
+        let index_selector = Selector::parse("sitemapindex > sitemap").unwrap();
+        let url_selector = Selector::parse("urlset > url").unwrap();
+
+        let index: Vec<SitemapEntry> = document.select(&index_selector)
+            .filter_map(Self::parse_entry)
+            .collect();
+        if !index.is_empty() {
+            return Sitemap::Index(index);
+        }
+
+        Sitemap::UrlSet(document.select(&url_selector)
+            .filter_map(Self::parse_entry)
+            .collect())
+    }
+
+    fn parse_entry(element: ElementRef) -> Option<SitemapEntry> {
+        let child_text = |name: &str| {
+            let selector = Selector::parse(name).ok()?;
+            element.select(&selector)
+                .next()
+                .map(|el| el.text().collect::<String>().trim().to_string())
+                .filter(|text| !text.is_empty())
+        };
+
+        Some(SitemapEntry {
+            url: child_text("loc")?,
+            lastmod: child_text("lastmod").and_then(|date| Self::parse_lastmod(&date)),
+        })
+    }
+
+    /// Sitemaps use W3C datetimes, which may be a bare date
+    fn parse_lastmod(date: &str) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(date)
+            .map(|dt| dt.with_timezone(&Utc))
+            .ok()
+            .or_else(|| {
+                NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+                    .and_then(|d| d.and_hms_opt(0, 0, 0))
+                    .map(|dt| dt.and_utc())
+            })
+    }
+
+    /// Fetches a sitemap and every sitemap it indexes, returning the pages found
+    pub async fn discover(&self, url: &str) -> Result<Vec<SitemapEntry>> {
+        self.discover_with(url, |url| async move {
+            http::read_text(http::get(&url).await?).await
+        }).await
+    }
+
+    /// Like [`discover`](Self::discover), but reads documents through `fetch`
+    pub async fn discover_with<F, Fut>(&self, url: &str, fetch: F) -> Result<Vec<SitemapEntry>>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<String>>,
+    {
+        let mut entries = Vec::new();
+        let mut pending = vec![(url.to_string(), 0)];
+
+        while let Some((url, depth)) = pending.pop() {
+            match Self::parse(&fetch(url.clone()).await?) {
+                Sitemap::UrlSet(mut pages) => entries.append(&mut pages),
+                Sitemap::Index(sitemaps) if depth < self.max_depth => {
+                    // Reversed so nested sitemaps are read in document order
+                    pending.extend(sitemaps.into_iter().rev().map(|sitemap| (sitemap.url, depth + 1)));
+                }
+                Sitemap::Index(_) => {
+                    tracing::warn!("Not following sitemap index {} deeper than {} levels", url, self.max_depth);
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INDEX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+            <sitemap><loc>https://news.test/sitemap-politics.xml</loc><lastmod>2024-03-14</lastmod></sitemap>
+            <sitemap><loc>https://news.test/sitemap-nested.xml</loc></sitemap>
+        </sitemapindex>"#;
+
+    const NESTED_INDEX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+            <sitemap><loc>https://news.test/sitemap-economy.xml</loc></sitemap>
+        </sitemapindex>"#;
+
+    const POLITICS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+            <url><loc>https://news.test/politics/senate-vote</loc><lastmod>2024-03-14T15:30:00+00:00</lastmod></url>
+            <url><loc>https://news.test/politics/cabinet</loc></url>
+        </urlset>"#;
+
+    const ECONOMY: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+            <url><loc> https://news.test/economy/inflation </loc><lastmod>2024-03-10</lastmod></url>
+        </urlset>"#;
+
+    async fn fetch(url: String) -> Result<String> {
+        match url.as_str() {
+            "https://news.test/sitemap.xml" => Ok(INDEX.to_string()),
+            "https://news.test/sitemap-nested.xml" => Ok(NESTED_INDEX.to_string()),
+            "https://news.test/sitemap-politics.xml" => Ok(POLITICS.to_string()),
+            "https://news.test/sitemap-economy.xml" => Ok(ECONOMY.to_string()),
+            _ => Err(nt_core::Error::Scraping(format!("Unexpected fetch: {}", url))),
+        }
+    }
+
+    #[test]
+    fn test_parse_urlset() {
+        let Sitemap::UrlSet(entries) = SitemapDiscovery::parse(POLITICS) else {
+            panic!("expected a urlset");
+        };
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].url, "https://news.test/politics/senate-vote");
+        assert_eq!(entries[0].lastmod.unwrap().to_rfc3339(), "2024-03-14T15:30:00+00:00");
+        assert_eq!(entries[1].lastmod, None);
+    }
+
+    #[tokio::test]
+    async fn test_discover_nested_index() {
+        let entries = SitemapDiscovery::new()
+            .discover_with("https://news.test/sitemap.xml", fetch)
+            .await
+            .unwrap();
+
+        let urls: Vec<&str> = entries.iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(urls, vec![
+            "https://news.test/politics/senate-vote",
+            "https://news.test/politics/cabinet",
+            "https://news.test/economy/inflation",
+        ]);
+        assert_eq!(entries[2].lastmod.unwrap().to_rfc3339(), "2024-03-10T00:00:00+00:00");
+
+        // A depth of one stops before the nested index
+        let shallow = SitemapDiscovery::new()
+            .with_max_depth(1)
+            .discover_with("https://news.test/sitemap.xml", fetch)
+            .await
+            .unwrap();
+        assert_eq!(shallow.len(), 2);
+    }
+}
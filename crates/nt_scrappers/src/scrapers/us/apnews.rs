@@ -0,0 +1,124 @@
+use crate::scrapers::configurable::{ConfigurableScraper, ScraperConfig};
+use super::REGION;
+
+pub const CONFIG: ScraperConfig = ScraperConfig {
+    name: "AP News",
+    emoji: "📰",
+    region: REGION,
+    language: "en",
+    base_url: "https://apnews.com",
+    domains: &["apnews.com"],
+    cli_names: &["apnews", "ap"],
+    article_path: "/article/",
+    link_selector: "a[href]",
+    title_selector: "h1",
+    content_selector: ".RichTextStoryBody p",
+    date_selector: "meta[property='article:published_time']",
+    date_attribute: "content",
+    boilerplate_patterns: &[],
+};
+
+impl ConfigurableScraper {
+    pub fn apnews() -> Self {
+        ConfigurableScraper::new(CONFIG)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nt_core::Scraper;
+
+    const ARTICLE_FIXTURE: &str = r#"
+        <html>
+        <head>
+            <meta property="article:published_time" content="2024-03-14T15:30:00Z">
+            <script type="application/ld+json">{"author": [{"name": "Jane Doe"}]}</script>
+        </head>
+        <body>
+            <h1 class="Page-headline">Senate passes spending bill</h1>
+            <div class="RichTextStoryBody RichTextBody">
+                <p>WASHINGTON (AP) — The Senate passed the bill on Thursday.</p>
+                <p></p>
+                <p>The measure now goes to the House.</p>
+            </div>
+            <div class="Footer"><p>Copyright notice</p></div>
+        </body>
+        </html>
+    "#;
+
+    const FRONT_PAGE_FIXTURE: &str = r#"
+        <html><body>
+            <a href="https://apnews.com/article/senate-spending-bill-1a2b3c">Senate</a>
+            <a href="/article/election-results-4d5e6f?utm_source=home">Election</a>
+            <a href="/article/senate-spending-bill-1a2b3c#comments">Senate again</a>
+            <a href="/hub/politics">Politics hub</a>
+            <a href="https://example.com/article/elsewhere">Elsewhere</a>
+        </body></html>
+    "#;
+
+    #[test]
+    fn test_can_handle() {
+        let scraper = ConfigurableScraper::apnews();
+        assert!(scraper.can_handle("https://apnews.com/article/some-story-123"));
+        assert!(scraper.can_handle("https://www.apnews.com/article/some-story-123"));
+        assert!(!scraper.can_handle("https://notapnews.com/article/some-story-123"));
+        assert!(!scraper.can_handle("https://www.clarin.com/article"));
+    }
+
+    #[test]
+    fn test_parse_article() {
+        let url = "https://apnews.com/article/senate-spending-bill-1a2b3c";
+        let article = ConfigurableScraper::apnews().parse_article(url, ARTICLE_FIXTURE).unwrap();
+
+        assert_eq!(article.url, url);
+        assert_eq!(article.title, "Senate passes spending bill");
+        assert_eq!(article.source, "AP News");
+        assert_eq!(article.sections.len(), 2);
+        assert_eq!(
+            article.content,
+            "WASHINGTON (AP) — The Senate passed the bill on Thursday.\nThe measure now goes to the House."
+        );
+        assert_eq!(article.authors, vec!["Jane Doe"]);
+        assert_eq!(article.published_at.to_rfc3339(), "2024-03-14T15:30:00+00:00");
+    }
+
+    #[test]
+    fn test_debug_parse() {
+        let url = "https://apnews.com/article/senate-spending-bill-1a2b3c";
+        let trace = ConfigurableScraper::apnews().debug_parse(url, ARTICLE_FIXTURE).unwrap();
+
+        assert_eq!(trace.url, url);
+        assert!(trace.error.is_none());
+        let title = trace.selector("title").unwrap();
+        assert_eq!((title.selector.as_str(), title.matches), ("h1", 1));
+        assert_eq!(title.first.as_deref(), Some("Senate passes spending bill"));
+        assert_eq!(trace.selector("content").unwrap().matches, 3);
+        assert_eq!(trace.selector("published_at").unwrap().first.as_deref(), Some("2024-03-14T15:30:00Z"));
+        assert_eq!(trace.fields["title"], "Senate passes spending bill");
+        assert_eq!(trace.fields["sections"], "2");
+        assert_eq!(trace.fields["authors"], "Jane Doe");
+
+        // A page without a headline reports the selector that came up empty
+        let trace = ConfigurableScraper::apnews().debug_parse(url, "<html><body><p>No headline</p></body></html>").unwrap();
+        assert_eq!(trace.selector("title").unwrap().matches, 0);
+        assert!(trace.fields.is_empty());
+        assert_eq!(trace.error.as_deref(), Some(format!("Scraping error: No title found at {}", url).as_str()));
+    }
+
+    #[test]
+    fn test_parse_article_urls() {
+        let urls = ConfigurableScraper::apnews().parse_article_urls(FRONT_PAGE_FIXTURE);
+        assert_eq!(urls, vec![
+            "https://apnews.com/article/election-results-4d5e6f",
+            "https://apnews.com/article/senate-spending-bill-1a2b3c",
+        ]);
+    }
+
+    #[test]
+    fn test_source_metadata() {
+        let metadata = ConfigurableScraper::apnews().source_metadata();
+        assert_eq!(metadata.region.name, "US");
+        assert_eq!(metadata.language, "en");
+    }
+}
@@ -0,0 +1,39 @@
+use std::sync::{Arc, Mutex};
+use nt_core::RegionMetadata;
+use crate::scrapers::BoxedScraper;
+use crate::scrapers::configurable::ConfigurableScraper;
+use crate::scrapers::feed::FeedScraper;
+pub mod apnews;
+pub mod npr;
+
+pub const REGION: RegionMetadata = RegionMetadata {
+    name: "US",
+    emoji: "🇺🇸",
+};
+
+/// Returns a vector of all available US news scrapers
+pub fn get_all_scrapers() -> Vec<Arc<Mutex<BoxedScraper>>> {
+    vec![
+        Arc::new(Mutex::new(Box::new(ConfigurableScraper::apnews()))),
+        Arc::new(Mutex::new(Box::new(FeedScraper::npr()))),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scrapers::get_scraper_factories;
+
+    #[test]
+    fn test_scrapers_are_registered() {
+        let factories = get_scraper_factories();
+        for scraper in get_all_scrapers() {
+            let name = scraper.lock().unwrap().source_metadata().name;
+            assert!(
+                factories.iter().any(|factory| factory().source_metadata().name == name),
+                "{} should have a factory",
+                name
+            );
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use nt_core::SourceMetadata;
+use crate::scrapers::feed::FeedScraper;
+use super::REGION;
+
+pub const FEED_URL: &str = "https://feeds.npr.org/1001/rss.xml";
+
+impl FeedScraper {
+    /// NPR's top stories, read from its RSS feed
+    pub fn npr() -> Self {
+        FeedScraper::new(
+            SourceMetadata {
+                name: "NPR",
+                emoji: "📻",
+                region: REGION,
+                language: "en",
+            },
+            FEED_URL,
+        )
+        .with_domains(&["npr.org"])
+        .with_cli_names(&["npr"])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nt_core::Scraper;
+
+    #[test]
+    fn test_can_handle() {
+        let scraper = FeedScraper::npr();
+        assert!(scraper.can_handle("https://www.npr.org/2024/03/14/123456/senate-bill"));
+        assert!(!scraper.can_handle("https://apnews.com/article/senate-bill"));
+        assert_eq!(scraper.cli_names(), vec!["npr"]);
+        assert_eq!(scraper.source_metadata().region.name, "US");
+    }
+}
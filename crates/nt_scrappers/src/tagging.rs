@@ -0,0 +1,130 @@
+//! Keyword extraction used to tag scraped articles.
+//!
+//! The text is split into runs of words at punctuation and stopwords, every
+//! phrase of up to [`MAX_PHRASE_WORDS`] words inside a run is counted, and the
+//! most frequent phrases win, longer ones first among equals. A phrase sharing
+//! a word with an already picked tag is skipped so `central bank` does not also
+//! produce `bank`.
+
+use std::collections::{HashMap, HashSet};
+
+/// Number of tags attached to an article when none are given explicitly
+pub const DEFAULT_TAG_LIMIT: usize = 5;
+
+/// Longest phrase, in words, that can become a tag
+const MAX_PHRASE_WORDS: usize = 3;
+
+/// Words shorter than this are never used as tags on their own
+const MIN_WORD_CHARS: usize = 3;
+
+/// English and Spanish stopwords, since most sources are Argentine outlets
+const STOPWORDS: &[&str] = &[
+    // English
+    "a", "about", "after", "all", "also", "an", "and", "any", "are", "as", "at", "be", "been",
+    "before", "but", "by", "can", "could", "did", "do", "does", "for", "from", "had", "has",
+    "have", "he", "her", "his", "how", "i", "if", "in", "into", "is", "it", "its", "more",
+    "most", "no", "not", "of", "on", "one", "or", "other", "our", "out", "over", "said", "she",
+    "so", "some", "than", "that", "the", "their", "them", "then", "there", "these", "they",
+    "this", "to", "up", "was", "we", "were", "what", "when", "which", "who", "will", "with",
+    "would", "you",
+    // Spanish
+    "al", "algo", "ante", "como", "con", "contra", "cual", "cuando", "de", "del", "desde",
+    "donde", "dos", "e", "el", "ella", "ellos", "en", "entre", "era", "es", "esa", "ese", "eso",
+    "esta", "está", "este", "esto", "están", "fue", "ha", "hace", "han", "hasta", "hay", "la",
+    "las", "le", "les", "lo", "los", "más", "mas", "me", "mi", "muy", "ni", "nos", "o", "otro",
+    "para", "pero", "por", "porque", "que", "qué", "se", "ser", "si", "sí", "sin", "sobre", "son",
+    "su", "sus", "también", "tiene", "todo", "todos", "tras", "u", "un", "una", "uno", "y", "ya",
+];
+
+/// Extracts up to `limit` keyword phrases from `text`, best first.
+///
+/// Tags are lowercased; ties are broken alphabetically so the result is stable.
+pub fn extract_tags(text: &str, limit: usize) -> Vec<String> {
+    let stopwords: HashSet<&str> = STOPWORDS.iter().copied().collect();
+
+    let mut counts: HashMap<Vec<String>, usize> = HashMap::new();
+    for run in word_runs(text, &stopwords) {
+        for len in 1..=MAX_PHRASE_WORDS.min(run.len()) {
+            for phrase in run.windows(len) {
+                if len == 1 && phrase[0].chars().count() < MIN_WORD_CHARS {
+                    continue;
+                }
+                *counts.entry(phrase.to_vec()).or_default() += 1;
+            }
+        }
+    }
+
+    let mut ranked: Vec<(Vec<String>, usize)> = counts.into_iter().collect();
+    ranked.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then_with(|| b.0.len().cmp(&a.0.len()))
+            .then_with(|| a.0.cmp(&b.0))
+    });
+
+    let mut used_words: HashSet<String> = HashSet::new();
+    let mut tags = Vec::new();
+    for (phrase, _) in ranked {
+        if tags.len() >= limit {
+            break;
+        }
+        if phrase.iter().any(|word| used_words.contains(word)) {
+            continue;
+        }
+        used_words.extend(phrase.iter().cloned());
+        tags.push(phrase.join(" "));
+    }
+    tags
+}
+
+/// Splits text into lowercased runs of consecutive words, breaking at
+/// punctuation, stopwords and bare numbers.
+fn word_runs(text: &str, stopwords: &HashSet<&str>) -> Vec<Vec<String>> {
+    let mut runs = Vec::new();
+
+    for fragment in text.split(|c: char| !(c.is_alphanumeric() || c.is_whitespace() || c == '-' || c == '\'')) {
+        let mut current: Vec<String> = Vec::new();
+        for token in fragment.split_whitespace() {
+            let word = token.trim_matches(|c: char| c == '-' || c == '\'').to_lowercase();
+            let is_break = word.is_empty()
+                || stopwords.contains(word.as_str())
+                || word.chars().all(|c| c.is_numeric());
+            if is_break {
+                if !current.is_empty() {
+                    runs.push(std::mem::take(&mut current));
+                }
+            } else {
+                current.push(word);
+            }
+        }
+        if !current.is_empty() {
+            runs.push(current);
+        }
+    }
+
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_tags() {
+        let text = "The Central Bank raised rates. Inflation is high, \
+                    and the central bank expects inflation to fall.";
+        assert_eq!(extract_tags(text, 3), vec!["central bank", "inflation", "raised rates"]);
+        assert_eq!(extract_tags(text, 5), vec!["central bank", "inflation", "raised rates", "expects", "fall"]);
+    }
+
+    #[test]
+    fn test_extract_tags_spanish_stopwords() {
+        let tags = extract_tags("El Banco Central de la República Argentina y el Gobierno.", 5);
+        assert_eq!(tags, vec!["banco central", "república argentina", "gobierno"]);
+    }
+
+    #[test]
+    fn test_extract_tags_empty() {
+        assert!(extract_tags("", 5).is_empty());
+        assert!(extract_tags("de la y el", 5).is_empty());
+    }
+}
@@ -1,15 +1,22 @@
 use async_trait::async_trait;
-use nt_core::{Article, Result, ArticleStorage};
+use nt_core::{Article, ArticleFilter, Result, ArticleStorage, StorageStats};
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use chromadb::v1::{
     client::ChromaClient,
-    collection::{CollectionEntries, QueryOptions},
+    collection::{ChromaCollection, CollectionEntries, GetOptions, QueryOptions},
 };
-use crate::{StorageBackend, BackendConfig, EmbeddingModel};
+use crate::{StorageBackend, BackendConfig, DistanceMetric, EmbeddingModel};
+use std::collections::HashMap;
 use std::env;
 use std::ops::Deref;
 
+/// Metadata key flagging a record as carrying `tag`
+fn tag_key(tag: &str) -> String {
+    format!("tag:{}", tag)
+}
+
 #[derive(Debug, Clone)]
 pub struct ChromaConfig {
     pub config: BackendConfig,
@@ -56,8 +63,10 @@ impl EmbeddingStore {
     }
 
     pub async fn store_article(&self, article: &Article, embedding: &[f32]) -> Result<()> {
+        crate::require_embedding(&article.url, embedding)?;
+
         let collection = self.client.get_or_create_collection(&self.collection_name, None)
-            .map_err(|e| nt_core::Error::External(e))?;
+            .map_err(nt_core::Error::External)?;
 
         let doc_str = serde_json::to_string(article)
             .map_err(|e| nt_core::Error::Serialization(e))?;
@@ -77,15 +86,15 @@ impl EmbeddingStore {
             documents: None,
         };
 
-        collection.add(entries, None)
-            .map_err(|e| nt_core::Error::External(e))?;
+        collection.upsert(entries, None)
+            .map_err(nt_core::Error::External)?;
 
         Ok(())
     }
 
     pub async fn find_similar(&self, embedding: &[f32], limit: usize) -> Result<Vec<Article>> {
         let collection = self.client.get_or_create_collection(&self.collection_name, None)
-            .map_err(|e| nt_core::Error::External(e))?;
+            .map_err(nt_core::Error::External)?;
 
         let query_options = QueryOptions {
             query_embeddings: Some(vec![embedding.to_vec()]),
@@ -97,7 +106,7 @@ impl EmbeddingStore {
         };
 
         let results = collection.query(query_options, None)
-            .map_err(|e| nt_core::Error::External(e))?;
+            .map_err(nt_core::Error::External)?;
 
         let mut articles = Vec::new();
         if let Some(metadatas) = results.metadatas {
@@ -121,7 +130,7 @@ impl EmbeddingStore {
 
     pub async fn get_by_source(&self, source: &str) -> Result<Vec<Article>> {
         let collection = self.client.get_or_create_collection(&self.collection_name, None)
-            .map_err(|e| nt_core::Error::External(e))?;
+            .map_err(nt_core::Error::External)?;
 
         let where_metadata = serde_json::Value::Object(serde_json::Map::from_iter(vec![
             ("source".to_string(), serde_json::Value::String(source.to_string())),
@@ -137,7 +146,7 @@ impl EmbeddingStore {
         };
 
         let results = collection.query(query_options, None)
-            .map_err(|e| nt_core::Error::External(e))?;
+            .map_err(nt_core::Error::External)?;
 
         let mut articles = Vec::new();
         if let Some(metadatas) = results.metadatas {
@@ -174,20 +183,39 @@ impl ChromaStore {
         })
     }
 
+    /// Chroma only reads the `hnsw:space` metadata when the collection is
+    /// first created, so changing the metric requires a new collection.
+    fn collection(&self) -> Result<ChromaCollection> {
+        let space = match self.config.distance_metric {
+            DistanceMetric::Cosine => "cosine",
+            DistanceMetric::Dot => "ip",
+            DistanceMetric::Euclidean => "l2",
+        };
+        let metadata = serde_json::Map::from_iter(vec![
+            ("hnsw:space".to_string(), serde_json::Value::String(space.to_string())),
+        ]);
+        self.client.get_or_create_collection(&self.config.collection, Some(metadata))
+            .map_err(nt_core::Error::External)
+    }
+
     pub async fn store_article(&self, article: &Article, embedding: &[f32]) -> Result<()> {
-        let collection = self.client.get_or_create_collection(&self.config.collection, None)
-            .map_err(|e| nt_core::Error::External(e))?;
+        crate::require_embedding(&article.url, embedding)?;
+
+        let collection = self.collection()?;
 
         let doc_str = serde_json::to_string(article)
             .map_err(|e| nt_core::Error::Serialization(e))?;
 
-        let metadata = serde_json::Map::from_iter(vec![
+        let mut metadata = serde_json::Map::from_iter(vec![
             ("url".to_string(), serde_json::Value::String(article.url.clone())),
             ("title".to_string(), serde_json::Value::String(article.title.clone())),
             ("source".to_string(), serde_json::Value::String(article.source.clone())),
             ("published_at".to_string(), serde_json::Value::String(article.published_at.to_rfc3339())),
             ("doc".to_string(), serde_json::Value::String(doc_str)),
         ]);
+        for tag in &article.tags {
+            metadata.insert(tag_key(tag), serde_json::Value::Bool(true));
+        }
 
         let entries = CollectionEntries {
             ids: vec![&article.url],
@@ -196,15 +224,14 @@ impl ChromaStore {
             documents: None,
         };
 
-        collection.add(entries, None)
-            .map_err(|e| nt_core::Error::External(e))?;
+        collection.upsert(entries, None)
+            .map_err(nt_core::Error::External)?;
 
         Ok(())
     }
 
     pub async fn find_similar(&self, embedding: &[f32], limit: usize) -> Result<Vec<Article>> {
-        let collection = self.client.get_or_create_collection(&self.config.collection, None)
-            .map_err(|e| nt_core::Error::External(e))?;
+        let collection = self.collection()?;
 
         let query_options = QueryOptions {
             query_embeddings: Some(vec![embedding.to_vec()]),
@@ -216,7 +243,7 @@ impl ChromaStore {
         };
 
         let results = collection.query(query_options, None)
-            .map_err(|e| nt_core::Error::External(e))?;
+            .map_err(nt_core::Error::External)?;
 
         let mut articles = Vec::new();
         if let Some(metadatas) = results.metadatas {
@@ -239,8 +266,7 @@ impl ChromaStore {
     }
 
     pub async fn get_by_source(&self, source: &str) -> Result<Vec<Article>> {
-        let collection = self.client.get_or_create_collection(&self.config.collection, None)
-            .map_err(|e| nt_core::Error::External(e))?;
+        let collection = self.collection()?;
 
         let where_metadata = serde_json::Value::Object(serde_json::Map::from_iter(vec![
             ("source".to_string(), serde_json::Value::String(source.to_string())),
@@ -256,7 +282,7 @@ impl ChromaStore {
         };
 
         let results = collection.query(query_options, None)
-            .map_err(|e| nt_core::Error::External(e))?;
+            .map_err(nt_core::Error::External)?;
 
         let mut articles = Vec::new();
         if let Some(metadatas) = results.metadatas {
@@ -277,6 +303,237 @@ impl ChromaStore {
 
         Ok(articles)
     }
+
+    pub async fn find_similar_scored(&self, embedding: &[f32], limit: usize) -> Result<Vec<(Article, f32)>> {
+        self.find_similar_filtered(embedding, limit, &ArticleFilter::default()).await
+    }
+
+    /// Chroma can only range-filter numeric metadata, so sources are filtered on
+    /// the server and publication dates on the returned articles.
+    pub async fn find_similar_filtered(&self, embedding: &[f32], limit: usize, filter: &ArticleFilter) -> Result<Vec<(Article, f32)>> {
+        let collection = self.collection()?;
+
+        let mut conditions = Vec::new();
+        if !filter.sources.is_empty() {
+            conditions.push(serde_json::json!({ "source": { "$in": filter.sources } }));
+        }
+        if !filter.exclude_sources.is_empty() {
+            conditions.push(serde_json::json!({ "source": { "$nin": filter.exclude_sources } }));
+        }
+        let where_metadata = match conditions.len() {
+            0 => None,
+            1 => conditions.pop(),
+            _ => Some(serde_json::json!({ "$and": conditions })),
+        };
+
+        // Dates are only checked here, so every candidate has to come back when they are set
+        let n_results = if filter.since.is_some() || filter.until.is_some() {
+            collection.count().map_err(nt_core::Error::External)?
+        } else {
+            limit
+        };
+        if n_results == 0 {
+            return Ok(Vec::new());
+        }
+
+        let query_options = QueryOptions {
+            query_embeddings: Some(vec![embedding.to_vec()]),
+            query_texts: None,
+            n_results: Some(n_results),
+            where_document: None,
+            where_metadata,
+            include: Some(vec!["metadatas", "embeddings"]),
+        };
+
+        let results = collection.query(query_options, None)
+            .map_err(nt_core::Error::External)?;
+
+        let metadatas = results.metadatas.into_iter().flatten().flatten().flatten();
+        let embeddings = results.embeddings.into_iter().flatten().flatten().flatten();
+        let mut scored = Vec::new();
+        for (metadata, article_embedding) in metadatas.zip(embeddings) {
+            let article = metadata.as_ref()
+                .and_then(|metadata| metadata.get("doc"))
+                .and_then(|v| v.as_str())
+                .and_then(|doc_str| serde_json::from_str::<Article>(doc_str).ok());
+            if let Some(article) = article.filter(|article| filter.matches(article)) {
+                // Scored with the collection's own metric, so scores follow its ranking
+                scored.push((article, self.config.distance_metric.score(embedding, &article_embedding)));
+            }
+        }
+        scored.truncate(limit);
+
+        Ok(scored)
+    }
+
+    pub async fn get_article_embeddings(&self, urls: &[String]) -> Result<Vec<Vec<f32>>> {
+        if urls.is_empty() {
+            return Ok(Vec::new());
+        }
+        let results = self.collection()?
+            .get(GetOptions {
+                ids: urls.to_vec(),
+                include: Some(vec!["embeddings".to_string()]),
+                ..Default::default()
+            })
+            .map_err(nt_core::Error::External)?;
+
+        let mut embeddings: HashMap<String, Vec<f32>> = results.ids.into_iter()
+            .zip(results.embeddings.into_iter().flatten())
+            .filter_map(|(url, embedding)| Some((url, embedding?)))
+            .collect();
+        Ok(urls.iter().map(|url| embeddings.remove(url).unwrap_or_default()).collect())
+    }
+
+    /// Chroma fixes a collection's dimension with its first embedding, so the
+    /// dimension is read from any stored one, or `None` while it's empty
+    pub async fn index_dimension(&self) -> Result<Option<usize>> {
+        let results = self.collection()?
+            .get(GetOptions {
+                limit: Some(1),
+                include: Some(vec!["embeddings".to_string()]),
+                ..Default::default()
+            })
+            .map_err(nt_core::Error::External)?;
+        Ok(results.embeddings.into_iter().flatten().flatten().next().map(|embedding| embedding.len()))
+    }
+
+    /// Drops the collection, which is created again on next use
+    pub async fn recreate_index(&mut self, dimension: usize) -> Result<()> {
+        self.client.delete_collection(&self.config.collection)
+            .map_err(nt_core::Error::External)?;
+        self.config.config.vector_size = dimension as u64;
+        Ok(())
+    }
+
+    pub async fn get_article(&self, url: &str) -> Result<Option<Article>> {
+        let where_metadata = serde_json::Value::Object(serde_json::Map::from_iter(vec![
+            ("url".to_string(), serde_json::Value::String(url.to_string())),
+        ]));
+        let articles = self.query_metadata(Some(where_metadata), 1)?;
+        Ok(articles.into_iter().next())
+    }
+
+    pub async fn list_articles(&self, offset: usize, limit: usize) -> Result<Vec<Article>> {
+        let mut articles = self.all_articles()?;
+        articles.sort_by(|a, b| a.url.cmp(&b.url));
+        Ok(articles.into_iter().skip(offset).take(limit).collect())
+    }
+
+    /// Chroma metadata cannot hold lists, so each tag is stored as a flag of
+    /// its own and filtered on with a `where` clause.
+    pub async fn get_by_tag(&self, tag: &str) -> Result<Vec<Article>> {
+        let count = self.collection()?
+            .count()
+            .map_err(nt_core::Error::External)?;
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+        let where_metadata = serde_json::Value::Object(serde_json::Map::from_iter(vec![
+            (tag_key(tag), serde_json::Value::Bool(true)),
+        ]));
+        self.query_metadata(Some(where_metadata), count)
+    }
+
+    pub async fn list_tags(&self) -> Result<Vec<(String, usize)>> {
+        Ok(nt_core::count_tags(&self.all_articles()?))
+    }
+
+    pub async fn search_text(&self, query: &str, limit: usize) -> Result<Vec<Article>> {
+        Ok(nt_core::search_articles(self.all_articles()?, query, limit))
+    }
+
+    /// Chroma can only range-filter numeric metadata, so the cutoff is checked
+    /// against every stored article and the old ones are deleted by id.
+    pub async fn delete_older_than(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let urls: Vec<String> = self.all_articles()?
+            .into_iter()
+            .filter(|article| article.published_at < cutoff)
+            .map(|article| article.url)
+            .collect();
+        if urls.is_empty() {
+            return Ok(0);
+        }
+
+        self.collection()?
+            .delete(Some(urls.iter().map(String::as_str).collect()), None, None)
+            .map_err(|e| nt_core::Error::Database(format!("Failed to delete old articles: {}", e)))?;
+        Ok(urls.len())
+    }
+
+    /// Chroma has no aggregate queries, so the total comes from `count` and the
+    /// rest is tallied from each record's source and date metadata, without
+    /// reading the stored documents. Every record is stored with an embedding,
+    /// so every article counts as embedded.
+    pub async fn stats(&self) -> Result<StorageStats> {
+        let collection = self.collection()?;
+        let articles = collection.count().map_err(nt_core::Error::External)?;
+        let mut stats = StorageStats {
+            articles,
+            embeddings: articles,
+            ..Default::default()
+        };
+        if articles == 0 {
+            return Ok(stats);
+        }
+
+        let query_options = QueryOptions {
+            query_embeddings: Some(vec![vec![0.0; self.config.vector_size as usize]]), // Dummy embedding for filtering
+            n_results: Some(articles),
+            include: Some(vec!["metadatas"]),
+            ..Default::default()
+        };
+        let results = collection.query(query_options, None)
+            .map_err(nt_core::Error::External)?;
+
+        for metadata in results.metadatas.into_iter().flatten().flatten().flatten().flatten() {
+            let source = metadata.get("source").and_then(|v| v.as_str());
+            let published_at = metadata.get("published_at")
+                .and_then(|v| v.as_str())
+                .and_then(|date| DateTime::parse_from_rfc3339(date).ok());
+            if let (Some(source), Some(published_at)) = (source, published_at) {
+                stats.record_source(source, published_at.with_timezone(&Utc));
+            }
+        }
+        Ok(stats)
+    }
+
+    fn all_articles(&self) -> Result<Vec<Article>> {
+        let collection = self.collection()?;
+        let count = collection.count()
+            .map_err(nt_core::Error::External)?;
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+        self.query_metadata(None, count)
+    }
+
+    fn query_metadata(&self, where_metadata: Option<serde_json::Value>, n_results: usize) -> Result<Vec<Article>> {
+        let collection = self.collection()?;
+
+        let query_options = QueryOptions {
+            query_embeddings: Some(vec![vec![0.0; self.config.vector_size as usize]]), // Dummy embedding for filtering
+            query_texts: None,
+            n_results: Some(n_results),
+            where_document: None,
+            where_metadata,
+            include: None,
+        };
+
+        let results = collection.query(query_options, None)
+            .map_err(nt_core::Error::External)?;
+
+        let mut articles = Vec::new();
+        for metadata in results.metadatas.into_iter().flatten().flatten().flatten().flatten() {
+            if let Some(doc_str) = metadata.get("doc").and_then(|v| v.as_str()) {
+                if let Ok(article) = serde_json::from_str::<Article>(doc_str) {
+                    articles.push(article);
+                }
+            }
+        }
+
+        Ok(articles)
+    }
 }
 
 pub struct ChromaStorage {
@@ -319,25 +576,78 @@ impl ArticleStorage for ChromaStorage {
         store.find_similar(embedding, limit).await
     }
 
+    async fn find_similar_scored(&self, embedding: &[f32], limit: usize) -> Result<Vec<(Article, f32)>> {
+        let store = self.store.read().await;
+        store.find_similar_scored(embedding, limit).await
+    }
+
+    async fn find_similar_filtered(&self, embedding: &[f32], limit: usize, filter: &ArticleFilter) -> Result<Vec<(Article, f32)>> {
+        let store = self.store.read().await;
+        store.find_similar_filtered(embedding, limit, filter).await
+    }
+
     async fn get_by_source(&self, source: &str) -> Result<Vec<Article>> {
         let store = self.store.read().await;
         store.get_by_source(source).await
     }
 
+    async fn get_article(&self, url: &str) -> Result<Option<Article>> {
+        let store = self.store.read().await;
+        store.get_article(url).await
+    }
+
+    async fn list_articles(&self, offset: usize, limit: usize) -> Result<Vec<Article>> {
+        let store = self.store.read().await;
+        store.list_articles(offset, limit).await
+    }
+
+    async fn get_by_tag(&self, tag: &str) -> Result<Vec<Article>> {
+        let store = self.store.read().await;
+        store.get_by_tag(tag).await
+    }
+
+    async fn list_tags(&self) -> Result<Vec<(String, usize)>> {
+        let store = self.store.read().await;
+        store.list_tags().await
+    }
+
+    async fn search_text(&self, query: &str, limit: usize) -> Result<Vec<Article>> {
+        let store = self.store.read().await;
+        store.search_text(query, limit).await
+    }
+
     async fn delete_article(&self, url: &str) -> Result<()> {
         let store = self.store.read().await;
-        let collection = store.client.get_or_create_collection(&store.config.collection, None)
-            .map_err(|e| nt_core::Error::External(e))?;
+        let collection = store.collection()?;
         
         collection.delete(Some(vec![url]), None, None)
             .map_err(|e| nt_core::Error::Database(format!("Failed to delete article: {}", e)))?;
         Ok(())
     }
 
+    async fn delete_older_than(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let store = self.store.read().await;
+        store.delete_older_than(cutoff).await
+    }
+
+    async fn stats(&self) -> Result<StorageStats> {
+        let store = self.store.read().await;
+        store.stats().await
+    }
+
+    async fn index_dimension(&self) -> Result<Option<usize>> {
+        let store = self.store.read().await;
+        store.index_dimension().await
+    }
+
+    async fn recreate_index(&self, dimension: usize) -> Result<()> {
+        let mut store = self.store.write().await;
+        store.recreate_index(dimension).await
+    }
+
     async fn get_article_embedding(&self, url: &str) -> Result<Vec<f32>> {
         let store = self.store.read().await;
-        let collection = store.client.get_or_create_collection(&store.config.collection, None)
-            .map_err(|e| nt_core::Error::External(e))?;
+        let collection = store.collection()?;
 
         let query_options = QueryOptions {
             query_embeddings: Some(vec![vec![0.0; store.config.vector_size as usize]]), // Dummy embedding for filtering
@@ -351,7 +661,7 @@ impl ArticleStorage for ChromaStorage {
         };
 
         let results = collection.query(query_options, None)
-            .map_err(|e| nt_core::Error::External(e))?;
+            .map_err(nt_core::Error::External)?;
 
         let embeddings = results.embeddings
             .ok_or_else(|| nt_core::Error::Database("No embeddings found in results".to_string()))?;
@@ -366,12 +676,16 @@ impl ArticleStorage for ChromaStorage {
 
         Ok(embedding_vec)
     }
+
+    async fn get_article_embeddings(&self, urls: &[String]) -> Result<Vec<Vec<f32>>> {
+        let store = self.store.read().await;
+        store.get_article_embeddings(urls).await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
 
     #[tokio::test]
     async fn test_chroma_storage() {
@@ -385,6 +699,7 @@ mod tests {
             summary: None,
             authors: vec!["Test Author".to_string()],
             related_articles: Vec::new(),
+            tags: Vec::new(),
         };
 
         let storage = ChromaStorage::new().await.unwrap();
@@ -1,9 +1,15 @@
 use async_trait::async_trait;
-use nt_core::{Article, Result, ArticleStorage};
+use nt_core::{mmr_rerank, Article, ArticleFilter, ArticleIdScheme, EmbeddingModelInfo, Result, ArticleStorage, StorageStats, MMR_CANDIDATE_FACTOR};
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use crate::{StorageBackend, BackendConfig, EmbeddingModel};
+use tokio_util::sync::CancellationToken;
+use crate::{StorageBackend, BackendConfig, DistanceMetric, EmbeddingModel};
 use std::ops::Deref;
+use std::path::Path;
+
+/// Articles scored between yields to the runtime during a similarity scan
+const SCAN_CHUNK: usize = 256;
 
 #[derive(Debug, Clone)]
 pub struct MemoryConfig {
@@ -35,14 +41,18 @@ pub struct MemoryStore {
     collection: String,
     articles: std::collections::HashMap<String, (Article, Vec<f32>)>,
     vector_size: u64,
+    distance_metric: DistanceMetric,
+    embedding_model: Option<EmbeddingModelInfo>,
 }
 
 impl MemoryStore {
-    pub fn new(collection: String, vector_size: u64) -> Self {
+    pub fn new(collection: String, vector_size: u64, distance_metric: DistanceMetric) -> Self {
         Self {
             collection,
             articles: std::collections::HashMap::new(),
             vector_size,
+            distance_metric,
+            embedding_model: None,
         }
     }
 
@@ -51,16 +61,82 @@ impl MemoryStore {
         Ok(())
     }
 
+    pub async fn insert_only(&mut self, article: &Article, embedding: &[f32]) -> Result<()> {
+        if self.articles.contains_key(&article.url) {
+            return Err(nt_core::Error::AlreadyExists(article.url.clone()));
+        }
+        self.store_article(article, embedding).await
+    }
+
+    pub async fn store_articles(&mut self, batch: &[(Article, Vec<f32>)]) -> Result<()> {
+        for (article, embedding) in batch {
+            self.articles.insert(article.url.clone(), (article.clone(), embedding.clone()));
+        }
+        Ok(())
+    }
+
     pub async fn find_similar(&self, embedding: &[f32], limit: usize) -> Result<Vec<Article>> {
+        let scored = self.find_similar_scored(embedding, limit).await?;
+        Ok(scored.into_iter().map(|(a, _)| a).collect())
+    }
+
+    pub async fn find_similar_scored(&self, embedding: &[f32], limit: usize) -> Result<Vec<(Article, f32)>> {
+        self.find_similar_cancellable(embedding, limit, &CancellationToken::new()).await
+    }
+
+    pub async fn find_similar_cancellable(&self, embedding: &[f32], limit: usize, cancel: &CancellationToken) -> Result<Vec<(Article, f32)>> {
+        self.scan(embedding, limit, &ArticleFilter::default(), cancel).await
+    }
+
+    pub async fn find_similar_filtered(&self, embedding: &[f32], limit: usize, filter: &ArticleFilter) -> Result<Vec<(Article, f32)>> {
+        self.scan(embedding, limit, filter, &CancellationToken::new()).await
+    }
+
+    pub async fn find_similar_diverse(&self, embedding: &[f32], limit: usize, lambda: f32) -> Result<Vec<(Article, f32)>> {
+        let scored = self.find_similar_scored(embedding, limit.saturating_mul(MMR_CANDIDATE_FACTOR)).await?;
+        let candidates = scored.into_iter()
+            .map(|(article, score)| {
+                let article_embedding = self.embedding(&article.url);
+                (article, score, article_embedding)
+            })
+            .collect();
+        Ok(mmr_rerank(candidates, limit, lambda))
+    }
+
+    pub async fn get_article_embeddings(&self, urls: &[String]) -> Result<Vec<Vec<f32>>> {
+        Ok(urls.iter().map(|url| self.embedding(url)).collect())
+    }
+
+    fn embedding(&self, url: &str) -> Vec<f32> {
+        self.articles.get(url).map(|(_, e)| e.clone()).unwrap_or_default()
+    }
+
+    /// Scores every stored article meeting `filter`, yielding to the runtime every
+    /// [`SCAN_CHUNK`] articles so large scans don't starve other tasks, and giving
+    /// up with `Error::Cancelled` once `cancel` is triggered.
+    async fn scan(&self, embedding: &[f32], limit: usize, filter: &ArticleFilter, cancel: &CancellationToken) -> Result<Vec<(Article, f32)>> {
         let mut articles_with_scores = Vec::new();
 
-        for (_, (article, article_embedding)) in &self.articles {
-            let similarity = nt_core::cosine_similarity(embedding, article_embedding);
+        for (i, (article, article_embedding)) in self.articles.values().enumerate() {
+            if i % SCAN_CHUNK == 0 {
+                if cancel.is_cancelled() {
+                    return Err(nt_core::Error::Cancelled("similarity search".to_string()));
+                }
+                if i > 0 {
+                    tokio::task::yield_now().await;
+                }
+            }
+            // Articles stored without an embedding can still be fetched and
+            // text-searched, but have nothing to compare against
+            if article_embedding.is_empty() || !filter.matches(article) {
+                continue;
+            }
+            let similarity = self.distance_metric.score(embedding, article_embedding);
             articles_with_scores.push((article.clone(), similarity));
         }
 
         articles_with_scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-        Ok(articles_with_scores.into_iter().take(limit).map(|(a, _)| a).collect())
+        Ok(articles_with_scores.into_iter().take(limit).collect())
     }
 
     pub async fn get_by_source(&self, source: &str) -> Result<Vec<Article>> {
@@ -70,17 +146,101 @@ impl MemoryStore {
             .collect())
     }
 
+    pub async fn get_article(&self, url: &str) -> Result<Option<Article>> {
+        Ok(self.articles.get(url).map(|(article, _)| article.clone()))
+    }
+
+    /// Hashes the stored URLs in place instead of paging through copies of the articles
+    pub async fn get_by_id(&self, id: &str, scheme: ArticleIdScheme) -> Result<Option<Article>> {
+        let found = match scheme {
+            ArticleIdScheme::Base64Url => scheme.url(id).and_then(|url| self.articles.get(&url)),
+            ArticleIdScheme::UrlHash => self.articles.values().find(|(article, _)| scheme.id(&article.url) == id),
+        };
+        Ok(found.map(|(article, _)| article.clone()))
+    }
+
+    pub async fn list_articles(&self, offset: usize, limit: usize) -> Result<Vec<Article>> {
+        let mut articles: Vec<&Article> = self.articles.values().map(|(article, _)| article).collect();
+        articles.sort_by(|a, b| a.url.cmp(&b.url));
+        Ok(articles.into_iter().skip(offset).take(limit).cloned().collect())
+    }
+
+    /// Pages by URL, the cursor being the last URL of the previous page
+    pub async fn list_articles_page(&self, cursor: Option<&str>, limit: usize) -> Result<(Vec<Article>, Option<String>)> {
+        let mut articles: Vec<&Article> = self.articles.values()
+            .map(|(article, _)| article)
+            .filter(|article| cursor.is_none_or(|cursor| article.url.as_str() > cursor))
+            .collect();
+        articles.sort_by(|a, b| a.url.cmp(&b.url));
+        let page: Vec<Article> = articles.into_iter().take(limit).cloned().collect();
+        let next = nt_core::next_url_cursor(&page, limit);
+        Ok((page, next))
+    }
+
+    pub async fn get_by_tag(&self, tag: &str) -> Result<Vec<Article>> {
+        Ok(self.articles.values()
+            .filter(|(article, _)| article.tags.iter().any(|t| t == tag))
+            .map(|(article, _)| article.clone())
+            .collect())
+    }
+
+    pub async fn list_tags(&self) -> Result<Vec<(String, usize)>> {
+        Ok(nt_core::count_tags(self.articles.values().map(|(article, _)| article)))
+    }
+
+    pub async fn search_text(&self, query: &str, limit: usize) -> Result<Vec<Article>> {
+        Ok(nt_core::search_articles(
+            self.articles.values().map(|(article, _)| article.clone()),
+            query,
+            limit,
+        ))
+    }
+
     pub async fn delete_article(&mut self, url: &str) -> Result<()> {
         self.articles.remove(url);
         Ok(())
     }
 
+    pub async fn delete_older_than(&mut self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let before = self.articles.len();
+        self.articles.retain(|_, (article, _)| article.published_at >= cutoff);
+        Ok(before - self.articles.len())
+    }
+
     pub async fn get_article_embedding(&self, url: &str) -> Result<Vec<f32>> {
         match self.articles.get(url) {
             Some((_, embedding)) => Ok(embedding.clone()),
             None => Err(nt_core::Error::Database(format!("Article not found: {}", url)))
         }
     }
+
+    pub async fn stats(&self) -> Result<StorageStats> {
+        let mut stats = StorageStats::default();
+        for (article, embedding) in self.articles.values() {
+            stats.record(article, !embedding.is_empty());
+        }
+        Ok(stats)
+    }
+
+    pub fn embedding_model(&self) -> Option<EmbeddingModelInfo> {
+        self.embedding_model.clone()
+    }
+
+    pub fn set_embedding_model(&mut self, model: &EmbeddingModelInfo) {
+        self.embedding_model = Some(model.clone());
+    }
+
+    pub fn dump(&self) -> Vec<(Article, Vec<f32>)> {
+        let mut items: Vec<(Article, Vec<f32>)> = self.articles.values().cloned().collect();
+        items.sort_by(|a, b| a.0.url.cmp(&b.0.url));
+        items
+    }
+
+    pub fn load(&mut self, items: Vec<(Article, Vec<f32>)>) {
+        for (article, embedding) in items {
+            self.articles.insert(article.url.clone(), (article, embedding));
+        }
+    }
 }
 
 pub struct MemoryStorage {
@@ -90,12 +250,49 @@ pub struct MemoryStorage {
 
 impl MemoryStorage {
     pub async fn new() -> Result<Self> {
-        let config = MemoryConfig::new();
+        Ok(Self::with_config(MemoryConfig::new()))
+    }
+
+    /// Like [`find_similar_scored`](ArticleStorage::find_similar_scored), but aborts
+    /// with `Error::Cancelled` once `cancel` is triggered.
+    pub async fn find_similar_cancellable(&self, embedding: &[f32], limit: usize, cancel: &CancellationToken) -> Result<Vec<(Article, f32)>> {
+        let store = self.store.read().await;
+        store.find_similar_cancellable(embedding, limit, cancel).await
+    }
+
+    /// Every stored article with its embedding, ordered by url.
+    pub async fn dump(&self) -> Vec<(Article, Vec<f32>)> {
+        self.store.read().await.dump()
+    }
+
+    /// Stores the output of [`dump`](Self::dump), replacing articles with the same url.
+    pub async fn load(&self, items: Vec<(Article, Vec<f32>)>) {
+        self.store.write().await.load(items)
+    }
+
+    /// Writes a snapshot of the storage to `path` as JSON.
+    pub async fn save_to(&self, path: impl AsRef<Path>) -> Result<()> {
+        let snapshot = serde_json::to_vec(&self.dump().await)?;
+        tokio::fs::write(path, snapshot).await?;
+        Ok(())
+    }
+
+    /// Loads a snapshot written by [`save_to`](Self::save_to), returning the number of articles read.
+    pub async fn load_from(&self, path: impl AsRef<Path>) -> Result<usize> {
+        let snapshot = tokio::fs::read(path).await?;
+        let items: Vec<(Article, Vec<f32>)> = serde_json::from_slice(&snapshot)?;
+        let count = items.len();
+        self.load(items).await;
+        Ok(count)
+    }
+
+    pub fn with_config(config: MemoryConfig) -> Self {
         let store = Arc::new(RwLock::new(MemoryStore::new(
             config.collection.clone(),
-            config.vector_size
+            config.vector_size,
+            config.distance_metric,
         )));
-        Ok(Self { store, config })
+        Self { store, config }
     }
 }
 
@@ -121,45 +318,134 @@ impl ArticleStorage for MemoryStorage {
         store.store_article(article, embedding).await
     }
 
+    async fn insert_only(&self, article: &Article, embedding: &[f32]) -> Result<()> {
+        let mut store = self.store.write().await;
+        store.insert_only(article, embedding).await
+    }
+
+    async fn store_articles(&self, batch: &[(Article, Vec<f32>)]) -> Result<()> {
+        let mut store = self.store.write().await;
+        store.store_articles(batch).await
+    }
+
     async fn find_similar(&self, embedding: &[f32], limit: usize) -> Result<Vec<Article>> {
         let store = self.store.read().await;
         store.find_similar(embedding, limit).await
     }
 
+    async fn find_similar_scored(&self, embedding: &[f32], limit: usize) -> Result<Vec<(Article, f32)>> {
+        let store = self.store.read().await;
+        store.find_similar_scored(embedding, limit).await
+    }
+
+    async fn find_similar_filtered(&self, embedding: &[f32], limit: usize, filter: &ArticleFilter) -> Result<Vec<(Article, f32)>> {
+        let store = self.store.read().await;
+        store.find_similar_filtered(embedding, limit, filter).await
+    }
+
+    async fn find_similar_diverse(&self, embedding: &[f32], limit: usize, lambda: f32) -> Result<Vec<(Article, f32)>> {
+        let store = self.store.read().await;
+        store.find_similar_diverse(embedding, limit, lambda).await
+    }
+
     async fn get_by_source(&self, source: &str) -> Result<Vec<Article>> {
         let store = self.store.read().await;
         store.get_by_source(source).await
     }
 
+    async fn get_article(&self, url: &str) -> Result<Option<Article>> {
+        let store = self.store.read().await;
+        store.get_article(url).await
+    }
+
+    async fn get_by_id(&self, id: &str, scheme: ArticleIdScheme) -> Result<Option<Article>> {
+        let store = self.store.read().await;
+        store.get_by_id(id, scheme).await
+    }
+
+    async fn list_articles(&self, offset: usize, limit: usize) -> Result<Vec<Article>> {
+        let store = self.store.read().await;
+        store.list_articles(offset, limit).await
+    }
+
+    async fn list_articles_page(&self, cursor: Option<&str>, limit: usize) -> Result<(Vec<Article>, Option<String>)> {
+        let store = self.store.read().await;
+        store.list_articles_page(cursor, limit).await
+    }
+
+    async fn get_by_tag(&self, tag: &str) -> Result<Vec<Article>> {
+        let store = self.store.read().await;
+        store.get_by_tag(tag).await
+    }
+
+    async fn list_tags(&self) -> Result<Vec<(String, usize)>> {
+        let store = self.store.read().await;
+        store.list_tags().await
+    }
+
+    async fn search_text(&self, query: &str, limit: usize) -> Result<Vec<Article>> {
+        let store = self.store.read().await;
+        store.search_text(query, limit).await
+    }
+
     async fn delete_article(&self, url: &str) -> Result<()> {
         let mut store = self.store.write().await;
         store.delete_article(url).await
     }
 
+    async fn delete_older_than(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let mut store = self.store.write().await;
+        store.delete_older_than(cutoff).await
+    }
+
     async fn get_article_embedding(&self, url: &str) -> Result<Vec<f32>> {
         let store = self.store.read().await;
         store.get_article_embedding(url).await
     }
+
+    async fn get_article_embeddings(&self, urls: &[String]) -> Result<Vec<Vec<f32>>> {
+        let store = self.store.read().await;
+        store.get_article_embeddings(urls).await
+    }
+
+    async fn stats(&self) -> Result<StorageStats> {
+        let store = self.store.read().await;
+        store.stats().await
+    }
+
+    async fn embedding_model(&self) -> Result<Option<EmbeddingModelInfo>> {
+        Ok(self.store.read().await.embedding_model())
+    }
+
+    async fn set_embedding_model(&self, model: &EmbeddingModelInfo) -> Result<()> {
+        self.store.write().await.set_embedding_model(model);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
 
-    #[tokio::test]
-    async fn test_memory_storage() {
-        let article = Article {
-            url: "http://test.com".to_string(),
-            title: "Test Article".to_string(),
-            content: "This is a test article about politics.".to_string(),
+    fn test_article(url: &str, title: &str, content: &str) -> Article {
+        Article {
+            url: url.to_string(),
+            title: title.to_string(),
+            content: content.to_string(),
             published_at: Utc::now(),
             source: "test".to_string(),
             sections: vec![],
             summary: None,
-            authors: vec!["Test Author".to_string()],
-            related_articles: Vec::new(),
-        };
+            authors: vec![],
+            related_articles: vec![],
+            tags: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_storage() {
+        let mut article = test_article("http://test.com", "Test Article", "This is a test article about politics.");
+        article.authors = vec!["Test Author".to_string()];
 
         let storage = MemoryStorage::new().await.unwrap();
         let vector_size = storage.config.vector_size;
@@ -168,4 +454,313 @@ mod tests {
         let similar = storage.find_similar(&embedding, 1).await.unwrap();
         assert!(!similar.is_empty());
     }
-} 
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_store_article_upserts_by_url() {
+        let mut article = test_article("http://test.com", "Test Article", "First version.");
+
+        let storage = MemoryStorage::new().await.unwrap();
+        storage.store_article(&article, &[1.0, 0.0]).await.unwrap();
+        article.content = "Second version.".to_string();
+        storage.store_article(&article, &[0.0, 1.0]).await.unwrap();
+
+        let stored = storage.get_by_source("test").await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].content, "Second version.");
+        assert_eq!(storage.get_article_embedding(&article.url).await.unwrap(), vec![0.0, 1.0]);
+
+        let result = storage.insert_only(&article, &[1.0, 1.0]).await;
+        assert!(matches!(result, Err(nt_core::Error::AlreadyExists(_))));
+        assert_eq!(storage.get_article_embedding(&article.url).await.unwrap(), vec![0.0, 1.0]);
+
+        article.url = "http://test.com/other".to_string();
+        storage.insert_only(&article, &[1.0, 1.0]).await.unwrap();
+        assert_eq!(storage.get_by_source("test").await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_tags() {
+        let storage = MemoryStorage::new().await.unwrap();
+        for (url, tags) in [
+            ("http://test.com/a", vec!["economy", "politics"]),
+            ("http://test.com/b", vec!["politics"]),
+            ("http://test.com/c", vec![]),
+        ] {
+            let mut article = test_article(url, "Test Article", "Content.");
+            article.tags = tags.into_iter().map(String::from).collect();
+            storage.store_article(&article, &[1.0, 0.0]).await.unwrap();
+        }
+
+        let mut politics: Vec<String> = storage.get_by_tag("politics").await.unwrap()
+            .into_iter().map(|a| a.url).collect();
+        politics.sort();
+        assert_eq!(politics, vec!["http://test.com/a", "http://test.com/b"]);
+        assert!(storage.get_by_tag("sports").await.unwrap().is_empty());
+
+        assert_eq!(storage.list_tags().await.unwrap(), vec![
+            ("politics".to_string(), 2),
+            ("economy".to_string(), 1),
+        ]);
+
+        let article = storage.get_article("http://test.com/b").await.unwrap().unwrap();
+        assert_eq!(article.tags, vec!["politics"]);
+        assert!(storage.get_article("http://missing.com").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_search_text() {
+        let storage = MemoryStorage::new().await.unwrap();
+        for (url, title, content) in [
+            ("http://test.com/a", "Senate passes budget", "The budget was approved after a long debate."),
+            ("http://test.com/b", "Markets rally", "Stocks rose as the Senate budget vote neared."),
+            ("http://test.com/c", "Football final", "The home team won the cup."),
+        ] {
+            let article = test_article(url, title, content);
+            storage.store_article(&article, &[1.0, 0.0]).await.unwrap();
+        }
+
+        let urls = |articles: Vec<Article>| articles.into_iter().map(|a| a.url).collect::<Vec<_>>();
+
+        // Title matches rank first, and case is ignored
+        assert_eq!(urls(storage.search_text("SENATE budget", 10).await.unwrap()),
+            vec!["http://test.com/a", "http://test.com/b"]);
+        assert_eq!(urls(storage.search_text("senate budget", 1).await.unwrap()),
+            vec!["http://test.com/a"]);
+        assert_eq!(urls(storage.search_text("cup", 10).await.unwrap()),
+            vec!["http://test.com/c"]);
+
+        // Every term must match
+        assert!(storage.search_text("senate cup", 10).await.unwrap().is_empty());
+        assert!(storage.search_text("election", 10).await.unwrap().is_empty());
+        assert!(storage.search_text("   ", 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_older_than() {
+        let storage = MemoryStorage::new().await.unwrap();
+        let now = Utc::now();
+        for (url, age_days) in [("http://test.com/old", 90), ("http://test.com/month", 30), ("http://test.com/new", 1)] {
+            let mut article = test_article(url, "Test Article", "Content.");
+            article.published_at = now - chrono::Duration::days(age_days);
+            storage.store_article(&article, &[1.0, 0.0]).await.unwrap();
+        }
+
+        assert_eq!(storage.delete_older_than(now - chrono::Duration::days(60)).await.unwrap(), 1);
+        let urls: Vec<String> = storage.list_articles(0, 10).await.unwrap().into_iter().map(|a| a.url).collect();
+        assert_eq!(urls, vec!["http://test.com/month", "http://test.com/new"]);
+
+        // Nothing is old enough anymore
+        assert_eq!(storage.delete_older_than(now - chrono::Duration::days(60)).await.unwrap(), 0);
+
+        assert_eq!(storage.delete_older_than(now).await.unwrap(), 2);
+        assert!(storage.list_articles(0, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_stats() {
+        let storage = MemoryStorage::new().await.unwrap();
+        assert_eq!(storage.stats().await.unwrap(), StorageStats::default());
+
+        let now = Utc::now();
+        for (url, source, age_days, embedding) in [
+            ("http://a.com/1", "a", 10, vec![1.0, 0.0]),
+            ("http://a.com/2", "a", 3, vec![0.0, 1.0]),
+            ("http://b.com/1", "b", 7, vec![]),
+        ] {
+            let mut article = test_article(url, "Test Article", "Content.");
+            article.published_at = now - chrono::Duration::days(age_days);
+            article.source = source.to_string();
+            storage.store_article(&article, &embedding).await.unwrap();
+        }
+
+        let stats = storage.stats().await.unwrap();
+        assert_eq!(stats.articles, 3);
+        assert_eq!(stats.per_source.into_iter().collect::<Vec<_>>(), vec![("a".to_string(), 2), ("b".to_string(), 1)]);
+        assert_eq!(stats.earliest, Some(now - chrono::Duration::days(10)));
+        assert_eq!(stats.latest, Some(now - chrono::Duration::days(3)));
+        assert_eq!(stats.embeddings, 2);
+    }
+
+    #[tokio::test]
+    async fn test_find_similar_cancellable() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let storage = MemoryStorage::new().await.unwrap();
+        for i in 0..SCAN_CHUNK * 8 {
+            let article = test_article(&format!("http://test.com/{}", i), "Test Article", "Content.");
+            storage.store_article(&article, &[1.0, i as f32]).await.unwrap();
+        }
+
+        // The test runtime has a single thread, so the ticker only runs when the scan yields
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let ticker = tokio::spawn({
+            let ticks = ticks.clone();
+            async move {
+                loop {
+                    ticks.fetch_add(1, Ordering::SeqCst);
+                    tokio::task::yield_now().await;
+                }
+            }
+        });
+        let before = ticks.load(Ordering::SeqCst);
+        let scored = storage.find_similar_cancellable(&[1.0, 0.0], 3, &CancellationToken::new()).await.unwrap();
+        assert_eq!(scored.len(), 3);
+        assert!(ticks.load(Ordering::SeqCst) > before);
+        ticker.abort();
+
+        // Cancelled before the scan even starts
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let result = storage.find_similar_cancellable(&[1.0, 0.0], 3, &cancel).await;
+        assert!(result.unwrap_err().is_cancelled());
+
+        // Cancelled by another task while the scan is running
+        let cancel = CancellationToken::new();
+        tokio::spawn({
+            let cancel = cancel.clone();
+            async move { cancel.cancel() }
+        });
+        let result = storage.find_similar_cancellable(&[1.0, 0.0], 3, &cancel).await;
+        assert!(result.unwrap_err().is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_round_trip() {
+        let storage = MemoryStorage::new().await.unwrap();
+        for (url, embedding) in [("http://test.com/a", [1.0, 0.0]), ("http://test.com/b", [0.0, 1.0])] {
+            let mut article = test_article(url, "Test Article", "Content.");
+            article.tags = vec!["politics".to_string()];
+            storage.store_article(&article, &embedding).await.unwrap();
+        }
+
+        let dump = storage.dump().await;
+        assert_eq!(dump.len(), 2);
+        let restored = MemoryStorage::new().await.unwrap();
+        restored.load(dump).await;
+        let similar = restored.find_similar_scored(&[0.0, 1.0], 1).await.unwrap();
+        assert_eq!(similar[0].0.url, "http://test.com/b");
+        assert_eq!(restored.get_article_embedding("http://test.com/a").await.unwrap(), vec![1.0, 0.0]);
+
+        let path = std::env::temp_dir().join(format!("nt-memory-snapshot-{}.json", std::process::id()));
+        storage.save_to(&path).await.unwrap();
+        let restored = MemoryStorage::new().await.unwrap();
+        assert_eq!(restored.load_from(&path).await.unwrap(), 2);
+        std::fs::remove_file(&path).unwrap();
+        let similar = restored.find_similar_scored(&[1.0, 0.0], 1).await.unwrap();
+        assert_eq!(similar[0].0.url, "http://test.com/a");
+        assert_eq!(similar[0].0.tags, vec!["politics"]);
+
+        assert!(restored.load_from(&path).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_find_similar_filtered() {
+        let storage = MemoryStorage::new().await.unwrap();
+        let now = Utc::now();
+        for (url, source, age_days, embedding) in [
+            ("http://clarin.com/a", "clarin", 1, [1.0, 0.0]),
+            ("http://clarin.com/old", "clarin", 30, [1.0, 0.1]),
+            ("http://lanacion.com/a", "lanacion", 2, [1.0, 0.2]),
+            ("http://lanacion.com/old", "lanacion", 30, [1.0, 0.0]),
+            ("http://lavoz.com/a", "lavoz", 3, [0.0, 1.0]),
+        ] {
+            let mut article = test_article(url, "Test Article", "Content.");
+            article.published_at = now - chrono::Duration::days(age_days);
+            article.source = source.to_string();
+            storage.store_article(&article, &embedding).await.unwrap();
+        }
+
+        let urls = |scored: Vec<(Article, f32)>| scored.into_iter().map(|(a, _)| a.url).collect::<Vec<_>>();
+
+        // Articles from other sources in the last week, best match first
+        let filter = ArticleFilter::new()
+            .exclude_source("clarin")
+            .since(now - chrono::Duration::days(7));
+        assert_eq!(urls(storage.find_similar_filtered(&[1.0, 0.0], 10, &filter).await.unwrap()),
+            vec!["http://lanacion.com/a", "http://lavoz.com/a"]);
+        assert_eq!(urls(storage.find_similar_filtered(&[1.0, 0.0], 1, &filter).await.unwrap()),
+            vec!["http://lanacion.com/a"]);
+
+        let filter = ArticleFilter::new().source("clarin").until(now - chrono::Duration::days(7));
+        assert_eq!(urls(storage.find_similar_filtered(&[1.0, 0.0], 10, &filter).await.unwrap()),
+            vec!["http://clarin.com/old"]);
+
+        // The default filter searches everything
+        assert_eq!(storage.find_similar_filtered(&[1.0, 0.0], 10, &ArticleFilter::new()).await.unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_find_similar_diverse() {
+        let storage = MemoryStorage::new().await.unwrap();
+        // Three near-identical takes on one story, and two other angles
+        for (url, embedding) in [
+            ("http://test.com/story-1", [1.0, 0.05]),
+            ("http://test.com/story-2", [1.0, 0.06]),
+            ("http://test.com/story-3", [1.0, 0.07]),
+            ("http://test.com/left", [0.7, 0.7]),
+            ("http://test.com/right", [0.7, -0.7]),
+        ] {
+            let article = test_article(url, "Test Article", "Content.");
+            storage.store_article(&article, &embedding).await.unwrap();
+        }
+
+        let urls = |scored: Vec<(Article, f32)>| scored.into_iter().map(|(a, _)| a.url).collect::<Vec<_>>();
+        let top_k = urls(storage.find_similar_scored(&[1.0, 0.0], 3).await.unwrap());
+        assert_eq!(top_k, vec!["http://test.com/story-1", "http://test.com/story-2", "http://test.com/story-3"]);
+
+        // Relevance alone is the plain top-k
+        assert_eq!(urls(storage.find_similar_diverse(&[1.0, 0.0], 3, 1.0).await.unwrap()), top_k);
+
+        // Favouring diversity picks one take on the story and the other angles
+        let diverse = storage.find_similar_diverse(&[1.0, 0.0], 3, 0.3).await.unwrap();
+        assert_eq!(urls(diverse.clone()), vec!["http://test.com/story-1", "http://test.com/right", "http://test.com/left"]);
+        // Scores stay the similarity to the query
+        assert!((diverse[1].1 - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-4);
+    }
+
+    #[tokio::test]
+    async fn test_distance_metric_ranking() {
+        let mut rankings = Vec::new();
+        for metric in [DistanceMetric::Cosine, DistanceMetric::Dot] {
+            let storage = MemoryStorage::with_config(MemoryConfig {
+                config: MemoryConfig::new().config.with_distance_metric(metric),
+            });
+            // "aligned" points the same way as the query, "long" is off-axis but much larger
+            for (url, embedding) in [("http://test.com/aligned", [1.0, 0.0]), ("http://test.com/long", [10.0, 10.0])] {
+                let article = test_article(url, "Test Article", "Content.");
+                storage.store_article(&article, &embedding).await.unwrap();
+            }
+
+            let ranked: Vec<String> = storage.find_similar(&[1.0, 0.0], 2).await.unwrap()
+                .into_iter().map(|a| a.url).collect();
+            rankings.push(ranked);
+        }
+
+        assert_eq!(rankings[0], vec!["http://test.com/aligned", "http://test.com/long"]);
+        assert_eq!(rankings[1], vec!["http://test.com/long", "http://test.com/aligned"]);
+    }
+
+    #[tokio::test]
+    async fn test_empty_embedding() {
+        let storage = MemoryStorage::with_config(MemoryConfig {
+            config: MemoryConfig::new().config.with_distance_metric(DistanceMetric::Euclidean),
+        });
+        for (url, title, embedding) in [
+            ("http://test.com/embedded", "Budget vote", vec![1.0, 0.0]),
+            ("http://test.com/keyword", "Budget debate", vec![]),
+        ] {
+            let article = test_article(url, title, "Content.");
+            storage.store_article(&article, &embedding).await.unwrap();
+        }
+
+        // The article without an embedding is kept for lookups and text search
+        assert!(storage.get_article("http://test.com/keyword").await.unwrap().is_some());
+        assert_eq!(storage.search_text("budget", 10).await.unwrap().len(), 2);
+        assert_eq!(storage.stats().await.unwrap().embeddings, 1);
+
+        // but never shows up in similarity searches, where it would otherwise score 0
+        let similar = storage.find_similar_scored(&[5.0, 5.0], 10).await.unwrap();
+        assert_eq!(similar.len(), 1);
+        assert_eq!(similar[0].0.url, "http://test.com/embedded");
+    }
+}
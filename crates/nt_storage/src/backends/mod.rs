@@ -9,6 +9,9 @@ pub mod chroma;
 #[cfg(feature = "sqlite")]
 pub mod sqlite;
 
+#[cfg(feature = "postgres")]
+pub mod postgres;
+
 pub use memory::MemoryStorage;
 
 #[cfg(feature = "qdrant")]
@@ -18,4 +21,7 @@ pub use qdrant::QdrantStorage;
 pub use chroma::ChromaStorage;
 
 #[cfg(feature = "sqlite")]
-pub use sqlite::SQLiteStorage; 
\ No newline at end of file
+pub use sqlite::SQLiteStorage;
+
+#[cfg(feature = "postgres")]
+pub use postgres::PostgresStorage; 
\ No newline at end of file
@@ -0,0 +1,914 @@
+use async_trait::async_trait;
+use nt_core::{Article, ArticleIdScheme, EmbeddingModelInfo, Result, ArticleStorage, StorageStats};
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use crate::{StorageBackend, BackendConfig, EmbeddingModel};
+use std::ops::Deref;
+use sqlx::postgres::{PgPool, PgRow};
+use sqlx::types::Json;
+use sqlx::{Executor, Row};
+
+const MIGRATIONS: &[&str] = &[
+    r#"
+    CREATE TABLE IF NOT EXISTS articles (
+        url TEXT PRIMARY KEY,
+        title TEXT NOT NULL,
+        content TEXT NOT NULL,
+        source TEXT NOT NULL,
+        published_at TIMESTAMPTZ NOT NULL,
+        sections JSONB NOT NULL DEFAULT '[]',
+        summary TEXT,
+        authors JSONB NOT NULL DEFAULT '[]',
+        related_articles JSONB NOT NULL DEFAULT '[]',
+        embedding FLOAT4[]
+    );
+
+    CREATE INDEX IF NOT EXISTS articles_source ON articles(source);
+    CREATE INDEX IF NOT EXISTS articles_published_at ON articles(published_at)
+    "#,
+    r#"
+    CREATE TABLE IF NOT EXISTS article_tags (
+        url TEXT NOT NULL REFERENCES articles(url) ON DELETE CASCADE,
+        tag TEXT NOT NULL,
+        PRIMARY KEY (url, tag)
+    );
+
+    CREATE INDEX IF NOT EXISTS article_tags_tag ON article_tags(tag)
+    "#,
+    r#"
+    CREATE INDEX IF NOT EXISTS articles_text ON articles USING GIN (
+        (setweight(to_tsvector('simple', title), 'A') || setweight(to_tsvector('simple', content), 'B'))
+    )
+    "#,
+    r#"
+    CREATE TABLE IF NOT EXISTS storage_metadata (
+        key TEXT PRIMARY KEY,
+        value JSONB NOT NULL
+    )
+    "#,
+    r#"
+    ALTER TABLE articles ADD COLUMN IF NOT EXISTS url_hash TEXT;
+    UPDATE articles SET url_hash = encode(sha256(convert_to(url, 'UTF8')), 'hex') WHERE url_hash IS NULL;
+    CREATE INDEX IF NOT EXISTS articles_url_hash ON articles(url_hash)
+    "#,
+];
+
+/// Run after [`MIGRATIONS`] when pgvector is installed: a `vector` copy of the
+/// embedding, kept in sync by the database, that HNSW indexes can cover
+const PGVECTOR_MIGRATION: &str = r#"
+    ALTER TABLE articles ADD COLUMN IF NOT EXISTS embedding_vector vector
+        GENERATED ALWAYS AS (embedding::vector) STORED
+"#;
+
+/// Largest embedding pgvector's HNSW indexes accept
+const HNSW_MAX_DIMENSIONS: usize = 2000;
+
+/// Key of the embedding model in `storage_metadata`
+const EMBEDDING_MODEL_KEY: &str = "embedding_model";
+
+/// Columns of an article, with its tags collected as a JSON array, for queries
+/// selecting from `articles a`. The embedding is left out, most queries don't need it.
+const ARTICLE_COLUMNS: &str = r#"
+    a.url, a.title, a.content, a.source, a.published_at, a.sections, a.summary, a.authors, a.related_articles,
+    COALESCE((SELECT json_agg(t.tag ORDER BY t.tag) FROM article_tags t WHERE t.url = a.url), '[]') AS tags
+"#;
+
+/// Weighted document searched by `search_text`, matching the `articles_text` index.
+const TEXT_VECTOR: &str = "(setweight(to_tsvector('simple', a.title), 'A') || setweight(to_tsvector('simple', a.content), 'B'))";
+
+#[derive(Debug, Clone)]
+pub struct PostgresConfig {
+    pub config: BackendConfig,
+}
+
+impl PostgresConfig {
+    pub fn new() -> Self {
+        let url = env::var("DATABASE_URL").unwrap_or_else(|_| "postgres://localhost:5432/nt".to_string());
+        Self {
+            config: BackendConfig::new(
+                url,
+                "articles".to_string(),
+                EmbeddingModel::default(),
+                1536,
+            ),
+        }
+    }
+}
+
+impl Deref for PostgresConfig {
+    type Target = BackendConfig;
+
+    fn deref(&self) -> &Self::Target {
+        &self.config
+    }
+}
+
+pub struct PostgresStore {
+    pool: PgPool,
+    /// Whether the `vector` extension is installed, so similarity is computed
+    /// by the database rather than by scanning every embedding here
+    pgvector: bool,
+    /// Embedding sizes known to have an HNSW index
+    indexed_dimensions: std::sync::Mutex<HashSet<usize>>,
+}
+
+impl PostgresStore {
+    pub async fn new(url: &str) -> Result<Self> {
+        let pool = PgPool::connect(url)
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to connect to PostgreSQL: {}", e)))?;
+
+        // Migrations hold several statements, which only the simple query protocol runs
+        for (i, migration) in MIGRATIONS.iter().enumerate() {
+            pool.execute(*migration)
+                .await
+                .map_err(|e| nt_core::Error::Database(format!("Failed to apply migration {}: {}", i, e)))?;
+        }
+
+        // Creating the extension needs privileges the user may not have, or it
+        // may not be available on the server at all
+        if let Err(e) = pool.execute("CREATE EXTENSION IF NOT EXISTS vector").await {
+            tracing::debug!("⚠️ Could not create the pgvector extension: {}", e);
+        }
+        let pgvector = sqlx::query("SELECT 1 FROM pg_extension WHERE extname = 'vector'")
+            .fetch_optional(&pool)
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to look up extensions: {}", e)))?
+            .is_some();
+        if !pgvector {
+            tracing::info!("🐘 pgvector is not available, similarity search will scan every embedding");
+        }
+
+        let store = Self { pool, pgvector, indexed_dimensions: Default::default() };
+        if pgvector {
+            store.pool.execute(PGVECTOR_MIGRATION)
+                .await
+                .map_err(|e| nt_core::Error::Database(format!("Failed to add the vector column: {}", e)))?;
+            let dimensions: Vec<i32> = sqlx::query_scalar(
+                "SELECT DISTINCT vector_dims(embedding_vector) FROM articles WHERE embedding_vector IS NOT NULL"
+            )
+            .fetch_all(&store.pool)
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to look up embedding sizes: {}", e)))?;
+            for dimension in dimensions {
+                store.ensure_vector_index(dimension as usize).await?;
+            }
+        }
+        Ok(store)
+    }
+
+    /// Creates the HNSW index covering embeddings of `dimension` values, if
+    /// pgvector is installed and the index is missing.
+    ///
+    /// An index needs a fixed size, so each size gets a partial index of its own.
+    async fn ensure_vector_index(&self, dimension: usize) -> Result<()> {
+        if !self.pgvector || dimension == 0 || dimension > HNSW_MAX_DIMENSIONS {
+            return Ok(());
+        }
+        if !self.indexed_dimensions.lock().unwrap().insert(dimension) {
+            return Ok(());
+        }
+
+        let created = self.pool.execute(format!(
+            r#"
+            CREATE INDEX IF NOT EXISTS articles_embedding_hnsw_{0} ON articles
+            USING hnsw ((embedding_vector::vector({0})) vector_cosine_ops)
+            WHERE vector_dims(embedding_vector) = {0}
+            "#,
+            dimension
+        ).as_str()).await;
+        if let Err(e) = created {
+            self.indexed_dimensions.lock().unwrap().remove(&dimension);
+            return Err(nt_core::Error::Database(format!("Failed to create the vector index: {}", e)));
+        }
+        Ok(())
+    }
+
+    pub async fn store_article(&self, article: &Article, embedding: &[f32]) -> Result<()> {
+        self.write_article(article, embedding, true).await
+    }
+
+    pub async fn insert_only(&self, article: &Article, embedding: &[f32]) -> Result<()> {
+        self.write_article(article, embedding, false).await
+    }
+
+    /// Writes an article, its embedding and its tags in one transaction,
+    /// replacing an existing row with the same URL when `replace` is set and
+    /// failing with `AlreadyExists` otherwise.
+    ///
+    /// An empty embedding is stored as `NULL`: the article can be fetched and
+    /// searched by text, but is left out of similarity searches.
+    async fn write_article(&self, article: &Article, embedding: &[f32], replace: bool) -> Result<()> {
+        tracing::debug!("💾 Storing article: {}", article.title);
+
+        let conflict = if replace {
+            r#"
+            ON CONFLICT (url) DO UPDATE SET
+                title = EXCLUDED.title,
+                content = EXCLUDED.content,
+                source = EXCLUDED.source,
+                published_at = EXCLUDED.published_at,
+                sections = EXCLUDED.sections,
+                summary = EXCLUDED.summary,
+                authors = EXCLUDED.authors,
+                related_articles = EXCLUDED.related_articles,
+                embedding = EXCLUDED.embedding
+            "#
+        } else {
+            ""
+        };
+
+        let mut tx = self.pool.begin()
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to start transaction: {}", e)))?;
+
+        match sqlx::query(&format!(
+            r#"
+            INSERT INTO articles (
+                url, title, content, source, published_at, sections, summary, authors, related_articles, embedding, url_hash
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            {}
+            "#,
+            conflict
+        ))
+        .bind(&article.url)
+        .bind(&article.title)
+        .bind(&article.content)
+        .bind(&article.source)
+        .bind(article.published_at)
+        .bind(Json(&article.sections))
+        .bind(article.summary.as_deref())
+        .bind(Json(&article.authors))
+        .bind(Json(&article.related_articles))
+        .bind((!embedding.is_empty()).then_some(embedding))
+        .bind(ArticleIdScheme::UrlHash.id(&article.url))
+        .execute(&mut *tx)
+        .await {
+            Ok(_) => {}
+            Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+                tracing::debug!("⚠️ Article already exists: {}", article.url);
+                return Err(nt_core::Error::AlreadyExists(article.url.clone()));
+            }
+            Err(e) => {
+                tracing::error!("❌ Failed to store article: {}", e);
+                return Err(nt_core::Error::Database(format!("Failed to store article: {}", e)));
+            }
+        }
+
+        // Store tags, replacing whatever the previous version of the article had
+        sqlx::query("DELETE FROM article_tags WHERE url = $1")
+            .bind(&article.url)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to clear tags: {}", e)))?;
+        sqlx::query("INSERT INTO article_tags (url, tag) SELECT $1, UNNEST($2::TEXT[]) ON CONFLICT DO NOTHING")
+            .bind(&article.url)
+            .bind(&article.tags)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to store tags: {}", e)))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to commit article: {}", e)))?;
+        // The article is stored either way, searches just scan until the index exists
+        if let Err(e) = self.ensure_vector_index(embedding.len()).await {
+            tracing::warn!("⚠️ {}", e);
+        }
+
+        tracing::debug!("✨ Article and embedding stored successfully");
+        Ok(())
+    }
+
+    pub async fn find_similar(&self, embedding: &[f32], limit: usize) -> Result<Vec<Article>> {
+        let scored = self.find_similar_scored(embedding, limit).await?;
+        Ok(scored.into_iter().map(|(a, _)| a).collect())
+    }
+
+    /// Ranks articles by the cosine similarity of their embedding to `embedding`,
+    /// in the database when pgvector is installed and by scanning otherwise.
+    pub async fn find_similar_scored(&self, embedding: &[f32], limit: usize) -> Result<Vec<(Article, f32)>> {
+        if embedding.is_empty() {
+            return Ok(Vec::new());
+        }
+        if self.pgvector {
+            return self.find_similar_pgvector(embedding, limit).await;
+        }
+
+        let rows = sqlx::query(&format!(
+            "SELECT {}, a.embedding FROM articles a WHERE a.embedding IS NOT NULL",
+            ARTICLE_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| nt_core::Error::Database(format!("Failed to fetch articles: {}", e)))?;
+
+        let mut articles_with_scores = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let article_embedding: Vec<f32> = row.get("embedding");
+            let similarity = nt_core::cosine_similarity(embedding, &article_embedding);
+            articles_with_scores.push((self.row_to_article(row)?, similarity));
+        }
+
+        articles_with_scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        Ok(articles_with_scores.into_iter().take(limit).collect())
+    }
+
+    /// Only embeddings of the query's size can be compared, and the query
+    /// matches the partial index of that size, see [`ensure_vector_index`](Self::ensure_vector_index).
+    async fn find_similar_pgvector(&self, embedding: &[f32], limit: usize) -> Result<Vec<(Article, f32)>> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {0}, (1 - (a.embedding_vector::vector({1}) <=> $1::FLOAT4[]::vector({1})))::FLOAT4 AS score
+            FROM articles a
+            WHERE vector_dims(a.embedding_vector) = {1}
+            ORDER BY a.embedding_vector::vector({1}) <=> $1::FLOAT4[]::vector({1})
+            LIMIT $2
+            "#,
+            ARTICLE_COLUMNS,
+            embedding.len()
+        ))
+        .bind(embedding)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| nt_core::Error::Database(format!("Failed to find similar articles: {}", e)))?;
+
+        rows.iter()
+            .map(|row| Ok((self.row_to_article(row)?, row.get("score"))))
+            .collect()
+    }
+
+    pub async fn get_by_source(&self, source: &str) -> Result<Vec<Article>> {
+        let rows = sqlx::query(&format!("SELECT {} FROM articles a WHERE a.source = $1", ARTICLE_COLUMNS))
+            .bind(source)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to fetch articles: {}", e)))?;
+
+        rows.iter()
+            .map(|row| self.row_to_article(row))
+            .collect()
+    }
+
+    pub async fn get_article(&self, url: &str) -> Result<Option<Article>> {
+        let row = sqlx::query(&format!("SELECT {} FROM articles a WHERE a.url = $1", ARTICLE_COLUMNS))
+            .bind(url)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to fetch article: {}", e)))?;
+
+        row.map(|row| self.row_to_article(&row)).transpose()
+    }
+
+    pub async fn list_articles(&self, offset: usize, limit: usize) -> Result<Vec<Article>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM articles a ORDER BY a.url LIMIT $1 OFFSET $2",
+            ARTICLE_COLUMNS
+        ))
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| nt_core::Error::Database(format!("Failed to fetch articles: {}", e)))?;
+
+        rows.iter()
+            .map(|row| self.row_to_article(row))
+            .collect()
+    }
+
+    /// URL hashes are looked up in the indexed `url_hash` column
+    pub async fn get_by_id(&self, id: &str, scheme: ArticleIdScheme) -> Result<Option<Article>> {
+        match scheme {
+            ArticleIdScheme::Base64Url => match scheme.url(id) {
+                Some(url) => self.get_article(&url).await,
+                None => Ok(None),
+            },
+            ArticleIdScheme::UrlHash => {
+                let row = sqlx::query(&format!("SELECT {} FROM articles a WHERE a.url_hash = $1", ARTICLE_COLUMNS))
+                    .bind(id)
+                    .fetch_optional(&self.pool)
+                    .await
+                    .map_err(|e| nt_core::Error::Database(format!("Failed to fetch article: {}", e)))?;
+                row.map(|row| self.row_to_article(&row)).transpose()
+            }
+        }
+    }
+
+    /// Pages by URL, the cursor being the last URL of the previous page
+    pub async fn list_articles_page(&self, cursor: Option<&str>, limit: usize) -> Result<(Vec<Article>, Option<String>)> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM articles a WHERE $1::TEXT IS NULL OR a.url > $1 ORDER BY a.url LIMIT $2",
+            ARTICLE_COLUMNS
+        ))
+        .bind(cursor)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| nt_core::Error::Database(format!("Failed to fetch articles: {}", e)))?;
+
+        let articles = rows.iter()
+            .map(|row| self.row_to_article(row))
+            .collect::<Result<Vec<_>>>()?;
+        let next = nt_core::next_url_cursor(&articles, limit);
+        Ok((articles, next))
+    }
+
+    pub async fn get_by_tag(&self, tag: &str) -> Result<Vec<Article>> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM articles a
+            JOIN article_tags tg ON a.url = tg.url
+            WHERE tg.tag = $1
+            "#,
+            ARTICLE_COLUMNS
+        ))
+        .bind(tag)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| nt_core::Error::Database(format!("Failed to fetch articles: {}", e)))?;
+
+        rows.iter()
+            .map(|row| self.row_to_article(row))
+            .collect()
+    }
+
+    pub async fn list_tags(&self) -> Result<Vec<(String, usize)>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT tag, COUNT(*) AS count
+            FROM article_tags
+            GROUP BY tag
+            ORDER BY count DESC, tag
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| nt_core::Error::Database(format!("Failed to fetch tags: {}", e)))?;
+
+        Ok(rows.into_iter()
+            .map(|row| (row.get("tag"), row.get::<i64, _>("count") as usize))
+            .collect())
+    }
+
+    /// Searches the full-text index, every term having to match. Terms are
+    /// parsed by `plainto_tsquery`, so user input is never read as query syntax.
+    pub async fn search_text(&self, query: &str, limit: usize) -> Result<Vec<Article>> {
+        if query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {columns}
+            FROM articles a
+            WHERE {text} @@ plainto_tsquery('simple', $1)
+            ORDER BY ts_rank({text}, plainto_tsquery('simple', $1)) DESC, a.url
+            LIMIT $2
+            "#,
+            columns = ARTICLE_COLUMNS,
+            text = TEXT_VECTOR
+        ))
+        .bind(query)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| nt_core::Error::Database(format!("Failed to search articles: {}", e)))?;
+
+        rows.iter()
+            .map(|row| self.row_to_article(row))
+            .collect()
+    }
+
+    fn row_to_article(&self, row: &PgRow) -> Result<Article> {
+        let decode_error = |e: sqlx::Error| nt_core::Error::Database(format!("Failed to decode article: {}", e));
+
+        Ok(Article {
+            url: row.try_get("url").map_err(decode_error)?,
+            title: row.try_get("title").map_err(decode_error)?,
+            content: row.try_get("content").map_err(decode_error)?,
+            source: row.try_get("source").map_err(decode_error)?,
+            published_at: row.try_get("published_at").map_err(decode_error)?,
+            sections: row.try_get::<Json<_>, _>("sections").map_err(decode_error)?.0,
+            summary: row.try_get("summary").map_err(decode_error)?,
+            authors: row.try_get::<Json<_>, _>("authors").map_err(decode_error)?.0,
+            related_articles: row.try_get::<Json<_>, _>("related_articles").map_err(decode_error)?.0,
+            tags: row.try_get::<Json<_>, _>("tags").map_err(decode_error)?.0,
+        })
+    }
+
+    pub async fn delete_article(&self, url: &str) -> Result<()> {
+        tracing::debug!("🗑️ Deleting article: {}", url);
+
+        // Tags are deleted with the article through ON DELETE CASCADE
+        sqlx::query("DELETE FROM articles WHERE url = $1")
+            .bind(url)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to delete article: {}", e)))?;
+        Ok(())
+    }
+
+    pub async fn delete_older_than(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        tracing::debug!("🗑️ Deleting articles published before {}", cutoff);
+
+        let result = sqlx::query("DELETE FROM articles WHERE published_at < $1")
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to delete old articles: {}", e)))?;
+        Ok(result.rows_affected() as usize)
+    }
+
+    pub async fn get_article_embedding(&self, url: &str) -> Result<Vec<f32>> {
+        let row = sqlx::query("SELECT embedding FROM articles WHERE url = $1")
+            .bind(url)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to fetch embedding: {}", e)))?
+            .ok_or_else(|| nt_core::Error::Database(format!("Article not found: {}", url)))?;
+
+        // Articles stored without an embedding hand back an empty one
+        Ok(row.get::<Option<Vec<f32>>, _>("embedding").unwrap_or_default())
+    }
+
+    pub async fn get_article_embeddings(&self, urls: &[String]) -> Result<Vec<Vec<f32>>> {
+        let rows = sqlx::query("SELECT url, embedding FROM articles WHERE url = ANY($1)")
+            .bind(urls)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to fetch embeddings: {}", e)))?;
+
+        let mut embeddings: HashMap<String, Vec<f32>> = rows.iter()
+            .map(|row| (row.get("url"), row.get::<Option<Vec<f32>>, _>("embedding").unwrap_or_default()))
+            .collect();
+        Ok(urls.iter().map(|url| embeddings.remove(url).unwrap_or_default()).collect())
+    }
+
+    pub async fn embedding_model(&self) -> Result<Option<EmbeddingModelInfo>> {
+        let value: Option<Json<EmbeddingModelInfo>> = sqlx::query_scalar("SELECT value FROM storage_metadata WHERE key = $1")
+            .bind(EMBEDDING_MODEL_KEY)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to fetch the embedding model: {}", e)))?;
+        Ok(value.map(|Json(model)| model))
+    }
+
+    pub async fn set_embedding_model(&self, model: &EmbeddingModelInfo) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO storage_metadata (key, value) VALUES ($1, $2)
+            ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value
+            "#,
+        )
+        .bind(EMBEDDING_MODEL_KEY)
+        .bind(Json(model))
+        .execute(&self.pool)
+        .await
+        .map_err(|e| nt_core::Error::Database(format!("Failed to store the embedding model: {}", e)))?;
+        Ok(())
+    }
+
+    pub async fn stats(&self) -> Result<StorageStats> {
+        let db_error = |e: sqlx::Error| nt_core::Error::Database(format!("Failed to compute storage stats: {}", e));
+        let mut stats = StorageStats::default();
+
+        let rows = sqlx::query("SELECT source, COUNT(*) AS count FROM articles GROUP BY source")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(db_error)?;
+        for row in rows {
+            let count = row.get::<i64, _>("count") as usize;
+            stats.articles += count;
+            stats.per_source.insert(row.get("source"), count);
+        }
+
+        let row = sqlx::query(
+            r#"
+            SELECT MIN(published_at) AS earliest, MAX(published_at) AS latest, COUNT(embedding) AS embeddings
+            FROM articles
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        stats.earliest = row.get("earliest");
+        stats.latest = row.get("latest");
+        stats.embeddings = row.get::<i64, _>("embeddings") as usize;
+        Ok(stats)
+    }
+}
+
+pub struct PostgresStorage {
+    store: Arc<RwLock<PostgresStore>>,
+    config: PostgresConfig,
+}
+
+impl PostgresStorage {
+    pub async fn new() -> Result<Self> {
+        Self::with_config(PostgresConfig::new()).await
+    }
+
+    /// Connect to the database at `config.url`, which is read once: changing
+    /// the URL through [`StorageBackend::get_config`] afterwards has no effect.
+    pub async fn with_config(config: PostgresConfig) -> Result<Self> {
+        let store = Arc::new(RwLock::new(PostgresStore::new(&config.url).await?));
+        Ok(Self { store, config })
+    }
+}
+
+#[async_trait]
+impl StorageBackend for PostgresStorage {
+    fn get_error_message() -> &'static str {
+        "PostgreSQL should be running on postgres://localhost:5432/nt, or at DATABASE_URL"
+    }
+
+    async fn new() -> Result<Self> where Self: Sized {
+        Self::new().await
+    }
+
+    fn get_config(&mut self) -> Option<&mut BackendConfig> {
+        Some(&mut self.config.config)
+    }
+}
+
+#[async_trait]
+impl ArticleStorage for PostgresStorage {
+    async fn store_article(&self, article: &Article, embedding: &[f32]) -> Result<()> {
+        let store = self.store.read().await;
+        store.store_article(article, embedding).await
+    }
+
+    async fn insert_only(&self, article: &Article, embedding: &[f32]) -> Result<()> {
+        let store = self.store.read().await;
+        store.insert_only(article, embedding).await
+    }
+
+    async fn find_similar(&self, embedding: &[f32], limit: usize) -> Result<Vec<Article>> {
+        let store = self.store.read().await;
+        store.find_similar(embedding, limit).await
+    }
+
+    async fn find_similar_scored(&self, embedding: &[f32], limit: usize) -> Result<Vec<(Article, f32)>> {
+        let store = self.store.read().await;
+        store.find_similar_scored(embedding, limit).await
+    }
+
+    async fn get_by_source(&self, source: &str) -> Result<Vec<Article>> {
+        let store = self.store.read().await;
+        store.get_by_source(source).await
+    }
+
+    async fn get_article(&self, url: &str) -> Result<Option<Article>> {
+        let store = self.store.read().await;
+        store.get_article(url).await
+    }
+
+    async fn list_articles(&self, offset: usize, limit: usize) -> Result<Vec<Article>> {
+        let store = self.store.read().await;
+        store.list_articles(offset, limit).await
+    }
+
+    async fn get_by_tag(&self, tag: &str) -> Result<Vec<Article>> {
+        let store = self.store.read().await;
+        store.get_by_tag(tag).await
+    }
+
+    async fn list_tags(&self) -> Result<Vec<(String, usize)>> {
+        let store = self.store.read().await;
+        store.list_tags().await
+    }
+
+    async fn search_text(&self, query: &str, limit: usize) -> Result<Vec<Article>> {
+        let store = self.store.read().await;
+        store.search_text(query, limit).await
+    }
+
+    async fn delete_article(&self, url: &str) -> Result<()> {
+        let store = self.store.read().await;
+        store.delete_article(url).await
+    }
+
+    async fn delete_older_than(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let store = self.store.read().await;
+        store.delete_older_than(cutoff).await
+    }
+
+    async fn stats(&self) -> Result<StorageStats> {
+        let store = self.store.read().await;
+        store.stats().await
+    }
+
+    async fn get_article_embedding(&self, url: &str) -> Result<Vec<f32>> {
+        let store = self.store.read().await;
+        store.get_article_embedding(url).await
+    }
+
+    async fn get_article_embeddings(&self, urls: &[String]) -> Result<Vec<Vec<f32>>> {
+        let store = self.store.read().await;
+        store.get_article_embeddings(urls).await
+    }
+
+    async fn get_by_id(&self, id: &str, scheme: ArticleIdScheme) -> Result<Option<Article>> {
+        let store = self.store.read().await;
+        store.get_by_id(id, scheme).await
+    }
+
+    async fn list_articles_page(&self, cursor: Option<&str>, limit: usize) -> Result<(Vec<Article>, Option<String>)> {
+        let store = self.store.read().await;
+        store.list_articles_page(cursor, limit).await
+    }
+
+    async fn embedding_model(&self) -> Result<Option<EmbeddingModelInfo>> {
+        let store = self.store.read().await;
+        store.embedding_model().await
+    }
+
+    async fn set_embedding_model(&self, model: &EmbeddingModelInfo) -> Result<()> {
+        let store = self.store.read().await;
+        store.set_embedding_model(model).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Connects to the server at `NT_TEST_POSTGRES_URL`, in a schema of its own
+    /// so tests run side by side. The tests are ignored by default; run them with
+    /// `cargo test -- --ignored` with the variable set.
+    async fn test_store() -> PostgresStore {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let url = env::var("NT_TEST_POSTGRES_URL").expect("NT_TEST_POSTGRES_URL is not set");
+        let schema = format!("nt_test_{}_{}", std::process::id(), NEXT.fetch_add(1, Ordering::Relaxed));
+
+        let pool = PgPool::connect(&url).await.unwrap();
+        pool.execute(format!("DROP SCHEMA IF EXISTS {0} CASCADE; CREATE SCHEMA {0}", schema).as_str()).await.unwrap();
+        let separator = if url.contains('?') { '&' } else { '?' };
+        PostgresStore::new(&format!("{}{}options=-c%20search_path%3D{},public", url, separator, schema)).await.unwrap()
+    }
+
+    fn test_article(url: &str, title: &str, content: &str) -> Article {
+        Article {
+            url: url.to_string(),
+            title: title.to_string(),
+            content: content.to_string(),
+            published_at: Utc::now(),
+            source: "test".to_string(),
+            sections: vec![],
+            summary: None,
+            authors: vec![],
+            related_articles: vec![],
+            tags: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a PostgreSQL server at NT_TEST_POSTGRES_URL"]
+    async fn test_postgres_store_article_upserts_by_url() {
+        let store = test_store().await;
+        let mut article = test_article("http://test.com", "Test Article", "First version.");
+        article.authors = vec!["Test Author".to_string()];
+        article.tags = vec!["politics".to_string(), "economy".to_string()];
+
+        store.store_article(&article, &[1.0, 0.0]).await.unwrap();
+        article.content = "Second version.".to_string();
+        article.tags = vec!["sports".to_string()];
+        store.store_article(&article, &[0.0, 1.0]).await.unwrap();
+
+        let stored = store.get_by_source("test").await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].content, "Second version.");
+        assert_eq!(stored[0].authors, vec!["Test Author"]);
+        assert_eq!(stored[0].tags, vec!["sports"]);
+        assert_eq!(store.get_article_embedding("http://test.com").await.unwrap(), vec![0.0, 1.0]);
+
+        let result = store.insert_only(&article, &[1.0, 1.0]).await;
+        assert!(matches!(result, Err(nt_core::Error::AlreadyExists(_))));
+
+        article.url = "http://test.com/other".to_string();
+        store.insert_only(&article, &[1.0, 1.0]).await.unwrap();
+        assert_eq!(store.list_articles(0, 10).await.unwrap().len(), 2);
+        assert_eq!(store.list_tags().await.unwrap(), vec![("sports".to_string(), 2)]);
+        assert!(store.get_article("http://missing.com").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a PostgreSQL server at NT_TEST_POSTGRES_URL"]
+    async fn test_postgres_get_article_embeddings() {
+        let store = test_store().await;
+        store.store_article(&test_article("http://test.com/a", "A", "Content."), &[1.0, 0.0]).await.unwrap();
+        store.store_article(&test_article("http://test.com/b", "B", "Content."), &[0.0, 1.0]).await.unwrap();
+
+        let urls = ["http://test.com/b", "http://missing.com", "http://test.com/a"].map(String::from);
+        assert_eq!(store.get_article_embeddings(&urls).await.unwrap(), vec![vec![0.0, 1.0], vec![], vec![1.0, 0.0]]);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a PostgreSQL server at NT_TEST_POSTGRES_URL"]
+    async fn test_postgres_list_articles_page_and_embedding_model() {
+        let store = test_store().await;
+        for url in ["http://test.com/c", "http://test.com/a", "http://test.com/b"] {
+            store.store_article(&test_article(url, "Title", "Content."), &[1.0, 0.0]).await.unwrap();
+        }
+
+        let (page, cursor) = store.list_articles_page(None, 2).await.unwrap();
+        assert_eq!(page.iter().map(|a| a.url.as_str()).collect::<Vec<_>>(), vec!["http://test.com/a", "http://test.com/b"]);
+        let (page, cursor) = store.list_articles_page(cursor.as_deref(), 2).await.unwrap();
+        assert_eq!(page.iter().map(|a| a.url.as_str()).collect::<Vec<_>>(), vec!["http://test.com/c"]);
+        assert_eq!(cursor, None);
+
+        assert_eq!(store.embedding_model().await.unwrap(), None);
+        let model = EmbeddingModelInfo { name: "model".to_string(), dimension: 2 };
+        store.set_embedding_model(&model).await.unwrap();
+        store.set_embedding_model(&model).await.unwrap();
+        assert_eq!(store.embedding_model().await.unwrap(), Some(model));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a PostgreSQL server at NT_TEST_POSTGRES_URL"]
+    async fn test_postgres_get_by_url_hash() {
+        let store = test_store().await;
+        let url = "http://test.com/news/2024/story";
+        store.store_article(&test_article(url, "Title", "Content."), &[1.0, 0.0]).await.unwrap();
+
+        // The migration computes the same hash for rows written before the column existed
+        sqlx::query("UPDATE articles SET url_hash = encode(sha256(convert_to(url, 'UTF8')), 'hex')")
+            .execute(&store.pool)
+            .await
+            .unwrap();
+
+        let id = ArticleIdScheme::UrlHash.id(url);
+        let found = store.get_by_id(&id, ArticleIdScheme::UrlHash).await.unwrap();
+        assert_eq!(found.map(|a| a.url).as_deref(), Some(url));
+        assert!(store.get_by_id(&ArticleIdScheme::UrlHash.id("http://missing.com"), ArticleIdScheme::UrlHash).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a PostgreSQL server at NT_TEST_POSTGRES_URL"]
+    async fn test_postgres_similarity_and_text_search() {
+        let store = test_store().await;
+        for (url, title, content, embedding) in [
+            ("http://test.com/a", "Senate passes budget", "The budget was approved.", vec![1.0, 0.0]),
+            ("http://test.com/b", "Markets rally", "Stocks rose as the Senate budget vote neared.", vec![0.6, 0.8]),
+            ("http://test.com/c", "Football final", "The home team won the cup.", vec![]),
+        ] {
+            store.store_article(&test_article(url, title, content), &embedding).await.unwrap();
+        }
+
+        // The article without an embedding is left out of similarity searches
+        let similar = store.find_similar_scored(&[1.0, 0.0], 10).await.unwrap();
+        let urls: Vec<&str> = similar.iter().map(|(a, _)| a.url.as_str()).collect();
+        assert_eq!(urls, vec!["http://test.com/a", "http://test.com/b"]);
+        assert!((similar[1].1 - 0.6).abs() < 1e-6);
+        assert!(store.get_article_embedding("http://test.com/c").await.unwrap().is_empty());
+
+        // Title matches rank first, every term must match, and quotes are plain text
+        let found = store.search_text("SENATE \"budget", 10).await.unwrap();
+        assert_eq!(found.iter().map(|a| a.url.as_str()).collect::<Vec<_>>(), vec!["http://test.com/a", "http://test.com/b"]);
+        assert_eq!(store.search_text("cup", 10).await.unwrap()[0].url, "http://test.com/c");
+        assert!(store.search_text("senate cup", 10).await.unwrap().is_empty());
+        assert!(store.search_text("   ", 10).await.unwrap().is_empty());
+
+        store.delete_article("http://test.com/c").await.unwrap();
+        assert!(store.search_text("cup", 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a PostgreSQL server at NT_TEST_POSTGRES_URL"]
+    async fn test_postgres_stats_and_delete_older_than() {
+        let store = test_store().await;
+        assert_eq!(store.stats().await.unwrap(), StorageStats::default());
+
+        // Postgres keeps microseconds, so compare whole seconds
+        let now = DateTime::from_timestamp(Utc::now().timestamp(), 0).unwrap();
+        for (url, source, age_days, embedding) in [
+            ("http://a.com/1", "a", 90, vec![1.0, 0.0]),
+            ("http://a.com/2", "a", 3, vec![]),
+            ("http://b.com/1", "b", 7, vec![0.0, 1.0]),
+        ] {
+            let mut article = test_article(url, "Test Article", "Content.");
+            article.source = source.to_string();
+            article.published_at = now - chrono::Duration::days(age_days);
+            article.tags = vec!["politics".to_string()];
+            store.store_article(&article, &embedding).await.unwrap();
+        }
+
+        let stats = store.stats().await.unwrap();
+        assert_eq!(stats.articles, 3);
+        assert_eq!(stats.per_source.into_iter().collect::<Vec<_>>(), vec![("a".to_string(), 2), ("b".to_string(), 1)]);
+        assert_eq!(stats.earliest, Some(now - chrono::Duration::days(90)));
+        assert_eq!(stats.latest, Some(now - chrono::Duration::days(3)));
+        assert_eq!(stats.embeddings, 2);
+
+        assert_eq!(store.delete_older_than(now - chrono::Duration::days(60)).await.unwrap(), 1);
+        assert!(store.get_article("http://a.com/1").await.unwrap().is_none());
+        assert_eq!(store.list_tags().await.unwrap(), vec![("politics".to_string(), 2)]);
+    }
+}
@@ -1,22 +1,86 @@
 use async_trait::async_trait;
-use nt_core::{Article, Result, ArticleStorage};
+use nt_core::{Article, ArticleFilter, Result, ArticleStorage, StorageStats};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use qdrant_client::{
     prelude::*,
     qdrant::{
-        vectors_config::Config, CreateCollectionBuilder, Distance, Filter, PointStruct, ScalarQuantizationBuilder, SearchPointsBuilder, UpsertPointsBuilder, VectorParamsBuilder, Condition,
+        vectors_config::Config, CreateCollectionBuilder, Distance, Filter, PointStruct, ScalarQuantizationBuilder, SearchPointsBuilder, ScrollPointsBuilder, UpsertPointsBuilder, VectorParamsBuilder, Condition,
         CreateCollection, DeleteCollection, GetCollectionInfoRequest, DeletePoints, PointsSelector, DeletePointsBuilder,
-        VectorParams, VectorsConfig, PointId,
+        VectorParams, VectorsConfig, PointId, CountPointsBuilder, DatetimeRange, Timestamp,
+        GetPointsBuilder, vectors_output::VectorsOptions, CreateFieldIndexCollectionBuilder, FieldType,
+        FacetCountsBuilder, facet_value, OrderBy, Direction, PayloadIncludeSelector, point_id::PointIdOptions,
     },
     Payload, Qdrant,
 };
-use crate::{StorageBackend, BackendConfig, EmbeddingModel};
+use crate::{StorageBackend, BackendConfig, DistanceMetric, EmbeddingModel};
+use std::collections::HashMap;
 use std::env;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use std::ops::Deref;
 
+/// Qdrant's name for a distance metric
+fn distance(metric: DistanceMetric) -> Distance {
+    match metric {
+        DistanceMetric::Cosine => Distance::Cosine,
+        DistanceMetric::Dot => Distance::Dot,
+        DistanceMetric::Euclidean => Distance::Euclid,
+    }
+}
+
+/// Qdrant's representation of a point in time
+fn timestamp(time: DateTime<Utc>) -> Timestamp {
+    Timestamp {
+        seconds: time.timestamp(),
+        nanos: time.timestamp_subsec_nanos() as i32,
+    }
+}
+
+/// Qdrant payload filter equivalent to `filter`
+fn payload_filter(filter: &ArticleFilter) -> Filter {
+    let mut must = Vec::new();
+    if !filter.sources.is_empty() {
+        must.push(Condition::matches("source", filter.sources.clone()));
+    }
+    if filter.since.is_some() || filter.until.is_some() {
+        must.push(Condition::datetime_range("published_at", DatetimeRange {
+            gte: filter.since.map(timestamp),
+            lt: filter.until.map(timestamp),
+            ..Default::default()
+        }));
+    }
+
+    let mut must_not = Vec::new();
+    if !filter.exclude_sources.is_empty() {
+        must_not.push(Condition::matches("source", filter.exclude_sources.clone()));
+    }
+
+    Filter {
+        must,
+        must_not,
+        ..Default::default()
+    }
+}
+
+/// Stable point id for an article URL
+fn point_id(url: &str) -> String {
+    Uuid::new_v5(&Uuid::NAMESPACE_URL, url.as_bytes()).to_string()
+}
+
+/// Indexes the payload fields that stats facet and order by, which Qdrant
+/// only allows on indexed fields, and the tags articles are filtered by
+async fn create_field_indexes(client: &Qdrant, collection: &str) -> Result<()> {
+    for (field, field_type) in [("source", FieldType::Keyword), ("published_at", FieldType::Datetime), ("tags", FieldType::Keyword)] {
+        client.create_field_index(
+            CreateFieldIndexCollectionBuilder::new(collection, field, field_type).wait(true)
+        )
+        .await
+        .map_err(|e| nt_core::Error::External(e.into()))?;
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone)]
 pub struct QdrantConfig {
     pub config: BackendConfig,
@@ -104,13 +168,15 @@ impl QdrantStore {
             client
                 .create_collection(
                     CreateCollectionBuilder::new(collection)
-                        .vectors_config(VectorParamsBuilder::new(config.vector_size, Distance::Cosine))
+                        .vectors_config(VectorParamsBuilder::new(config.vector_size, distance(config.distance_metric)))
                         .quantization_config(ScalarQuantizationBuilder::default()),
                 )
                 .await
                 .map_err(|e| nt_core::Error::External(e.into()))?;
         }
 
+        create_field_indexes(&client, &config.collection).await?;
+
         Ok(Self {
             client: Arc::new(client),
             config,
@@ -118,6 +184,19 @@ impl QdrantStore {
     }
 
     pub async fn store_article(&self, article: &Article, embedding: &[f32]) -> Result<()> {
+        self.upsert(vec![Self::to_point(article, embedding)?]).await
+    }
+
+    pub async fn store_articles(&self, batch: &[(Article, Vec<f32>)]) -> Result<()> {
+        let points = batch.iter()
+            .map(|(article, embedding)| Self::to_point(article, embedding))
+            .collect::<Result<Vec<_>>>()?;
+        self.upsert(points).await
+    }
+
+    fn to_point(article: &Article, embedding: &[f32]) -> Result<PointStruct> {
+        crate::require_embedding(&article.url, embedding)?;
+
         let doc_str = serde_json::to_string(article)
             .map_err(|e| nt_core::Error::Serialization(e))?;
 
@@ -126,16 +205,21 @@ impl QdrantStore {
         payload.insert("title", article.title.clone());
         payload.insert("source", article.source.clone());
         payload.insert("published_at", article.published_at.to_rfc3339());
+        payload.insert("tags", article.tags.clone());
         payload.insert("doc", doc_str);
 
-        let point = PointStruct::new(
-            Uuid::new_v4().to_string(),
+        // Derive the point id from the URL so storing the same article again
+        // overwrites its point instead of adding a duplicate
+        Ok(PointStruct::new(
+            point_id(&article.url),
             embedding.to_vec(),
             payload
-        );
+        ))
+    }
 
+    async fn upsert(&self, points: Vec<PointStruct>) -> Result<()> {
         self.client.upsert_points(
-            UpsertPointsBuilder::new(self.config.collection.clone(), vec![point])
+            UpsertPointsBuilder::new(self.config.collection.clone(), points)
         )
         .await
         .map_err(|e| nt_core::Error::External(e.into()))?;
@@ -144,14 +228,26 @@ impl QdrantStore {
     }
 
     pub async fn find_similar(&self, embedding: &[f32], limit: usize) -> Result<Vec<Article>> {
-        let results = self.client.search_points(
-            SearchPointsBuilder::new(
-                self.config.collection.clone(),
-                embedding.to_vec(),
-                limit as u64
-            )
-            .with_payload(true)
+        let scored = self.find_similar_scored(embedding, limit).await?;
+        Ok(scored.into_iter().map(|(a, _)| a).collect())
+    }
+
+    pub async fn find_similar_scored(&self, embedding: &[f32], limit: usize) -> Result<Vec<(Article, f32)>> {
+        self.find_similar_filtered(embedding, limit, &ArticleFilter::default()).await
+    }
+
+    pub async fn find_similar_filtered(&self, embedding: &[f32], limit: usize, filter: &ArticleFilter) -> Result<Vec<(Article, f32)>> {
+        let mut request = SearchPointsBuilder::new(
+            self.config.collection.clone(),
+            embedding.to_vec(),
+            limit as u64
         )
+        .with_payload(true);
+        if !filter.is_empty() {
+            request = request.filter(payload_filter(filter));
+        }
+
+        let results = self.client.search_points(request)
         .await
         .map_err(|e| nt_core::Error::External(e.into()))?;
 
@@ -159,7 +255,7 @@ impl QdrantStore {
         for point in results.result {
             if let Some(doc_str) = point.payload.get("doc").and_then(|v| v.as_str()) {
                 if let Ok(article) = serde_json::from_str::<Article>(doc_str) {
-                    articles.push(article);
+                    articles.push((article, point.score));
                 }
             }
         }
@@ -192,6 +288,203 @@ impl QdrantStore {
         Ok(articles)
     }
 
+    /// Fetches the article's point by its id, which is derived from the URL
+    pub async fn get_article(&self, url: &str) -> Result<Option<Article>> {
+        let response = self.client.get_points(
+            GetPointsBuilder::new(self.config.collection.clone(), vec![PointId::from(point_id(url))])
+                .with_payload(true)
+        )
+        .await
+        .map_err(|e| nt_core::Error::External(e.into()))?;
+
+        Ok(response.result.first()
+            .and_then(|point| point.payload.get("doc").and_then(|v| v.as_str()))
+            .and_then(|doc_str| serde_json::from_str::<Article>(doc_str).ok()))
+    }
+
+    pub async fn get_by_tag(&self, tag: &str) -> Result<Vec<Article>> {
+        self.scroll_articles(Some(Filter::all([Condition::matches("tags", tag.to_string())]))).await
+    }
+
+    pub async fn list_articles(&self, offset: usize, limit: usize) -> Result<Vec<Article>> {
+        let mut articles = self.all_articles().await?;
+        articles.sort_by(|a, b| a.url.cmp(&b.url));
+        Ok(articles.into_iter().skip(offset).take(limit).collect())
+    }
+
+    /// Scrolls in point id order, the cursor being the id of the next page's first point
+    pub async fn list_articles_page(&self, cursor: Option<&str>, limit: usize) -> Result<(Vec<Article>, Option<String>)> {
+        let mut request = ScrollPointsBuilder::new(self.config.collection.clone())
+            .with_payload(true)
+            .limit(limit as u32);
+        if let Some(cursor) = cursor {
+            request = request.offset(PointId::from(cursor.to_string()));
+        }
+
+        let response = self.client.scroll(request)
+            .await
+            .map_err(|e| nt_core::Error::External(e.into()))?;
+
+        let articles = response.result.iter()
+            .filter_map(|point| point.payload.get("doc").and_then(|v| v.as_str()))
+            .filter_map(|doc_str| serde_json::from_str::<Article>(doc_str).ok())
+            .collect();
+        let next = response.next_page_offset
+            .and_then(|id| id.point_id_options)
+            .map(|id| match id {
+                PointIdOptions::Uuid(uuid) => uuid,
+                PointIdOptions::Num(num) => num.to_string(),
+            });
+        Ok((articles, next))
+    }
+
+    /// Drops the collection and creates it again for vectors of `dimension` values
+    pub async fn recreate_index(&mut self, dimension: usize) -> Result<()> {
+        self.delete_collection().await?;
+        self.config.config.vector_size = dimension as u64;
+        self.create_collection().await?;
+        create_field_indexes(&self.client, &self.config.collection).await
+    }
+
+    pub fn index_dimension(&self) -> usize {
+        self.config.vector_size as usize
+    }
+
+    pub async fn list_tags(&self) -> Result<Vec<(String, usize)>> {
+        Ok(nt_core::count_tags(&self.all_articles().await?))
+    }
+
+    pub async fn search_text(&self, query: &str, limit: usize) -> Result<Vec<Article>> {
+        Ok(nt_core::search_articles(self.all_articles().await?, query, limit))
+    }
+
+    /// Counted, faceted by source and ordered by date on the server. Every point
+    /// carries a vector, so every article counts as embedded.
+    pub async fn stats(&self) -> Result<StorageStats> {
+        let articles = self.client.count(
+            CountPointsBuilder::new(self.config.collection.clone()).exact(true)
+        )
+        .await
+        .map_err(|e| nt_core::Error::External(e.into()))?
+        .result
+        .map_or(0, |result| result.count as usize);
+
+        let mut stats = StorageStats {
+            articles,
+            embeddings: articles,
+            ..Default::default()
+        };
+        if articles == 0 {
+            return Ok(stats);
+        }
+
+        let facets = self.client.facet(
+            FacetCountsBuilder::new(self.config.collection.clone(), "source")
+                .limit(articles as u64)
+                .exact(true)
+        )
+        .await
+        .map_err(|e| nt_core::Error::External(e.into()))?;
+        for hit in facets.hits {
+            if let Some(facet_value::Variant::StringValue(source)) = hit.value.and_then(|value| value.variant) {
+                stats.per_source.insert(source, hit.count as usize);
+            }
+        }
+
+        stats.earliest = self.published_at_bound(Direction::Asc).await?;
+        stats.latest = self.published_at_bound(Direction::Desc).await?;
+        Ok(stats)
+    }
+
+    /// Publication date of the first article in `direction`
+    async fn published_at_bound(&self, direction: Direction) -> Result<Option<DateTime<Utc>>> {
+        let response = self.client.scroll(
+            ScrollPointsBuilder::new(self.config.collection.clone())
+                .with_payload(PayloadIncludeSelector::new(vec!["published_at".to_string()]))
+                .order_by(OrderBy {
+                    key: "published_at".to_string(),
+                    direction: Some(direction as i32),
+                    start_from: None,
+                })
+                .limit(1)
+        )
+        .await
+        .map_err(|e| nt_core::Error::External(e.into()))?;
+
+        Ok(response.result.first()
+            .and_then(|point| point.payload.get("published_at"))
+            .and_then(|v| v.as_str())
+            .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+            .map(|date| date.with_timezone(&Utc)))
+    }
+
+    async fn all_articles(&self) -> Result<Vec<Article>> {
+        self.scroll_articles(None).await
+    }
+
+    /// Every article whose payload matches `filter`, or every article without one
+    async fn scroll_articles(&self, filter: Option<Filter>) -> Result<Vec<Article>> {
+        let mut articles = Vec::new();
+        let mut offset: Option<PointId> = None;
+        loop {
+            let mut request = ScrollPointsBuilder::new(self.config.collection.clone())
+                .with_payload(true)
+                .limit(256);
+            if let Some(filter) = filter.clone() {
+                request = request.filter(filter);
+            }
+            if let Some(offset) = offset.take() {
+                request = request.offset(offset);
+            }
+
+            let response = self.client.scroll(request)
+                .await
+                .map_err(|e| nt_core::Error::External(e.into()))?;
+
+            for point in response.result {
+                if let Some(doc_str) = point.payload.get("doc").and_then(|v| v.as_str()) {
+                    if let Ok(article) = serde_json::from_str::<Article>(doc_str) {
+                        articles.push(article);
+                    }
+                }
+            }
+
+            match response.next_page_offset {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(articles)
+    }
+
+    pub async fn delete_older_than(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let filter = Filter::all([Condition::datetime_range("published_at", DatetimeRange {
+            lt: Some(timestamp(cutoff)),
+            ..Default::default()
+        })]);
+
+        let count = self.client.count(
+            CountPointsBuilder::new(self.config.collection.clone())
+                .filter(filter.clone())
+                .exact(true)
+        )
+        .await
+        .map_err(|e| nt_core::Error::External(e.into()))?
+        .result
+        .map_or(0, |result| result.count as usize);
+
+        self.client.delete_points(
+            DeletePointsBuilder::new(self.config.collection.clone())
+                .points(filter)
+                .wait(true)
+        )
+        .await
+        .map_err(|e| nt_core::Error::External(e.into()))?;
+
+        Ok(count)
+    }
+
     async fn create_collection(&self) -> Result<()> {
         let collection_name = self.config.collection.clone();
         let collection_info = self.client.collection_info(GetCollectionInfoRequest {
@@ -203,7 +496,7 @@ impl QdrantStore {
             self.client
                 .create_collection(
                     CreateCollectionBuilder::new(collection_name)
-                        .vectors_config(VectorParamsBuilder::new(self.config.vector_size, Distance::Cosine))
+                        .vectors_config(VectorParamsBuilder::new(self.config.vector_size, distance(self.config.distance_metric)))
                         .quantization_config(ScalarQuantizationBuilder::default()),
                 )
                 .await
@@ -256,6 +549,37 @@ impl QdrantStore {
     }
 }
 
+impl QdrantStore {
+    pub async fn get_article_embeddings(&self, urls: &[String]) -> Result<Vec<Vec<f32>>> {
+        if urls.is_empty() {
+            return Ok(Vec::new());
+        }
+        let ids: Vec<PointId> = urls.iter().map(|url| PointId::from(point_id(url))).collect();
+        let response = self.client.get_points(
+            GetPointsBuilder::new(self.config.collection.clone(), ids)
+                .with_payload(true)
+                .with_vectors(true)
+        )
+        .await
+        .map_err(|e| nt_core::Error::External(e.into()))?;
+
+        let mut embeddings = HashMap::new();
+        for point in response.result {
+            let url = point.payload.get("url").and_then(|v| v.as_str()).map(String::from);
+            let vector = point.vectors
+                .and_then(|vectors| vectors.vectors_options)
+                .and_then(|options| match options {
+                    VectorsOptions::Vector(vector) => Some(vector.data),
+                    _ => None,
+                });
+            if let (Some(url), Some(vector)) = (url, vector) {
+                embeddings.insert(url, vector);
+            }
+        }
+        Ok(urls.iter().map(|url| embeddings.remove(url).unwrap_or_default()).collect())
+    }
+}
+
 pub struct QdrantStorage {
     store: Arc<RwLock<QdrantStore>>,
     config: QdrantConfig,
@@ -293,16 +617,56 @@ impl ArticleStorage for QdrantStorage {
         store.store_article(article, embedding).await
     }
 
+    async fn store_articles(&self, batch: &[(Article, Vec<f32>)]) -> Result<()> {
+        let store = self.store.read().await;
+        store.store_articles(batch).await
+    }
+
     async fn find_similar(&self, embedding: &[f32], limit: usize) -> Result<Vec<Article>> {
         let store = self.store.read().await;
         store.find_similar(embedding, limit).await
     }
 
+    async fn find_similar_scored(&self, embedding: &[f32], limit: usize) -> Result<Vec<(Article, f32)>> {
+        let store = self.store.read().await;
+        store.find_similar_scored(embedding, limit).await
+    }
+
+    async fn find_similar_filtered(&self, embedding: &[f32], limit: usize, filter: &ArticleFilter) -> Result<Vec<(Article, f32)>> {
+        let store = self.store.read().await;
+        store.find_similar_filtered(embedding, limit, filter).await
+    }
+
     async fn get_by_source(&self, source: &str) -> Result<Vec<Article>> {
         let store = self.store.read().await;
         store.get_by_source(source).await
     }
 
+    async fn get_article(&self, url: &str) -> Result<Option<Article>> {
+        let store = self.store.read().await;
+        store.get_article(url).await
+    }
+
+    async fn list_articles(&self, offset: usize, limit: usize) -> Result<Vec<Article>> {
+        let store = self.store.read().await;
+        store.list_articles(offset, limit).await
+    }
+
+    async fn get_by_tag(&self, tag: &str) -> Result<Vec<Article>> {
+        let store = self.store.read().await;
+        store.get_by_tag(tag).await
+    }
+
+    async fn list_tags(&self) -> Result<Vec<(String, usize)>> {
+        let store = self.store.read().await;
+        store.list_tags().await
+    }
+
+    async fn search_text(&self, query: &str, limit: usize) -> Result<Vec<Article>> {
+        let store = self.store.read().await;
+        store.search_text(query, limit).await
+    }
+
     async fn delete_article(&self, url: &str) -> Result<()> {
         let store = self.store.read().await;
         
@@ -320,10 +684,40 @@ impl ArticleStorage for QdrantStorage {
         Ok(())
     }
 
+    async fn delete_older_than(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let store = self.store.read().await;
+        store.delete_older_than(cutoff).await
+    }
+
     async fn get_article_embedding(&self, url: &str) -> Result<Vec<f32>> {
         let store = self.store.read().await;
         store.get_article_embedding(url).await
     }
+
+    async fn get_article_embeddings(&self, urls: &[String]) -> Result<Vec<Vec<f32>>> {
+        let store = self.store.read().await;
+        store.get_article_embeddings(urls).await
+    }
+
+    async fn stats(&self) -> Result<StorageStats> {
+        let store = self.store.read().await;
+        store.stats().await
+    }
+
+    async fn list_articles_page(&self, cursor: Option<&str>, limit: usize) -> Result<(Vec<Article>, Option<String>)> {
+        let store = self.store.read().await;
+        store.list_articles_page(cursor, limit).await
+    }
+
+    async fn index_dimension(&self) -> Result<Option<usize>> {
+        let store = self.store.read().await;
+        Ok(Some(store.index_dimension()))
+    }
+
+    async fn recreate_index(&self, dimension: usize) -> Result<()> {
+        let mut store = self.store.write().await;
+        store.recreate_index(dimension).await
+    }
 }
 
 #[cfg(test)]
@@ -343,6 +737,7 @@ mod tests {
             summary: None,
             authors: vec!["Test Author".to_string()],
             related_articles: Vec::new(),
+            tags: Vec::new(),
         };
 
         let storage = QdrantStorage::new().await.unwrap();
@@ -352,4 +747,10 @@ mod tests {
         let similar = storage.find_similar(&embedding, 1).await.unwrap();
         assert!(!similar.is_empty());
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_point_id_is_stable_per_url() {
+        assert_eq!(point_id("http://test.com"), point_id("http://test.com"));
+        assert_ne!(point_id("http://test.com"), point_id("http://test.com/other"));
+    }
+}
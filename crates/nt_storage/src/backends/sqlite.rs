@@ -1,5 +1,7 @@
 use async_trait::async_trait;
-use nt_core::{Article, Result, ArticleStorage};
+use nt_core::{Article, ArticleIdScheme, EmbeddingModelInfo, Result, ArticleStorage, StorageStats};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use crate::{StorageBackend, BackendConfig, EmbeddingModel, UrlConfig};
@@ -33,8 +35,81 @@ const MIGRATIONS: &[&str] = &[
         FOREIGN KEY (url) REFERENCES articles(url) ON DELETE CASCADE
     )
     "#,
+    r#"
+    CREATE TABLE IF NOT EXISTS article_tags (
+        url TEXT NOT NULL,
+        tag TEXT NOT NULL,
+        PRIMARY KEY (url, tag),
+        FOREIGN KEY (url) REFERENCES articles(url) ON DELETE CASCADE
+    );
+
+    CREATE INDEX IF NOT EXISTS article_tags_tag ON article_tags(tag)
+    "#,
+    r#"
+    CREATE VIRTUAL TABLE IF NOT EXISTS articles_fts USING fts5(
+        url UNINDEXED,
+        title,
+        content
+    );
+
+    CREATE TRIGGER IF NOT EXISTS articles_fts_insert AFTER INSERT ON articles BEGIN
+        DELETE FROM articles_fts WHERE url = new.url;
+        INSERT INTO articles_fts (url, title, content) VALUES (new.url, new.title, new.content);
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS articles_fts_update AFTER UPDATE ON articles BEGIN
+        DELETE FROM articles_fts WHERE url = old.url;
+        INSERT INTO articles_fts (url, title, content) VALUES (new.url, new.title, new.content);
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS articles_fts_delete AFTER DELETE ON articles BEGIN
+        DELETE FROM articles_fts WHERE url = old.url;
+    END;
+
+    INSERT INTO articles_fts (url, title, content)
+    SELECT url, title, content FROM articles
+    WHERE url NOT IN (SELECT url FROM articles_fts)
+    "#,
+    r#"
+    CREATE TABLE IF NOT EXISTS storage_metadata (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    )
+    "#,
+    r#"
+    CREATE TABLE IF NOT EXISTS article_ids (
+        url TEXT PRIMARY KEY,
+        url_hash TEXT NOT NULL,
+        FOREIGN KEY (url) REFERENCES articles(url) ON DELETE CASCADE
+    );
+
+    CREATE INDEX IF NOT EXISTS article_ids_url_hash ON article_ids(url_hash)
+    "#,
 ];
 
+/// Key of the embedding model in `storage_metadata`, stored as JSON
+const EMBEDDING_MODEL_KEY: &str = "embedding_model";
+
+/// Statements writing an article row, replacing an existing one with the same
+/// URL or failing on it. The embedding always replaces, its article row guards it.
+const REPLACE_ARTICLE: &str = r#"
+    INSERT OR REPLACE INTO articles (
+        url, title, content, source, published_at, sections, summary, authors, related_articles
+    ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+"#;
+const INSERT_ARTICLE: &str = r#"
+    INSERT INTO articles (
+        url, title, content, source, published_at, sections, summary, authors, related_articles
+    ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+"#;
+
+/// Most URLs bound in a single `IN` list, well under SQLite's variable limit
+const MAX_BOUND_URLS: usize = 500;
+
+/// Column expression collecting an article's tags as a JSON array, for queries
+/// selecting from `articles a`.
+const TAGS_COLUMN: &str = "(SELECT json_group_array(tag) FROM article_tags t WHERE t.url = a.url) AS tags";
+
 #[derive(Debug, Clone)]
 pub struct SQLiteConfig {
     pub config: BackendConfig,
@@ -86,6 +161,21 @@ impl SQLiteStore {
                 .map_err(|e| nt_core::Error::Database(format!("Failed to apply migration {}: {}", i, e)))?;
         }
 
+        // SQLite has no SHA-256 function, so ids of articles stored before the
+        // `article_ids` table existed are filled in here
+        let missing: Vec<String> = sqlx::query_scalar("SELECT url FROM articles WHERE url NOT IN (SELECT url FROM article_ids)")
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to look up article ids: {}", e)))?;
+        for url in missing {
+            sqlx::query("INSERT OR REPLACE INTO article_ids (url, url_hash) VALUES (?, ?)")
+                .bind(&url)
+                .bind(ArticleIdScheme::UrlHash.id(&url))
+                .execute(&pool)
+                .await
+                .map_err(|e| nt_core::Error::Database(format!("Failed to store article id: {}", e)))?;
+        }
+
         Ok(Self {
             pool,
             table,
@@ -94,6 +184,18 @@ impl SQLiteStore {
     }
 
     pub async fn store_article(&self, article: &Article, embedding: &[f32]) -> Result<()> {
+        self.write_article(article, embedding, true).await
+    }
+
+    pub async fn insert_only(&self, article: &Article, embedding: &[f32]) -> Result<()> {
+        self.write_article(article, embedding, false).await
+    }
+
+    /// Writes an article and its embedding, replacing an existing row with the
+    /// same URL when `replace` is set and failing with `AlreadyExists` otherwise.
+    async fn write_article(&self, article: &Article, embedding: &[f32], replace: bool) -> Result<()> {
+        let insert_article = if replace { REPLACE_ARTICLE } else { INSERT_ARTICLE };
+
         tracing::debug!("💾 Storing article: {}", article.title);
         tracing::debug!("🔗 URL: {}", article.url);
         tracing::debug!("📝 Content length: {} chars", article.content.len());
@@ -135,15 +237,14 @@ impl SQLiteStore {
             }
         };
 
+        // The article, its embedding and its tags are written together or not at all
+        let mut tx = self.pool.begin()
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to start transaction: {}", e)))?;
+
         // Store article
         tracing::debug!("📝 Inserting article into database");
-        match sqlx::query(
-            r#"
-            INSERT OR REPLACE INTO articles (
-                url, title, content, source, published_at, sections, summary, authors, related_articles
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
-            "#,
-        )
+        match sqlx::query(insert_article)
         .bind(&article.url)
         .bind(&article.title)
         .bind(&article.content)
@@ -153,9 +254,13 @@ impl SQLiteStore {
         .bind(article.summary.as_deref())
         .bind(&authors_json)
         .bind(&related_articles_json)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await {
             Ok(_) => tracing::debug!("✅ Successfully stored article"),
+            Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+                tracing::debug!("⚠️ Article already exists: {}", article.url);
+                return Err(nt_core::Error::AlreadyExists(article.url.clone()));
+            }
             Err(e) => {
                 tracing::error!("❌ Failed to store article: {}", e);
                 return Err(nt_core::Error::Database(format!("Failed to store article: {}", e)));
@@ -172,14 +277,10 @@ impl SQLiteStore {
         };
 
         tracing::debug!("💾 Storing embedding in database");
-        match sqlx::query(
-            r#"
-            INSERT OR REPLACE INTO embeddings (url, embedding) VALUES (?, ?)
-            "#,
-        )
+        match sqlx::query("INSERT OR REPLACE INTO embeddings (url, embedding) VALUES (?, ?)")
         .bind(&article.url)
         .bind(embedding_bytes)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await {
             Ok(_) => tracing::debug!("✅ Successfully stored embedding"),
             Err(e) => {
@@ -188,11 +289,43 @@ impl SQLiteStore {
             }
         }
 
+        sqlx::query("INSERT OR REPLACE INTO article_ids (url, url_hash) VALUES (?, ?)")
+            .bind(&article.url)
+            .bind(ArticleIdScheme::UrlHash.id(&article.url))
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to store article id: {}", e)))?;
+
+        // Store tags, replacing whatever the previous version of the article had
+        tracing::debug!("🏷️ Storing {} tags", article.tags.len());
+        sqlx::query("DELETE FROM article_tags WHERE url = ?")
+            .bind(&article.url)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to clear tags: {}", e)))?;
+        for tag in &article.tags {
+            sqlx::query("INSERT OR IGNORE INTO article_tags (url, tag) VALUES (?, ?)")
+                .bind(&article.url)
+                .bind(tag)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| nt_core::Error::Database(format!("Failed to store tag: {}", e)))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to commit article: {}", e)))?;
+
         tracing::debug!("✨ Article and embedding stored successfully");
         Ok(())
     }
 
     pub async fn find_similar(&self, embedding: &[f32], limit: usize) -> Result<Vec<Article>> {
+        let scored = self.find_similar_scored(embedding, limit).await?;
+        Ok(scored.into_iter().map(|(a, _)| a).collect())
+    }
+
+    pub async fn find_similar_scored(&self, embedding: &[f32], limit: usize) -> Result<Vec<(Article, f32)>> {
         tracing::debug!("🔍 Finding similar articles with embedding size: {}", embedding.len());
         
         // For SQLite, we'll use a simple cosine similarity calculation
@@ -205,13 +338,14 @@ impl SQLiteStore {
 
         // Get all articles with their embeddings
         tracing::debug!("📚 Fetching all articles with embeddings");
-        let rows = match sqlx::query(
+        let rows = match sqlx::query(&format!(
             r#"
-            SELECT a.*, e.embedding
+            SELECT a.*, {}, e.embedding
             FROM articles a
             JOIN embeddings e ON a.url = e.url
             "#,
-        )
+            TAGS_COLUMN
+        ))
         .fetch_all(&self.pool)
         .await {
             Ok(rows) => {
@@ -250,6 +384,10 @@ impl SQLiteStore {
             };
             
             tracing::debug!("🔢 Converted to f32 slice of size: {}", article_embedding.len());
+            if article_embedding.is_empty() {
+                tracing::debug!("⏭️ Skipping article without embedding: {}", article.url);
+                continue;
+            }
             
             let similarity = nt_core::cosine_similarity(embedding, article_embedding);
             tracing::debug!("📊 Calculated similarity score: {}", similarity);
@@ -261,17 +399,38 @@ impl SQLiteStore {
         tracing::debug!("📊 Sorting {} articles by similarity", articles_with_scores.len());
         articles_with_scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
         
-        let result: Vec<Article> = articles_with_scores.into_iter().take(limit).map(|(a, _)| a).collect();
+        let result: Vec<(Article, f32)> = articles_with_scores.into_iter().take(limit).collect();
         tracing::debug!("✨ Returning {} similar articles", result.len());
         Ok(result)
     }
 
+    pub async fn get_article_embeddings(&self, urls: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut embeddings = HashMap::new();
+        for chunk in urls.chunks(MAX_BOUND_URLS) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let sql = format!("SELECT url, embedding FROM embeddings WHERE url IN ({})", placeholders);
+            let mut query = sqlx::query(&sql);
+            for url in chunk {
+                query = query.bind(url);
+            }
+            let rows = query.fetch_all(&self.pool)
+                .await
+                .map_err(|e| nt_core::Error::Database(format!("Failed to fetch embeddings: {}", e)))?;
+            for row in rows {
+                let bytes: Vec<u8> = row.get("embedding");
+                embeddings.insert(row.get::<String, _>("url"), embedding_from_bytes(&bytes));
+            }
+        }
+        Ok(urls.iter().map(|url| embeddings.remove(url).unwrap_or_default()).collect())
+    }
+
     pub async fn get_by_source(&self, source: &str) -> Result<Vec<Article>> {
-        let rows = sqlx::query(
+        let rows = sqlx::query(&format!(
             r#"
-            SELECT * FROM articles WHERE source = ?
+            SELECT a.*, {} FROM articles a WHERE a.source = ?
             "#,
-        )
+            TAGS_COLUMN
+        ))
         .bind(source)
         .fetch_all(&self.pool)
         .await
@@ -282,6 +441,156 @@ impl SQLiteStore {
             .collect()
     }
 
+    pub async fn get_article(&self, url: &str) -> Result<Option<Article>> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT a.*, {} FROM articles a WHERE a.url = ?
+            "#,
+            TAGS_COLUMN
+        ))
+        .bind(url)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| nt_core::Error::Database(format!("Failed to fetch article: {}", e)))?;
+
+        row.map(|row| self.row_to_article(&row)).transpose()
+    }
+
+    pub async fn list_articles(&self, offset: usize, limit: usize) -> Result<Vec<Article>> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT a.*, {} FROM articles a ORDER BY a.url LIMIT ? OFFSET ?
+            "#,
+            TAGS_COLUMN
+        ))
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| nt_core::Error::Database(format!("Failed to fetch articles: {}", e)))?;
+
+        rows.into_iter()
+            .map(|row| self.row_to_article(&row))
+            .collect()
+    }
+
+    /// URL hashes are looked up in the indexed `article_ids` table
+    pub async fn get_by_id(&self, id: &str, scheme: ArticleIdScheme) -> Result<Option<Article>> {
+        match scheme {
+            ArticleIdScheme::Base64Url => match scheme.url(id) {
+                Some(url) => self.get_article(&url).await,
+                None => Ok(None),
+            },
+            ArticleIdScheme::UrlHash => {
+                let row = sqlx::query(&format!(
+                    r#"
+                    SELECT a.*, {}
+                    FROM articles a
+                    JOIN article_ids i ON a.url = i.url
+                    WHERE i.url_hash = ?
+                    "#,
+                    TAGS_COLUMN
+                ))
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| nt_core::Error::Database(format!("Failed to fetch article: {}", e)))?;
+                row.map(|row| self.row_to_article(&row)).transpose()
+            }
+        }
+    }
+
+    /// Pages by URL, the cursor being the last URL of the previous page
+    pub async fn list_articles_page(&self, cursor: Option<&str>, limit: usize) -> Result<(Vec<Article>, Option<String>)> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT a.*, {} FROM articles a WHERE a.url > ? ORDER BY a.url LIMIT ?
+            "#,
+            TAGS_COLUMN
+        ))
+        .bind(cursor.unwrap_or(""))
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| nt_core::Error::Database(format!("Failed to fetch articles: {}", e)))?;
+
+        let articles = rows.into_iter()
+            .map(|row| self.row_to_article(&row))
+            .collect::<Result<Vec<_>>>()?;
+        let next = nt_core::next_url_cursor(&articles, limit);
+        Ok((articles, next))
+    }
+
+    pub async fn get_by_tag(&self, tag: &str) -> Result<Vec<Article>> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT a.*, {}
+            FROM articles a
+            JOIN article_tags tg ON a.url = tg.url
+            WHERE tg.tag = ?
+            "#,
+            TAGS_COLUMN
+        ))
+        .bind(tag)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| nt_core::Error::Database(format!("Failed to fetch articles: {}", e)))?;
+
+        rows.into_iter()
+            .map(|row| self.row_to_article(&row))
+            .collect()
+    }
+
+    pub async fn list_tags(&self) -> Result<Vec<(String, usize)>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT tag, COUNT(*) AS count
+            FROM article_tags
+            GROUP BY tag
+            ORDER BY count DESC, tag
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| nt_core::Error::Database(format!("Failed to fetch tags: {}", e)))?;
+
+        Ok(rows.into_iter()
+            .map(|row| (row.get("tag"), row.get::<i64, _>("count") as usize))
+            .collect())
+    }
+
+    /// Searches the FTS5 index, quoting each term so user input is never read
+    /// as query syntax.
+    pub async fn search_text(&self, query: &str, limit: usize) -> Result<Vec<Article>> {
+        let terms: Vec<String> = query.split_whitespace()
+            .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+            .collect();
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT a.*, {}
+            FROM articles_fts f
+            JOIN articles a ON a.url = f.url
+            WHERE articles_fts MATCH ?
+            ORDER BY f.rank
+            LIMIT ?
+            "#,
+            TAGS_COLUMN
+        ))
+        .bind(terms.join(" "))
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| nt_core::Error::Database(format!("Failed to search articles: {}", e)))?;
+
+        rows.into_iter()
+            .map(|row| self.row_to_article(&row))
+            .collect()
+    }
+
     fn row_to_article(&self, row: &sqlx::sqlite::SqliteRow) -> Result<Article> {
         tracing::debug!("🔍 Converting SQLite row to Article");
         
@@ -371,6 +680,12 @@ impl SQLiteStore {
             }
         };
 
+        // Rows selected without TAGS_COLUMN simply have no tags
+        let tags: Vec<String> = match row.try_get::<Option<String>, _>("tags") {
+            Ok(Some(json)) => serde_json::from_str(&json).map_err(nt_core::Error::Serialization)?,
+            _ => Vec::new(),
+        };
+
         tracing::debug!("✨ Successfully converted row to Article");
         Ok(Article {
             url: row.get("url"),
@@ -382,6 +697,7 @@ impl SQLiteStore {
             summary: row.get("summary"),
             authors,
             related_articles,
+            tags,
         })
     }
 
@@ -403,6 +719,79 @@ impl SQLiteStore {
                 }
             }
     }
+
+    pub async fn delete_older_than(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        tracing::debug!("🗑️ Deleting articles published before {}", cutoff);
+
+        // Compare as dates rather than strings, RFC 3339 fractions vary in length
+        let result = sqlx::query("DELETE FROM articles WHERE julianday(published_at) < julianday(?)")
+            .bind(cutoff.to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to delete old articles: {}", e)))?;
+        Ok(result.rows_affected() as usize)
+    }
+
+    pub async fn stats(&self) -> Result<StorageStats> {
+        let db_error = |e: sqlx::Error| nt_core::Error::Database(format!("Failed to compute storage stats: {}", e));
+        let mut stats = StorageStats::default();
+
+        let rows = sqlx::query("SELECT source, COUNT(*) AS count FROM articles GROUP BY source")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(db_error)?;
+        for row in rows {
+            let count = row.get::<i64, _>("count") as usize;
+            stats.articles += count;
+            stats.per_source.insert(row.get("source"), count);
+        }
+
+        // Compare as dates rather than strings, RFC 3339 fractions vary in length
+        let row = sqlx::query(
+            r#"
+            SELECT
+                (SELECT published_at FROM articles ORDER BY julianday(published_at) ASC LIMIT 1) AS earliest,
+                (SELECT published_at FROM articles ORDER BY julianday(published_at) DESC LIMIT 1) AS latest,
+                (SELECT COUNT(*) FROM embeddings) AS embeddings
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        let parse = |column: &str| -> Result<Option<DateTime<Utc>>> {
+            row.get::<Option<String>, _>(column)
+                .map(|date| DateTime::parse_from_rfc3339(&date)
+                    .map(|date| date.with_timezone(&Utc))
+                    .map_err(|e| nt_core::Error::Database(format!("Failed to parse date: {}", e))))
+                .transpose()
+        };
+        stats.earliest = parse("earliest")?;
+        stats.latest = parse("latest")?;
+        stats.embeddings = row.get::<i64, _>("embeddings") as usize;
+        Ok(stats)
+    }
+}
+
+impl SQLiteStore {
+    pub async fn embedding_model(&self) -> Result<Option<EmbeddingModelInfo>> {
+        let value: Option<String> = sqlx::query_scalar("SELECT value FROM storage_metadata WHERE key = ?")
+            .bind(EMBEDDING_MODEL_KEY)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to fetch the embedding model: {}", e)))?;
+        Ok(value.map(|value| serde_json::from_str(&value)).transpose()?)
+    }
+
+    pub async fn set_embedding_model(&self, model: &EmbeddingModelInfo) -> Result<()> {
+        sqlx::query("INSERT OR REPLACE INTO storage_metadata (key, value) VALUES (?, ?)")
+            .bind(EMBEDDING_MODEL_KEY)
+            .bind(serde_json::to_string(model)?)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| nt_core::Error::Database(format!("Failed to store the embedding model: {}", e)))?;
+        Ok(())
+    }
 }
 
 pub struct SQLiteStorage {
@@ -444,21 +833,91 @@ impl ArticleStorage for SQLiteStorage {
         store.store_article(article, embedding).await
     }
 
+    async fn insert_only(&self, article: &Article, embedding: &[f32]) -> Result<()> {
+        let store = self.store.read().await;
+        store.insert_only(article, embedding).await
+    }
+
     async fn find_similar(&self, embedding: &[f32], limit: usize) -> Result<Vec<Article>> {
         let store = self.store.read().await;
         store.find_similar(embedding, limit).await
     }
 
+    async fn find_similar_scored(&self, embedding: &[f32], limit: usize) -> Result<Vec<(Article, f32)>> {
+        let store = self.store.read().await;
+        store.find_similar_scored(embedding, limit).await
+    }
+
     async fn get_by_source(&self, source: &str) -> Result<Vec<Article>> {
         let store = self.store.read().await;
         store.get_by_source(source).await
     }
 
+    async fn get_article(&self, url: &str) -> Result<Option<Article>> {
+        let store = self.store.read().await;
+        store.get_article(url).await
+    }
+
+    async fn list_articles(&self, offset: usize, limit: usize) -> Result<Vec<Article>> {
+        let store = self.store.read().await;
+        store.list_articles(offset, limit).await
+    }
+
+    async fn get_by_id(&self, id: &str, scheme: ArticleIdScheme) -> Result<Option<Article>> {
+        let store = self.store.read().await;
+        store.get_by_id(id, scheme).await
+    }
+
+    async fn list_articles_page(&self, cursor: Option<&str>, limit: usize) -> Result<(Vec<Article>, Option<String>)> {
+        let store = self.store.read().await;
+        store.list_articles_page(cursor, limit).await
+    }
+
+    async fn get_by_tag(&self, tag: &str) -> Result<Vec<Article>> {
+        let store = self.store.read().await;
+        store.get_by_tag(tag).await
+    }
+
+    async fn list_tags(&self) -> Result<Vec<(String, usize)>> {
+        let store = self.store.read().await;
+        store.list_tags().await
+    }
+
+    async fn search_text(&self, query: &str, limit: usize) -> Result<Vec<Article>> {
+        let store = self.store.read().await;
+        store.search_text(query, limit).await
+    }
+
     async fn delete_article(&self, url: &str) -> Result<()> {
         let store = self.store.read().await;
         store.delete_article(url).await
     }
 
+    async fn delete_older_than(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let store = self.store.read().await;
+        store.delete_older_than(cutoff).await
+    }
+
+    async fn stats(&self) -> Result<StorageStats> {
+        let store = self.store.read().await;
+        store.stats().await
+    }
+
+    async fn get_article_embeddings(&self, urls: &[String]) -> Result<Vec<Vec<f32>>> {
+        let store = self.store.read().await;
+        store.get_article_embeddings(urls).await
+    }
+
+    async fn embedding_model(&self) -> Result<Option<EmbeddingModelInfo>> {
+        let store = self.store.read().await;
+        store.embedding_model().await
+    }
+
+    async fn set_embedding_model(&self, model: &EmbeddingModelInfo) -> Result<()> {
+        let store = self.store.read().await;
+        store.set_embedding_model(model).await
+    }
+
     async fn get_article_embedding(&self, url: &str) -> Result<Vec<f32>> {
         let store = self.store.read().await;
         let row = sqlx::query("SELECT embedding FROM embeddings WHERE url = ?")
@@ -478,24 +937,36 @@ impl ArticleStorage for SQLiteStorage {
     }
 }
 
+/// Reads an embedding stored as native-endian `f32` bytes
+fn embedding_from_bytes(bytes: &[u8]) -> Vec<f32> {
+    bytes.chunks_exact(std::mem::size_of::<f32>())
+        .map(|chunk| f32::from_ne_bytes(chunk.try_into().unwrap()))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
 
-    #[tokio::test]
-    async fn test_sqlite_storage() {
-        let article = Article {
-            url: "http://test.com".to_string(),
-            title: "Test Article".to_string(),
-            content: "This is a test article about politics.".to_string(),
+    fn test_article(url: &str, title: &str, content: &str) -> Article {
+        Article {
+            url: url.to_string(),
+            title: title.to_string(),
+            content: content.to_string(),
             published_at: Utc::now(),
             source: "test".to_string(),
             sections: vec![],
             summary: None,
-            authors: vec!["Test Author".to_string()],
+            authors: vec![],
             related_articles: vec![],
-        };
+            tags: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_storage() {
+        let mut article = test_article("http://test.com", "Test Article", "This is a test article about politics.");
+        article.authors = vec!["Test Author".to_string()];
 
         let storage = SQLiteStorage::new().await.unwrap();
         let vector_size = storage.config.vector_size;
@@ -504,4 +975,166 @@ mod tests {
         let similar = storage.find_similar(&embedding, 1).await.unwrap();
         assert!(!similar.is_empty());
     }
-} 
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_sqlite_store_article_upserts_by_url() {
+        let mut article = test_article("http://test.com", "Test Article", "First version.");
+
+        let store = SQLiteStore::new(":memory:".to_string(), "articles".to_string(), 2).await.unwrap();
+        store.store_article(&article, &[1.0, 0.0]).await.unwrap();
+        article.content = "Second version.".to_string();
+        store.store_article(&article, &[0.0, 1.0]).await.unwrap();
+
+        let stored = store.get_by_source("test").await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].content, "Second version.");
+
+        let result = store.insert_only(&article, &[1.0, 1.0]).await;
+        assert!(matches!(result, Err(nt_core::Error::AlreadyExists(_))));
+
+        article.url = "http://test.com/other".to_string();
+        store.insert_only(&article, &[1.0, 1.0]).await.unwrap();
+        assert_eq!(store.get_by_source("test").await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_tags() {
+        let mut article = test_article("http://test.com/a", "Test Article", "Content.");
+        article.tags = vec!["economy".to_string(), "politics".to_string()];
+
+        let store = SQLiteStore::new(":memory:".to_string(), "articles".to_string(), 2).await.unwrap();
+        store.store_article(&article, &[1.0, 0.0]).await.unwrap();
+        article.url = "http://test.com/b".to_string();
+        article.tags = vec!["politics".to_string()];
+        store.store_article(&article, &[0.0, 1.0]).await.unwrap();
+
+        assert_eq!(store.get_by_tag("politics").await.unwrap().len(), 2);
+        assert_eq!(store.get_by_tag("economy").await.unwrap()[0].url, "http://test.com/a");
+        assert_eq!(store.list_tags().await.unwrap(), vec![
+            ("politics".to_string(), 2),
+            ("economy".to_string(), 1),
+        ]);
+
+        // Re-storing an article replaces its tags
+        article.tags = vec!["sports".to_string()];
+        store.store_article(&article, &[0.0, 1.0]).await.unwrap();
+        let stored = store.get_article("http://test.com/b").await.unwrap().unwrap();
+        assert_eq!(stored.tags, vec!["sports"]);
+        assert_eq!(store.get_by_tag("politics").await.unwrap().len(), 1);
+        assert!(store.get_article("http://missing.com").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_delete_older_than() {
+        let now = Utc::now();
+        let store = SQLiteStore::new(":memory:".to_string(), "articles".to_string(), 2).await.unwrap();
+        for (url, age_days) in [("http://test.com/old", 90), ("http://test.com/month", 30), ("http://test.com/new", 1)] {
+            let mut article = test_article(url, "Test Article", "Content.");
+            article.published_at = now - chrono::Duration::days(age_days);
+            article.tags = vec!["politics".to_string()];
+            store.store_article(&article, &[1.0, 0.0]).await.unwrap();
+        }
+
+        assert_eq!(store.delete_older_than(now - chrono::Duration::days(60)).await.unwrap(), 1);
+        assert!(store.get_article("http://test.com/old").await.unwrap().is_none());
+        assert_eq!(store.list_articles(0, 10).await.unwrap().len(), 2);
+        assert_eq!(store.list_tags().await.unwrap(), vec![("politics".to_string(), 2)]);
+
+        assert_eq!(store.delete_older_than(now).await.unwrap(), 2);
+        assert!(store.list_articles(0, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_stats() {
+        let now = Utc::now();
+        let store = SQLiteStore::new(":memory:".to_string(), "articles".to_string(), 2).await.unwrap();
+        assert_eq!(store.stats().await.unwrap(), StorageStats::default());
+
+        for (url, source, age_days) in [("http://a.com/1", "a", 10), ("http://a.com/2", "a", 3), ("http://b.com/1", "b", 7)] {
+            let mut article = test_article(url, "Test Article", "Content.");
+            article.published_at = now - chrono::Duration::days(age_days);
+            article.source = source.to_string();
+            store.store_article(&article, &[1.0, 0.0]).await.unwrap();
+        }
+
+        let stats = store.stats().await.unwrap();
+        assert_eq!(stats.articles, 3);
+        assert_eq!(stats.per_source.into_iter().collect::<Vec<_>>(), vec![("a".to_string(), 2), ("b".to_string(), 1)]);
+        assert_eq!(stats.earliest, Some(now - chrono::Duration::days(10)));
+        assert_eq!(stats.latest, Some(now - chrono::Duration::days(3)));
+        assert_eq!(stats.embeddings, 3);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_get_article_embeddings() {
+        let mut article = test_article("http://test.com/a", "Test Article", "Content.");
+
+        let store = SQLiteStore::new(":memory:".to_string(), "articles".to_string(), 2).await.unwrap();
+        store.store_article(&article, &[1.0, 0.0]).await.unwrap();
+        article.url = "http://test.com/b".to_string();
+        store.store_article(&article, &[0.0, 1.0]).await.unwrap();
+
+        let urls = ["http://test.com/b", "http://missing.com", "http://test.com/a"].map(String::from);
+        assert_eq!(store.get_article_embeddings(&urls).await.unwrap(), vec![vec![0.0, 1.0], vec![], vec![1.0, 0.0]]);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_list_articles_page_and_embedding_model() {
+        let store = SQLiteStore::new(":memory:".to_string(), "articles".to_string(), 2).await.unwrap();
+        for url in ["http://test.com/c", "http://test.com/a", "http://test.com/b"] {
+            store.store_article(&test_article(url, "Test Article", "Content."), &[1.0, 0.0]).await.unwrap();
+        }
+
+        let (page, cursor) = store.list_articles_page(None, 2).await.unwrap();
+        assert_eq!(page.iter().map(|a| a.url.as_str()).collect::<Vec<_>>(), vec!["http://test.com/a", "http://test.com/b"]);
+        let (page, cursor) = store.list_articles_page(cursor.as_deref(), 2).await.unwrap();
+        assert_eq!(page.iter().map(|a| a.url.as_str()).collect::<Vec<_>>(), vec!["http://test.com/c"]);
+        assert_eq!(cursor, None);
+
+        assert_eq!(store.embedding_model().await.unwrap(), None);
+        let model = EmbeddingModelInfo { name: "model".to_string(), dimension: 2 };
+        store.set_embedding_model(&model).await.unwrap();
+        store.set_embedding_model(&model).await.unwrap();
+        assert_eq!(store.embedding_model().await.unwrap(), Some(model));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_get_by_url_hash() {
+        let store = SQLiteStore::new(":memory:".to_string(), "articles".to_string(), 2).await.unwrap();
+        let url = "http://test.com/news/2024/story";
+        store.store_article(&test_article(url, "Test Article", "Content."), &[1.0, 0.0]).await.unwrap();
+
+        let id = ArticleIdScheme::UrlHash.id(url);
+        let found = store.get_by_id(&id, ArticleIdScheme::UrlHash).await.unwrap();
+        assert_eq!(found.map(|a| a.url).as_deref(), Some(url));
+        assert!(store.get_by_id(&ArticleIdScheme::UrlHash.id("http://missing.com"), ArticleIdScheme::UrlHash).await.unwrap().is_none());
+
+        store.delete_article(url).await.unwrap();
+        assert!(store.get_by_id(&id, ArticleIdScheme::UrlHash).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_search_text() {
+        let mut article = test_article("http://test.com/a", "Senate passes budget", "The budget was approved.");
+
+        let store = SQLiteStore::new(":memory:".to_string(), "articles".to_string(), 2).await.unwrap();
+        store.store_article(&article, &[1.0, 0.0]).await.unwrap();
+        article.url = "http://test.com/b".to_string();
+        article.title = "Football final".to_string();
+        article.content = "The home team won the cup.".to_string();
+        store.store_article(&article, &[0.0, 1.0]).await.unwrap();
+
+        let found = store.search_text("SENATE \"budget", 10).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].url, "http://test.com/a");
+        assert!(store.search_text("senate cup", 10).await.unwrap().is_empty());
+
+        // Re-storing and deleting keep the index in sync
+        article.content = "The away team won the trophy.".to_string();
+        store.store_article(&article, &[0.0, 1.0]).await.unwrap();
+        assert!(store.search_text("cup", 10).await.unwrap().is_empty());
+        assert_eq!(store.search_text("trophy", 10).await.unwrap().len(), 1);
+        store.delete_article("http://test.com/b").await.unwrap();
+        assert!(store.search_text("trophy", 10).await.unwrap().is_empty());
+    }
+}
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use nt_core::{Result, Article, ArticleStorage};
+use nt_core::{Result, Article, ArticleStorage, EmbeddingModelInfo, InferenceModel};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::str::FromStr;
@@ -15,6 +15,8 @@ pub use backends::chroma::ChromaStorage;
 pub use backends::qdrant::QdrantStorage;
 #[cfg(feature = "sqlite")]
 pub use backends::sqlite::SQLiteStorage;
+#[cfg(feature = "postgres")]
+pub use backends::postgres::PostgresStorage;
 
 #[async_trait]
 pub trait StorageBackend: Send + Sync + Any {
@@ -37,6 +39,39 @@ impl Default for EmbeddingModel {
     }
 }
 
+/// How vector backends compare embeddings when searching for similar articles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DistanceMetric {
+    #[default]
+    Cosine,
+    Dot,
+    Euclidean,
+}
+
+impl DistanceMetric {
+    /// Score how similar two embeddings are, higher meaning more similar.
+    ///
+    /// Euclidean distance is negated so every metric ranks the same way.
+    pub fn score(&self, a: &[f32], b: &[f32]) -> f32 {
+        match self {
+            Self::Cosine => nt_core::cosine_similarity(a, b),
+            Self::Dot => a.iter().zip(b.iter()).map(|(x, y)| x * y).sum(),
+            Self::Euclidean => -a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum::<f32>().sqrt(),
+        }
+    }
+}
+
+/// Fails with `Error::Storage` when `embedding` is empty.
+///
+/// Vector backends index every article by its embedding, so they call this
+/// before writing instead of storing a point they could never search.
+pub fn require_embedding(url: &str, embedding: &[f32]) -> Result<()> {
+    if embedding.is_empty() {
+        return Err(nt_core::Error::Storage(format!("Empty embedding for article: {}", url)));
+    }
+    Ok(())
+}
+
 pub trait UrlConfig {
     fn get_url(&self) -> String;
     fn with_url(&mut self, url: &str);
@@ -75,6 +110,7 @@ pub struct BackendConfig {
     pub collection: String,
     pub embedding_model: EmbeddingModel,
     pub vector_size: u64,
+    pub distance_metric: DistanceMetric,
 }
 
 impl BackendConfig {
@@ -84,8 +120,14 @@ impl BackendConfig {
             collection,
             embedding_model,
             vector_size,
+            distance_metric: DistanceMetric::default(),
         }
     }
+
+    pub fn with_distance_metric(mut self, distance_metric: DistanceMetric) -> Self {
+        self.distance_metric = distance_metric;
+        self
+    }
 }
 
 impl UrlConfig for BackendConfig {
@@ -113,10 +155,204 @@ pub trait ModelConfig: UrlConfig {
 }
 
 pub mod prelude {
-    pub use super::BackendConfig;
+    pub use super::{BackendConfig, DistanceMetric};
     pub use super::backends::*;
 }
 
+/// Number of articles read from the source backend at a time by [`migrate`]
+pub const MIGRATION_BATCH_SIZE: usize = 100;
+
+/// Copies every article and its embedding from one backend into another.
+///
+/// Articles are read in pages of [`MIGRATION_BATCH_SIZE`] with their embeddings
+/// fetched in one call per page, and written with `store_articles`, so existing
+/// articles in `to` with the same URL are replaced.
+///
+/// # Returns
+/// The number of articles copied
+pub async fn migrate(from: &dyn ArticleStorage, to: &dyn ArticleStorage) -> Result<usize> {
+    let mut migrated = 0;
+    let mut cursor = None;
+    loop {
+        let (articles, next) = from.list_articles_page(cursor.as_deref(), MIGRATION_BATCH_SIZE).await?;
+        if !articles.is_empty() {
+            let urls: Vec<String> = articles.iter().map(|article| article.url.clone()).collect();
+            let embeddings = from.get_article_embeddings(&urls).await?;
+            let batch: Vec<(Article, Vec<f32>)> = articles.into_iter().zip(embeddings).collect();
+            to.store_articles(&batch).await?;
+            migrated += batch.len();
+            tracing::debug!("📦 Migrated {} articles", migrated);
+        }
+
+        match next {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    Ok(migrated)
+}
+
+/// Text embedded to find out a model's output dimension
+const DIMENSION_PROBE: &str = "dimension";
+
+/// Checks whether the embeddings in `storage` were generated by a different
+/// model than `model`, making similarity search meaningless.
+///
+/// When the storage recorded the model its embeddings came from, its name and
+/// dimension are compared. Otherwise the dimension of the first non-empty
+/// stored embedding is; an empty storage never mismatches.
+pub async fn embedding_mismatch(storage: &dyn ArticleStorage, model: &dyn InferenceModel) -> Result<bool> {
+    if let Some(recorded) = storage.embedding_model().await? {
+        if recorded.name != model.name() {
+            return Ok(true);
+        }
+        let dimension = model.generate_embeddings(DIMENSION_PROBE).await?.len();
+        return Ok(recorded.dimension != dimension);
+    }
+
+    let mut cursor = None;
+    loop {
+        let (articles, next) = storage.list_articles_page(cursor.as_deref(), MIGRATION_BATCH_SIZE).await?;
+        let urls: Vec<String> = articles.into_iter().map(|article| article.url).collect();
+        let stored = storage.get_article_embeddings(&urls).await?
+            .into_iter()
+            .find(|embedding| !embedding.is_empty());
+        if let Some(stored) = stored {
+            let dimension = model.generate_embeddings(DIMENSION_PROBE).await?.len();
+            return Ok(stored.len() != dimension);
+        }
+        match next {
+            Some(next) => cursor = Some(next),
+            None => return Ok(false),
+        }
+    }
+}
+
+/// Regenerates the embedding of every stored article, and of its sections,
+/// with `model`.
+///
+/// Backends whose index is built for one dimension are copied into memory and
+/// their index rebuilt first when the new model's dimension differs. Articles
+/// are rewritten in pages of [`MIGRATION_BATCH_SIZE`], then the model is
+/// recorded in the storage and `config.vector_size` set to its dimension.
+///
+/// # Returns
+/// The number of articles re-embedded
+pub async fn reembed_all(
+    storage: &dyn ArticleStorage,
+    model: &dyn InferenceModel,
+    config: &mut BackendConfig,
+) -> Result<usize> {
+    let dimension = model.generate_embeddings(DIMENSION_PROBE).await?.len();
+
+    let copy;
+    let source: &dyn ArticleStorage = match storage.index_dimension().await? {
+        Some(current) if current != dimension => {
+            copy = InMemoryStorage::new().await?;
+            let copied = migrate(storage, &copy).await?;
+            tracing::info!("🔢 Rebuilding the index for {} dimensions, {} articles kept in memory", dimension, copied);
+            storage.recreate_index(dimension).await?;
+            &copy
+        }
+        _ => storage,
+    };
+
+    let mut reembedded = 0;
+    let mut cursor = None;
+    loop {
+        let (articles, next) = source.list_articles_page(cursor.as_deref(), MIGRATION_BATCH_SIZE).await?;
+
+        let mut batch = Vec::with_capacity(articles.len());
+        for mut article in articles {
+            for section in &mut article.sections {
+                if section.embedding.is_some() {
+                    section.embedding = Some(model.generate_embeddings(&section.content).await?);
+                }
+            }
+            let embedding = model.generate_embeddings(&article.content).await?;
+            batch.push((article, embedding));
+        }
+        if !batch.is_empty() {
+            storage.store_articles(&batch).await?;
+            reembedded += batch.len();
+            tracing::debug!("🔢 Re-embedded {} articles with {}", reembedded, model.name());
+        }
+
+        match next {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    storage.set_embedding_model(&EmbeddingModelInfo {
+        name: model.name().to_string(),
+        dimension,
+    }).await?;
+    config.vector_size = dimension as u64;
+    Ok(reembedded)
+}
+
+/// Outcome of [`import_ndjson`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportReport {
+    /// Number of articles stored
+    pub imported: usize,
+    /// Lines that could not be imported, as 1-based line number and reason
+    pub errors: Vec<(usize, String)>,
+}
+
+/// Imports articles from newline-delimited JSON, one [`Article`] per line.
+///
+/// Each article is embedded with `model` and stored in batches of
+/// [`MIGRATION_BATCH_SIZE`]. Blank lines are skipped; lines that fail to parse
+/// or embed are reported in [`ImportReport::errors`] instead of stopping the
+/// import, while storage errors abort it.
+pub async fn import_ndjson<R>(storage: &dyn ArticleStorage, model: &dyn InferenceModel, reader: R) -> Result<ImportReport>
+where
+    R: tokio::io::AsyncBufRead + Unpin,
+{
+    use tokio::io::AsyncBufReadExt;
+
+    let mut report = ImportReport::default();
+    let mut batch = Vec::with_capacity(MIGRATION_BATCH_SIZE);
+    let mut lines = reader.lines();
+    let mut line_number = 0;
+    while let Some(line) = lines.next_line().await? {
+        line_number += 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let article: Article = match serde_json::from_str(&line) {
+            Ok(article) => article,
+            Err(e) => {
+                report.errors.push((line_number, e.to_string()));
+                continue;
+            }
+        };
+        match model.generate_embeddings(&article.content).await {
+            Ok(embedding) => batch.push((article, embedding)),
+            Err(e) => {
+                report.errors.push((line_number, e.to_string()));
+                continue;
+            }
+        }
+
+        if batch.len() == MIGRATION_BATCH_SIZE {
+            storage.store_articles(&batch).await?;
+            report.imported += batch.len();
+            batch.clear();
+            tracing::debug!("📥 Imported {} articles", report.imported);
+        }
+    }
+
+    if !batch.is_empty() {
+        storage.store_articles(&batch).await?;
+        report.imported += batch.len();
+    }
+    Ok(report)
+}
+
 pub async fn get_available_storage_backends() -> HashMap<String, String> {
     let mut backends = HashMap::new();
     backends.insert("memory".to_string(), "In-memory storage".to_string());
@@ -130,6 +366,9 @@ pub async fn get_available_storage_backends() -> HashMap<String, String> {
     #[cfg(feature = "sqlite")]
     backends.insert("sqlite".to_string(), "SQLite database".to_string());
     
+    #[cfg(feature = "postgres")]
+    backends.insert("postgres".to_string(), "PostgreSQL database".to_string());
+    
     backends
 }
 
@@ -174,6 +413,15 @@ pub async fn create_storage(backend: &str, url: Option<&str>) -> Result<Arc<dyn
             }
             Ok(Arc::new(storage))
         }
+        #[cfg(feature = "postgres")]
+        "postgres" => {
+            // The pool can't be pointed elsewhere once open, so the URL is set before connecting
+            let mut config = backends::postgres::PostgresConfig::new();
+            if let Some(url) = url {
+                config.config.with_url(url);
+            }
+            Ok(Arc::new(PostgresStorage::with_config(config).await?))
+        }
         _ => {
             let backends = get_available_storage_backends().await;
             let available = backends.keys().map(|s| s.as_str()).collect::<Vec<_>>().join(", ");
@@ -184,3 +432,200 @@ pub async fn create_storage(backend: &str, url: Option<&str>) -> Result<Arc<dyn
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_require_embedding() {
+        assert!(require_embedding("http://test.com", &[0.5]).is_ok());
+        assert!(matches!(
+            require_embedding("http://test.com", &[]),
+            Err(nt_core::Error::Storage(message)) if message.contains("http://test.com")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_available_storage_backends() {
+        let backends = get_available_storage_backends().await;
+        assert!(backends.contains_key("memory"));
+        assert_eq!(backends.contains_key("chroma"), cfg!(feature = "chroma"));
+        assert_eq!(backends.contains_key("qdrant"), cfg!(feature = "qdrant"));
+        assert_eq!(backends.contains_key("sqlite"), cfg!(feature = "sqlite"));
+        assert_eq!(backends.contains_key("postgres"), cfg!(feature = "postgres"));
+    }
+
+    #[tokio::test]
+    async fn test_migrate_memory_to_memory() {
+        let from = InMemoryStorage::new().await.unwrap();
+        let to = InMemoryStorage::new().await.unwrap();
+
+        // More than one batch, so paging is exercised
+        let total = MIGRATION_BATCH_SIZE + 5;
+        for i in 0..total {
+            let article = Article {
+                url: format!("http://test.com/{:03}", i),
+                title: format!("Article {}", i),
+                content: "Content.".to_string(),
+                published_at: Utc::now(),
+                source: "test".to_string(),
+                sections: vec![],
+                summary: None,
+                authors: vec![],
+                related_articles: Vec::new(),
+                tags: vec!["migrated".to_string()],
+            };
+            from.store_article(&article, &[i as f32, 1.0]).await.unwrap();
+        }
+
+        let migrated = migrate(&from, &to).await.unwrap();
+        assert_eq!(migrated, total);
+        assert_eq!(to.get_by_source("test").await.unwrap().len(), total);
+        assert_eq!(to.get_article_embedding("http://test.com/042").await.unwrap(), vec![42.0, 1.0]);
+        assert_eq!(to.list_tags().await.unwrap(), vec![("migrated".to_string(), total)]);
+
+        let empty = InMemoryStorage::new().await.unwrap();
+        assert_eq!(migrate(&empty, &to).await.unwrap(), 0);
+    }
+
+    /// Embeds every text as `dimension` copies of its length
+    struct FixedModel {
+        name: &'static str,
+        dimension: usize,
+    }
+
+    impl FixedModel {
+        fn new(dimension: usize) -> Self {
+            Self { name: "Fixed", dimension }
+        }
+    }
+
+    #[async_trait]
+    impl InferenceModel for FixedModel {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn summarize_article(&self, article: &Article) -> Result<String> {
+            Ok(article.title.clone())
+        }
+
+        async fn summarize_sections(&self, sections: &[nt_core::ArticleSection]) -> Result<Vec<String>> {
+            Ok(sections.iter().map(|s| s.content.clone()).collect())
+        }
+
+        async fn generate_embeddings(&self, text: &str) -> Result<Vec<f32>> {
+            Ok(vec![text.len() as f32; self.dimension])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reembed_all() {
+        let storage = InMemoryStorage::new().await.unwrap();
+        let old_model = FixedModel::new(2);
+        let new_model = FixedModel::new(3);
+        let mut config = memory::MemoryConfig::new().config;
+
+        assert!(!embedding_mismatch(&storage, &new_model).await.unwrap());
+
+        for i in 0..3 {
+            let content = "x".repeat(i + 1);
+            let article = Article {
+                url: format!("http://test.com/{}", i),
+                title: format!("Article {}", i),
+                content: content.clone(),
+                published_at: Utc::now(),
+                source: "test".to_string(),
+                sections: vec![nt_core::ArticleSection {
+                    content: content.clone(),
+                    summary: None,
+                    embedding: Some(old_model.generate_embeddings(&content).await.unwrap()),
+                }],
+                summary: None,
+                authors: vec![],
+                related_articles: Vec::new(),
+                tags: Vec::new(),
+            };
+            let embedding = old_model.generate_embeddings(&content).await.unwrap();
+            storage.store_article(&article, &embedding).await.unwrap();
+        }
+
+        assert!(!embedding_mismatch(&storage, &old_model).await.unwrap());
+        assert!(embedding_mismatch(&storage, &new_model).await.unwrap());
+
+        assert_eq!(reembed_all(&storage, &new_model, &mut config).await.unwrap(), 3);
+        assert_eq!(config.vector_size, 3);
+        assert!(!embedding_mismatch(&storage, &new_model).await.unwrap());
+        assert_eq!(storage.get_article_embedding("http://test.com/1").await.unwrap(), vec![2.0; 3]);
+
+        let article = storage.get_article("http://test.com/2").await.unwrap().unwrap();
+        assert_eq!(article.sections[0].embedding, Some(vec![3.0; 3]));
+
+        // The recorded model is compared by name too, not only by dimension
+        assert_eq!(
+            storage.embedding_model().await.unwrap(),
+            Some(EmbeddingModelInfo { name: "Fixed".to_string(), dimension: 3 })
+        );
+        let renamed = FixedModel { name: "Other", dimension: 3 };
+        assert!(embedding_mismatch(&storage, &renamed).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_embedding_mismatch_skips_empty_embeddings() {
+        let storage = InMemoryStorage::new().await.unwrap();
+        for (url, embedding) in [("http://test.com/a", vec![]), ("http://test.com/b", vec![1.0, 1.0])] {
+            let article = Article {
+                url: url.to_string(),
+                title: "Article".to_string(),
+                content: "Content.".to_string(),
+                published_at: Utc::now(),
+                source: "test".to_string(),
+                sections: vec![],
+                summary: None,
+                authors: vec![],
+                related_articles: Vec::new(),
+                tags: Vec::new(),
+            };
+            storage.store_article(&article, &embedding).await.unwrap();
+        }
+
+        assert!(!embedding_mismatch(&storage, &FixedModel::new(2)).await.unwrap());
+        assert!(embedding_mismatch(&storage, &FixedModel::new(3)).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_import_ndjson() {
+        let storage = InMemoryStorage::new().await.unwrap();
+        let model = FixedModel::new(2);
+
+        let mut ndjson = String::new();
+        for i in 0..3 {
+            let article = Article {
+                url: format!("http://test.com/{}", i),
+                title: format!("Article {}", i),
+                content: "x".repeat(i + 1),
+                published_at: Utc::now(),
+                source: "test".to_string(),
+                sections: vec![],
+                summary: None,
+                authors: vec![],
+                related_articles: Vec::new(),
+                tags: Vec::new(),
+            };
+            ndjson.push_str(&serde_json::to_string(&article).unwrap());
+            ndjson.push('\n');
+        }
+        // A blank line, then an article missing most of its fields
+        ndjson.push_str("\n{\"url\": \"http://test.com/broken\"}\n");
+
+        let report = import_ndjson(&storage, &model, ndjson.as_bytes()).await.unwrap();
+        assert_eq!(report.imported, 3);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].0, 5);
+
+        assert_eq!(storage.get_by_source("test").await.unwrap().len(), 3);
+        assert_eq!(storage.get_article_embedding("http://test.com/2").await.unwrap(), vec![3.0; 2]);
+    }
+}
@@ -1,18 +1,112 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
+    http::StatusCode,
     Json,
     response::IntoResponse,
 };
 use std::sync::Arc;
 use nt_core::Article;
+use nt_inference::divergence::{DivergenceAnalysis, DivergenceAnalyzer};
+use nt_scrappers::ScrapeEvent;
 use crate::AppState;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use chrono::Utc;
 
+pub(crate) const DEFAULT_SIMILAR_LIMIT: usize = 10;
+pub(crate) const DEFAULT_LIST_LIMIT: usize = 50;
+pub(crate) const DEFAULT_DIVERGENCE_LIMIT: usize = 5;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ListQuery {
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SimilarQuery {
+    pub limit: Option<usize>,
+    pub min_score: Option<f32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct DivergenceQuery {
+    /// How many similar articles to compare against
+    pub limit: Option<usize>,
+}
+
+/// An article together with the id that addresses it under the state's id scheme
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArticleResponse {
+    pub id: String,
+    #[serde(flatten)]
+    pub article: Article,
+}
+
+impl ArticleResponse {
+    fn new(state: &AppState, article: Article) -> Self {
+        Self { id: state.id_scheme.id(&article.url), article }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RelatedArticleResponse {
+    pub id: String,
+    pub article: Article,
+    pub similarity_score: Option<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScrapeRequest {
+    /// Source to scrape, in the same `country[/name]` form as the CLI
+    pub source: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScrapeJob {
+    pub job_id: usize,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+/// Tags are stored trimmed and lowercased so `Economy` and `economy ` are the same tag
+fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().to_lowercase();
+    (!tag.is_empty()).then_some(tag)
+}
+
+/// Resolves an article id, made by the state's id scheme, to the stored article
+async fn find_article(state: &AppState, id: &str) -> Result<Article, (StatusCode, String)> {
+    state.storage.get_by_id(id, state.id_scheme)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Article not found: {}", id)))
+}
+
+/// Stores an article again under its existing embedding, so only its metadata changes
+async fn restore_article(state: &AppState, article: &Article) -> Result<(), (StatusCode, String)> {
+    let embedding = state.storage.get_article_embedding(&article.url)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    state.storage.store_article(article, &embedding)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
 pub async fn list_articles(
-    State(_state): State<Arc<AppState>>,
-) -> impl IntoResponse {
-    Json::<Vec<Article>>(vec![])
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ListQuery>,
+) -> Result<Json<Vec<ArticleResponse>>, (StatusCode, String)> {
+    let offset = query.offset.unwrap_or(0);
+    let limit = query.limit.unwrap_or(DEFAULT_LIST_LIMIT);
+    let articles = state.storage.list_articles(offset, limit)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(articles.into_iter().map(|article| ArticleResponse::new(&state, article)).collect()))
 }
 
 pub async fn create_article(
@@ -29,38 +123,545 @@ pub async fn create_article(
         summary: None,
         authors: vec![],
         related_articles: Vec::new(),
+        tags: Vec::new(),
     };
     Json(default_article)
 }
 
 pub async fn get_article(
-    State(_state): State<Arc<AppState>>,
-    Path(_id): Path<String>,
-) -> impl IntoResponse {
-    let default_article = Article {
-        url: "".to_string(),
-        title: "".to_string(),
-        content: "".to_string(),
-        published_at: Utc::now(),
-        source: "".to_string(),
-        sections: vec![],
-        summary: None,
-        authors: vec![],
-        related_articles: Vec::new(),
-    };
-    Json(default_article)
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<ArticleResponse>, (StatusCode, String)> {
+    let article = find_article(&state, &id).await?;
+    Ok(Json(ArticleResponse::new(&state, article)))
 }
 
 pub async fn get_similar_articles(
-    State(_state): State<Arc<AppState>>,
-    Path(_id): Path<String>,
-) -> impl IntoResponse {
-    Json::<Vec<Article>>(vec![])
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Query(query): Query<SimilarQuery>,
+) -> Result<Json<Vec<RelatedArticleResponse>>, (StatusCode, String)> {
+    let limit = query.limit.unwrap_or(DEFAULT_SIMILAR_LIMIT);
+    let min_score = query.min_score.unwrap_or(f32::MIN);
+
+    let url = find_article(&state, &id).await?.url;
+    let embedding = state.storage.get_article_embedding(&url)
+        .await
+        .map_err(|e| (StatusCode::NOT_FOUND, e.to_string()))?;
+
+    // Ask for one extra result since the article itself is usually the best match
+    let scored = state.storage.find_similar_scored(&embedding, limit.saturating_add(1))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let related = scored.into_iter()
+        .filter(|(article, score)| article.url != url && *score >= min_score)
+        .take(limit)
+        .map(|(article, score)| RelatedArticleResponse {
+            id: state.id_scheme.id(&article.url),
+            article,
+            similarity_score: Some(score),
+        })
+        .collect();
+
+    Ok(Json(related))
+}
+
+pub async fn get_tags(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<String>>, (StatusCode, String)> {
+    let article = find_article(&state, &id).await?;
+    Ok(Json(article.tags))
+}
+
+pub async fn add_tags(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(tags): Json<Vec<String>>,
+) -> Result<Json<Vec<String>>, (StatusCode, String)> {
+    let mut article = find_article(&state, &id).await?;
+
+    let mut changed = false;
+    for tag in tags.iter().filter_map(|t| normalize_tag(t)) {
+        if !article.tags.contains(&tag) {
+            article.tags.push(tag);
+            changed = true;
+        }
+    }
+    if changed {
+        restore_article(&state, &article).await?;
+    }
+
+    Ok(Json(article.tags))
+}
+
+pub async fn delete_tag(
+    State(state): State<Arc<AppState>>,
+    Path((id, tag)): Path<(String, String)>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let mut article = find_article(&state, &id).await?;
+    let tag = normalize_tag(&tag).unwrap_or_default();
+
+    let before = article.tags.len();
+    article.tags.retain(|t| *t != tag);
+    if article.tags.len() == before {
+        return Err((StatusCode::NOT_FOUND, format!("Article {} has no tag: {}", id, tag)));
+    }
+    restore_article(&state, &article).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn list_tags(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<TagCount>>, (StatusCode, String)> {
+    let tags = state.storage.list_tags()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(tags.into_iter().map(|(tag, count)| TagCount { tag, count }).collect()))
+}
+
+/// Line shown in a scrape job's output for an event of one of its `sources`
+fn scrape_event_line(event: &ScrapeEvent, sources: &[&str]) -> Option<String> {
+    match event {
+        ScrapeEvent::Started { source } if sources.contains(&source.as_str()) => {
+            Some(format!("Scraping {}", source))
+        }
+        ScrapeEvent::ArticleScraped { source, url, status } if sources.contains(&source.as_str()) => {
+            Some(format!("{:?}: {}", status, url))
+        }
+        ScrapeEvent::Error { source, url, msg } if sources.contains(&source.as_str()) => {
+            Some(format!("Failed {}: {}", url.as_deref().unwrap_or(source), msg))
+        }
+        _ => None,
+    }
+}
+
+pub async fn start_scrape(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<ScrapeRequest>,
+) -> Result<(StatusCode, Json<ScrapeJob>), (StatusCode, String)> {
+    let scrapers = state.scraper.get_scrapers_for_source(&request.source)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    if scrapers.is_empty() {
+        return Err((StatusCode::NOT_FOUND, format!("No scrapers found for source: {}", request.source)));
+    }
+
+    let scraper = state.scraper.clone();
+    let source = request.source;
+    let sources: Vec<&'static str> = scrapers.iter().map(|s| s.source_metadata().name).collect();
+    let mut events = scraper.subscribe();
+    let task = state.progress.progress_manager().spawn(move |mut handle| async move {
+        let job_id = handle.thread_id();
+        // Other jobs publish on the same channel, so only this job's sources are shown
+        let scrape = scraper.scrape_source_report(Some(&source), true);
+        tokio::pin!(scrape);
+        let result = loop {
+            tokio::select! {
+                result = &mut scrape => break result,
+                Ok(event) = events.recv() => {
+                    if let Some(line) = scrape_event_line(&event, &sources) {
+                        handle.capture_stdout(line).await?;
+                    }
+                }
+            }
+        };
+        while let Ok(event) = events.try_recv() {
+            if let Some(line) = scrape_event_line(&event, &sources) {
+                handle.capture_stdout(line).await?;
+            }
+        }
+
+        // The job is marked completed or failed from the result
+        match result {
+            Ok((_, report)) => {
+                tracing::info!("Scrape job {} for {} finished: {}", job_id, source, report);
+                handle.capture_stdout(format!("Finished: {}", report)).await?;
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Scrape job {} for {} failed: {}", job_id, source, e);
+                Err(e.into())
+            }
+        }
+    })
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok((StatusCode::ACCEPTED, Json(ScrapeJob { job_id: task.thread_id() })))
+}
+
+pub async fn cancel_scrape(
+    State(state): State<Arc<AppState>>,
+    Path(job_id): Path<usize>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let manager = state.progress.progress_manager();
+    match manager.thread_manager().get_thread_state(job_id).await {
+        None => Err((StatusCode::NOT_FOUND, format!("Scrape job {} not found", job_id))),
+        Some(state) if state.is_terminal() => {
+            Err((StatusCode::CONFLICT, format!("Scrape job {} has already finished", job_id)))
+        }
+        Some(_) => {
+            manager.cancel_task(job_id, Some("Cancelled via API".to_string()))
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            Ok(StatusCode::NO_CONTENT)
+        }
+    }
+}
+
+pub async fn get_openapi() -> Json<Value> {
+    Json(crate::openapi::document())
 }
 
 pub async fn get_article_divergence(
-    State(_state): State<Arc<AppState>>,
-    Path(_id): Path<String>,
-) -> impl IntoResponse {
-    Json(Value::Null)
-} 
\ No newline at end of file
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Query(query): Query<DivergenceQuery>,
+) -> Result<Json<DivergenceAnalysis>, (StatusCode, String)> {
+    let article = find_article(&state, &id).await?;
+    let limit = query.limit.unwrap_or(DEFAULT_DIVERGENCE_LIMIT);
+    DivergenceAnalyzer::new(state.inference_model.clone())
+        .analyze_against(&article, state.storage.as_ref(), limit)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nt_core::{ArticleIdScheme, ArticleStorage};
+    use nt_inference::models::dummy::DummyModel;
+    use nt_storage::InMemoryStorage;
+    use nt_core::{RegionMetadata, Scraper, SourceMetadata};
+    use nt_progress::ProgressDisplay;
+    use nt_progress::thread::ThreadState;
+    use nt_scrappers::ScraperManager;
+
+    fn test_article(url: &str) -> Article {
+        Article {
+            url: url.to_string(),
+            title: format!("Article {}", url),
+            content: "Test content".to_string(),
+            published_at: Utc::now(),
+            source: "test".to_string(),
+            sections: vec![],
+            summary: None,
+            authors: vec![],
+            related_articles: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    async fn seeded_state() -> Arc<AppState> {
+        let storage = InMemoryStorage::new().await.unwrap();
+        storage.store_article(&test_article("http://test.com/a"), &[1.0, 0.0, 0.0]).await.unwrap();
+        storage.store_article(&test_article("http://test.com/b"), &[0.9, 0.1, 0.0]).await.unwrap();
+        storage.store_article(&test_article("http://test.com/c"), &[0.5, 0.5, 0.0]).await.unwrap();
+        storage.store_article(&test_article("http://test.com/d"), &[0.0, 0.0, 1.0]).await.unwrap();
+
+        let inference_model = Arc::new(DummyModel::new(None).await.unwrap());
+        Arc::new(AppState::new(inference_model, Arc::new(storage)).await.unwrap())
+    }
+
+    /// Scraper whose article listing never finishes, so jobs stay running until cancelled
+    struct SlowScraper;
+
+    #[async_trait::async_trait]
+    impl Scraper for SlowScraper {
+        fn source_metadata(&self) -> SourceMetadata {
+            SourceMetadata {
+                name: "Slow",
+                emoji: "🐢",
+                region: RegionMetadata { name: "test", emoji: "🧪" },
+                language: "en",
+            }
+        }
+
+        fn can_handle(&self, url: &str) -> bool {
+            url.contains("slow.test")
+        }
+
+        async fn scrape_article(&mut self, url: &str) -> nt_core::Result<Article> {
+            Ok(test_article(url))
+        }
+
+        async fn get_article_urls(&self) -> nt_core::Result<Vec<String>> {
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            Ok(Vec::new())
+        }
+
+        fn cli_names(&self) -> Vec<&str> {
+            vec!["slow"]
+        }
+    }
+
+    /// Scraper whose article listing fails at once
+    struct BrokenScraper;
+
+    #[async_trait::async_trait]
+    impl Scraper for BrokenScraper {
+        fn source_metadata(&self) -> SourceMetadata {
+            SourceMetadata {
+                name: "Broken",
+                emoji: "💥",
+                region: RegionMetadata { name: "test", emoji: "🧪" },
+                language: "en",
+            }
+        }
+
+        fn can_handle(&self, url: &str) -> bool {
+            url.contains("broken.test")
+        }
+
+        async fn scrape_article(&mut self, url: &str) -> nt_core::Result<Article> {
+            Ok(test_article(url))
+        }
+
+        async fn get_article_urls(&self) -> nt_core::Result<Vec<String>> {
+            Err(nt_core::Error::Scraping("front page unavailable".to_string()))
+        }
+
+        fn cli_names(&self) -> Vec<&str> {
+            vec!["broken"]
+        }
+    }
+
+    async fn scrape_state() -> Arc<AppState> {
+        let storage: Arc<dyn ArticleStorage> = Arc::new(InMemoryStorage::new().await.unwrap());
+        let inference_model: Arc<dyn nt_core::InferenceModel> = Arc::new(DummyModel::new(None).await.unwrap());
+        let mut scraper = ScraperManager::new(storage.clone(), inference_model.clone()).await.unwrap();
+        scraper.add_scraper_factory(Box::new(|| Box::new(SlowScraper)));
+        scraper.add_scraper_factory(Box::new(|| Box::new(BrokenScraper)));
+
+        Arc::new(AppState {
+            inference_model,
+            storage,
+            scraper: Arc::new(scraper),
+            progress: ProgressDisplay::new().await.unwrap(),
+            id_scheme: ArticleIdScheme::default(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_similar_articles_limit_and_threshold() {
+        let state = seeded_state().await;
+        let url = "http://test.com/a";
+        let id = test_article(url).id();
+
+        let query = SimilarQuery { limit: None, min_score: None };
+        let Json(related) = get_similar_articles(State(state.clone()), Path(id.clone()), Query(query)).await.unwrap();
+        assert_eq!(related.len(), 3);
+        assert!(related.iter().all(|r| r.article.url != url));
+        assert_eq!(related[0].article.url, "http://test.com/b");
+        assert_eq!(related[0].id, test_article("http://test.com/b").id());
+
+        let query = SimilarQuery { limit: Some(1), min_score: None };
+        let Json(related) = get_similar_articles(State(state.clone()), Path(id.clone()), Query(query)).await.unwrap();
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].article.url, "http://test.com/b");
+
+        let query = SimilarQuery { limit: None, min_score: Some(0.5) };
+        let Json(related) = get_similar_articles(State(state.clone()), Path(id.clone()), Query(query)).await.unwrap();
+        assert_eq!(related.len(), 2);
+        assert!(related.iter().all(|r| r.similarity_score.unwrap() >= 0.5));
+    }
+
+    #[tokio::test]
+    async fn test_similar_articles_unknown_id() {
+        let state = seeded_state().await;
+        let query = SimilarQuery::default();
+        let result = get_similar_articles(State(state), Path(test_article("http://missing.com").id()), Query(query)).await;
+        assert_eq!(result.unwrap_err().0, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_start_and_cancel_scrape() {
+        let state = scrape_state().await;
+        let threads = state.progress.progress_manager().thread_manager().clone();
+
+        let request = ScrapeRequest { source: "test/slow".to_string() };
+        let (status, Json(job)) = start_scrape(State(state.clone()), Json(request)).await.unwrap();
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(threads.get_thread_state(job.job_id).await, Some(ThreadState::Running));
+
+        let status = cancel_scrape(State(state.clone()), Path(job.job_id)).await.unwrap();
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        assert_eq!(
+            threads.get_thread_state(job.job_id).await,
+            Some(ThreadState::Cancelled)
+        );
+
+        // A cancelled job cannot be cancelled again
+        let result = cancel_scrape(State(state.clone()), Path(job.job_id)).await;
+        assert_eq!(result.unwrap_err().0, StatusCode::CONFLICT);
+
+        state.progress.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fast_failing_scrape_finishes() {
+        let state = scrape_state().await;
+        let threads = state.progress.progress_manager().thread_manager().clone();
+
+        let request = ScrapeRequest { source: "test/broken".to_string() };
+        let (_, Json(job)) = start_scrape(State(state.clone()), Json(request)).await.unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while threads.get_thread_state(job.job_id).await == Some(ThreadState::Running) {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        }).await.unwrap();
+        // The failing source is recorded in the report, the job itself completes
+        assert_eq!(threads.get_thread_state(job.job_id).await, Some(ThreadState::Completed));
+
+        state.progress.flush().await.unwrap();
+        let lines = state.progress.get_task_lines(job.job_id).await.unwrap();
+        assert!(lines.iter().any(|line| line == "Finished: 0 new, 0 updated, 0 unchanged, 1 failed"), "{:?}", lines);
+
+        state.progress.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_scrape_unknown_source_and_job() {
+        let state = scrape_state().await;
+
+        let request = ScrapeRequest { source: "nowhere".to_string() };
+        let result = start_scrape(State(state.clone()), Json(request)).await;
+        assert_eq!(result.unwrap_err().0, StatusCode::NOT_FOUND);
+
+        let request = ScrapeRequest { source: "a/b/c".to_string() };
+        let result = start_scrape(State(state.clone()), Json(request)).await;
+        assert_eq!(result.unwrap_err().0, StatusCode::BAD_REQUEST);
+
+        let result = cancel_scrape(State(state.clone()), Path(12345)).await;
+        assert_eq!(result.unwrap_err().0, StatusCode::NOT_FOUND);
+
+        state.progress.stop().await.unwrap();
+    }
+
+    #[test]
+    fn test_scrape_event_line() {
+        let sources = ["Slow"];
+        let started = ScrapeEvent::Started { source: "Slow".to_string() };
+        assert_eq!(scrape_event_line(&started, &sources).unwrap(), "Scraping Slow");
+
+        let failed = ScrapeEvent::Error { source: "Slow".to_string(), url: None, msg: "timed out".to_string() };
+        assert_eq!(scrape_event_line(&failed, &sources).unwrap(), "Failed Slow: timed out");
+        let failed = ScrapeEvent::Error {
+            source: "Slow".to_string(),
+            url: Some("http://slow.test/a".to_string()),
+            msg: "not found".to_string(),
+        };
+        assert_eq!(scrape_event_line(&failed, &sources).unwrap(), "Failed http://slow.test/a: not found");
+
+        // Events of other jobs' sources are not shown
+        let other = ScrapeEvent::Started { source: "Other".to_string() };
+        assert!(scrape_event_line(&other, &sources).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tags_crud() {
+        let state = seeded_state().await;
+        let url = "http://test.com/a";
+        let id = test_article(url).id();
+
+        let Json(tags) = get_tags(State(state.clone()), Path(id.clone())).await.unwrap();
+        assert!(tags.is_empty());
+
+        let input = vec!["Economy".to_string(), " politics ".to_string(), "economy".to_string(), "".to_string()];
+        let Json(tags) = add_tags(State(state.clone()), Path(id.clone()), Json(input)).await.unwrap();
+        assert_eq!(tags, vec!["economy", "politics"]);
+
+        let Json(tags) = get_tags(State(state.clone()), Path(id.clone())).await.unwrap();
+        assert_eq!(tags, vec!["economy", "politics"]);
+        // Re-storing keeps the embedding, so similarity search is unaffected
+        assert_eq!(state.storage.get_article_embedding(url).await.unwrap(), vec![1.0, 0.0, 0.0]);
+
+        let _ = add_tags(State(state.clone()), Path(test_article("http://test.com/b").id()), Json(vec!["politics".to_string()])).await.unwrap();
+        let Json(counts) = list_tags(State(state.clone())).await.unwrap();
+        assert_eq!(counts, vec![
+            TagCount { tag: "politics".to_string(), count: 2 },
+            TagCount { tag: "economy".to_string(), count: 1 },
+        ]);
+        assert_eq!(state.storage.get_by_tag("politics").await.unwrap().len(), 2);
+
+        let status = delete_tag(State(state.clone()), Path((id.clone(), "Economy".to_string()))).await.unwrap();
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let Json(tags) = get_tags(State(state.clone()), Path(id.clone())).await.unwrap();
+        assert_eq!(tags, vec!["politics"]);
+
+        let result = delete_tag(State(state.clone()), Path((id.clone(), "economy".to_string()))).await;
+        assert_eq!(result.unwrap_err().0, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_tags_missing_article() {
+        let state = seeded_state().await;
+        let missing = test_article("http://missing.com").id();
+
+        let result = get_tags(State(state.clone()), Path(missing.clone())).await;
+        assert_eq!(result.unwrap_err().0, StatusCode::NOT_FOUND);
+        let result = add_tags(State(state.clone()), Path(missing.clone()), Json(vec!["tag".to_string()])).await;
+        assert_eq!(result.unwrap_err().0, StatusCode::NOT_FOUND);
+        let result = delete_tag(State(state), Path((missing, "tag".to_string()))).await;
+        assert_eq!(result.unwrap_err().0, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_get_article_by_id() {
+        let url = "http://test.com/news/2024/05/story";
+        for scheme in [ArticleIdScheme::Base64Url, ArticleIdScheme::UrlHash] {
+            let storage = InMemoryStorage::new().await.unwrap();
+            storage.store_article(&test_article(url), &[1.0, 0.0, 0.0]).await.unwrap();
+            storage.store_article(&test_article("http://test.com/news"), &[0.0, 1.0, 0.0]).await.unwrap();
+            let inference_model = Arc::new(DummyModel::new(None).await.unwrap());
+            let state = Arc::new(AppState::new(inference_model, Arc::new(storage)).await.unwrap().with_id_scheme(scheme));
+
+            let id = scheme.id(url);
+            assert!(!id.contains('/'));
+            let Json(article) = get_article(State(state.clone()), Path(id.clone())).await.unwrap();
+            assert_eq!(article.article.url, url);
+            assert_eq!(article.id, id);
+            let Json(tags) = add_tags(State(state.clone()), Path(id), Json(vec!["local".to_string()])).await.unwrap();
+            assert_eq!(tags, vec!["local"]);
+
+            // The URL itself is not an id
+            let result = get_article(State(state.clone()), Path(url.to_string())).await;
+            assert_eq!(result.unwrap_err().0, StatusCode::NOT_FOUND);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_articles_with_ids() {
+        let state = seeded_state().await;
+
+        let Json(articles) = list_articles(State(state.clone()), Query(ListQuery::default())).await.unwrap();
+        assert_eq!(articles.len(), 4);
+        assert!(articles.iter().all(|a| a.id == state.id_scheme.id(&a.article.url)));
+
+        let query = ListQuery { offset: Some(1), limit: Some(2) };
+        let Json(page) = list_articles(State(state.clone()), Query(query)).await.unwrap();
+        assert_eq!(page.len(), 2);
+        assert_eq!(page[0].article.url, articles[1].article.url);
+
+        // The id sits next to the article's own fields
+        let json = serde_json::to_value(&page[0]).unwrap();
+        assert_eq!(json["id"], page[0].id.as_str());
+        assert_eq!(json["url"], page[0].article.url.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_article_divergence_by_id() {
+        let state = seeded_state().await;
+
+        let id = test_article("http://test.com/a").id();
+        let Json(analysis) = get_article_divergence(State(state.clone()), Path(id), Query(DivergenceQuery::default())).await.unwrap();
+        assert_eq!(analysis.article_url, "http://test.com/a");
+
+        let result = get_article_divergence(State(state), Path(test_article("http://missing.com").id()), Query(DivergenceQuery::default())).await;
+        assert_eq!(result.unwrap_err().0, StatusCode::NOT_FOUND);
+    }
+}
@@ -1,16 +1,36 @@
 use axum::{
-    routing::{get, post},
+    routing::{delete, get, post},
     Router,
 };
+use nt_core::ArticleIdScheme;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tower_http::cors::CorsLayer;
 
 pub mod handlers;
+pub mod openapi;
 pub mod state;
 pub mod routes;
 
 pub use state::AppState;
 
+/// Where the API listens and how it addresses articles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WebConfig {
+    pub addr: SocketAddr,
+    /// How the `:id` of article routes, and the `id` of returned articles, map to URLs
+    pub id_scheme: ArticleIdScheme,
+}
+
+impl Default for WebConfig {
+    fn default() -> Self {
+        Self {
+            addr: SocketAddr::from(([127, 0, 0, 1], 3000)),
+            id_scheme: ArticleIdScheme::default(),
+        }
+    }
+}
+
 pub async fn create_app(state: AppState) -> Router {
     let cors = CorsLayer::permissive();
     
@@ -20,10 +40,25 @@ pub async fn create_app(state: AppState) -> Router {
         .route("/api/articles/:id", get(handlers::get_article))
         .route("/api/articles/:id/similar", get(handlers::get_similar_articles))
         .route("/api/articles/:id/divergence", get(handlers::get_article_divergence))
+        .route("/api/articles/:id/tags", get(handlers::get_tags).post(handlers::add_tags))
+        .route("/api/articles/:id/tags/:tag", delete(handlers::delete_tag))
+        .route("/api/tags", get(handlers::list_tags))
+        .route("/api/scrape", post(handlers::start_scrape))
+        .route("/api/scrape/:job_id", delete(handlers::cancel_scrape))
+        .route("/api/openapi.json", get(handlers::get_openapi))
         .layer(cors)
         .with_state(Arc::new(state))
 }
 
+/// Serves the API on `config.addr` until the server fails
+pub async fn serve(state: AppState, config: WebConfig) -> anyhow::Result<()> {
+    let app = create_app(state.with_id_scheme(config.id_scheme)).await;
+    let listener = tokio::net::TcpListener::bind(config.addr).await?;
+    tracing::info!("🌐 Serving the API on http://{}", listener.local_addr()?);
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
 pub mod prelude {
     pub use nt_core::{Article, Result, Error};
     pub use crate::AppState;
@@ -0,0 +1,380 @@
+//! OpenAPI description of the HTTP API served by [`crate::create_app`].
+//!
+//! The document is written by hand, so a route or a response shape changed in
+//! [`crate::handlers`] has to be changed here too.
+
+use serde_json::{json, Value};
+
+fn schema_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{}", name) })
+}
+
+fn json_response(description: &str, schema: Value) -> Value {
+    json!({
+        "description": description,
+        "content": { "application/json": { "schema": schema } }
+    })
+}
+
+fn error_response(description: &str) -> Value {
+    json!({
+        "description": description,
+        "content": { "text/plain": { "schema": { "type": "string" } } }
+    })
+}
+
+fn path_param(name: &str, description: &str, schema: Value) -> Value {
+    json!({
+        "name": name,
+        "in": "path",
+        "required": true,
+        "description": description,
+        "schema": schema
+    })
+}
+
+fn article_id() -> Value {
+    path_param(
+        "id",
+        "Id of the article as returned in its `id` field; the server's `--id-scheme` picks between its URL as unpadded base64url (the default) and the hex SHA-256 of its URL",
+        json!({ "type": "string" }),
+    )
+}
+
+fn limit_param(description: &str, default: usize) -> Value {
+    json!({
+        "name": "limit",
+        "in": "query",
+        "required": false,
+        "description": description,
+        "schema": { "type": "integer", "minimum": 0, "default": default }
+    })
+}
+
+fn paths() -> Value {
+    json!({
+        "/api/articles": {
+            "get": {
+                "summary": "List articles",
+                "operationId": "listArticles",
+                "parameters": [
+                    {
+                        "name": "offset",
+                        "in": "query",
+                        "required": false,
+                        "description": "Number of articles to skip",
+                        "schema": { "type": "integer", "minimum": 0, "default": 0 }
+                    },
+                    limit_param("Maximum number of articles returned", crate::handlers::DEFAULT_LIST_LIMIT)
+                ],
+                "responses": {
+                    "200": json_response("Stored articles", json!({ "type": "array", "items": schema_ref("ArticleWithId") })),
+                    "500": error_response("Storage failed")
+                }
+            },
+            "post": {
+                "summary": "Create an article",
+                "operationId": "createArticle",
+                "requestBody": {
+                    "required": true,
+                    "content": { "application/json": { "schema": schema_ref("Article") } }
+                },
+                "responses": { "200": json_response("The created article", schema_ref("Article")) }
+            }
+        },
+        "/api/articles/{id}": {
+            "get": {
+                "summary": "Get an article",
+                "operationId": "getArticle",
+                "parameters": [article_id()],
+                "responses": {
+                    "200": json_response("The article", schema_ref("ArticleWithId")),
+                    "404": error_response("Article not found"),
+                    "500": error_response("Storage failed")
+                }
+            }
+        },
+        "/api/articles/{id}/similar": {
+            "get": {
+                "summary": "Find articles similar to an article",
+                "operationId": "getSimilarArticles",
+                "parameters": [
+                    article_id(),
+                    limit_param("Maximum number of articles returned", crate::handlers::DEFAULT_SIMILAR_LIMIT),
+                    {
+                        "name": "min_score",
+                        "in": "query",
+                        "required": false,
+                        "description": "Lowest similarity score of the articles returned",
+                        "schema": { "type": "number", "format": "float" }
+                    }
+                ],
+                "responses": {
+                    "200": json_response("Similar articles, best match first", json!({
+                        "type": "array",
+                        "items": schema_ref("RelatedArticleWithId")
+                    })),
+                    "404": error_response("Article not found, or it has no embedding"),
+                    "500": error_response("Storage failed")
+                }
+            }
+        },
+        "/api/articles/{id}/divergence": {
+            "get": {
+                "summary": "Get how the coverage of an article diverges between sources",
+                "operationId": "getArticleDivergence",
+                "parameters": [
+                    article_id(),
+                    limit_param("Number of similar articles to compare against", crate::handlers::DEFAULT_DIVERGENCE_LIMIT)
+                ],
+                "responses": {
+                    "200": json_response("Divergence of each section of the article", schema_ref("DivergenceAnalysis")),
+                    "404": error_response("Article not found"),
+                    "500": error_response("Storage or inference failed")
+                }
+            }
+        },
+        "/api/articles/{id}/tags": {
+            "get": {
+                "summary": "List the tags of an article",
+                "operationId": "getTags",
+                "parameters": [article_id()],
+                "responses": {
+                    "200": json_response("Tags of the article", schema_ref("Tags")),
+                    "404": error_response("Article not found"),
+                    "500": error_response("Storage failed")
+                }
+            },
+            "post": {
+                "summary": "Add tags to an article",
+                "description": "Tags are trimmed and lowercased, and tags the article already has are skipped.",
+                "operationId": "addTags",
+                "parameters": [article_id()],
+                "requestBody": {
+                    "required": true,
+                    "content": { "application/json": { "schema": schema_ref("Tags") } }
+                },
+                "responses": {
+                    "200": json_response("All tags of the article", schema_ref("Tags")),
+                    "404": error_response("Article not found"),
+                    "500": error_response("Storage failed")
+                }
+            }
+        },
+        "/api/articles/{id}/tags/{tag}": {
+            "delete": {
+                "summary": "Remove a tag from an article",
+                "operationId": "deleteTag",
+                "parameters": [article_id(), path_param("tag", "Tag to remove", json!({ "type": "string" }))],
+                "responses": {
+                    "204": { "description": "Tag removed" },
+                    "404": error_response("Article not found, or it does not have the tag"),
+                    "500": error_response("Storage failed")
+                }
+            }
+        },
+        "/api/tags": {
+            "get": {
+                "summary": "List all tags with the number of articles carrying them",
+                "operationId": "listTags",
+                "responses": {
+                    "200": json_response("Tag counts", json!({ "type": "array", "items": schema_ref("TagCount") })),
+                    "500": error_response("Storage failed")
+                }
+            }
+        },
+        "/api/scrape": {
+            "post": {
+                "summary": "Start scraping a source in the background",
+                "operationId": "startScrape",
+                "requestBody": {
+                    "required": true,
+                    "content": { "application/json": { "schema": schema_ref("ScrapeRequest") } }
+                },
+                "responses": {
+                    "202": json_response("Scrape job started", schema_ref("ScrapeJob")),
+                    "400": error_response("Invalid source"),
+                    "404": error_response("No scrapers found for the source"),
+                    "500": error_response("The job could not be started")
+                }
+            }
+        },
+        "/api/scrape/{job_id}": {
+            "delete": {
+                "summary": "Cancel a scrape job",
+                "operationId": "cancelScrape",
+                "parameters": [path_param("job_id", "Id returned when the job started", json!({ "type": "integer", "minimum": 0 }))],
+                "responses": {
+                    "204": { "description": "Job cancelled" },
+                    "404": error_response("Scrape job not found"),
+                    "409": error_response("Scrape job has already finished"),
+                    "500": error_response("The job could not be cancelled")
+                }
+            }
+        },
+        "/api/openapi.json": {
+            "get": {
+                "summary": "Get this document",
+                "operationId": "getOpenApi",
+                "responses": { "200": json_response("OpenAPI document", json!({ "type": "object" })) }
+            }
+        }
+    })
+}
+
+fn schemas() -> Value {
+    json!({
+        "Article": {
+            "type": "object",
+            "required": ["url", "title", "content", "published_at", "source", "sections", "authors", "related_articles"],
+            "properties": {
+                "url": { "type": "string" },
+                "title": { "type": "string" },
+                "content": { "type": "string" },
+                "summary": { "type": "string", "nullable": true },
+                "published_at": { "type": "string", "format": "date-time" },
+                "source": { "type": "string" },
+                "sections": { "type": "array", "items": schema_ref("ArticleSection") },
+                "authors": { "type": "array", "items": { "type": "string" } },
+                "related_articles": { "type": "array", "items": schema_ref("RelatedArticle") },
+                "tags": schema_ref("Tags")
+            }
+        },
+        "ArticleWithId": {
+            "allOf": [
+                schema_ref("Article"),
+                {
+                    "type": "object",
+                    "required": ["id"],
+                    "properties": { "id": { "type": "string" } }
+                }
+            ]
+        },
+        "ArticleSection": {
+            "type": "object",
+            "required": ["content"],
+            "properties": {
+                "content": { "type": "string" },
+                "summary": { "type": "string", "nullable": true },
+                "embedding": { "type": "array", "items": { "type": "number", "format": "float" }, "nullable": true }
+            }
+        },
+        "RelatedArticle": {
+            "type": "object",
+            "required": ["article"],
+            "properties": {
+                "article": schema_ref("Article"),
+                "similarity_score": { "type": "number", "format": "float", "nullable": true }
+            }
+        },
+        "RelatedArticleWithId": {
+            "type": "object",
+            "required": ["id", "article"],
+            "properties": {
+                "id": { "type": "string" },
+                "article": schema_ref("Article"),
+                "similarity_score": { "type": "number", "format": "float", "nullable": true }
+            }
+        },
+        "DivergenceAnalysis": {
+            "type": "object",
+            "required": ["article_url", "article_title", "sections"],
+            "properties": {
+                "article_url": { "type": "string" },
+                "article_title": { "type": "string" },
+                "sections": { "type": "array", "items": schema_ref("SectionAnalysis") }
+            }
+        },
+        "SectionAnalysis": {
+            "type": "object",
+            "required": ["content", "similar_sections"],
+            "properties": {
+                "content": { "type": "string" },
+                "divergence_score": { "type": "number", "format": "float", "nullable": true },
+                "similar_sections": { "type": "array", "items": schema_ref("SimilarSection") }
+            }
+        },
+        "SimilarSection": {
+            "type": "object",
+            "required": ["content", "similarity_score", "source_url"],
+            "properties": {
+                "content": { "type": "string" },
+                "similarity_score": { "type": "number", "format": "float" },
+                "source_url": { "type": "string" }
+            }
+        },
+        "Tags": {
+            "type": "array",
+            "items": { "type": "string" }
+        },
+        "TagCount": {
+            "type": "object",
+            "required": ["tag", "count"],
+            "properties": {
+                "tag": { "type": "string" },
+                "count": { "type": "integer", "minimum": 0 }
+            }
+        },
+        "ScrapeRequest": {
+            "type": "object",
+            "required": ["source"],
+            "properties": {
+                "source": { "type": "string", "description": "Source to scrape, as `country[/name]`", "example": "argentina/clarin" }
+            }
+        },
+        "ScrapeJob": {
+            "type": "object",
+            "required": ["job_id"],
+            "properties": {
+                "job_id": { "type": "integer", "minimum": 0 }
+            }
+        }
+    })
+}
+
+/// Builds the OpenAPI 3 document describing every `/api` route.
+pub fn document() -> Value {
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "nt API",
+            "description": "Scraped news articles, their tags and similarity, and scrape jobs.",
+            "version": env!("CARGO_PKG_VERSION")
+        },
+        "paths": paths(),
+        "components": { "schemas": schemas() }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_lists_article_paths() {
+        let body = serde_json::to_string(&document()).unwrap();
+        let parsed: Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(parsed["openapi"], "3.0.3");
+        let paths = parsed["paths"].as_object().unwrap();
+        for path in [
+            "/api/articles",
+            "/api/articles/{id}",
+            "/api/articles/{id}/similar",
+            "/api/articles/{id}/tags",
+            "/api/articles/{id}/tags/{tag}",
+        ] {
+            assert!(paths.contains_key(path), "missing {}", path);
+        }
+        assert!(paths["/api/articles"]["get"].is_object());
+        assert!(paths["/api/articles"]["post"].is_object());
+
+        // Every schema referenced from a path is defined
+        let schemas = parsed["components"]["schemas"].as_object().unwrap();
+        for name in body.match_indices("#/components/schemas/").map(|(i, prefix)| {
+            body[i + prefix.len()..].split('"').next().unwrap()
+        }) {
+            assert!(schemas.contains_key(name), "undefined schema {}", name);
+        }
+    }
+}
@@ -1,6 +1,33 @@
 use std::sync::Arc;
-use nt_core::InferenceModel;
+use nt_core::{ArticleIdScheme, ArticleStorage, InferenceModel};
+use nt_progress::ProgressDisplay;
+use nt_scrappers::ScraperManager;
 
 pub struct AppState {
     pub inference_model: Arc<dyn InferenceModel>,
-} 
\ No newline at end of file
+    pub storage: Arc<dyn ArticleStorage>,
+    pub scraper: Arc<ScraperManager>,
+    /// Tracks background scrape jobs; job ids are progress thread ids
+    pub progress: ProgressDisplay,
+    /// How the `:id` of article routes maps to the article URL
+    pub id_scheme: ArticleIdScheme,
+}
+
+impl AppState {
+    pub async fn new(inference_model: Arc<dyn InferenceModel>, storage: Arc<dyn ArticleStorage>) -> anyhow::Result<Self> {
+        let scraper = ScraperManager::new(storage.clone(), inference_model.clone()).await?;
+        Ok(Self {
+            inference_model,
+            storage,
+            scraper: Arc::new(scraper),
+            progress: ProgressDisplay::new().await?,
+            id_scheme: ArticleIdScheme::default(),
+        })
+    }
+
+    /// Address articles with ids made by `scheme` instead of the default one
+    pub fn with_id_scheme(mut self, scheme: ArticleIdScheme) -> Self {
+        self.id_scheme = scheme;
+        self
+    }
+}